@@ -54,6 +54,12 @@ pub enum Command {
     /// Upgrade dependencies and refresh the lockfile
     #[command(visible_alias = "update")]
     Upgrade(commands::upgrade::UpgradeArgs),
+    /// Collapse duplicate package versions in the lockfile
+    Dedupe(commands::dedupe::DedupeArgs),
+    /// Download packages from the lockfile into the store without installing
+    Fetch(commands::fetch::FetchArgs),
+    /// Export or diff snpm-lock.yaml
+    Lockfile(commands::lockfile::LockfileArgs),
     /// Check for outdated dependencies
     Outdated(commands::outdated::OutdatedArgs),
     /// List licenses of installed packages
@@ -70,6 +76,8 @@ pub enum Command {
     Config(commands::config::ConfigArgs),
     /// Create a tarball from the current package
     Pack(commands::pack::PackArgs),
+    /// Copy a workspace project and its production dependencies into a standalone directory
+    Deploy(commands::deploy::DeployArgs),
     /// Publish a package to the registry
     Publish(commands::publish::PublishArgs),
     /// Rebuild native modules
@@ -139,6 +147,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn lockfile_only_conflicts_with_production() {
+        let cli = Cli::try_parse_from(["snpm", "install", "--lockfile-only"]).unwrap();
+        match cli.command {
+            Command::Install(args) => assert!(args.lockfile_only),
+            other => panic!("expected install command, got {other:?}"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["snpm", "install", "--lockfile-only", "--production"]).is_err()
+        );
+    }
+
+    #[test]
+    fn fetch_production_conflicts_with_dev() {
+        let cli = Cli::try_parse_from(["snpm", "fetch", "--production"]).unwrap();
+        match cli.command {
+            Command::Fetch(args) => assert!(args.production && !args.dev),
+            other => panic!("expected fetch command, got {other:?}"),
+        }
+
+        assert!(Cli::try_parse_from(["snpm", "fetch", "--production", "--dev"]).is_err());
+    }
+
+    #[test]
+    fn parses_lockfile_export_format() {
+        let cli = Cli::try_parse_from(["snpm", "lockfile", "export", "--format", "bun"]).unwrap();
+        match cli.command {
+            Command::Lockfile(args) => match args.command {
+                crate::commands::lockfile::LockfileCommand::Export(export) => {
+                    assert_eq!(export.format, crate::commands::lockfile::Format::Bun);
+                }
+                other => panic!("expected lockfile export, got {other:?}"),
+            },
+            other => panic!("expected lockfile command, got {other:?}"),
+        }
+
+        assert!(Cli::try_parse_from(["snpm", "lockfile", "export", "--format", "cargo"]).is_err());
+    }
+
+    #[test]
+    fn parses_lockfile_diff_defaults_to_head() {
+        let cli = Cli::try_parse_from(["snpm", "lockfile", "diff", "--json"]).unwrap();
+        match cli.command {
+            Command::Lockfile(args) => match args.command {
+                crate::commands::lockfile::LockfileCommand::Diff(diff) => {
+                    assert_eq!(diff.from, "HEAD");
+                    assert_eq!(diff.to, None);
+                    assert!(diff.json);
+                }
+                other => panic!("expected lockfile diff, got {other:?}"),
+            },
+            other => panic!("expected lockfile command, got {other:?}"),
+        }
+
+        let cli = Cli::try_parse_from(["snpm", "lockfile", "diff", "main", "feature"]).unwrap();
+        match cli.command {
+            Command::Lockfile(args) => match args.command {
+                crate::commands::lockfile::LockfileCommand::Diff(diff) => {
+                    assert_eq!(diff.from, "main");
+                    assert_eq!(diff.to.as_deref(), Some("feature"));
+                }
+                other => panic!("expected lockfile diff, got {other:?}"),
+            },
+            other => panic!("expected lockfile command, got {other:?}"),
+        }
+    }
+
     #[test]
     fn parses_node_install_with_default_flag() {
         let cli = Cli::try_parse_from(["snpm", "node", "install", "20.10.0", "--default"]).unwrap();
@@ -195,4 +271,55 @@ mod tests {
             other => panic!("expected node command, got {other:?}"),
         }
     }
+
+    #[test]
+    fn parses_audit_db_sync_and_local_database_flag() {
+        use crate::commands::audit::AuditCommand;
+        use crate::commands::audit::db::DbCommand;
+        use std::path::PathBuf;
+
+        let cli =
+            Cli::try_parse_from(["snpm", "audit", "db", "sync", "--from", "all.zip"]).unwrap();
+        match cli.command {
+            Command::Audit(args) => match args.command {
+                Some(AuditCommand::Db(db)) => match db.command {
+                    DbCommand::Sync(sync) => assert_eq!(sync.from, PathBuf::from("all.zip")),
+                },
+                other => panic!("expected audit db command, got {other:?}"),
+            },
+            other => panic!("expected audit command, got {other:?}"),
+        }
+
+        let cli = Cli::try_parse_from(["snpm", "audit", "--db"]).unwrap();
+        match cli.command {
+            Command::Audit(args) => {
+                assert!(args.command.is_none());
+                assert_eq!(args.db, Some(None));
+            }
+            other => panic!("expected audit command, got {other:?}"),
+        }
+
+        let cli = Cli::try_parse_from(["snpm", "audit", "--db", "osv", "lodash"]).unwrap();
+        match cli.command {
+            Command::Audit(args) => {
+                assert_eq!(args.db, Some(Some(PathBuf::from("osv"))));
+                assert_eq!(args.packages, vec!["lodash".to_string()]);
+            }
+            other => panic!("expected audit command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_audit_signatures() {
+        use crate::commands::audit::AuditCommand;
+
+        let cli = Cli::try_parse_from(["snpm", "audit", "signatures", "--json"]).unwrap();
+        match cli.command {
+            Command::Audit(args) => match args.command {
+                Some(AuditCommand::Signatures(signatures)) => assert!(signatures.json),
+                other => panic!("expected audit signatures command, got {other:?}"),
+            },
+            other => panic!("expected audit command, got {other:?}"),
+        }
+    }
 }
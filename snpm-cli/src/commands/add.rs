@@ -1,3 +1,5 @@
+use super::architectures::ArchitectureArgs;
+use super::before::BeforeArgs;
 use super::workspace::{self as workspace_selector, WorkspaceSelection};
 use anyhow::{Context, Result};
 use clap::Args;
@@ -21,6 +23,16 @@ pub struct AddArgs {
     /// Range prefix to write for added deps (e.g. "^" or "~")
     #[arg(long = "save-prefix", value_name = "PREFIX")]
     pub save_prefix: Option<String>,
+    /// Never touch the network; fail if metadata or a tarball is not cached
+    #[arg(long)]
+    pub offline: bool,
+    /// Use cached metadata and tarballs when present, fetching only what is missing
+    #[arg(long = "prefer-offline", conflicts_with = "offline")]
+    pub prefer_offline: bool,
+    #[command(flatten)]
+    pub architectures: ArchitectureArgs,
+    #[command(flatten)]
+    pub before: BeforeArgs,
     /// Packages to add
     pub packages: Vec<String>,
     /// Target a specific workspace project by its package name
@@ -45,6 +57,10 @@ pub async fn run(args: AddArgs, config: &SnpmConfig) -> Result<()> {
         force,
         save_exact,
         save_prefix,
+        offline,
+        prefer_offline,
+        architectures,
+        before,
         workspace,
         recursive,
         filter,
@@ -65,6 +81,9 @@ pub async fn run(args: AddArgs, config: &SnpmConfig) -> Result<()> {
     } else {
         config
     };
+    let config = &*super::offline::apply_offline_flags(config, offline, prefer_offline);
+    let config = &*super::architectures::apply_architecture_flags(config, architectures);
+    let config = &*super::before::apply_before_flag(config, &before)?;
 
     let frozen_lockfile = super::frozen::resolve_frozen_lockfile_mode(config, None);
 
@@ -94,6 +113,7 @@ pub async fn run(args: AddArgs, config: &SnpmConfig) -> Result<()> {
                 frozen_lockfile: frozen_lockfile.mode,
                 strict_no_lockfile: frozen_lockfile.strict_no_lockfile,
                 force,
+                lockfile_only: false,
                 silent_summary: false,
             };
 
@@ -132,6 +152,7 @@ pub async fn run(args: AddArgs, config: &SnpmConfig) -> Result<()> {
             frozen_lockfile: frozen_lockfile.mode,
             strict_no_lockfile: frozen_lockfile.strict_no_lockfile,
             force,
+            lockfile_only: false,
             silent_summary: false,
         };
         operations::install(config, project, options).await?;
@@ -144,6 +165,7 @@ pub async fn run(args: AddArgs, config: &SnpmConfig) -> Result<()> {
             frozen_lockfile: frozen_lockfile.mode,
             strict_no_lockfile: frozen_lockfile.strict_no_lockfile,
             force,
+            lockfile_only: false,
             silent_summary: false,
         };
         operations::install(config, &mut project, options).await?;
@@ -0,0 +1,37 @@
+use std::borrow::Cow;
+
+use clap::Args;
+use snpm_core::SnpmConfig;
+use snpm_core::platform::SupportedArchitectures;
+
+/// `--os` / `--cpu` / `--libc`, shared by the commands that install.
+#[derive(Args, Debug)]
+pub struct ArchitectureArgs {
+    /// Install optional packages built for this OS instead of the host's (repeatable; include `current` to keep the host)
+    #[arg(long = "os", value_name = "OS")]
+    pub os: Vec<String>,
+    /// Install optional packages built for this CPU instead of the host's (repeatable; include `current` to keep the host)
+    #[arg(long = "cpu", value_name = "CPU")]
+    pub cpu: Vec<String>,
+    /// Install optional packages built for this libc instead of the host's (repeatable; include `current` to keep the host)
+    #[arg(long = "libc", value_name = "LIBC")]
+    pub libc: Vec<String>,
+}
+
+/// `config` with `--os` / `--cpu` / `--libc` replacing the matching
+/// `supportedArchitectures` list, borrowed unchanged when none was passed.
+pub(crate) fn apply_architecture_flags(
+    config: &SnpmConfig,
+    args: ArchitectureArgs,
+) -> Cow<'_, SnpmConfig> {
+    let ArchitectureArgs { os, cpu, libc } = args;
+    let flags = SupportedArchitectures { os, cpu, libc };
+    if flags.is_empty() {
+        return Cow::Borrowed(config);
+    }
+
+    Cow::Owned(SnpmConfig {
+        supported_architectures: flags.or(&config.supported_architectures),
+        ..config.clone()
+    })
+}
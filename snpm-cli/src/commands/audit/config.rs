@@ -1,5 +1,5 @@
 use anyhow::Result;
-use snpm_core::operations;
+use snpm_core::{SnpmConfig, operations};
 
 use std::collections::HashSet;
 
@@ -7,6 +7,7 @@ use super::AuditArgs;
 
 pub(super) fn build_audit_options(
     args: &AuditArgs,
+    config: &SnpmConfig,
 ) -> Result<(operations::AuditOptions, Option<operations::Severity>)> {
     let audit_level = args
         .audit_level
@@ -23,6 +24,10 @@ pub(super) fn build_audit_options(
         ignore_cves: args.ignore_cves.iter().cloned().collect::<HashSet<_>>(),
         ignore_ghsas: args.ignore_ghsas.iter().cloned().collect::<HashSet<_>>(),
         ignore_unfixable: args.ignore_unfixable,
+        database: args
+            .db
+            .as_ref()
+            .map(|db| db.clone().unwrap_or_else(|| config.advisory_db_dir())),
     };
 
     Ok((options, audit_level))
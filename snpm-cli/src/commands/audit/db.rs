@@ -0,0 +1,43 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use snpm_core::{SnpmConfig, console, operations};
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct DbArgs {
+    #[command(subcommand)]
+    pub command: DbCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DbCommand {
+    /// Replace the local advisory database with the npm advisories in an
+    /// OSV dump (.zip, .tar.gz or directory)
+    Sync(SyncArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SyncArgs {
+    /// Archive or directory to import, e.g. osv.dev's npm/all.zip
+    #[arg(long, value_name = "ARCHIVE")]
+    pub from: PathBuf,
+
+    /// Database directory to write (defaults to the one `audit --db` reads)
+    #[arg(long, value_name = "DIR")]
+    pub db: Option<PathBuf>,
+}
+
+pub(super) fn run_db(args: DbArgs, config: &SnpmConfig) -> Result<()> {
+    match args.command {
+        DbCommand::Sync(args) => {
+            let database = args.db.unwrap_or_else(|| config.advisory_db_dir());
+            let report = operations::sync_advisory_database(&args.from, &database)?;
+            console::info(&format!(
+                "Imported {} npm advisories into {}",
+                report.advisories,
+                report.path.display()
+            ));
+            Ok(())
+        }
+    }
+}
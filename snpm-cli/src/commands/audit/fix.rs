@@ -1,15 +1,16 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use snpm_core::{Project, SnpmConfig, console, operations};
 
-use super::output::render_fix_report;
+use super::output::{render_fix_outcomes, render_fix_plan, render_fix_report};
 
 pub(super) async fn run_fix(
     config: &SnpmConfig,
     cwd: &std::path::Path,
     options: &operations::AuditOptions,
+    dry_run: bool,
 ) -> Result<()> {
-    let project = Project::discover(cwd)?;
-    let result = operations::fix(config, &project, options).await?;
+    let mut project = Project::discover(cwd)?;
+    let result = operations::fix(config, &mut project, options, dry_run).await?;
 
     if result.fixable.is_empty() && result.unfixable.is_empty() {
         console::info("No vulnerabilities found.");
@@ -17,12 +18,25 @@ pub(super) async fn run_fix(
     }
 
     render_fix_report(&result);
+    render_fix_plan(&result.actions, dry_run);
 
-    if !result.fixable.is_empty() {
-        console::info(
-            "Update the listed dependencies (e.g. `snpm upgrade <package>` or edit \
-             package.json to a patched range) and reinstall to resolve them.",
-        );
+    if dry_run {
+        if !result.actions.is_empty() {
+            console::info("Run `snpm audit --fix` to apply these changes.");
+        }
+        return Ok(());
+    }
+
+    render_fix_outcomes(&result.outcomes);
+
+    let remaining = result
+        .outcomes
+        .iter()
+        .filter(|outcome| !outcome.fixed)
+        .count()
+        + result.unfixable.len();
+    if remaining > 0 {
+        bail!("{remaining} vulnerabilities remain");
     }
 
     Ok(())
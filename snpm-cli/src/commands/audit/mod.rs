@@ -1,16 +1,21 @@
 mod config;
+pub(crate) mod db;
 mod fix;
 mod output;
+mod signatures;
 mod target;
 
 use anyhow::{Context, Result};
-use clap::{Args, ValueEnum};
-use snpm_core::{SnpmConfig, console};
+use clap::{Args, Subcommand, ValueEnum};
+use snpm_core::{SnpmConfig, console, operations};
 use std::env;
+use std::path::PathBuf;
 
 use config::build_audit_options;
+use db::{DbArgs, run_db};
 use fix::run_fix;
 use output::{print_json, print_sarif, print_table};
+use signatures::{SignaturesArgs, run_signatures};
 use target::run_audit;
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -23,8 +28,20 @@ pub enum OutputFormat {
     Sarif,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum AuditCommand {
+    /// Manage the local advisory database used by `--db`
+    Db(DbArgs),
+    /// Verify the registry signatures of every package in the lockfile
+    Signatures(SignaturesArgs),
+}
+
 #[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct AuditArgs {
+    #[command(subcommand)]
+    pub command: Option<AuditCommand>,
+
     /// Only report vulnerabilities at or above this severity
     #[arg(long, value_name = "LEVEL")]
     pub audit_level: Option<String>,
@@ -41,10 +58,15 @@ pub struct AuditArgs {
     #[arg(long, value_enum, default_value = "table")]
     pub format: OutputFormat,
 
-    /// Attempt to fix vulnerabilities by updating packages
+    /// Fix vulnerabilities by raising dependency ranges or adding scoped
+    /// overrides, then re-resolve
     #[arg(long)]
     pub fix: bool,
 
+    /// With --fix, show the planned changes without applying them
+    #[arg(long = "dry-run", requires = "fix")]
+    pub dry_run: bool,
+
     /// Ignore vulnerabilities by CVE ID (can be specified multiple times)
     #[arg(long = "ignore-cve", value_name = "CVE")]
     pub ignore_cves: Vec<String>,
@@ -57,6 +79,11 @@ pub struct AuditArgs {
     #[arg(long)]
     pub ignore_unfixable: bool,
 
+    /// Match against a local OSV advisory database instead of the registry
+    /// (without DIR, the copy `snpm audit db sync` maintains)
+    #[arg(long, value_name = "DIR")]
+    pub db: Option<Option<PathBuf>>,
+
     /// Continue with exit code 0 even if registry returns an error
     #[arg(long)]
     pub ignore_registry_errors: bool,
@@ -69,11 +96,28 @@ pub struct AuditArgs {
 pub async fn run(args: AuditArgs, config: &SnpmConfig) -> Result<()> {
     console::header("audit", env!("CARGO_PKG_VERSION"));
 
+    match args.command {
+        Some(AuditCommand::Db(args)) => return run_db(args, config),
+        Some(AuditCommand::Signatures(args)) => return run_signatures(args, config).await,
+        None => {}
+    }
+
     let cwd = env::current_dir().context("failed to determine current directory")?;
-    let (options, audit_level) = build_audit_options(&args)?;
+    let (options, audit_level) = build_audit_options(&args, config)?;
+
+    let expired = operations::expired_suppressions(&cwd)?;
+    for suppression in &expired {
+        console::warn(&format!(
+            "suppression of {} in snpm-audit.yaml (owner {}) expired on {}",
+            suppression.id, suppression.owner, suppression.expires
+        ));
+    }
+    if !expired.is_empty() {
+        anyhow::bail!("snpm-audit.yaml has expired suppressions; renew or remove them");
+    }
 
     if args.fix {
-        return run_fix(config, &cwd, &options).await;
+        return run_fix(config, &cwd, &options, args.dry_run).await;
     }
 
     let results = match run_audit(config, &cwd, &options).await {
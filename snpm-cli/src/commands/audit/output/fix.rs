@@ -55,3 +55,93 @@ pub(crate) fn render_fix_report(result: &operations::FixResult) {
 
     println!();
 }
+
+pub(crate) fn render_fix_plan(actions: &[operations::FixAction], dry_run: bool) {
+    if actions.is_empty() {
+        return;
+    }
+
+    let heading = if dry_run {
+        "Planned changes (dry run):"
+    } else {
+        "Applied changes:"
+    };
+    println!("{}", paint("1", heading));
+    println!();
+
+    for action in actions {
+        match action {
+            operations::FixAction::BumpDependency { package, from, to } => println!(
+                "  package.json         {} {} -> {}",
+                package,
+                paint("2", from),
+                paint("32", to),
+            ),
+            operations::FixAction::Override { selector, range } => println!(
+                "  snpm-overrides.yaml  {} -> {}",
+                selector,
+                paint("32", range),
+            ),
+        }
+    }
+
+    println!();
+}
+
+pub(crate) fn render_fix_outcomes(outcomes: &[operations::FixOutcome]) {
+    if outcomes.is_empty() {
+        return;
+    }
+
+    let rows: Vec<[String; 4]> = outcomes
+        .iter()
+        .map(|outcome| {
+            let after = if outcome.after.is_empty() {
+                "removed".to_string()
+            } else {
+                outcome.after.join(", ")
+            };
+            [
+                outcome.package.clone(),
+                outcome.advisory_id.to_string(),
+                outcome.before.clone(),
+                after,
+            ]
+        })
+        .collect();
+    let headers = ["Package", "Advisory", "Before", "After"];
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].len())
+                .chain([headers[column].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |cells: [&str; 4]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+    println!("  {}  {}", paint("1", &line(headers)), paint("1", "Status"));
+
+    for (row, outcome) in rows.iter().zip(outcomes) {
+        let status = if outcome.fixed {
+            paint("32", "fixed")
+        } else {
+            paint("31", "still vulnerable")
+        };
+        println!(
+            "  {}  {}",
+            line([&row[0], &row[1], &row[2], &row[3]]),
+            status
+        );
+    }
+
+    println!();
+}
@@ -1,10 +1,12 @@
 mod advisory;
 mod summary;
+mod suppressed;
 
 use snpm_core::operations;
 
 use advisory::print_advisory;
 use summary::print_summary;
+use suppressed::print_suppressed;
 
 pub(crate) fn print_table(
     results: &[operations::AuditResult],
@@ -13,6 +15,7 @@ pub(crate) fn print_table(
     let mut total_counts = operations::VulnerabilityCounts::default();
     let mut total_packages = 0;
     let mut any_vulnerabilities = false;
+    let mut suppressed = Vec::new();
 
     for result in results {
         total_packages += result.total_packages;
@@ -27,8 +30,11 @@ pub(crate) fn print_table(
             any_vulnerabilities = true;
             print_advisory(advisory);
         }
+        suppressed.extend(&result.suppressed);
     }
 
+    print_suppressed(&suppressed);
+
     println!();
     print_summary(&total_counts, total_packages);
 
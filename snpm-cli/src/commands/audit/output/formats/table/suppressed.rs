@@ -0,0 +1,49 @@
+use snpm_core::operations;
+
+use super::super::super::style::paint;
+
+pub(super) fn print_suppressed(suppressed: &[&operations::SuppressedAdvisory]) {
+    if suppressed.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "{}",
+        paint(
+            "1",
+            &format!("{} suppressed in snpm-audit.yaml:", suppressed.len()),
+        ),
+    );
+
+    for entry in suppressed {
+        let advisory = &entry.advisory;
+        let suppression = &entry.suppression;
+        let id = advisory
+            .github_advisory_id
+            .clone()
+            .unwrap_or_else(|| advisory.id.to_string());
+        let versions: Vec<&str> = advisory
+            .findings
+            .iter()
+            .map(|finding| finding.version.as_str())
+            .collect();
+
+        println!(
+            "  {} {}@{} {}",
+            paint("2", &id),
+            advisory.module_name,
+            versions.join(", "),
+            paint("2", &format!("({})", advisory.severity)),
+        );
+        println!(
+            "    {} {} {}",
+            suppression.reason,
+            paint("2", "—"),
+            paint(
+                "2",
+                &format!("{}, until {}", suppression.owner, suppression.expires),
+            ),
+        );
+    }
+}
@@ -2,5 +2,5 @@ mod fix;
 mod formats;
 mod style;
 
-pub(super) use fix::render_fix_report;
+pub(super) use fix::{render_fix_outcomes, render_fix_plan, render_fix_report};
 pub(super) use formats::{print_json, print_sarif, print_table};
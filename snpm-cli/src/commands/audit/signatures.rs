@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use snpm_core::{SnpmConfig, Workspace, console, operations};
+use std::env;
+
+#[derive(Args, Debug)]
+pub struct SignaturesArgs {
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub(super) async fn run_signatures(args: SignaturesArgs, config: &SnpmConfig) -> Result<()> {
+    let cwd = env::current_dir().context("failed to determine current directory")?;
+    let root = match Workspace::discover(&cwd)? {
+        Some(workspace) => workspace.root,
+        None => cwd,
+    };
+
+    let result = operations::audit_signatures(config, &root).await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        print_report(&result);
+    }
+
+    if !result.invalid.is_empty() || !result.missing.is_empty() {
+        anyhow::bail!(
+            "{} invalid and {} missing registry signatures",
+            result.invalid.len(),
+            result.missing.len()
+        );
+    }
+
+    Ok(())
+}
+
+fn print_report(result: &operations::SignatureAuditResult) {
+    console::info(&format!(
+        "{} {} verified registry signatures",
+        result.verified,
+        plural(result.verified)
+    ));
+    if result.unchecked > 0 {
+        console::info(&format!(
+            "{} {} from registries that publish no signing keys were not checked",
+            result.unchecked,
+            if result.unchecked == 1 {
+                "package"
+            } else {
+                "packages"
+            }
+        ));
+    }
+
+    if !result.invalid.is_empty() {
+        println!();
+        println!(
+            "{} {} invalid registry signatures:",
+            result.invalid.len(),
+            plural(result.invalid.len())
+        );
+        for issue in &result.invalid {
+            println!("  {}@{}: {}", issue.name, issue.version, issue.reason);
+        }
+    }
+
+    if !result.missing.is_empty() {
+        println!();
+        println!(
+            "{} {} missing registry signatures:",
+            result.missing.len(),
+            plural(result.missing.len())
+        );
+        for issue in &result.missing {
+            println!("  {}@{}: {}", issue.name, issue.version, issue.reason);
+        }
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        "package has"
+    } else {
+        "packages have"
+    }
+}
@@ -0,0 +1,62 @@
+use std::borrow::Cow;
+
+use anyhow::{Result, anyhow};
+use clap::Args;
+use snpm_core::SnpmConfig;
+use snpm_core::config::parse_before;
+
+/// `--before`, shared by the commands that resolve.
+#[derive(Args, Debug)]
+pub struct BeforeArgs {
+    /// Resolve as if the registry stopped at this date (RFC 3339 or YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    pub before: Option<String>,
+}
+
+/// `config` resolving as of `--before`, borrowed unchanged when the flag
+/// wasn't passed.
+pub(crate) fn apply_before_flag<'a>(
+    config: &'a SnpmConfig,
+    args: &BeforeArgs,
+) -> Result<Cow<'a, SnpmConfig>> {
+    let Some(value) = args.before.as_deref() else {
+        return Ok(Cow::Borrowed(config));
+    };
+
+    let cutoff = parse_before(value).ok_or_else(|| {
+        anyhow!("invalid --before value {value:?}: expected an RFC 3339 timestamp or YYYY-MM-DD")
+    })?;
+
+    Ok(Cow::Owned(SnpmConfig {
+        before: Some(cutoff),
+        ..config.clone()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn before(value: Option<&str>) -> BeforeArgs {
+        BeforeArgs {
+            before: value.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn rejects_values_that_are_not_dates() {
+        let config = SnpmConfig::from_env();
+
+        assert!(apply_before_flag(&config, &before(Some("last tuesday"))).is_err());
+        assert!(matches!(
+            apply_before_flag(&config, &before(None)).unwrap(),
+            Cow::Borrowed(_)
+        ));
+        assert!(
+            apply_before_flag(&config, &before(Some("2024-05-01")))
+                .unwrap()
+                .before
+                .is_some()
+        );
+    }
+}
@@ -5,6 +5,8 @@ use std::env;
 use std::fs;
 use std::path::Path;
 
+use super::architectures::ArchitectureArgs;
+use super::before::BeforeArgs;
 use super::install::InstallArgs;
 
 /// Clean, reproducible install for CI: wipe existing `node_modules` and
@@ -14,6 +16,16 @@ pub struct CiArgs {
     /// Skip devDependencies
     #[arg(long)]
     pub production: bool,
+    /// Never touch the network; fail if metadata or a tarball is not cached
+    #[arg(long)]
+    pub offline: bool,
+    /// Use cached metadata and tarballs when present, fetching only what is missing
+    #[arg(long = "prefer-offline", conflicts_with = "offline")]
+    pub prefer_offline: bool,
+    #[command(flatten)]
+    pub architectures: ArchitectureArgs,
+    #[command(flatten)]
+    pub before: BeforeArgs,
     /// Target a specific workspace project by its package name
     #[arg(short = 'w', long = "workspace")]
     pub workspace: Option<String>,
@@ -88,8 +100,13 @@ pub async fn run(args: CiArgs, config: &SnpmConfig) -> Result<()> {
         prefer_frozen_lockfile: false,
         fix_lockfile: false,
         force: false,
+        lockfile_only: false,
         packages: Vec::new(),
         workspace: args.workspace,
+        offline: args.offline,
+        prefer_offline: args.prefer_offline,
+        architectures: args.architectures,
+        before: args.before,
     };
 
     super::install::run(install_args, config).await
@@ -0,0 +1,56 @@
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use snpm_core::{Project, SnpmConfig, Workspace, console, operations};
+use std::env;
+
+#[derive(Args, Debug)]
+pub struct DedupeArgs {
+    /// Exit with an error instead of writing when the lockfile can be deduplicated
+    #[arg(long)]
+    pub check: bool,
+}
+
+pub async fn run(args: DedupeArgs, config: &SnpmConfig) -> Result<()> {
+    console::header("dedupe", env!("CARGO_PKG_VERSION"));
+
+    let cwd = env::current_dir().context("failed to determine current directory")?;
+
+    // The lockfile is shared across a workspace, so dedupe always works on
+    // the whole workspace when there is one.
+    let result = if let Some(mut workspace) = Workspace::discover(&cwd)? {
+        operations::dedupe_workspace(config, &mut workspace, args.check).await?
+    } else {
+        let mut project = Project::discover(&cwd)?;
+        operations::dedupe(config, &mut project, args.check).await?
+    };
+
+    if !result.changed {
+        console::info("Lockfile is already deduplicated");
+        return Ok(());
+    }
+
+    for package in &result.collapsed {
+        println!(
+            "  {} {} → {}",
+            package.name,
+            package.before.join(", "),
+            package.after.join(", ")
+        );
+    }
+
+    if args.check {
+        bail!(
+            "snpm-lock.yaml can be deduplicated ({} packages collapse, {} entries removed). Run `snpm dedupe`.",
+            result.collapsed.len(),
+            result.removed
+        );
+    }
+
+    console::info(&format!(
+        "Deduplicated {} packages, removed {} lockfile entries",
+        result.collapsed.len(),
+        result.removed
+    ));
+
+    Ok(())
+}
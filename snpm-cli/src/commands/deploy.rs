@@ -0,0 +1,74 @@
+use super::workspace::{project_label, select_workspace_projects};
+use anyhow::{Context, Result, anyhow};
+use clap::Args;
+use snpm_core::{SnpmConfig, Workspace, console, operations};
+use std::env;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct DeployArgs {
+    /// Filter workspace projects (name, glob, path, or dependency graph selector)
+    #[arg(long)]
+    pub filter: Vec<String>,
+
+    /// Production-only filter (same selector syntax as --filter)
+    #[arg(long)]
+    pub filter_prod: Vec<String>,
+
+    /// Directory to deploy into (must be missing or empty)
+    pub target: PathBuf,
+}
+
+pub async fn run(args: DeployArgs, config: &SnpmConfig) -> Result<()> {
+    console::header("deploy", env!("CARGO_PKG_VERSION"));
+
+    let cwd = env::current_dir().context("failed to determine current directory")?;
+    if args.filter.is_empty() && args.filter_prod.is_empty() {
+        return Err(anyhow!(
+            "snpm deploy: pass --filter to choose the workspace project to deploy"
+        ));
+    }
+
+    let selection =
+        select_workspace_projects(&cwd, "deploy", false, &args.filter, &args.filter_prod)?
+            .context("snpm deploy: --filter used outside a workspace")?;
+    let [project] = selection.projects.as_slice() else {
+        let labels = selection
+            .projects
+            .iter()
+            .map(project_label)
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(anyhow!(
+            "snpm deploy: {} matched {} projects ({labels}); select exactly one",
+            selection.filter_label,
+            selection.projects.len()
+        ));
+    };
+
+    let workspace =
+        Workspace::discover(&cwd)?.context("snpm deploy must run inside a workspace")?;
+    let target = cwd.join(&args.target);
+
+    console::step(&format!("Deploying {}", project_label(project)));
+    let result = operations::deploy(config, &workspace, project, &target).await?;
+
+    if !result.injected.is_empty() {
+        console::info(&format!(
+            "Injected workspace packages: {}",
+            result.injected.join(", ")
+        ));
+    }
+    if !result.blocked_scripts.is_empty() {
+        console::blocked_scripts(&result.blocked_scripts);
+    }
+
+    console::info(&format!(
+        "Deployed {} files and {} packages to {}",
+        result.file_count,
+        result.package_count,
+        result.target.display()
+    ));
+
+    Ok(())
+}
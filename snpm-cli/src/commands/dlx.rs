@@ -1,6 +1,6 @@
 use anyhow::Result;
 use clap::Args;
-use snpm_core::{OfflineMode, SnpmConfig, console, operations};
+use snpm_core::{SnpmConfig, console, operations};
 use std::env;
 
 #[derive(Args, Debug)]
@@ -13,7 +13,7 @@ pub struct DlxArgs {
     pub offline: bool,
 
     /// Prefer cached packages; only fetch if not in cache
-    #[arg(long = "prefer-offline")]
+    #[arg(long = "prefer-offline", conflicts_with = "offline")]
     pub prefer_offline: bool,
 
     /// Arguments to pass to the package's binary
@@ -24,13 +24,8 @@ pub struct DlxArgs {
 pub async fn run(args: DlxArgs, config: &SnpmConfig) -> Result<()> {
     console::header(&format!("dlx {}", args.package), env!("CARGO_PKG_VERSION"));
 
-    let offline_mode = if args.offline {
-        OfflineMode::Offline
-    } else if args.prefer_offline {
-        OfflineMode::PreferOffline
-    } else {
-        OfflineMode::Online
-    };
+    let offline_mode = super::offline::offline_mode_from_flags(args.offline, args.prefer_offline)
+        .unwrap_or(config.offline_mode);
 
     operations::dlx_with_offline(config, args.package, args.args, offline_mode).await?;
     Ok(())
@@ -0,0 +1,44 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use snpm_core::operations::FetchScope;
+use snpm_core::{SnpmConfig, Workspace, console, operations};
+use std::env;
+
+#[derive(Args, Debug)]
+pub struct FetchArgs {
+    /// Only fetch packages needed by dependencies and optionalDependencies
+    #[arg(long, conflicts_with = "dev")]
+    pub production: bool,
+    /// Only fetch packages needed by devDependencies
+    #[arg(long)]
+    pub dev: bool,
+}
+
+pub async fn run(args: FetchArgs, config: &SnpmConfig) -> Result<()> {
+    console::header("fetch", env!("CARGO_PKG_VERSION"));
+
+    let cwd = env::current_dir().context("failed to determine current directory")?;
+    let root = match Workspace::discover(&cwd)? {
+        Some(workspace) => workspace.root,
+        None => cwd,
+    };
+
+    let scope = if args.production {
+        FetchScope::Production
+    } else if args.dev {
+        FetchScope::Development
+    } else {
+        FetchScope::All
+    };
+
+    console::step("Fetching packages");
+    let result = operations::fetch(config, &root, scope).await?;
+
+    console::info(&format!(
+        "Fetched {} packages from {} into the store",
+        result.package_count,
+        result.lockfile_path.display()
+    ));
+
+    Ok(())
+}
@@ -80,7 +80,9 @@ pub(crate) fn frozen_override_from_cli(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use snpm_core::config::{AuthScheme, HoistingMode, LinkBackend, SnpmConfig};
+    use snpm_core::config::{
+        AuthScheme, HoistingMode, LinkBackend, OfflineMode, ResolutionMode, SnpmConfig,
+    };
     use std::collections::{BTreeMap, BTreeSet};
     use std::path::Path;
 
@@ -106,6 +108,16 @@ mod tests {
             frozen_lockfile_default,
             always_auth: false,
             registry_concurrency: 64,
+            offline_mode: OfflineMode::Online,
+            supported_architectures: Default::default(),
+            resolution_mode: ResolutionMode::Highest,
+            before: None,
+            fail_on_deprecated: false,
+            engine_strict: false,
+            engine_targets: Default::default(),
+            verify_signatures: false,
+            trust_policy: Default::default(),
+            trust_policy_exclude: BTreeSet::new(),
             verbose: false,
             log_file: None,
             remote_cache_url: None,
@@ -1,3 +1,5 @@
+use super::architectures::ArchitectureArgs;
+use super::before::BeforeArgs;
 use anyhow::{Context, Result};
 use clap::Args;
 use snpm_core::{Project, SnpmConfig, Workspace, console, operations};
@@ -32,6 +34,19 @@ pub struct InstallArgs {
     /// Ignore cached state and force a full install
     #[arg(short = 'f', long = "force")]
     pub force: bool,
+    /// Update snpm-lock.yaml without downloading packages or touching node_modules
+    #[arg(long = "lockfile-only", conflicts_with = "production")]
+    pub lockfile_only: bool,
+    /// Never touch the network; fail if metadata or a tarball is not cached
+    #[arg(long)]
+    pub offline: bool,
+    /// Use cached metadata and tarballs when present, fetching only what is missing
+    #[arg(long = "prefer-offline", conflicts_with = "offline")]
+    pub prefer_offline: bool,
+    #[command(flatten)]
+    pub architectures: ArchitectureArgs,
+    #[command(flatten)]
+    pub before: BeforeArgs,
     /// Packages to install (also updates package.json)
     pub packages: Vec<String>,
     /// Target a specific workspace project by its package name
@@ -43,6 +58,10 @@ pub async fn run(arguments: InstallArgs, config: &SnpmConfig) -> Result<()> {
     console::header("install", env!("CARGO_PKG_VERSION"));
 
     let current_directory = env::current_dir().context("failed to determine current directory")?;
+    let config =
+        &*super::offline::apply_offline_flags(config, arguments.offline, arguments.prefer_offline);
+    let config = &*super::architectures::apply_architecture_flags(config, arguments.architectures);
+    let config = &*super::before::apply_before_flag(config, &arguments.before)?;
     let frozen_lockfile = super::frozen::resolve_frozen_lockfile_mode_for_flags(
         config,
         arguments.frozen_lockfile,
@@ -69,6 +88,7 @@ pub async fn run(arguments: InstallArgs, config: &SnpmConfig) -> Result<()> {
             frozen_lockfile: frozen_lockfile.mode,
             strict_no_lockfile: frozen_lockfile.strict_no_lockfile,
             force: arguments.force,
+            lockfile_only: arguments.lockfile_only,
             silent_summary: false,
         };
         operations::install(config, project, options).await?;
@@ -84,6 +104,7 @@ pub async fn run(arguments: InstallArgs, config: &SnpmConfig) -> Result<()> {
                 frozen_lockfile.mode,
                 frozen_lockfile.strict_no_lockfile,
                 arguments.force,
+                arguments.lockfile_only,
             )
             .await?;
 
@@ -98,6 +119,7 @@ pub async fn run(arguments: InstallArgs, config: &SnpmConfig) -> Result<()> {
             frozen_lockfile: frozen_lockfile.mode,
             strict_no_lockfile: frozen_lockfile.strict_no_lockfile,
             force: arguments.force,
+            lockfile_only: arguments.lockfile_only,
             silent_summary: false,
         };
         operations::install(config, &mut project, options).await?;
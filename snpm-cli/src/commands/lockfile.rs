@@ -0,0 +1,194 @@
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand, ValueEnum};
+use snpm_core::operations::{DiffSource, ExportFormat, LockfileDiff};
+use snpm_core::{Workspace, console, operations};
+use std::env;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug)]
+pub struct LockfileArgs {
+    #[command(subcommand)]
+    pub command: LockfileCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LockfileCommand {
+    /// Write snpm-lock.yaml out as another package manager's lockfile
+    Export(ExportArgs),
+    /// Show which packages changed between two lockfiles
+    Diff(DiffArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Lockfile format to write
+    #[arg(long, value_enum)]
+    pub format: Format,
+}
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Git revision or lockfile path to compare from
+    #[arg(default_value = "HEAD")]
+    pub from: String,
+    /// Git revision or lockfile path to compare to (defaults to the working tree)
+    pub to: Option<String>,
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// pnpm-lock.yaml (v9)
+    Pnpm,
+    /// package-lock.json (v3)
+    Npm,
+    /// yarn.lock (classic v1)
+    Yarn,
+    /// bun.lock (text, v1)
+    Bun,
+}
+
+impl From<Format> for ExportFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Pnpm => ExportFormat::Pnpm,
+            Format::Npm => ExportFormat::Npm,
+            Format::Yarn => ExportFormat::Yarn,
+            Format::Bun => ExportFormat::Bun,
+        }
+    }
+}
+
+pub async fn run(args: LockfileArgs) -> Result<()> {
+    let json = matches!(&args.command, LockfileCommand::Diff(diff_args) if diff_args.json);
+    if !json {
+        console::header("lockfile", env!("CARGO_PKG_VERSION"));
+    }
+
+    let cwd = env::current_dir().context("failed to determine current directory")?;
+    let root = match Workspace::discover(&cwd)? {
+        Some(workspace) => workspace.root,
+        None => cwd,
+    };
+
+    match args.command {
+        LockfileCommand::Export(export_args) => {
+            let result = operations::export_lockfile(&root, export_args.format.into())?;
+            console::info(&format!(
+                "Exported {} packages to {}",
+                result.package_count,
+                result.path.display()
+            ));
+        }
+        LockfileCommand::Diff(diff_args) => {
+            let from = source(&diff_args.from);
+            let to = diff_args
+                .to
+                .as_deref()
+                .map(source)
+                .unwrap_or(DiffSource::WorkingTree);
+
+            let diff = operations::diff_lockfile(&root, &from, &to)?;
+            if diff_args.json {
+                println!("{}", serde_json::to_string_pretty(&diff)?);
+            } else {
+                print_diff(&diff);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// An argument naming an existing file is a lockfile path; anything else
+/// is a git revision.
+fn source(value: &str) -> DiffSource {
+    if Path::new(value).is_file() {
+        DiffSource::File(PathBuf::from(value))
+    } else {
+        DiffSource::Revision(value.to_string())
+    }
+}
+
+fn print_diff(diff: &LockfileDiff) {
+    if diff.is_empty() {
+        println!("No lockfile changes between {} and {}.", diff.from, diff.to);
+        return;
+    }
+
+    let rows: Vec<[&str; 4]> = diff
+        .changes
+        .iter()
+        .map(|change| {
+            [
+                change.name.as_str(),
+                change.kind.as_str(),
+                change.from.as_deref().unwrap_or("-"),
+                change.to.as_deref().unwrap_or("-"),
+            ]
+        })
+        .collect();
+
+    if !rows.is_empty() {
+        let headers = ["Package", "Change", "From", "To"];
+        let widths: Vec<usize> = (0..3)
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].len())
+                    .chain([headers[column].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        println!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            headers[0],
+            headers[1],
+            headers[2],
+            headers[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+        );
+        println!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  ────",
+            "───────",
+            "──────",
+            "────",
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+        );
+        for row in &rows {
+            println!(
+                "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+            );
+        }
+
+        println!();
+        println!(
+            "{} changes between {} and {}.",
+            diff.changes.len(),
+            diff.from,
+            diff.to
+        );
+    }
+
+    if !diff.new_lifecycle_scripts.is_empty() {
+        println!();
+        println!("New packages with install scripts:");
+        for package in &diff.new_lifecycle_scripts {
+            println!("  {}@{}", package.name, package.version);
+        }
+    }
+}
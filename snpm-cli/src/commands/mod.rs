@@ -1,20 +1,27 @@
 pub mod add;
+pub mod architectures;
 pub mod audit;
+pub mod before;
 pub mod ci;
 pub mod clean;
 pub mod completions;
 pub mod config;
+pub mod dedupe;
+pub mod deploy;
 pub mod dlx;
 pub mod exec;
+pub mod fetch;
 pub mod frozen;
 pub mod init;
 pub mod install;
 pub mod licenses;
 pub mod link;
 pub mod list;
+pub mod lockfile;
 pub mod login;
 pub mod logout;
 pub mod node;
+pub mod offline;
 pub mod outdated;
 pub mod pack;
 pub mod patch;
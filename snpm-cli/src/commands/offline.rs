@@ -0,0 +1,47 @@
+use std::borrow::Cow;
+
+use snpm_core::{OfflineMode, SnpmConfig};
+
+pub(crate) fn offline_mode_from_flags(offline: bool, prefer_offline: bool) -> Option<OfflineMode> {
+    if offline {
+        Some(OfflineMode::Offline)
+    } else if prefer_offline {
+        Some(OfflineMode::PreferOffline)
+    } else {
+        None
+    }
+}
+
+/// `config` with `--offline` / `--prefer-offline` applied, borrowed
+/// unchanged when neither flag was passed.
+pub(crate) fn apply_offline_flags(
+    config: &SnpmConfig,
+    offline: bool,
+    prefer_offline: bool,
+) -> Cow<'_, SnpmConfig> {
+    match offline_mode_from_flags(offline, prefer_offline) {
+        Some(offline_mode) => Cow::Owned(SnpmConfig {
+            offline_mode,
+            ..config.clone()
+        }),
+        None => Cow::Borrowed(config),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offline_wins_over_prefer_offline() {
+        assert_eq!(offline_mode_from_flags(false, false), None);
+        assert_eq!(
+            offline_mode_from_flags(false, true),
+            Some(OfflineMode::PreferOffline)
+        );
+        assert_eq!(
+            offline_mode_from_flags(true, true),
+            Some(OfflineMode::Offline)
+        );
+    }
+}
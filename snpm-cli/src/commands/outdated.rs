@@ -21,6 +21,9 @@ pub struct OutdatedArgs {
     /// Emit results as JSON
     #[arg(long)]
     pub json: bool,
+    /// List deprecated dependencies (direct and transitive) instead
+    #[arg(long)]
+    pub deprecated: bool,
 }
 
 /// One project's outdated report, paired with a display label.
@@ -29,13 +32,25 @@ struct ProjectReport {
     entries: Vec<operations::OutdatedEntry>,
 }
 
+/// One project's deprecated dependencies, paired with a display label.
+struct DeprecatedReport {
+    label: String,
+    packages: Vec<operations::DeprecatedPackage>,
+}
+
 pub async fn run(args: OutdatedArgs, config: &SnpmConfig) -> Result<()> {
     if !args.json {
         console::header("outdated", env!("CARGO_PKG_VERSION"));
     }
 
     let cwd = env::current_dir().context("failed to determine current directory")?;
-    let reports = collect_reports(&args, config, &cwd).await?;
+    let projects = select_projects(&args, &cwd)?;
+
+    if args.deprecated {
+        return run_deprecated(&args, config, projects).await;
+    }
+
+    let reports = collect_reports(&args, config, projects).await?;
 
     if args.json {
         print_json(&reports);
@@ -64,14 +79,9 @@ pub async fn run(args: OutdatedArgs, config: &SnpmConfig) -> Result<()> {
     Ok(())
 }
 
-/// Resolve the target projects and gather each one's outdated entries.
-async fn collect_reports(
-    args: &OutdatedArgs,
-    config: &SnpmConfig,
-    cwd: &std::path::Path,
-) -> Result<Vec<ProjectReport>> {
-    let include_dev = !args.production;
-
+/// The projects to report on: the selected workspace members, every
+/// member when run from the workspace root, or the current project.
+fn select_projects(args: &OutdatedArgs, cwd: &std::path::Path) -> Result<Vec<Project>> {
     if let Some(WorkspaceSelection {
         projects,
         filter_label: _,
@@ -82,37 +92,76 @@ async fn collect_reports(
         &args.filter,
         &args.filter_prod,
     )? {
-        let mut reports = Vec::new();
-        for project in projects.into_iter() {
-            let entries = operations::outdated(config, &project, include_dev, false).await?;
-            reports.push(ProjectReport {
-                label: workspace_selector::project_label(&project),
-                entries,
-            });
-        }
-        return Ok(reports);
+        return Ok(projects);
     }
 
     if let Some(workspace) = snpm_core::Workspace::discover(cwd)?
         && workspace.root == *cwd
     {
-        let mut reports = Vec::new();
-        for project in workspace.projects.iter() {
-            let entries = operations::outdated(config, project, include_dev, false).await?;
-            reports.push(ProjectReport {
-                label: workspace_selector::project_label(project),
-                entries,
-            });
+        return Ok(workspace.projects);
+    }
+
+    Ok(vec![Project::discover(cwd)?])
+}
+
+/// Gather each project's outdated entries.
+async fn collect_reports(
+    args: &OutdatedArgs,
+    config: &SnpmConfig,
+    projects: Vec<Project>,
+) -> Result<Vec<ProjectReport>> {
+    let include_dev = !args.production;
+    let mut reports = Vec::new();
+    for project in projects {
+        let entries = operations::outdated(config, &project, include_dev, false).await?;
+        reports.push(ProjectReport {
+            label: workspace_selector::project_label(&project),
+            entries,
+        });
+    }
+    Ok(reports)
+}
+
+async fn run_deprecated(
+    args: &OutdatedArgs,
+    config: &SnpmConfig,
+    projects: Vec<Project>,
+) -> Result<()> {
+    let include_dev = !args.production;
+    let mut reports = Vec::new();
+    for project in projects {
+        let packages = operations::deprecated(config, &project, include_dev, false).await?;
+        reports.push(DeprecatedReport {
+            label: workspace_selector::project_label(&project),
+            packages,
+        });
+    }
+
+    if args.json {
+        print_deprecated_json(&reports);
+        return Ok(());
+    }
+
+    let mut any = false;
+    for report in &reports {
+        if report.packages.is_empty() {
+            continue;
         }
-        return Ok(reports);
+        if any {
+            println!();
+        }
+        any = true;
+        if !report.label.is_empty() {
+            println!("{}", report.label);
+        }
+        print_deprecated(&report.packages);
+    }
+
+    if !any {
+        console::info("No deprecated dependencies.");
     }
 
-    let project = Project::discover(cwd)?;
-    let entries = operations::outdated(config, &project, include_dev, false).await?;
-    Ok(vec![ProjectReport {
-        label: workspace_selector::project_label(&project),
-        entries,
-    }])
+    Ok(())
 }
 
 fn print_json(reports: &[ProjectReport]) {
@@ -169,3 +218,39 @@ fn print_outdated(entries: &[operations::OutdatedEntry]) {
         );
     }
 }
+
+fn print_deprecated_json(reports: &[DeprecatedReport]) {
+    let mut rows = Vec::new();
+    for report in reports {
+        for package in &report.packages {
+            rows.push(serde_json::json!({
+                "name": package.name,
+                "version": package.version,
+                "direct": package.direct,
+                "path": package.path,
+                "message": package.message,
+                "project": report.label,
+            }));
+        }
+    }
+
+    match serde_json::to_string_pretty(&serde_json::Value::Array(rows)) {
+        Ok(text) => println!("{text}"),
+        Err(_) => println!("[]"),
+    }
+}
+
+fn print_deprecated(packages: &[operations::DeprecatedPackage]) {
+    for package in packages {
+        let kind = if package.direct {
+            "direct"
+        } else {
+            "transitive"
+        };
+        println!("{}@{} ({kind})", package.name, package.version);
+        println!("  {}", package.message);
+        if !package.direct {
+            println!("  via {}", package.path);
+        }
+    }
+}
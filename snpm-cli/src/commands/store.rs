@@ -1,6 +1,7 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use clap::Subcommand;
-use snpm_core::{SnpmConfig, console, operations};
+use snpm_core::{Project, SnpmConfig, Workspace, console, operations};
+use std::path::PathBuf;
 
 #[derive(clap::Args, Debug)]
 pub struct StoreArgs {
@@ -12,8 +13,10 @@ pub struct StoreArgs {
 pub enum StoreCommand {
     /// Show store disk usage
     Status,
-    /// Remove incomplete or orphaned packages from the store
+    /// Remove packages and cache entries no recorded project references
     Prune(PruneArgs),
+    /// Re-hash stored packages to find corrupt or modified files
+    Verify(VerifyArgs),
     /// Print the store path
     Path,
 }
@@ -25,6 +28,13 @@ pub struct PruneArgs {
     pub dry_run: bool,
 }
 
+#[derive(clap::Args, Debug)]
+pub struct VerifyArgs {
+    /// Re-download corrupt packages and relink the projects using them
+    #[arg(long)]
+    pub repair: bool,
+}
+
 pub async fn run(args: StoreArgs, config: &SnpmConfig) -> Result<()> {
     match args.command {
         StoreCommand::Status => {
@@ -36,6 +46,11 @@ pub async fn run(args: StoreArgs, config: &SnpmConfig) -> Result<()> {
                 status.packages_count,
                 operations::format_bytes(status.packages_size)
             );
+            println!(
+                "Files:     {} ({})",
+                status.files_count,
+                operations::format_bytes(status.files_size)
+            );
             println!(
                 "Metadata:  {} ({})",
                 status.metadata_count,
@@ -43,23 +58,171 @@ pub async fn run(args: StoreArgs, config: &SnpmConfig) -> Result<()> {
             );
             println!(
                 "Total:     {}",
-                operations::format_bytes(status.packages_size + status.metadata_size)
+                operations::format_bytes(
+                    status.packages_size + status.files_size + status.metadata_size
+                )
             );
         }
-        StoreCommand::Prune(prune_args) => {
-            let pruned = operations::store_prune(config, prune_args.dry_run)?;
-            if pruned == 0 {
-                println!("Store is clean, nothing to prune.");
-            } else if prune_args.dry_run {
-                println!();
-                console::info(&format!("Would remove {} incomplete packages.", pruned));
-            } else {
-                console::info(&format!("Pruned {} incomplete packages.", pruned));
-            }
-        }
+        StoreCommand::Prune(prune_args) => prune(config, prune_args)?,
+        StoreCommand::Verify(verify_args) => verify(config, verify_args).await?,
         StoreCommand::Path => {
             println!("{}", operations::store_path(config));
         }
     }
     Ok(())
 }
+
+fn prune(config: &SnpmConfig, args: PruneArgs) -> Result<()> {
+    let report = operations::store_prune(config, args.dry_run)?;
+
+    if report.live_projects.is_none() {
+        console::warn(
+            "No projects are recorded yet, so only incomplete packages were considered. Run `snpm install` in your projects to record them.",
+        );
+    }
+    for lockfile in &report.unreadable_lockfiles {
+        console::warn(&format!(
+            "Could not read {}, so no stored packages were removed. Fix or reinstall that project and prune again.",
+            lockfile.display()
+        ));
+    }
+    for lockfile in &report.changed_lockfiles {
+        console::warn(&format!(
+            "{} changed since its project was installed, so no stored packages were removed. Run `snpm install` there and prune again.",
+            lockfile.display()
+        ));
+    }
+    if report.kept_from_baseline > 0 {
+        console::info(&format!(
+            "Kept {} unreferenced packages and virtual store entries from before projects were recorded; a project installed by an older snpm may still use them.",
+            report.kept_from_baseline
+        ));
+    }
+
+    if report.is_empty() {
+        println!("Store is clean, nothing to prune.");
+        return Ok(());
+    }
+
+    if args.dry_run {
+        for package in &report.incomplete {
+            println!("{package} (incomplete)");
+        }
+        for package in &report.packages {
+            println!("{package}");
+        }
+        for project in &report.dead_projects {
+            println!("{} (project no longer exists)", project.display());
+        }
+        println!();
+    }
+
+    let verb = if args.dry_run {
+        "Would remove"
+    } else {
+        "Removed"
+    };
+    console::info(&format!(
+        "{verb} {} unreferenced and {} incomplete packages, {} virtual store entries, {} side-effects cache slots and {} pooled files.",
+        report.packages.len(),
+        report.incomplete.len(),
+        report.virtual_store_entries,
+        report.side_effects_slots,
+        report.pooled_files
+    ));
+
+    if !report.dead_projects.is_empty() {
+        let verb = if args.dry_run {
+            "Would forget"
+        } else {
+            "Forgot"
+        };
+        console::info(&format!(
+            "{verb} {} projects that no longer exist.",
+            report.dead_projects.len()
+        ));
+    }
+
+    let freed = operations::format_bytes(report.reclaimable_bytes);
+    if args.dry_run {
+        console::info(&format!("{freed} reclaimable."));
+    } else {
+        console::info(&format!("Freed {freed}."));
+    }
+
+    Ok(())
+}
+
+async fn verify(config: &SnpmConfig, args: VerifyArgs) -> Result<()> {
+    let options = operations::StoreVerifyOptions {
+        repair: args.repair,
+        project_roots: current_project_roots()?,
+    };
+    let report = operations::store_verify(config, &options).await?;
+
+    for package in &report.corrupt {
+        let status = if package.repaired { " (repaired)" } else { "" };
+        println!("{}@{}{status}", package.name, package.version);
+        for problem in &package.problems {
+            println!("  {problem}");
+        }
+        if let Some(error) = &package.repair_error {
+            console::warn(&format!(
+                "failed to repair {}@{}: {error}",
+                package.name, package.version
+            ));
+        }
+    }
+
+    if !report.corrupt.is_empty() {
+        println!();
+    }
+
+    console::info(&format!(
+        "Checked {} packages: {} corrupt, {} without a file manifest.",
+        report.checked,
+        report.corrupt.len(),
+        report.unverifiable
+    ));
+
+    if report.relinked > 0 {
+        console::info(&format!(
+            "Relinked {} virtual store entries.",
+            report.relinked
+        ));
+    }
+
+    let unrepaired = report
+        .corrupt
+        .iter()
+        .filter(|package| !package.repaired)
+        .count();
+    if unrepaired > 0 {
+        if args.repair {
+            bail!("{unrepaired} corrupt packages could not be repaired");
+        }
+        bail!("{unrepaired} corrupt packages in the store; run `snpm store verify --repair`");
+    }
+
+    Ok(())
+}
+
+/// The project (and workspace) in the current directory, whose virtual
+/// stores `--repair` relinks alongside the shared one.
+fn current_project_roots() -> Result<Vec<PathBuf>> {
+    let cwd = std::env::current_dir()?;
+    if let Some(workspace) = Workspace::discover(&cwd)? {
+        let mut roots = vec![workspace.root.clone()];
+        roots.extend(
+            workspace
+                .projects
+                .iter()
+                .map(|project| project.root.clone()),
+        );
+        return Ok(roots);
+    }
+
+    Ok(Project::discover(&cwd)
+        .map(|project| vec![project.root])
+        .unwrap_or_default())
+}
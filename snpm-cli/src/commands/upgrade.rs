@@ -1,3 +1,5 @@
+use super::architectures::ArchitectureArgs;
+use super::before::BeforeArgs;
 use super::workspace::{self as workspace_selector, WorkspaceSelection};
 use anyhow::{Context, Result};
 use clap::Args;
@@ -26,6 +28,16 @@ pub struct UpgradeArgs {
     /// ranges beyond the current constraint (npm's `update --latest`)
     #[arg(long)]
     pub latest: bool,
+    /// Never touch the network; fail if metadata or a tarball is not cached
+    #[arg(long)]
+    pub offline: bool,
+    /// Use cached metadata and tarballs when present, fetching only what is missing
+    #[arg(long = "prefer-offline", conflicts_with = "offline")]
+    pub prefer_offline: bool,
+    #[command(flatten)]
+    pub architectures: ArchitectureArgs,
+    #[command(flatten)]
+    pub before: BeforeArgs,
     /// Packages to upgrade (omit to refresh the lockfile and reinstall)
     pub packages: Vec<String>,
 }
@@ -34,6 +46,9 @@ pub async fn run(args: UpgradeArgs, config: &SnpmConfig) -> Result<()> {
     console::header("upgrade", env!("CARGO_PKG_VERSION"));
 
     let cwd = env::current_dir().context("failed to determine current directory")?;
+    let config = &*super::offline::apply_offline_flags(config, args.offline, args.prefer_offline);
+    let config = &*super::architectures::apply_architecture_flags(config, args.architectures);
+    let config = &*super::before::apply_before_flag(config, &args.before)?;
     let frozen_lockfile = super::frozen::resolve_frozen_lockfile_mode(config, None);
 
     if let Some(WorkspaceSelection {
@@ -142,6 +157,7 @@ pub async fn run(args: UpgradeArgs, config: &SnpmConfig) -> Result<()> {
             frozen_lockfile.mode,
             frozen_lockfile.strict_no_lockfile,
             args.force,
+            false,
         )
         .await?;
 
@@ -157,6 +173,7 @@ pub async fn run(args: UpgradeArgs, config: &SnpmConfig) -> Result<()> {
         frozen_lockfile: frozen_lockfile.mode,
         strict_no_lockfile: frozen_lockfile.strict_no_lockfile,
         force: args.force,
+        lockfile_only: false,
         silent_summary: false,
     };
 
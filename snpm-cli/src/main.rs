@@ -42,6 +42,9 @@ async fn run() -> Result<()> {
     init_tracing()?;
 
     let mut config = SnpmConfig::from_env();
+    config
+        .engine_targets
+        .set_snpm_version(env!("CARGO_PKG_VERSION"));
 
     if verbose || config.verbose || config.log_file.is_some() {
         config.verbose = true;
@@ -69,6 +72,9 @@ async fn run() -> Result<()> {
         Command::Init(args) => commands::init::run(args).await?,
         Command::Dlx(args) => commands::dlx::run(args, &config).await?,
         Command::Upgrade(args) => commands::upgrade::run(args, &config).await?,
+        Command::Dedupe(args) => commands::dedupe::run(args, &config).await?,
+        Command::Fetch(args) => commands::fetch::run(args, &config).await?,
+        Command::Lockfile(args) => commands::lockfile::run(args).await?,
         Command::Outdated(args) => commands::outdated::run(args, &config).await?,
         Command::Licenses(args) => commands::licenses::run(args).await?,
         Command::Link(args) => commands::link::run(args, &config).await?,
@@ -77,6 +83,7 @@ async fn run() -> Result<()> {
         Command::Logout(args) => commands::logout::run(args, &config).await?,
         Command::Config(args) => commands::config::run(args, &config).await?,
         Command::Pack(args) => commands::pack::run(args).await?,
+        Command::Deploy(args) => commands::deploy::run(args, &config).await?,
         Command::Publish(args) => commands::publish::run(args, &config).await?,
         Command::Rebuild(args) => commands::rebuild::run(args, &config).await?,
         Command::Patch(args) => commands::patch::run(args, &config).await?,
@@ -24,8 +24,7 @@ pub fn load_metadata_with_offline(
         && let Some(package) = record.package
     {
         let fresh = is_fresh(config, record.updated_at_unix_secs);
-        let has_required_age_metadata =
-            config.min_package_age_days.is_none() || !package.time.is_empty();
+        let has_required_age_metadata = !config.needs_publish_times() || !package.time.is_empty();
         let usable_for_mode = fresh
             || matches!(
                 offline_mode,
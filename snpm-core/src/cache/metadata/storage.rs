@@ -11,7 +11,7 @@ use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-const PACKUMENT_CACHE_VERSION: u32 = 1;
+const PACKUMENT_CACHE_VERSION: u32 = 6;
 
 static SHARD_WRITE_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
@@ -32,11 +32,16 @@ fn make_package() -> RegistryPackage {
             dist: RegistryDist {
                 tarball: "https://example.com/pkg.tgz".to_string(),
                 integrity: None,
+                signatures: Vec::new(),
+                attestations: None,
             },
             os: vec![],
             cpu: vec![],
             libc: vec![],
+            engines: BTreeMap::from([("node".to_string(), ">=18".to_string())]),
+            deprecated: Some("use pkg@2".to_string()),
             bin: None,
+            has_install_script: false,
         },
     );
     let mut dist_tags = BTreeMap::new();
@@ -62,6 +67,11 @@ fn save_and_load_metadata_roundtrip() {
 
     let loaded = loaded.unwrap();
     assert!(loaded.versions.contains_key("1.0.0"));
+    assert_eq!(loaded.versions["1.0.0"].engines["node"], ">=18");
+    assert_eq!(
+        loaded.versions["1.0.0"].deprecated.as_deref(),
+        Some("use pkg@2")
+    );
     assert_eq!(
         loaded.dist_tags.get("latest").map(String::as_str),
         Some("1.0.0")
@@ -136,11 +146,16 @@ fn save_metadata_updates_existing_shard_entry_without_dropping_headers() {
             dist: RegistryDist {
                 tarball: "https://registry.npmjs.org/test-pkg/-/test-pkg-2.0.0.tgz".to_string(),
                 integrity: None,
+                signatures: Vec::new(),
+                attestations: None,
             },
             os: Vec::new(),
             cpu: Vec::new(),
             libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
             bin: None,
+            has_install_script: false,
         },
     );
     let headers = CachedHeaders {
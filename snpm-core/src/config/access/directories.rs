@@ -11,6 +11,30 @@ impl SnpmConfig {
         self.data_dir.join("packages")
     }
 
+    /// Content-addressed pool every stored package file is hard-linked from.
+    pub fn files_dir(&self) -> PathBuf {
+        self.data_dir.join("files")
+    }
+
+    pub fn store_version_path(&self) -> PathBuf {
+        self.data_dir.join("store-version")
+    }
+
+    /// Projects that have installed from this store, for `store prune`.
+    pub fn project_registry_path(&self) -> PathBuf {
+        self.data_dir.join("projects-v1.json")
+    }
+
+    /// What the store held when the project registry was created.
+    pub fn store_baseline_path(&self) -> PathBuf {
+        self.data_dir.join("projects-v1-baseline.json")
+    }
+
+    /// Local OSV advisory dump that `audit db sync` refreshes.
+    pub fn advisory_db_dir(&self) -> PathBuf {
+        self.data_dir.join("advisory-db")
+    }
+
     pub fn virtual_store_dir(&self) -> PathBuf {
         self.data_dir.join("virtual-store")
     }
@@ -23,6 +47,11 @@ impl SnpmConfig {
         self.data_dir.join("metadata")
     }
 
+    /// Per-registry copies of the `/-/npm/v1/keys` signing key sets.
+    pub fn registry_keys_dir(&self) -> PathBuf {
+        self.metadata_dir().join("registry-keys-v1")
+    }
+
     pub fn store_residency_index_path(&self) -> PathBuf {
         self.metadata_dir().join("store-residency-v1.bin")
     }
@@ -1,4 +1,4 @@
-use crate::config::{AuthScheme, HoistingMode, LinkBackend, SnpmConfig};
+use crate::config::{AuthScheme, HoistingMode, LinkBackend, OfflineMode, SnpmConfig};
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
@@ -39,6 +39,16 @@ fn authorization_header_uses_scoped_basic_auth() {
         frozen_lockfile_default: false,
         always_auth: false,
         registry_concurrency: 64,
+        offline_mode: OfflineMode::Online,
+        supported_architectures: Default::default(),
+        resolution_mode: Default::default(),
+        before: None,
+        fail_on_deprecated: false,
+        engine_strict: false,
+        engine_targets: Default::default(),
+        verify_signatures: false,
+        trust_policy: Default::default(),
+        trust_policy_exclude: BTreeSet::new(),
         verbose: false,
         log_file: None,
         remote_cache_url: None,
@@ -320,6 +330,23 @@ fn derived_directories() {
         PathBuf::from("/tmp/cache/tarballs-v1")
     );
     assert_eq!(config.packages_dir(), PathBuf::from("/tmp/data/packages"));
+    assert_eq!(config.files_dir(), PathBuf::from("/tmp/data/files"));
+    assert_eq!(
+        config.store_version_path(),
+        PathBuf::from("/tmp/data/store-version")
+    );
+    assert_eq!(
+        config.project_registry_path(),
+        PathBuf::from("/tmp/data/projects-v1.json")
+    );
+    assert_eq!(
+        config.store_baseline_path(),
+        PathBuf::from("/tmp/data/projects-v1-baseline.json")
+    );
+    assert_eq!(
+        config.advisory_db_dir(),
+        PathBuf::from("/tmp/data/advisory-db")
+    );
     assert_eq!(
         config.virtual_store_dir(),
         PathBuf::from("/tmp/data/virtual-store")
@@ -329,6 +356,10 @@ fn derived_directories() {
         PathBuf::from("/tmp/data/side-effects-v1")
     );
     assert_eq!(config.metadata_dir(), PathBuf::from("/tmp/data/metadata"));
+    assert_eq!(
+        config.registry_keys_dir(),
+        PathBuf::from("/tmp/data/metadata/registry-keys-v1")
+    );
     assert_eq!(
         config.store_residency_index_path(),
         PathBuf::from("/tmp/data/metadata/store-residency-v1.bin")
@@ -1,6 +1,8 @@
 use super::super::env_vars::{ConfigEnvPrefix, read_config_env};
 use std::collections::BTreeSet;
 use std::env;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime, Time};
 
 pub fn expand_env_vars(text: &str) -> String {
     let mut expanded = String::new();
@@ -103,6 +105,36 @@ pub fn parse_package_name_list(value: &str) -> BTreeSet<String> {
         .collect()
 }
 
+/// Parses a `before` cutoff: an RFC 3339 timestamp, or a bare
+/// `YYYY-MM-DD` date meaning midnight UTC at the start of that day.
+pub fn parse_before(value: &str) -> Option<OffsetDateTime> {
+    let value = value.trim();
+    if let Ok(timestamp) = OffsetDateTime::parse(value, &Rfc3339) {
+        return Some(timestamp);
+    }
+
+    let format = time::format_description::parse("[year]-[month]-[day]").ok()?;
+    let date = Date::parse(value, &format).ok()?;
+    Some(date.with_time(Time::MIDNIGHT).assume_utc())
+}
+
+pub fn read_before_from_env() -> Option<OffsetDateTime> {
+    env::var("SNPM_BEFORE")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| {
+            read_config_env(
+                "before",
+                &[
+                    ConfigEnvPrefix::Snpm,
+                    ConfigEnvPrefix::Pnpm,
+                    ConfigEnvPrefix::Npm,
+                ],
+            )
+        })
+        .and_then(|value| parse_before(&value))
+}
+
 pub fn read_min_package_age_from_env() -> Option<u32> {
     parse_positive_u32_env("SNPM_MIN_PACKAGE_AGE_DAYS")
 }
@@ -223,6 +255,17 @@ mod tests {
         assert!(parse_package_name_list("[]").is_empty());
     }
 
+    #[test]
+    fn parse_before_accepts_timestamps_and_bare_dates() {
+        let timestamp = parse_before("2024-05-01T12:30:00+02:00").unwrap();
+        assert_eq!(timestamp.unix_timestamp(), 1_714_559_400);
+
+        let date = parse_before(" 2024-05-01 ").unwrap();
+        assert_eq!(date.unix_timestamp(), 1_714_521_600);
+
+        assert!(parse_before("May 1st").is_none());
+    }
+
     #[test]
     fn read_disable_global_virtual_store_accepts_pnpm_config_alias() {
         let _lock = env_lock();
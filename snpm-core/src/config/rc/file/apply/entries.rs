@@ -1,7 +1,9 @@
 use super::super::super::types::RegistryConfig;
 use super::super::super::url::normalize_registry_url;
 use super::auth::apply_scoped_auth;
-use crate::config::{HoistingMode, parse_package_name_list};
+use crate::config::{
+    HoistingMode, ResolutionMode, TrustPolicy, parse_before, parse_package_name_list,
+};
 
 pub(super) fn apply_rc_entry(config: &mut RegistryConfig, key: &str, value: String) {
     if key == "registry" {
@@ -62,6 +64,33 @@ pub(super) fn apply_rc_entry(config: &mut RegistryConfig, key: &str, value: Stri
             // exactly like npm. Preserve it verbatim.
             config.save_prefix = Some(value.trim().to_string());
         }
+        "fail-on-deprecated" | "fail_on_deprecated" | "failOnDeprecated" => {
+            config.fail_on_deprecated = Some(is_enabled(&value));
+        }
+        "engine-strict" | "engine_strict" | "engineStrict" => {
+            config.engine_strict = Some(is_enabled(&value));
+        }
+        "verify-signatures" | "verify_signatures" | "verifySignatures" => {
+            config.verify_signatures = Some(is_enabled(&value));
+        }
+        "before" => {
+            if let Some(cutoff) = parse_before(&value) {
+                config.before = Some(cutoff);
+            }
+        }
+        "trust-policy" | "trust_policy" | "trustPolicy" => {
+            if let Some(policy) = TrustPolicy::parse(&value) {
+                config.trust_policy = Some(policy);
+            }
+        }
+        "trust-policy-exclude" | "trust_policy_exclude" | "trustPolicyExclude" => {
+            config.trust_policy_exclude = Some(parse_package_name_list(&value));
+        }
+        "resolution-mode" | "resolution_mode" | "resolutionMode" => {
+            if let Some(mode) = ResolutionMode::parse(&value) {
+                config.resolution_mode = Some(mode);
+            }
+        }
         _ => {}
     }
 }
@@ -1,7 +1,8 @@
 use super::apply_rc_file;
 use crate::config::rc::types::RegistryConfig;
-use crate::config::{AuthScheme, HoistingMode};
+use crate::config::{AuthScheme, HoistingMode, ResolutionMode, TrustPolicy};
 
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::Path;
 use tempfile::NamedTempFile;
@@ -45,6 +46,89 @@ fn apply_rc_file_parses_save_exact_and_save_prefix() {
     assert_eq!(config.save_prefix.as_deref(), Some("~"));
 }
 
+#[test]
+fn apply_rc_file_parses_resolution_mode() {
+    let file = NamedTempFile::new().unwrap();
+    fs::write(file.path(), "resolution-mode=lowest-direct\n").unwrap();
+
+    let mut config = RegistryConfig::default();
+    apply_rc_file(file.path(), &mut config);
+
+    assert_eq!(config.resolution_mode, Some(ResolutionMode::LowestDirect));
+}
+
+#[test]
+fn apply_rc_file_parses_fail_on_deprecated() {
+    let file = NamedTempFile::new().unwrap();
+    fs::write(file.path(), "fail-on-deprecated=true\n").unwrap();
+
+    let mut config = RegistryConfig::default();
+    apply_rc_file(file.path(), &mut config);
+
+    assert_eq!(config.fail_on_deprecated, Some(true));
+}
+
+#[test]
+fn apply_rc_file_parses_engine_strict() {
+    let file = NamedTempFile::new().unwrap();
+    fs::write(file.path(), "engine-strict=true\n").unwrap();
+
+    let mut config = RegistryConfig::default();
+    apply_rc_file(file.path(), &mut config);
+
+    assert_eq!(config.engine_strict, Some(true));
+}
+
+#[test]
+fn apply_rc_file_parses_verify_signatures() {
+    let file = NamedTempFile::new().unwrap();
+    fs::write(file.path(), "verify-signatures=true\n").unwrap();
+
+    let mut config = RegistryConfig::default();
+    apply_rc_file(file.path(), &mut config);
+
+    assert_eq!(config.verify_signatures, Some(true));
+}
+
+#[test]
+fn apply_rc_file_parses_trust_policy() {
+    let file = NamedTempFile::new().unwrap();
+    fs::write(
+        file.path(),
+        "trust-policy=no-downgrade\ntrust-policy-exclude=legacy-lib, pinned@1.2.3\n",
+    )
+    .unwrap();
+
+    let mut config = RegistryConfig::default();
+    apply_rc_file(file.path(), &mut config);
+
+    assert_eq!(config.trust_policy, Some(TrustPolicy::NoDowngrade));
+    assert_eq!(
+        config.trust_policy_exclude,
+        Some(BTreeSet::from([
+            "legacy-lib".to_string(),
+            "pinned@1.2.3".to_string()
+        ]))
+    );
+}
+
+#[test]
+fn apply_rc_file_parses_before_and_ignores_invalid_dates() {
+    let file = NamedTempFile::new().unwrap();
+    fs::write(file.path(), "before=2024-05-01\n").unwrap();
+
+    let mut config = RegistryConfig::default();
+    apply_rc_file(file.path(), &mut config);
+    assert_eq!(
+        config.before.map(|cutoff| cutoff.unix_timestamp()),
+        Some(1_714_521_600)
+    );
+
+    fs::write(file.path(), "before=yesterday\n").unwrap();
+    apply_rc_file(file.path(), &mut config);
+    assert!(config.before.is_some());
+}
+
 #[test]
 fn apply_rc_file_treats_bare_flag_line_as_true() {
     // npm's ini parser reads a bare `save-exact` line as save-exact=true.
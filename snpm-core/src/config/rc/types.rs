@@ -1,5 +1,6 @@
-use crate::config::{AuthScheme, HoistingMode};
+use crate::config::{AuthScheme, HoistingMode, ResolutionMode, TrustPolicy};
 use std::collections::{BTreeMap, BTreeSet};
+use time::OffsetDateTime;
 
 #[derive(Default)]
 pub struct RegistryConfig {
@@ -14,4 +15,11 @@ pub struct RegistryConfig {
     pub always_auth: bool,
     pub save_exact: Option<bool>,
     pub save_prefix: Option<String>,
+    pub resolution_mode: Option<ResolutionMode>,
+    pub before: Option<OffsetDateTime>,
+    pub fail_on_deprecated: Option<bool>,
+    pub engine_strict: Option<bool>,
+    pub verify_signatures: Option<bool>,
+    pub trust_policy: Option<TrustPolicy>,
+    pub trust_policy_exclude: Option<BTreeSet<String>>,
 }
@@ -1,5 +1,5 @@
 use super::super::rc::{host_from_url, normalize_registry_url};
-use super::super::{AuthScheme, HoistingMode, LinkBackend};
+use super::super::{AuthScheme, HoistingMode, LinkBackend, ResolutionMode, TrustPolicy};
 use crate::config::env_vars::{ConfigEnvPrefix, read_config_env, read_non_empty_env};
 
 use std::path::PathBuf;
@@ -54,6 +54,7 @@ pub(super) fn apply_install_env(
     hoisting: &mut HoistingMode,
     link_backend: &mut LinkBackend,
     strict_peers: &mut bool,
+    resolution_mode: &mut ResolutionMode,
     frozen_lockfile_default: &mut bool,
     registry_concurrency: &mut usize,
     always_auth: &mut bool,
@@ -81,6 +82,16 @@ pub(super) fn apply_install_env(
         *strict_peers = env_flag_is_enabled(&value);
     }
 
+    if let Some(value) = read_non_empty_env("SNPM_RESOLUTION_MODE").or_else(|| {
+        read_config_env(
+            "resolution_mode",
+            &[ConfigEnvPrefix::Snpm, ConfigEnvPrefix::Pnpm],
+        )
+    }) && let Some(mode) = ResolutionMode::parse(&value)
+    {
+        *resolution_mode = mode;
+    }
+
     if let Some(value) = read_non_empty_env("SNPM_FROZEN_LOCKFILE").or_else(|| {
         read_config_env(
             "frozen_lockfile",
@@ -113,6 +124,52 @@ pub(super) fn apply_install_env(
     }
 }
 
+pub(super) fn apply_deprecation_env(fail_on_deprecated: &mut bool) {
+    if let Some(value) = read_non_empty_env("SNPM_FAIL_ON_DEPRECATED")
+        .or_else(|| read_config_env("fail_on_deprecated", &[ConfigEnvPrefix::Snpm]))
+    {
+        *fail_on_deprecated = env_flag_is_enabled(&value);
+    }
+}
+
+pub(super) fn apply_engine_env(engine_strict: &mut bool) {
+    if let Some(value) = read_non_empty_env("SNPM_ENGINE_STRICT").or_else(|| {
+        read_config_env(
+            "engine_strict",
+            &[
+                ConfigEnvPrefix::Snpm,
+                ConfigEnvPrefix::Pnpm,
+                ConfigEnvPrefix::Npm,
+            ],
+        )
+    }) {
+        *engine_strict = env_flag_is_enabled(&value);
+    }
+}
+
+pub(super) fn apply_signatures_env(verify_signatures: &mut bool) {
+    if let Some(value) = read_non_empty_env("SNPM_VERIFY_SIGNATURES").or_else(|| {
+        read_config_env(
+            "verify_signatures",
+            &[ConfigEnvPrefix::Snpm, ConfigEnvPrefix::Pnpm],
+        )
+    }) {
+        *verify_signatures = env_flag_is_enabled(&value);
+    }
+}
+
+pub(super) fn apply_trust_policy_env(trust_policy: &mut TrustPolicy) {
+    if let Some(value) = read_non_empty_env("SNPM_TRUST_POLICY").or_else(|| {
+        read_config_env(
+            "trust_policy",
+            &[ConfigEnvPrefix::Snpm, ConfigEnvPrefix::Pnpm],
+        )
+    }) && let Some(policy) = TrustPolicy::parse(&value)
+    {
+        *trust_policy = policy;
+    }
+}
+
 pub(super) fn apply_save_env(save_exact: &mut bool, save_prefix: &mut String) {
     if let Some(value) = read_config_env(
         "save_exact",
@@ -317,16 +374,23 @@ mod tests {
             "PNPM_CONFIG_ALWAYS_AUTH",
             "npm_config_always_auth",
             "NPM_CONFIG_ALWAYS_AUTH",
+            "SNPM_RESOLUTION_MODE",
+            "snpm_config_resolution_mode",
+            "SNPM_CONFIG_RESOLUTION_MODE",
+            "pnpm_config_resolution_mode",
+            "PNPM_CONFIG_RESOLUTION_MODE",
         ]);
         env.set("PNPM_CONFIG_HOIST", "none");
         env.set("PNPM_CONFIG_STRICT_PEER_DEPENDENCIES", "true");
         env.set("PNPM_CONFIG_FROZEN_LOCKFILE", "1");
         env.set("SNPM_CONFIG_REGISTRY_CONCURRENCY", "8");
         env.set("PNPM_CONFIG_ALWAYS_AUTH", "yes");
+        env.set("PNPM_CONFIG_RESOLUTION_MODE", "time-based");
 
         let mut hoisting = HoistingMode::SingleVersion;
         let mut link_backend = LinkBackend::Auto;
         let mut strict_peers = false;
+        let mut resolution_mode = ResolutionMode::Highest;
         let mut frozen_lockfile = false;
         let mut registry_concurrency = 128;
         let mut always_auth = false;
@@ -335,6 +399,7 @@ mod tests {
             &mut hoisting,
             &mut link_backend,
             &mut strict_peers,
+            &mut resolution_mode,
             &mut frozen_lockfile,
             &mut registry_concurrency,
             &mut always_auth,
@@ -342,6 +407,7 @@ mod tests {
 
         assert_eq!(hoisting, HoistingMode::None);
         assert!(strict_peers);
+        assert_eq!(resolution_mode, ResolutionMode::TimeBased);
         assert!(frozen_lockfile);
         assert_eq!(registry_concurrency, 8);
         assert!(always_auth);
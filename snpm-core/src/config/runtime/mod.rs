@@ -2,17 +2,19 @@ mod dirs;
 mod env;
 
 use super::rc::{
-    read_allow_scripts_from_env, read_disable_global_virtual_store_for_packages_from_env,
-    read_min_package_age_from_env, read_min_package_cache_age_from_env, read_registry_config,
+    read_allow_scripts_from_env, read_before_from_env,
+    read_disable_global_virtual_store_for_packages_from_env, read_min_package_age_from_env,
+    read_min_package_cache_age_from_env, read_registry_config,
 };
 use super::{
-    AuthScheme, HoistingMode, LinkBackend, SnpmConfig,
+    AuthScheme, HoistingMode, LinkBackend, OfflineMode, ResolutionMode, SnpmConfig, TrustPolicy,
     default_disable_global_virtual_store_for_packages,
 };
 
 use dirs::resolve_home_dirs;
 use env::{
-    apply_auth_env, apply_default_registry_env, apply_install_env, apply_save_env,
+    apply_auth_env, apply_default_registry_env, apply_deprecation_env, apply_engine_env,
+    apply_install_env, apply_save_env, apply_signatures_env, apply_trust_policy_env,
     read_logging_env, read_remote_cache_env,
 };
 
@@ -25,6 +27,7 @@ impl SnpmConfig {
 
         let runtime_config = read_registry_config();
         let runtime_config_default_auth_basic = runtime_config.default_auth_basic;
+        let before = read_before_from_env().or(runtime_config.before);
 
         let mut default_registry = runtime_config.default_registry;
         let scoped_registries = runtime_config.scoped;
@@ -44,6 +47,9 @@ impl SnpmConfig {
             .save_prefix
             .clone()
             .unwrap_or_else(|| "^".to_string());
+        let mut resolution_mode = runtime_config
+            .resolution_mode
+            .unwrap_or(ResolutionMode::Highest);
         let mut frozen_lockfile_default = false;
         let mut registry_concurrency = 128;
         let mut default_registry_auth_scheme = AuthScheme::Bearer;
@@ -58,11 +64,21 @@ impl SnpmConfig {
             &mut hoisting,
             &mut link_backend,
             &mut strict_peers,
+            &mut resolution_mode,
             &mut frozen_lockfile_default,
             &mut registry_concurrency,
             &mut always_auth,
         );
         apply_save_env(&mut save_exact, &mut save_prefix);
+        let mut fail_on_deprecated = runtime_config.fail_on_deprecated.unwrap_or(false);
+        apply_deprecation_env(&mut fail_on_deprecated);
+        let mut engine_strict = runtime_config.engine_strict.unwrap_or(false);
+        apply_engine_env(&mut engine_strict);
+        let mut verify_signatures = runtime_config.verify_signatures.unwrap_or(false);
+        apply_signatures_env(&mut verify_signatures);
+        let mut trust_policy = runtime_config.trust_policy.unwrap_or(TrustPolicy::Off);
+        apply_trust_policy_env(&mut trust_policy);
+        let trust_policy_exclude = runtime_config.trust_policy_exclude.unwrap_or_default();
         if let Some(packages) = read_disable_global_virtual_store_for_packages_from_env() {
             disable_global_virtual_store_for_packages = packages;
         }
@@ -96,6 +112,16 @@ impl SnpmConfig {
             frozen_lockfile_default,
             always_auth,
             registry_concurrency,
+            offline_mode: OfflineMode::Online,
+            supported_architectures: Default::default(),
+            resolution_mode,
+            before,
+            fail_on_deprecated,
+            engine_strict,
+            engine_targets: Default::default(),
+            verify_signatures,
+            trust_policy,
+            trust_policy_exclude,
             verbose,
             log_file,
             remote_cache_url,
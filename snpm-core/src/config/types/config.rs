@@ -1,7 +1,9 @@
-use super::{AuthScheme, HoistingMode, LinkBackend};
+use super::{AuthScheme, HoistingMode, LinkBackend, OfflineMode, ResolutionMode, TrustPolicy};
+use crate::platform::{EngineTargets, SupportedArchitectures};
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
+use time::OffsetDateTime;
 
 const DEFAULT_DISABLE_GLOBAL_VIRTUAL_STORE_FOR_PACKAGES: [&str; 5] =
     ["next", "nuxt", "vite", "vitepress", "parcel"];
@@ -32,6 +34,50 @@ pub struct SnpmConfig {
     pub frozen_lockfile_default: bool,
     pub always_auth: bool,
     pub registry_concurrency: usize,
+    /// Whether registry metadata and tarballs may come from the network
+    /// (`Online`), come from the cache first (`PreferOffline`), or must
+    /// come from the cache (`Offline`). Set per command by `--offline` /
+    /// `--prefer-offline`.
+    pub offline_mode: OfflineMode,
+    /// Platforms whose optional packages are resolved, locked and fetched
+    /// besides the host's. Set per command by `--os` / `--cpu` / `--libc`;
+    /// dimensions left empty fall back to `supportedArchitectures` in the
+    /// workspace or root manifest.
+    pub supported_architectures: SupportedArchitectures,
+    /// Which matching version the resolver picks for each range. Comes
+    /// from `resolution-mode` in `.snpmrc` / the environment, or
+    /// `resolutionMode` in `snpm-workspace.yaml`, which wins.
+    pub resolution_mode: ResolutionMode,
+    /// Resolve as if the registry stopped at this moment: versions
+    /// published later are invisible. Set by `--before`, `SNPM_BEFORE` or
+    /// `before` in `.snpmrc`.
+    pub before: Option<OffsetDateTime>,
+    /// Fail installs whose direct dependencies resolve to a deprecated
+    /// version instead of only listing them in the deprecation summary.
+    /// Set by `SNPM_FAIL_ON_DEPRECATED` or `fail-on-deprecated` in `.snpmrc`.
+    pub fail_on_deprecated: bool,
+    /// Fail installs that contain a package whose `engines` excludes
+    /// `engine_targets`, and prefer versions whose `engines` fit while
+    /// resolving. Off by default, which only warns. Set by
+    /// `SNPM_ENGINE_STRICT` or `engine-strict` in `.snpmrc`.
+    pub engine_strict: bool,
+    /// The Node, npm and snpm versions `engines` fields are checked
+    /// against. Node (and the npm it bundles) come from the project's
+    /// pinned Node per install; snpm is the running binary.
+    pub engine_targets: EngineTargets,
+    /// Check each installed registry package's `dist.signatures` against
+    /// the registry's published keys. Invalid signatures fail the install;
+    /// missing ones only warn. Set by `SNPM_VERIFY_SIGNATURES` or
+    /// `verify-signatures` in `.snpmrc`.
+    pub verify_signatures: bool,
+    /// Under `TrustPolicy::NoDowngrade`, the resolver rejects versions
+    /// published without the provenance attestation an earlier version
+    /// had. Comes from `trust-policy` in `.snpmrc` / the environment, or
+    /// `trustPolicy` in `snpm-workspace.yaml`, which wins.
+    pub trust_policy: TrustPolicy,
+    /// Packages (`name`) or single versions (`name@version`) the trust
+    /// policy does not apply to.
+    pub trust_policy_exclude: BTreeSet<String>,
     pub verbose: bool,
     pub log_file: Option<PathBuf>,
     /// Base URL of a remote side-effects cache (e.g.
@@ -61,6 +107,21 @@ impl SnpmConfig {
             &self.save_prefix
         }
     }
+
+    /// Whether resolving needs each version's publish time, so packuments
+    /// must be fetched in full rather than abbreviated.
+    pub fn needs_publish_times(&self) -> bool {
+        self.min_package_age_days.is_some()
+            || self.resolution_mode == ResolutionMode::TimeBased
+            || self.before.is_some()
+            || self.trust_policy == TrustPolicy::NoDowngrade
+    }
+
+    /// The exclusions to check versions against when the trust policy
+    /// rejects downgrades, `None` when it is off.
+    pub fn no_downgrade_exclusions(&self) -> Option<&BTreeSet<String>> {
+        (self.trust_policy == TrustPolicy::NoDowngrade).then_some(&self.trust_policy_exclude)
+    }
 }
 
 pub fn default_disable_global_virtual_store_for_packages() -> BTreeSet<String> {
@@ -98,6 +159,16 @@ impl SnpmConfig {
             frozen_lockfile_default: false,
             always_auth: false,
             registry_concurrency: 64,
+            offline_mode: OfflineMode::Online,
+            supported_architectures: Default::default(),
+            resolution_mode: ResolutionMode::Highest,
+            before: None,
+            fail_on_deprecated: false,
+            engine_strict: false,
+            engine_targets: EngineTargets::default(),
+            verify_signatures: false,
+            trust_policy: TrustPolicy::Off,
+            trust_policy_exclude: BTreeSet::new(),
             verbose: false,
             log_file: None,
             remote_cache_url: None,
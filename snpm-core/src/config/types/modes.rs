@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum OfflineMode {
     #[default]
@@ -51,3 +53,70 @@ impl HoistingMode {
         }
     }
 }
+
+/// Which version of a range the resolver picks (`resolutionMode`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResolutionMode {
+    /// The highest matching version, everywhere.
+    #[default]
+    Highest,
+    /// The lowest matching version, everywhere. For testing that declared
+    /// ranges really are the floor a library works with.
+    Lowest,
+    /// The lowest matching version for direct dependencies, the highest
+    /// for everything below them.
+    LowestDirect,
+    /// The highest version for direct dependencies; transitive ones take
+    /// the highest version published no later than the newest direct
+    /// dependency.
+    TimeBased,
+}
+
+impl ResolutionMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "highest" => Some(ResolutionMode::Highest),
+            "lowest" => Some(ResolutionMode::Lowest),
+            "lowest-direct" => Some(ResolutionMode::LowestDirect),
+            "time-based" => Some(ResolutionMode::TimeBased),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResolutionMode::Highest => "highest",
+            ResolutionMode::Lowest => "lowest",
+            ResolutionMode::LowestDirect => "lowest-direct",
+            ResolutionMode::TimeBased => "time-based",
+        }
+    }
+
+    pub fn is_highest(&self) -> bool {
+        *self == ResolutionMode::Highest
+    }
+}
+
+/// What the resolver demands of a version's publish attestations
+/// (`trustPolicy`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrustPolicy {
+    /// No checks.
+    #[default]
+    Off,
+    /// Reject a version published without a provenance attestation when
+    /// an earlier version of the package had one.
+    NoDowngrade,
+}
+
+impl TrustPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "off" => Some(TrustPolicy::Off),
+            "no-downgrade" => Some(TrustPolicy::NoDowngrade),
+            _ => None,
+        }
+    }
+}
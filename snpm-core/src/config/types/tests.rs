@@ -1,4 +1,4 @@
-use super::{HoistingMode, LinkBackend};
+use super::{HoistingMode, LinkBackend, ResolutionMode, TrustPolicy};
 
 #[test]
 fn link_backend_parse_auto() {
@@ -77,3 +77,38 @@ fn hoisting_mode_parse_all() {
 fn hoisting_mode_parse_unknown() {
     assert_eq!(HoistingMode::parse("unknown"), None);
 }
+
+#[test]
+fn resolution_mode_parse_accepts_dashes_and_underscores() {
+    assert_eq!(
+        ResolutionMode::parse("highest"),
+        Some(ResolutionMode::Highest)
+    );
+    assert_eq!(
+        ResolutionMode::parse("Lowest"),
+        Some(ResolutionMode::Lowest)
+    );
+    assert_eq!(
+        ResolutionMode::parse("lowest-direct"),
+        Some(ResolutionMode::LowestDirect)
+    );
+    assert_eq!(
+        ResolutionMode::parse("time_based"),
+        Some(ResolutionMode::TimeBased)
+    );
+    assert_eq!(ResolutionMode::parse("newest"), None);
+}
+
+#[test]
+fn trust_policy_parse() {
+    assert_eq!(
+        TrustPolicy::parse("no-downgrade"),
+        Some(TrustPolicy::NoDowngrade)
+    );
+    assert_eq!(
+        TrustPolicy::parse("No_Downgrade"),
+        Some(TrustPolicy::NoDowngrade)
+    );
+    assert_eq!(TrustPolicy::parse("off"), Some(TrustPolicy::Off));
+    assert_eq!(TrustPolicy::parse("strict"), None);
+}
@@ -4,6 +4,6 @@ mod style;
 
 pub use logging::{init_logging, is_logging_enabled, verbose};
 pub use output::{
-    added, blocked_scripts, clear_line, clear_steps, error, header, info, progress, removed, step,
-    step_with_count, summary, warn,
+    added, blocked_scripts, clear_line, clear_steps, deprecations, error, header, info, progress,
+    removed, step, step_with_count, summary, warn,
 };
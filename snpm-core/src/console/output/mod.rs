@@ -1,5 +1,5 @@
 mod report;
 mod status;
 
-pub use report::{added, blocked_scripts, error, info, removed, summary, warn};
+pub use report::{added, blocked_scripts, deprecations, error, info, removed, summary, warn};
 pub use status::{clear_line, clear_steps, header, progress, step, step_with_count};
@@ -107,6 +107,23 @@ pub fn blocked_scripts(packages: &[String]) {
     log_prefixed("INFO", &msg);
 }
 
+/// Grouped deprecation summary: each message once, followed by the
+/// packages (with how they got installed) that carry it.
+pub fn deprecations(groups: &[(String, Vec<String>)]) {
+    let count: usize = groups.iter().map(|(_, packages)| packages.len()).sum();
+    let noun = if count == 1 { "package" } else { "packages" };
+    println!("{} {count} {noun}", yellow("deprecated"));
+    log_prefixed("WARN", &format!("{count} deprecated {noun}"));
+
+    for (message, packages) in groups {
+        println!("  {message}");
+        for package in packages {
+            println!("    {}", dim(package));
+            log_prefixed("WARN", &format!("deprecated {package}: {message}"));
+        }
+    }
+}
+
 fn summary_speed(count: usize, seconds: f32) -> String {
     if seconds <= 0.0 {
         return String::new();
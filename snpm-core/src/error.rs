@@ -130,7 +130,7 @@ pub enum SnpmError {
     #[error("Invalid patch file at {path:?}: {reason}")]
     PatchInvalid { path: PathBuf, reason: String },
 
-    #[error("Offline mode: {resource} not available in cache")]
+    #[error("Offline mode: {resource} is not in the cache (run without --offline to fetch it)")]
     OfflineRequired { resource: String },
 
     #[error("Audit failed: {reason}")]
@@ -142,6 +142,12 @@ pub enum SnpmError {
     #[error("Lockfile required for audit. Run `snpm install` first.")]
     AuditLockfileRequired,
 
+    #[error("Advisory database at {path:?}: {reason}")]
+    AuditDatabase { path: PathBuf, reason: String },
+
+    #[error("Audit suppressions error at {path:?}: {reason}")]
+    AuditSuppressions { path: PathBuf, reason: String },
+
     #[error("Failed to publish {name}@{version}: {reason}")]
     PublishFailed {
         name: String,
@@ -149,6 +155,26 @@ pub enum SnpmError {
         reason: String,
     },
 
+    #[error("Deploy failed: {reason}")]
+    DeployFailed { reason: String },
+
+    #[error("Unmet peer dependencies:\n{report}")]
+    PeerDependencies { report: crate::resolve::PeerReport },
+
+    #[error("Deprecated direct dependencies (fail-on-deprecated is set):\n{packages}")]
+    DeprecatedDependencies { packages: String },
+
+    #[error(
+        "Packages that do not support this project's engines (engine-strict is set):\n{packages}"
+    )]
+    EngineIncompatible { packages: String },
+
+    #[error("Failed to load signing keys for {registry}: {reason}")]
+    RegistryKeys { registry: String, reason: String },
+
+    #[error("Packages with invalid registry signatures (verify-signatures is set):\n{packages}")]
+    InvalidSignatures { packages: String },
+
     #[error("Internal error: {reason}")]
     Internal { reason: String },
 }
@@ -0,0 +1,22 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Runs `git -C <dir> <args>` and returns its stdout. The error is the
+/// reason as a sentence fragment, for callers to wrap in whichever
+/// `SnpmError` variant fits the operation.
+pub(crate) fn output(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let action = args.first().copied().unwrap_or_default();
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|error| format!("failed to run git {action}: {error}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {action} failed: {}", stderr.trim()));
+    }
+
+    String::from_utf8(output.stdout).map_err(|error| error.to_string())
+}
@@ -3,6 +3,7 @@ pub mod config;
 pub mod console;
 mod copying;
 pub mod error;
+mod git;
 pub mod http;
 pub mod lifecycle;
 pub mod linker;
@@ -55,6 +55,12 @@ mod tests {
                 ignored_built_dependencies: ignored_built,
                 disable_global_virtual_store_for_packages: None,
                 hoisting: None,
+                package_extensions: Default::default(),
+                peer_dependency_rules: Default::default(),
+                supported_architectures: Default::default(),
+                resolution_mode: None,
+                trust_policy: None,
+                trust_policy_exclude: Vec::new(),
             },
         }
     }
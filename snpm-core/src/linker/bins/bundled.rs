@@ -115,6 +115,12 @@ mod tests {
                     bundled_dependencies: None,
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             )]),
         };
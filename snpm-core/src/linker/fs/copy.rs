@@ -1,5 +1,5 @@
 use crate::copying::clone_or_copy_file;
-use crate::store::read_package_filesystem_shape_lossy;
+use crate::store::{pooled_file_sources, read_package_filesystem_shape_lossy};
 use crate::{Result, SnpmError};
 
 use std::fs;
@@ -13,6 +13,7 @@ pub fn copy_dir(source: &Path, dest: &Path) -> Result<()> {
     })?;
 
     if let Some(shape) = read_package_filesystem_shape_lossy(source) {
+        let pooled = pooled_file_sources(source).unwrap_or_default();
         for directory in &shape.directories {
             let destination = dest.join(directory);
             fs::create_dir_all(&destination).map_err(|source| SnpmError::WriteFile {
@@ -23,6 +24,7 @@ pub fn copy_dir(source: &Path, dest: &Path) -> Result<()> {
 
         for file in &shape.files {
             let from = source.join(file);
+            let from = pooled.get(&from).unwrap_or(&from);
             let to = dest.join(file);
             if let Some(parent) = to.parent() {
                 fs::create_dir_all(parent).map_err(|source| SnpmError::WriteFile {
@@ -30,7 +32,7 @@ pub fn copy_dir(source: &Path, dest: &Path) -> Result<()> {
                     source,
                 })?;
             }
-            clone_or_copy_file(&from, &to)
+            clone_or_copy_file(from, &to)
                 .map_err(|source| SnpmError::WriteFile { path: to, source })?;
         }
 
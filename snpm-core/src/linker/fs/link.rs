@@ -3,7 +3,7 @@ use super::capabilities::{
 };
 use super::paths::ensure_parent_dir;
 use super::symlinks::symlink_file_entry;
-use crate::store::read_package_filesystem_shape_lossy;
+use crate::store::{pooled_file_sources, read_package_filesystem_shape_lossy};
 use crate::{LinkBackend, Result, SnpmConfig, SnpmError};
 
 use rayon::prelude::*;
@@ -21,6 +21,40 @@ pub fn link_dir(config: &SnpmConfig, source: &Path, dest: &Path) -> Result<()> {
         return Ok(());
     }
 
+    let files = prepare_link_ops(config, source, dest)?;
+    files
+        .par_iter()
+        .try_for_each(|(from, to)| link_file(config, from, to))
+}
+
+/// Re-links every file of `source` over the existing tree at `dest`, for
+/// when a store entry was replaced and `dest` still links the old files.
+/// Files in `dest` that `source` doesn't have, such as build output, are
+/// left alone.
+pub fn relink_dir(config: &SnpmConfig, source: &Path, dest: &Path) -> Result<()> {
+    let files = prepare_link_ops(config, source, dest)?;
+    files.par_iter().try_for_each(|(from, to)| {
+        match fs::remove_file(to) {
+            Ok(()) => {}
+            Err(error) if error.kind() == ErrorKind::NotFound => {}
+            Err(source_err) => {
+                return Err(SnpmError::WriteFile {
+                    path: to.clone(),
+                    source: source_err,
+                });
+            }
+        }
+        link_file(config, from, to)
+    })
+}
+
+/// Creates the directories of `dest` and returns the files to link into it.
+/// Files of an indexed store package are linked from their pooled blobs.
+fn prepare_link_ops(
+    config: &SnpmConfig,
+    source: &Path,
+    dest: &Path,
+) -> Result<Vec<(PathBuf, PathBuf)>> {
     let (directories, files) = match indexed_link_ops(source, dest) {
         Some(ops) => ops,
         None => {
@@ -38,9 +72,32 @@ pub fn link_dir(config: &SnpmConfig, source: &Path, dest: &Path) -> Result<()> {
         })?;
     }
 
-    files
-        .par_iter()
-        .try_for_each(|(from, to)| link_file(config, from, to))
+    if links_by_symlink(config, files.first()) {
+        return Ok(files);
+    }
+
+    match pooled_file_sources(source) {
+        Some(pooled) => Ok(files
+            .into_iter()
+            .map(|(from, to)| match pooled.get(&from) {
+                Some(blob) => (blob.clone(), to),
+                None => (from, to),
+            })
+            .collect()),
+        None => Ok(files),
+    }
+}
+
+/// File symlinks have to point into the package tree, not at a pooled
+/// blob: Node resolves a module's relative imports from its real path.
+fn links_by_symlink(config: &SnpmConfig, sample: Option<&(PathBuf, PathBuf)>) -> bool {
+    match config.link_backend {
+        LinkBackend::Symlink => true,
+        LinkBackend::Auto => sample.is_none_or(|(from, to)| {
+            matches!(resolve_auto_backend(from, to), LinkBackend::Symlink)
+        }),
+        _ => false,
+    }
 }
 
 // Only compiled where it has callers: the Apple-only clone fast path
@@ -248,8 +305,8 @@ fn copy_file(from: &Path, to: &Path) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::{link_dir, should_try_clone_store_package_dir};
-    use crate::config::{AuthScheme, HoistingMode, LinkBackend, SnpmConfig};
+    use super::{link_dir, relink_dir, should_try_clone_store_package_dir};
+    use crate::config::{AuthScheme, HoistingMode, LinkBackend, OfflineMode, SnpmConfig};
     use crate::store::PACKAGE_METADATA_FILE;
 
     use std::collections::{BTreeMap, BTreeSet};
@@ -279,6 +336,16 @@ mod tests {
             frozen_lockfile_default: false,
             always_auth: false,
             registry_concurrency: 64,
+            offline_mode: OfflineMode::Online,
+            supported_architectures: Default::default(),
+            resolution_mode: Default::default(),
+            before: None,
+            fail_on_deprecated: false,
+            engine_strict: false,
+            engine_targets: Default::default(),
+            verify_signatures: false,
+            trust_policy: Default::default(),
+            trust_policy_exclude: BTreeSet::new(),
             verbose: false,
             log_file: None,
             remote_cache_url: None,
@@ -313,6 +380,26 @@ mod tests {
         assert!(destination.join(PACKAGE_METADATA_FILE).is_file());
     }
 
+    #[test]
+    fn relink_dir_replaces_linked_files_and_keeps_extra_ones() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("source");
+        let destination = dir.path().join("destination");
+        fs::create_dir_all(source.join("lib")).unwrap();
+        fs::write(source.join("lib/index.js"), "good").unwrap();
+        fs::create_dir_all(destination.join("lib")).unwrap();
+        fs::write(destination.join("lib/index.js"), "corrupt").unwrap();
+        fs::write(destination.join("build.node"), "built").unwrap();
+
+        relink_dir(&make_config(), &source, &destination).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(destination.join("lib/index.js")).unwrap(),
+            "good"
+        );
+        assert!(destination.join("build.node").is_file());
+    }
+
     #[test]
     fn store_clone_gate_only_allows_store_paths_for_auto_and_reflink() {
         let dir = tempdir().unwrap();
@@ -5,6 +5,6 @@ mod paths;
 mod symlinks;
 
 pub use copy::copy_dir;
-pub use link::link_dir;
+pub use link::{link_dir, relink_dir};
 pub use paths::{ensure_parent_dir, package_node_modules, symlink_is_correct};
 pub use symlinks::{remove_symlink, symlink_dir_entry, symlink_file_entry};
@@ -207,6 +207,12 @@ mod tests {
                     bundled_dependencies: None,
                     has_bin: true,
                     bin: Some(BinField::Single("cli.js".to_string())),
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             )]),
         };
@@ -260,6 +266,12 @@ mod tests {
                     // Simulate a yarn lockfile import: bin info is missing.
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             )]),
         };
@@ -751,6 +751,43 @@ fn hashed_virtual_package_location(
         .join(&id.name)
 }
 
+/// Every real package directory for `id` under `virtual_store_dir`: the
+/// plain `name@version` entry and any hashed shared-store entries. Project
+/// entries that only symlink into the shared store are skipped.
+pub(crate) fn virtual_package_locations(virtual_store_dir: &Path, id: &PackageId) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(virtual_store_dir) else {
+        return Vec::new();
+    };
+
+    let plain = virtual_id_dir(virtual_store_dir, id);
+    let plain_name = plain
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let hashed_prefix = format!("{plain_name}-");
+
+    let mut locations: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name == plain_name.as_str()
+                || name.strip_prefix(&hashed_prefix).is_some_and(|hash| {
+                    hash.len() == 16 && hash.bytes().all(|byte| byte.is_ascii_hexdigit())
+                })
+        })
+        .map(|entry| entry.path().join("node_modules").join(&id.name))
+        .filter(|location| {
+            location
+                .symlink_metadata()
+                .is_ok_and(|metadata| metadata.is_dir())
+        })
+        .collect();
+
+    locations.sort();
+    locations
+}
+
 // Note: `.snpm_linked` is no longer written. `virtual_package_ready`
 // (presence of `.snpm-package-metadata.json`) is now the warm-hit
 // indicator and it's reflinked into every virtual store entry by
@@ -1053,6 +1090,12 @@ mod tests {
             bundled_dependencies: None,
             has_bin: false,
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         };
 
         ResolutionGraph {
@@ -1088,6 +1131,12 @@ mod tests {
             bundled_dependencies: None,
             has_bin: false,
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         };
         let child_pkg = ResolvedPackage {
             id: child_id.clone(),
@@ -1098,6 +1147,12 @@ mod tests {
             bundled_dependencies: None,
             has_bin: false,
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         };
 
         ResolutionGraph {
@@ -1120,6 +1175,12 @@ mod tests {
                 ignored_built_dependencies: Vec::new(),
                 disable_global_virtual_store_for_packages: None,
                 hoisting: None,
+                package_extensions: Default::default(),
+                peer_dependency_rules: Default::default(),
+                supported_architectures: Default::default(),
+                resolution_mode: None,
+                trust_policy: None,
+                trust_policy_exclude: Vec::new(),
             },
         }
     }
@@ -1362,6 +1423,9 @@ mod tests {
         let mut project = make_project(dir.path().join("project"));
         project.manifest.snpm = Some(ManifestSnpm {
             overrides: BTreeMap::new(),
+            package_extensions: BTreeMap::new(),
+            peer_dependency_rules: Default::default(),
+            supported_architectures: Default::default(),
             patched_dependencies: Some(BTreeMap::from([(
                 "patched-child@1.0.0".to_string(),
                 "patches/patched-child@1.0.0.patch".to_string(),
@@ -1406,6 +1470,12 @@ mod tests {
                     bundled_dependencies: None,
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             );
         }
@@ -1545,6 +1615,12 @@ mod tests {
                     bundled_dependencies: None,
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             );
         }
@@ -1,5 +1,5 @@
 use super::super::keys::{package_key, split_dep_key};
-use super::super::types::{LockPackage, LockRoot, LockRootDependency, Lockfile};
+use super::super::types::{LockPackage, LockRoot, LockRootDependency, LockSettings, Lockfile};
 use crate::project::BinField;
 use crate::protocols::encode_package_name;
 use crate::{Result, SnpmConfig, SnpmError};
@@ -32,7 +32,12 @@ pub(super) fn read(path: &Path, config: &SnpmConfig) -> Result<Lockfile> {
         });
     }
 
-    let entries = decode_entries(path, &raw.packages)?;
+    let mut entries = decode_entries(path, &raw.packages)?;
+    // Workspace members are listed as `name@workspace:path`; they are
+    // linked, not installed, and already known from `workspaces`.
+    entries.retain(|_, entry| {
+        split_ident(&entry.ident).is_none_or(|(_, version)| !version.starts_with("workspace:"))
+    });
     let key_info = build_key_info(path, &entries)?;
     let workspace_names = collect_workspace_names(&raw.workspaces);
     let packages = build_packages(path, config, &entries, &key_info)?;
@@ -40,7 +45,9 @@ pub(super) fn read(path: &Path, config: &SnpmConfig) -> Result<Lockfile> {
 
     Ok(Lockfile {
         version: 1,
+        settings: LockSettings::default(),
         root,
+        importers: BTreeMap::new(),
         packages,
     })
 }
@@ -225,6 +232,12 @@ fn build_packages(
             bundled_dependencies: None,
             has_bin: !entry.meta.bin.is_empty(),
             bin: (!entry.meta.bin.is_empty()).then(|| BinField::Map(entry.meta.bin.clone())),
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         };
 
         if let Some(existing) = packages.get(&lock_key) {
@@ -818,6 +831,45 @@ mod tests {
         assert!(!lockfile.root.dependencies.contains_key("lib"));
     }
 
+    #[test]
+    fn skips_workspace_member_package_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bun.lock");
+        fs::write(
+            &path,
+            r#"{
+  "lockfileVersion": 1,
+  "workspaces": {
+    "": { "name": "root" },
+    "packages/app": {
+      "name": "app",
+      "dependencies": {
+        "lib": "workspace:*",
+        "left-pad": "^1.3.0"
+      }
+    },
+    "packages/lib": {
+      "name": "lib"
+    }
+  },
+  "packages": {
+    "app": ["app@workspace:packages/app"],
+    "left-pad": ["left-pad@1.3.0", "", {}, "sha512-aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"],
+    "lib": ["lib@workspace:packages/lib"]
+  }
+}"#,
+        )
+        .unwrap();
+
+        let lockfile = read(&path, &test_config()).unwrap();
+
+        assert!(lockfile.root.dependencies.contains_key("left-pad"));
+        assert_eq!(
+            lockfile.packages.keys().collect::<Vec<_>>(),
+            ["left-pad@1.3.0"]
+        );
+    }
+
     #[test]
     fn rejects_unsupported_bun_lockfile_version() {
         let dir = tempfile::tempdir().unwrap();
@@ -7,7 +7,6 @@ use super::types::Lockfile;
 use crate::{Result, SnpmConfig};
 
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CompatibleLockfileKind {
@@ -97,17 +96,8 @@ fn compatible_lockfile_candidates(project_root: &Path) -> Vec<CompatibleLockfile
 }
 
 fn current_git_branch(project_root: &Path) -> Option<String> {
-    let output = Command::new("git")
-        .args(["-C"])
-        .arg(project_root)
-        .args(["branch", "--show-current"])
-        .output()
-        .ok()?;
-    if !output.status.success() {
-        return None;
-    }
-
-    let branch = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    let output = crate::git::output(project_root, &["branch", "--show-current"]).ok()?;
+    let branch = output.trim().to_string();
     if branch.is_empty() {
         None
     } else {
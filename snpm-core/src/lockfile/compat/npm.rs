@@ -1,5 +1,5 @@
 use super::super::keys::{package_key, split_dep_key};
-use super::super::types::{LockPackage, LockRoot, LockRootDependency, Lockfile};
+use super::super::types::{LockPackage, LockRoot, LockRootDependency, LockSettings, Lockfile};
 use crate::protocols::encode_package_name;
 use crate::registry::{BundledDependencies, PeerDependencyMeta};
 use crate::{Result, SnpmConfig, SnpmError};
@@ -95,7 +95,9 @@ pub(super) fn read(path: &Path, config: &SnpmConfig) -> Result<Lockfile> {
 
     Ok(Lockfile {
         version: 1,
+        settings: LockSettings::default(),
         root,
+        importers: BTreeMap::new(),
         packages,
     })
 }
@@ -625,6 +627,12 @@ fn build_packages(
                 .filter(|value| !value.is_empty()),
             has_bin: raw.bin.as_ref().is_some_and(has_bin),
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         };
 
         if let Some(existing) = packages.get(&entry.lock_key) {
@@ -735,7 +743,14 @@ fn build_workspace_root(
 ) -> Result<LockRoot> {
     let mut dependencies = BTreeMap::new();
 
-    for member_path in workspace_member_paths {
+    // The workspace root's own dependencies install alongside the members'.
+    let sources = std::iter::once((".", "")).chain(
+        workspace_member_paths
+            .iter()
+            .map(|member_path| (member_path.as_str(), member_path.as_str())),
+    );
+
+    for (label, member_path) in sources {
         let member = packages_by_path
             .get(member_path)
             .ok_or_else(|| SnpmError::Lockfile {
@@ -748,7 +763,7 @@ fn build_workspace_root(
 
         insert_root_block(
             path,
-            member_path,
+            label,
             member_path,
             &member.dependencies,
             false,
@@ -760,7 +775,7 @@ fn build_workspace_root(
         )?;
         insert_root_block(
             path,
-            member_path,
+            label,
             member_path,
             &member.dev_dependencies,
             false,
@@ -772,7 +787,7 @@ fn build_workspace_root(
         )?;
         insert_root_block(
             path,
-            member_path,
+            label,
             member_path,
             &member.optional_dependencies,
             true,
@@ -1549,6 +1564,61 @@ mod tests {
         assert!(!lockfile.packages.contains_key("bar@1.0.0"));
     }
 
+    #[test]
+    fn imports_workspace_root_dependencies_alongside_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("package-lock.json");
+        fs::write(
+            &path,
+            r#"{
+  "name": "workspace-root-deps",
+  "version": "1.0.0",
+  "lockfileVersion": 3,
+  "packages": {
+    "": {
+      "name": "workspace-root-deps",
+      "version": "1.0.0",
+      "workspaces": [
+        "packages/*"
+      ],
+      "devDependencies": {
+        "is-positive": "^1.0.0"
+      }
+    },
+    "node_modules/foo": {
+      "resolved": "packages/foo",
+      "link": true
+    },
+    "node_modules/is-negative": {
+      "version": "1.0.1",
+      "resolved": "https://registry.npmjs.org/is-negative/-/is-negative-1.0.1.tgz"
+    },
+    "node_modules/is-positive": {
+      "version": "1.0.0",
+      "resolved": "https://registry.npmjs.org/is-positive/-/is-positive-1.0.0.tgz",
+      "dev": true
+    },
+    "packages/foo": {
+      "version": "1.0.0",
+      "dependencies": {
+        "is-negative": "^1.0.0"
+      }
+    }
+  }
+}"#,
+        )
+        .unwrap();
+
+        let lockfile = read(&path, &test_config()).unwrap();
+
+        assert_eq!(lockfile.root.dependencies.len(), 2);
+        assert_eq!(
+            lockfile.root.dependencies["is-positive"].version.as_deref(),
+            Some("1.0.0")
+        );
+        assert!(lockfile.root.dependencies.contains_key("is-negative"));
+    }
+
     #[test]
     fn imports_empty_npm_lockfile_v1() {
         let dir = tempfile::tempdir().unwrap();
@@ -1,5 +1,7 @@
 use super::super::keys::{package_key, split_dep_key};
-use super::super::types::{LockPackage, LockRoot, LockRootDependency, Lockfile};
+use super::super::types::{
+    LockImporter, LockPackage, LockRoot, LockRootDependency, LockSettings, Lockfile,
+};
 use crate::protocols::encode_package_name;
 use crate::registry::{BundledDependencies, PeerDependencyMeta};
 use crate::{Result, SnpmConfig, SnpmError};
@@ -47,11 +49,13 @@ pub(super) fn read(path: &Path, config: &SnpmConfig) -> Result<Lockfile> {
         &package_entries,
         &dep_path_to_package_key,
     )?;
-    let root = build_root(path, &raw, &dep_path_to_package_key)?;
+    let (root, importers) = build_root(path, &raw, &dep_path_to_package_key)?;
 
     Ok(Lockfile {
         version: 1,
+        settings: LockSettings::default(),
         root,
+        importers,
         packages,
     })
 }
@@ -317,6 +321,12 @@ fn build_packages(
             bundled_dependencies: package_info.and_then(|info| info.bundled_dependencies.clone()),
             has_bin: package_info.map(|info| info.has_bin).unwrap_or(false),
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         };
 
         if let Some(existing) = packages.get(&entry.lock_key) {
@@ -338,13 +348,18 @@ fn build_packages(
     Ok(packages)
 }
 
+/// Flattens every importer into the snpm root while keeping each
+/// importer's own view. `link:` dependencies point at workspace siblings;
+/// they're recorded as importer links rather than root dependencies.
 fn build_root(
     path: &Path,
     raw: &RawPnpmLockfile,
     dep_path_to_package_key: &BTreeMap<String, String>,
-) -> Result<LockRoot> {
+) -> Result<(LockRoot, BTreeMap<String, LockImporter>)> {
     let mut dependencies = BTreeMap::new();
+    let mut importers = BTreeMap::new();
     for (importer_path, importer) in &raw.importers {
+        let mut lock_importer = LockImporter::default();
         insert_root_block(
             path,
             importer_path,
@@ -353,6 +368,8 @@ fn build_root(
             false,
             dep_path_to_package_key,
             &mut dependencies,
+            &mut lock_importer.dependencies,
+            &mut lock_importer.links,
         )?;
         insert_root_block(
             path,
@@ -362,6 +379,8 @@ fn build_root(
             false,
             dep_path_to_package_key,
             &mut dependencies,
+            &mut lock_importer.dev_dependencies,
+            &mut lock_importer.links,
         )?;
         insert_root_block(
             path,
@@ -371,12 +390,20 @@ fn build_root(
             true,
             dep_path_to_package_key,
             &mut dependencies,
+            &mut lock_importer.dependencies,
+            &mut lock_importer.links,
         )?;
+
+        importers.insert(
+            display_importer_path(importer_path).to_string(),
+            lock_importer,
+        );
     }
 
-    Ok(LockRoot { dependencies })
+    Ok((LockRoot { dependencies }, importers))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn insert_root_block(
     path: &Path,
     importer_path: &str,
@@ -385,8 +412,15 @@ fn insert_root_block(
     optional: bool,
     dep_path_to_package_key: &BTreeMap<String, String>,
     root: &mut BTreeMap<String, LockRootDependency>,
+    importer_block: &mut BTreeMap<String, LockRootDependency>,
+    links: &mut BTreeMap<String, String>,
 ) -> Result<()> {
     for (dep_name, dep) in block {
+        if let Some(target) = dep.version().strip_prefix("link:") {
+            links.insert(dep_name.clone(), join_importer_path(importer_path, target));
+            continue;
+        }
+
         let requested = dep
             .specifier(dep_name, &importer.specifiers)
             .unwrap_or(dep.version())
@@ -399,6 +433,7 @@ fn insert_root_block(
             build_required_root_dependency(path, dep_name, &requested, resolved.as_deref())?
         };
 
+        importer_block.insert(dep_name.clone(), incoming.clone());
         merge_root_dependency(path, importer_path, dep_name, incoming, root)?;
     }
 
@@ -532,6 +567,31 @@ fn display_importer_path(importer_path: &str) -> &str {
     }
 }
 
+/// Resolves a `link:` target relative to the importer that declares it,
+/// yielding the sibling's importer key (`packages/app`, or `.`).
+fn join_importer_path(importer_path: &str, target: &str) -> String {
+    let mut parts: Vec<&str> = importer_path
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect();
+
+    for part in target.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
 fn resolve_dependency_key(
     dep_name: &str,
     dep_ref: &str,
@@ -813,6 +873,69 @@ snapshots:
         );
         assert!(lockfile.root.dependencies["optional-shared"].optional);
         assert!(!lockfile.root.dependencies["required-optional"].optional);
+
+        let importer_c = &lockfile.importers["packages/c"];
+        assert!(importer_c.dependencies["required-optional"].optional);
+        assert_eq!(
+            lockfile.importers["packages/a"].dependencies["shared"]
+                .version
+                .as_deref(),
+            Some("1.0.0")
+        );
+    }
+
+    #[test]
+    fn keeps_importers_and_records_workspace_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pnpm-lock.yaml");
+        std::fs::write(
+            &path,
+            r#"lockfileVersion: '9.0'
+importers:
+  .:
+    devDependencies:
+      tool:
+        specifier: ^1.0.0
+        version: 1.0.0
+  packages/app:
+    dependencies:
+      lib:
+        specifier: workspace:*
+        version: link:../lib
+      shared:
+        specifier: ^2.0.0
+        version: 2.0.0
+  packages/lib: {}
+packages:
+  tool@1.0.0:
+    resolution:
+      integrity: sha512-tool
+  shared@2.0.0:
+    resolution:
+      integrity: sha512-shared
+snapshots:
+  tool@1.0.0: {}
+  shared@2.0.0: {}
+"#,
+        )
+        .unwrap();
+
+        let lockfile = read(&path, &test_config()).unwrap();
+
+        assert_eq!(
+            lockfile.importers.keys().collect::<Vec<_>>(),
+            vec![".", "packages/app", "packages/lib"]
+        );
+        assert_eq!(
+            lockfile.importers["."].dev_dependencies["tool"].requested,
+            "^1.0.0"
+        );
+
+        let app = &lockfile.importers["packages/app"];
+        assert_eq!(app.links["lib"], "packages/lib");
+        assert!(!app.dependencies.contains_key("lib"));
+        assert_eq!(app.dependencies["shared"].version.as_deref(), Some("2.0.0"));
+        assert!(!lockfile.root.dependencies.contains_key("lib"));
     }
 
     #[test]
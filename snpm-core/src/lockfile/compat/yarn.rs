@@ -1,4 +1,4 @@
-use super::super::types::{LockPackage, LockRoot, LockRootDependency, Lockfile};
+use super::super::types::{LockPackage, LockRoot, LockRootDependency, LockSettings, Lockfile};
 use crate::operations::install::{
     RootSpecSet, apply_specs, build_project_root_specs, collect_workspace_root_specs,
 };
@@ -152,6 +152,12 @@ fn parse_classic_str(
                 bundled_dependencies: None,
                 has_bin: false,
                 bin: None,
+                os: Vec::new(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: None,
+                has_install_script: false,
             },
         );
     }
@@ -160,7 +166,9 @@ fn parse_classic_str(
 
     Ok(Lockfile {
         version: 1,
+        settings: LockSettings::default(),
         root,
+        importers: BTreeMap::new(),
         packages,
     })
 }
@@ -303,6 +311,12 @@ fn parse_berry_str(
                 bundled_dependencies: None,
                 has_bin: seed.has_bin,
                 bin: None,
+                os: Vec::new(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: None,
+                has_install_script: false,
             },
         );
     }
@@ -311,7 +325,9 @@ fn parse_berry_str(
 
     Ok(Lockfile {
         version: 1,
+        settings: LockSettings::default(),
         root,
+        importers: BTreeMap::new(),
         packages,
     })
 }
@@ -573,16 +589,19 @@ fn parse_classic_header_specs(header: &str) -> std::result::Result<Vec<String>,
 
 fn split_classic_key_value(line: &str) -> Option<(String, String)> {
     let (key, value) = line.split_once(char::is_whitespace)?;
-    let value = value.trim();
-    let value = if (value.starts_with('"') && value.ends_with('"') && value.len() >= 2)
+
+    // yarn quotes scoped names as well as values: `"@scope/pkg" "^1.0.0"`.
+    Some((unquote(key).to_string(), unquote(value.trim()).to_string()))
+}
+
+fn unquote(value: &str) -> &str {
+    if (value.starts_with('"') && value.ends_with('"') && value.len() >= 2)
         || (value.starts_with('\'') && value.ends_with('\'') && value.len() >= 2)
     {
         &value[1..value.len() - 1]
     } else {
         value
-    };
-
-    Some((key.to_string(), value.to_string()))
+    }
 }
 
 fn parse_classic_identity(spec: &str) -> Option<ClassicIdentity> {
@@ -924,6 +943,47 @@ mod tests {
         assert_eq!(chokidar.dependencies["fsevents"], "fsevents@2.3.2");
     }
 
+    #[test]
+    fn imports_yarn_classic_quoted_scoped_dependency_names() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{
+  "name": "scoped-deps-test",
+  "version": "1.0.0",
+  "dependencies": {
+    "@babel/core": "^7.0.0"
+  }
+}"#,
+        )
+        .unwrap();
+        let path = dir.path().join("yarn.lock");
+        fs::write(
+            &path,
+            r#"# yarn lockfile v1
+
+"@babel/core@^7.0.0":
+  version "7.24.0"
+  resolved "https://registry.yarnpkg.com/@babel/core/-/core-7.24.0.tgz#hash"
+  integrity sha512-abc
+  dependencies:
+    "@babel/types" "^7.24.0"
+
+"@babel/types@^7.24.0":
+  version "7.24.0"
+  resolved "https://registry.yarnpkg.com/@babel/types/-/types-7.24.0.tgz#hash"
+  integrity sha512-def
+"#,
+        )
+        .unwrap();
+
+        let lockfile = read(&path, &test_config()).unwrap();
+        assert_eq!(
+            lockfile.packages["@babel/core@7.24.0"].dependencies["@babel/types"],
+            "@babel/types@7.24.0"
+        );
+    }
+
     #[test]
     fn imports_yarn_classic_alias_root_dependency() {
         let dir = tempfile::tempdir().unwrap();
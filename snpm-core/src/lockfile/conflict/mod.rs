@@ -0,0 +1,333 @@
+use super::io::write_lockfile;
+use super::keys::package_key;
+use super::types::{LockImporter, LockRoot, LockRootDependency, Lockfile};
+use crate::{Result, SnpmError};
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fs;
+use std::path::Path;
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const THEIRS_MARKER: &str = "=======";
+const END_MARKER: &str = ">>>>>>>";
+
+/// What happened to a root dependency the two sides of a merge locked
+/// differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconciledDependency {
+    pub name: String,
+    pub ours: Option<LockRootDependency>,
+    pub theirs: Option<LockRootDependency>,
+    pub outcome: Reconciliation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reconciliation {
+    KeptOurs,
+    KeptTheirs,
+    /// Neither side matches `package.json`; the next resolve picks it.
+    Reresolve,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictResolution {
+    pub lockfile: Lockfile,
+    pub reconciled: Vec<ReconciledDependency>,
+}
+
+/// Whether `content` still has git merge conflict markers in it.
+pub fn has_conflict_markers(content: &str) -> bool {
+    content.lines().any(|line| line.starts_with(OURS_MARKER))
+        && content.lines().any(|line| line.starts_with(END_MARKER))
+}
+
+/// Rewrites a `snpm-lock.yaml` left with merge conflict markers as one
+/// clean lockfile, or returns `None` when there are no markers.
+///
+/// Both sides are parsed on their own and their package maps merged.
+/// Root dependencies the sides disagree on keep whichever side matches
+/// `root_specs` (the specs `package.json` declares now, by name). If
+/// neither does, the entry is dropped so the next resolve re-resolves
+/// just that spec, seeded with everything else.
+pub fn resolve_conflicts(
+    path: &Path,
+    root_specs: &BTreeMap<String, String>,
+) -> Result<Option<ConflictResolution>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(SnpmError::ReadFile {
+                path: path.to_path_buf(),
+                source,
+            });
+        }
+    };
+
+    if !has_conflict_markers(&content) {
+        return Ok(None);
+    }
+
+    let (ours, theirs) = split_sides(&content).map_err(|reason| SnpmError::Lockfile {
+        path: path.to_path_buf(),
+        reason,
+    })?;
+    let parse = |side: &str, text: &str| -> Result<Lockfile> {
+        serde_yaml::from_str(text).map_err(|error| SnpmError::Lockfile {
+            path: path.to_path_buf(),
+            reason: format!("cannot parse the {side} side of the merge conflict: {error}"),
+        })
+    };
+    let ours = parse("ours", &ours)?;
+    let theirs = parse("theirs", &theirs)?;
+
+    let resolution = merge(ours, theirs, root_specs);
+    write_lockfile(path, &resolution.lockfile)?;
+
+    Ok(Some(resolution))
+}
+
+/// Splits conflicted text into the "ours" and "theirs" documents. Lines
+/// outside conflict blocks belong to both; diff3 base sections are dropped.
+fn split_sides(content: &str) -> std::result::Result<(String, String), String> {
+    #[derive(PartialEq)]
+    enum Section {
+        Shared,
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let mut section = Section::Shared;
+    let mut ours = String::new();
+    let mut theirs = String::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let unexpected =
+            || format!("unexpected merge conflict marker on line {line_number}: {line}");
+
+        if line.starts_with(OURS_MARKER) {
+            if section != Section::Shared {
+                return Err(unexpected());
+            }
+            section = Section::Ours;
+        } else if line.starts_with(BASE_MARKER) && section == Section::Ours {
+            section = Section::Base;
+        } else if line == THEIRS_MARKER && matches!(section, Section::Ours | Section::Base) {
+            section = Section::Theirs;
+        } else if line.starts_with(END_MARKER) {
+            if section != Section::Theirs {
+                return Err(unexpected());
+            }
+            section = Section::Shared;
+        } else {
+            match section {
+                Section::Shared => {
+                    ours.push_str(line);
+                    ours.push('\n');
+                    theirs.push_str(line);
+                    theirs.push('\n');
+                }
+                Section::Ours => {
+                    ours.push_str(line);
+                    ours.push('\n');
+                }
+                Section::Theirs => {
+                    theirs.push_str(line);
+                    theirs.push('\n');
+                }
+                Section::Base => {}
+            }
+        }
+    }
+
+    if section != Section::Shared {
+        return Err("merge conflict block is not closed".to_string());
+    }
+
+    Ok((ours, theirs))
+}
+
+fn merge(
+    ours: Lockfile,
+    theirs: Lockfile,
+    root_specs: &BTreeMap<String, String>,
+) -> ConflictResolution {
+    let mut reconciled = Vec::new();
+    let root = merge_root(&ours.root, &theirs.root, root_specs, &mut reconciled);
+
+    let mut importers = BTreeMap::new();
+    let keys: BTreeSet<&String> = ours
+        .importers
+        .keys()
+        .chain(theirs.importers.keys())
+        .collect();
+    for key in keys {
+        let empty = LockImporter::default();
+        let ours = ours.importers.get(key).unwrap_or(&empty);
+        let theirs = theirs.importers.get(key).unwrap_or(&empty);
+        let mut links = theirs.links.clone();
+        links.extend(ours.links.clone());
+
+        importers.insert(
+            key.clone(),
+            LockImporter {
+                dependencies: merge_importer_block(&ours.dependencies, &theirs.dependencies, &root),
+                dev_dependencies: merge_importer_block(
+                    &ours.dev_dependencies,
+                    &theirs.dev_dependencies,
+                    &root,
+                ),
+                links,
+            },
+        );
+    }
+
+    // The same key names the same published package on both sides.
+    let mut packages = theirs.packages;
+    packages.extend(ours.packages);
+
+    let mut lockfile = Lockfile {
+        version: ours.version,
+        settings: ours.settings.clone(),
+        root: LockRoot { dependencies: root },
+        importers,
+        packages,
+    };
+    retain_reachable(&mut lockfile);
+
+    ConflictResolution {
+        lockfile,
+        reconciled,
+    }
+}
+
+fn merge_root(
+    ours: &LockRoot,
+    theirs: &LockRoot,
+    root_specs: &BTreeMap<String, String>,
+    reconciled: &mut Vec<ReconciledDependency>,
+) -> BTreeMap<String, LockRootDependency> {
+    let mut merged = BTreeMap::new();
+    let names: BTreeSet<&String> = ours
+        .dependencies
+        .keys()
+        .chain(theirs.dependencies.keys())
+        .collect();
+
+    for name in names {
+        let ours = ours.dependencies.get(name);
+        let theirs = theirs.dependencies.get(name);
+        if ours == theirs {
+            if let Some(dependency) = ours {
+                merged.insert(name.clone(), dependency.clone());
+            }
+            continue;
+        }
+
+        // Dropped from package.json: nothing to keep or re-resolve.
+        let Some(requested) = root_specs.get(name) else {
+            continue;
+        };
+
+        let matches = |dependency: Option<&LockRootDependency>| {
+            dependency.is_some_and(|dependency| dependency.requested == *requested)
+        };
+        let outcome = match (matches(ours), matches(theirs)) {
+            (true, true) if newer(theirs, ours) => Reconciliation::KeptTheirs,
+            (true, _) => Reconciliation::KeptOurs,
+            (false, true) => Reconciliation::KeptTheirs,
+            (false, false) => Reconciliation::Reresolve,
+        };
+
+        let kept = match outcome {
+            Reconciliation::KeptOurs => ours,
+            Reconciliation::KeptTheirs => theirs,
+            Reconciliation::Reresolve => None,
+        };
+        if let Some(dependency) = kept {
+            merged.insert(name.clone(), dependency.clone());
+        }
+
+        // An entry only one side added is an ordinary merge, not a conflict.
+        if (ours.is_some() && theirs.is_some()) || outcome == Reconciliation::Reresolve {
+            reconciled.push(ReconciledDependency {
+                name: name.clone(),
+                ours: ours.cloned(),
+                theirs: theirs.cloned(),
+                outcome,
+            });
+        }
+    }
+
+    merged
+}
+
+/// Whether `left` locks a newer version than `right`. Both sides may have
+/// picked up a release for the same spec; keep the later one.
+fn newer(left: Option<&LockRootDependency>, right: Option<&LockRootDependency>) -> bool {
+    let version = |dependency: Option<&LockRootDependency>| {
+        dependency
+            .and_then(|dependency| dependency.version.as_deref())
+            .and_then(|version| snpm_semver::parse_version(version).ok())
+    };
+
+    match (version(left), version(right)) {
+        (Some(left), Some(right)) => left > right,
+        _ => false,
+    }
+}
+
+/// Importer entries follow the reconciled root: each side's entry is kept
+/// only if it locks what the root now does, ours winning a tie.
+fn merge_importer_block(
+    ours: &BTreeMap<String, LockRootDependency>,
+    theirs: &BTreeMap<String, LockRootDependency>,
+    root: &BTreeMap<String, LockRootDependency>,
+) -> BTreeMap<String, LockRootDependency> {
+    let mut merged = BTreeMap::new();
+
+    for (name, dependency) in theirs.iter().chain(ours) {
+        let agrees = root.get(name).is_some_and(|root| {
+            root.package == dependency.package && root.version == dependency.version
+        });
+        if agrees || (dependency.optional && dependency.version.is_none()) {
+            merged.insert(name.clone(), dependency.clone());
+        }
+    }
+
+    merged
+}
+
+/// Drops packages only the losing side of a reconciled dependency used.
+fn retain_reachable(lockfile: &mut Lockfile) {
+    let mut queue: VecDeque<String> = lockfile
+        .root
+        .dependencies
+        .iter()
+        .filter_map(|(name, dependency)| {
+            let version = dependency.version.as_deref()?;
+            Some(package_key(
+                dependency.package.as_deref().unwrap_or(name),
+                version,
+            ))
+        })
+        .collect();
+
+    let mut reachable = BTreeSet::new();
+    while let Some(key) = queue.pop_front() {
+        if !reachable.insert(key.clone()) {
+            continue;
+        }
+        if let Some(package) = lockfile.packages.get(&key) {
+            queue.extend(package.dependencies.values().cloned());
+        }
+    }
+
+    lockfile.packages.retain(|key, _| reachable.contains(key));
+}
+
+#[cfg(test)]
+mod tests;
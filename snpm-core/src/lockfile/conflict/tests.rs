@@ -0,0 +1,244 @@
+use super::{Reconciliation, has_conflict_markers, resolve_conflicts, split_sides};
+use crate::lockfile;
+
+use std::collections::BTreeMap;
+use std::fs;
+
+/// `left-pad` conflicts (ours bumped it, theirs did not), `lodash` was
+/// changed on both sides, and `react` was added by theirs only.
+const CONFLICTED: &str = r#"version: 1
+root:
+  dependencies:
+<<<<<<< HEAD
+    left-pad:
+      requested: ^1.3.0
+      version: 1.3.0
+    lodash:
+      requested: ^4.17.20
+      version: 4.17.20
+=======
+    left-pad:
+      requested: ^1.1.0
+      version: 1.1.3
+    lodash:
+      requested: ^4.17.21
+      version: 4.17.21
+    react:
+      requested: ^18.0.0
+      version: 18.2.0
+>>>>>>> feature
+packages:
+<<<<<<< HEAD
+  left-pad@1.3.0:
+    name: left-pad
+    version: 1.3.0
+    tarball: https://registry.npmjs.org/left-pad/-/left-pad-1.3.0.tgz
+    dependencies: {}
+  lodash@4.17.20:
+    name: lodash
+    version: 4.17.20
+    tarball: https://registry.npmjs.org/lodash/-/lodash-4.17.20.tgz
+    dependencies: {}
+=======
+  left-pad@1.1.3:
+    name: left-pad
+    version: 1.1.3
+    tarball: https://registry.npmjs.org/left-pad/-/left-pad-1.1.3.tgz
+    dependencies: {}
+  lodash@4.17.21:
+    name: lodash
+    version: 4.17.21
+    tarball: https://registry.npmjs.org/lodash/-/lodash-4.17.21.tgz
+    dependencies: {}
+  loose-envify@1.4.0:
+    name: loose-envify
+    version: 1.4.0
+    tarball: https://registry.npmjs.org/loose-envify/-/loose-envify-1.4.0.tgz
+    dependencies: {}
+  react@18.2.0:
+    name: react
+    version: 18.2.0
+    tarball: https://registry.npmjs.org/react/-/react-18.2.0.tgz
+    dependencies:
+      loose-envify: loose-envify@1.4.0
+>>>>>>> feature
+"#;
+
+fn specs(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+    entries
+        .iter()
+        .map(|(name, spec)| (name.to_string(), spec.to_string()))
+        .collect()
+}
+
+#[test]
+fn split_sides_keeps_shared_lines_and_drops_diff3_base() {
+    let (ours, theirs) = split_sides(
+        "a: 1\n<<<<<<< HEAD\nb: 2\n||||||| base\nb: 0\n=======\nb: 3\n>>>>>>> other\nc: 4\n",
+    )
+    .unwrap();
+
+    assert_eq!(ours, "a: 1\nb: 2\nc: 4\n");
+    assert_eq!(theirs, "a: 1\nb: 3\nc: 4\n");
+}
+
+#[test]
+fn split_sides_rejects_unclosed_blocks() {
+    assert!(split_sides("<<<<<<< HEAD\na: 1\n=======\na: 2\n").is_err());
+    assert!(split_sides("a: 1\n>>>>>>> other\n").is_err());
+}
+
+#[test]
+fn keeps_the_side_package_json_agrees_with() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snpm-lock.yaml");
+    fs::write(&path, CONFLICTED).unwrap();
+
+    let resolution = resolve_conflicts(
+        &path,
+        &specs(&[
+            ("left-pad", "^1.3.0"),
+            ("lodash", "^4.17.21"),
+            ("react", "^18.0.0"),
+        ]),
+    )
+    .unwrap()
+    .unwrap();
+
+    let outcomes: Vec<_> = resolution
+        .reconciled
+        .iter()
+        .map(|dependency| (dependency.name.as_str(), dependency.outcome))
+        .collect();
+    assert_eq!(
+        outcomes,
+        [
+            ("left-pad", Reconciliation::KeptOurs),
+            ("lodash", Reconciliation::KeptTheirs),
+        ]
+    );
+
+    let written = lockfile::read(&path).unwrap();
+    assert_eq!(written, resolution.lockfile);
+    assert!(!has_conflict_markers(&fs::read_to_string(&path).unwrap()));
+
+    let root = &written.root.dependencies;
+    assert_eq!(root["left-pad"].version.as_deref(), Some("1.3.0"));
+    assert_eq!(root["lodash"].version.as_deref(), Some("4.17.21"));
+    assert_eq!(root["react"].version.as_deref(), Some("18.2.0"));
+    assert_eq!(
+        written.packages.keys().collect::<Vec<_>>(),
+        [
+            "left-pad@1.3.0",
+            "lodash@4.17.21",
+            "loose-envify@1.4.0",
+            "react@18.2.0"
+        ]
+    );
+}
+
+#[test]
+fn drops_specs_neither_side_matches_so_they_re_resolve() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snpm-lock.yaml");
+    fs::write(&path, CONFLICTED).unwrap();
+
+    let resolution = resolve_conflicts(
+        &path,
+        &specs(&[
+            ("left-pad", "^1.3.0"),
+            ("lodash", "^5.0.0"),
+            ("react", "^18.0.0"),
+        ]),
+    )
+    .unwrap()
+    .unwrap();
+
+    let lodash = resolution
+        .reconciled
+        .iter()
+        .find(|dependency| dependency.name == "lodash")
+        .unwrap();
+    assert_eq!(lodash.outcome, Reconciliation::Reresolve);
+    assert!(!resolution.lockfile.root.dependencies.contains_key("lodash"));
+    assert!(
+        !resolution
+            .lockfile
+            .packages
+            .keys()
+            .any(|key| key.starts_with("lodash@"))
+    );
+    assert!(
+        !lockfile::root_specs_match(
+            &resolution.lockfile,
+            &specs(&[
+                ("left-pad", "^1.3.0"),
+                ("lodash", "^5.0.0"),
+                ("react", "^18.0.0")
+            ]),
+            &BTreeMap::new()
+        ),
+        "the dropped spec must send install down the resolve path"
+    );
+}
+
+#[test]
+fn prefers_the_newer_lock_when_both_sides_match() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snpm-lock.yaml");
+    fs::write(
+        &path,
+        CONFLICTED
+            .replace("requested: ^4.17.20", "requested: ^4.17.0")
+            .replace("requested: ^4.17.21", "requested: ^4.17.0"),
+    )
+    .unwrap();
+
+    let resolution = resolve_conflicts(
+        &path,
+        &specs(&[
+            ("left-pad", "^1.3.0"),
+            ("lodash", "^4.17.0"),
+            ("react", "^18.0.0"),
+        ]),
+    )
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(
+        resolution.lockfile.root.dependencies["lodash"]
+            .version
+            .as_deref(),
+        Some("4.17.21")
+    );
+}
+
+#[test]
+fn leaves_clean_lockfiles_alone() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snpm-lock.yaml");
+    let (ours, _) = split_sides(CONFLICTED).unwrap();
+    fs::write(&path, &ours).unwrap();
+
+    assert!(
+        resolve_conflicts(&path, &BTreeMap::new())
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), ours);
+    assert!(
+        resolve_conflicts(&dir.path().join("missing.yaml"), &BTreeMap::new())
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn read_reports_unresolved_conflicts() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snpm-lock.yaml");
+    fs::write(&path, CONFLICTED).unwrap();
+
+    let error = lockfile::read(&path).unwrap_err();
+    assert!(error.to_string().contains("unresolved merge conflicts"));
+}
@@ -24,6 +24,12 @@ pub(super) fn build_package_nodes(lockfile: &Lockfile) -> BTreeMap<PackageId, Re
                 bundled_dependencies: lock_pkg.bundled_dependencies.clone(),
                 has_bin: lock_pkg.has_bin,
                 bin: lock_pkg.bin.clone(),
+                os: lock_pkg.os.clone(),
+                cpu: lock_pkg.cpu.clone(),
+                libc: lock_pkg.libc.clone(),
+                engines: lock_pkg.engines.clone(),
+                deprecated: lock_pkg.deprecated.clone(),
+                has_install_script: lock_pkg.has_install_script,
             },
         );
     }
@@ -9,6 +9,7 @@ use std::collections::BTreeMap;
 fn to_graph_skips_unresolved_optional_roots() {
     let lockfile = Lockfile {
         version: 1,
+        settings: Default::default(),
         root: LockRoot {
             dependencies: BTreeMap::from([
                 (
@@ -31,6 +32,7 @@ fn to_graph_skips_unresolved_optional_roots() {
                 ),
             ]),
         },
+        importers: BTreeMap::new(),
         packages: BTreeMap::new(),
     };
 
@@ -44,6 +46,7 @@ fn to_graph_skips_unresolved_optional_roots() {
 fn to_graph_reconstructs_dependencies() {
     let lockfile = Lockfile {
         version: 1,
+        settings: Default::default(),
         root: LockRoot {
             dependencies: BTreeMap::from([(
                 "express".to_string(),
@@ -55,6 +58,7 @@ fn to_graph_reconstructs_dependencies() {
                 },
             )]),
         },
+        importers: BTreeMap::new(),
         packages: BTreeMap::from([
             (
                 "express@4.18.2".to_string(),
@@ -71,6 +75,12 @@ fn to_graph_reconstructs_dependencies() {
                     bundled_dependencies: None,
                     has_bin: true,
                     bin: Some(BinField::Single("cli.js".to_string())),
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             ),
             (
@@ -86,6 +96,12 @@ fn to_graph_reconstructs_dependencies() {
                     bundled_dependencies: None,
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             ),
         ]),
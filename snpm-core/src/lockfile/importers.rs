@@ -0,0 +1,176 @@
+use super::types::{LockImporter, LockRootDependency, Lockfile};
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+
+/// Importer key for `project_root`: its path relative to `workspace_root`
+/// with forward slashes, or `.` for the workspace root itself.
+pub fn importer_key(workspace_root: &Path, project_root: &Path) -> String {
+    let relative = project_root
+        .strip_prefix(workspace_root)
+        .unwrap_or(project_root);
+
+    let parts = relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
+/// Importer keys whose declared specs or workspace links differ between
+/// the lockfile and `expected`. Resolved versions are ignored; only what
+/// each project asked for is compared.
+///
+/// Lockfiles written before importers were recorded have none, and never
+/// report drift — the flattened root check still covers them.
+pub fn importer_drift(
+    lockfile: &Lockfile,
+    expected: &BTreeMap<String, LockImporter>,
+) -> Vec<String> {
+    if lockfile.importers.is_empty() {
+        return Vec::new();
+    }
+
+    let mut drifted = Vec::new();
+    let empty = LockImporter::default();
+
+    for (key, importer) in expected {
+        let locked = lockfile.importers.get(key).unwrap_or(&empty);
+        if !importer_specs_match(locked, importer) {
+            drifted.push(key.clone());
+        }
+    }
+
+    for key in lockfile.importers.keys() {
+        if !expected.contains_key(key) {
+            drifted.push(key.clone());
+        }
+    }
+
+    drifted.sort();
+    drifted
+}
+
+fn importer_specs_match(locked: &LockImporter, expected: &LockImporter) -> bool {
+    block_specs_match(&locked.dependencies, &expected.dependencies)
+        && block_specs_match(&locked.dev_dependencies, &expected.dev_dependencies)
+        && locked.links == expected.links
+}
+
+fn block_specs_match(
+    locked: &BTreeMap<String, LockRootDependency>,
+    expected: &BTreeMap<String, LockRootDependency>,
+) -> bool {
+    locked.len() == expected.len()
+        && expected.iter().all(|(name, dep)| {
+            locked.get(name).is_some_and(|locked| {
+                locked.requested == dep.requested && locked.optional == dep.optional
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockRoot;
+    use std::path::PathBuf;
+
+    fn spec(requested: &str, version: Option<&str>) -> LockRootDependency {
+        LockRootDependency {
+            requested: requested.to_string(),
+            package: None,
+            version: version.map(str::to_string),
+            optional: false,
+        }
+    }
+
+    fn lockfile_with(importers: BTreeMap<String, LockImporter>) -> Lockfile {
+        Lockfile {
+            version: 1,
+            settings: Default::default(),
+            root: LockRoot {
+                dependencies: BTreeMap::new(),
+            },
+            importers,
+            packages: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn importer_key_is_relative_with_forward_slashes() {
+        let root = PathBuf::from("/repo");
+        assert_eq!(importer_key(&root, &root), ".");
+        assert_eq!(
+            importer_key(&root, &root.join("packages").join("app")),
+            "packages/app"
+        );
+    }
+
+    #[test]
+    fn importer_drift_ignores_resolved_versions() {
+        let locked = BTreeMap::from([(
+            "packages/app".to_string(),
+            LockImporter {
+                dependencies: BTreeMap::from([("a".to_string(), spec("^1.0.0", Some("1.2.0")))]),
+                ..LockImporter::default()
+            },
+        )]);
+        let expected = BTreeMap::from([(
+            "packages/app".to_string(),
+            LockImporter {
+                dependencies: BTreeMap::from([("a".to_string(), spec("^1.0.0", None))]),
+                ..LockImporter::default()
+            },
+        )]);
+
+        assert!(importer_drift(&lockfile_with(locked), &expected).is_empty());
+    }
+
+    #[test]
+    fn importer_drift_names_changed_added_and_removed_projects() {
+        let locked = BTreeMap::from([
+            (
+                "packages/app".to_string(),
+                LockImporter {
+                    dependencies: BTreeMap::from([("a".to_string(), spec("^1.0.0", None))]),
+                    ..LockImporter::default()
+                },
+            ),
+            ("packages/gone".to_string(), LockImporter::default()),
+        ]);
+        let expected = BTreeMap::from([
+            (
+                "packages/app".to_string(),
+                LockImporter {
+                    dependencies: BTreeMap::from([("a".to_string(), spec("^2.0.0", None))]),
+                    ..LockImporter::default()
+                },
+            ),
+            (
+                "packages/new".to_string(),
+                LockImporter {
+                    links: BTreeMap::from([("app".to_string(), "packages/app".to_string())]),
+                    ..LockImporter::default()
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            importer_drift(&lockfile_with(locked), &expected),
+            vec!["packages/app", "packages/gone", "packages/new"]
+        );
+    }
+
+    #[test]
+    fn importer_drift_skips_lockfiles_without_importers() {
+        let expected = BTreeMap::from([(".".to_string(), LockImporter::default())]);
+        assert!(importer_drift(&lockfile_with(BTreeMap::new()), &expected).is_empty());
+    }
+}
@@ -18,7 +18,11 @@
 //! format at all. The mirrors serialize every field and use ordinary
 //! tagged enums.
 
-use super::super::types::{LockPackage, LockRoot, LockRootDependency, Lockfile};
+use super::super::types::{
+    LockImporter, LockPackage, LockRoot, LockRootDependency, LockSettings, Lockfile,
+};
+use crate::config::ResolutionMode;
+use crate::platform::SupportedArchitectures;
 use crate::project::BinField;
 use crate::registry::BundledDependencies;
 
@@ -28,16 +32,28 @@ use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 const MAGIC: [u8; 4] = *b"SNPB";
-const FORMAT_VERSION: u32 = 3;
+const FORMAT_VERSION: u32 = 4;
 const HEADER_LEN: usize = 4 + 4 + 32;
 
 #[derive(Serialize, Deserialize)]
 struct BinLockfile {
     version: u32,
+    settings: BinSettings,
     root: BTreeMap<String, BinRootDependency>,
+    importers: BTreeMap<String, BinImporter>,
     packages: BTreeMap<String, BinPackage>,
 }
 
+#[derive(Serialize, Deserialize)]
+struct BinSettings {
+    package_extensions_checksum: Option<String>,
+    supported_os: Vec<String>,
+    supported_cpu: Vec<String>,
+    supported_libc: Vec<String>,
+    resolution_mode: ResolutionMode,
+    before: Option<String>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct BinRootDependency {
     requested: String,
@@ -46,6 +62,13 @@ struct BinRootDependency {
     optional: bool,
 }
 
+#[derive(Serialize, Deserialize)]
+struct BinImporter {
+    dependencies: BTreeMap<String, BinRootDependency>,
+    dev_dependencies: BTreeMap<String, BinRootDependency>,
+    links: BTreeMap<String, String>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct BinPackage {
     name: String,
@@ -57,6 +80,12 @@ struct BinPackage {
     bundled_dependencies: Option<BinBundledDependencies>,
     has_bin: bool,
     bin: Option<BinBinField>,
+    os: Vec<String>,
+    cpu: Vec<String>,
+    libc: Vec<String>,
+    engines: BTreeMap<String, String>,
+    deprecated: Option<String>,
+    has_install_script: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -75,18 +104,25 @@ impl From<&Lockfile> for BinLockfile {
     fn from(lockfile: &Lockfile) -> Self {
         Self {
             version: lockfile.version,
-            root: lockfile
-                .root
-                .dependencies
+            settings: BinSettings {
+                package_extensions_checksum: lockfile.settings.package_extensions_checksum.clone(),
+                supported_os: lockfile.settings.supported_architectures.os.clone(),
+                supported_cpu: lockfile.settings.supported_architectures.cpu.clone(),
+                supported_libc: lockfile.settings.supported_architectures.libc.clone(),
+                resolution_mode: lockfile.settings.resolution_mode,
+                before: lockfile.settings.before.clone(),
+            },
+            root: encode_root_dependencies(&lockfile.root.dependencies),
+            importers: lockfile
+                .importers
                 .iter()
-                .map(|(name, dep)| {
+                .map(|(key, importer)| {
                     (
-                        name.clone(),
-                        BinRootDependency {
-                            requested: dep.requested.clone(),
-                            package: dep.package.clone(),
-                            version: dep.version.clone(),
-                            optional: dep.optional,
+                        key.clone(),
+                        BinImporter {
+                            dependencies: encode_root_dependencies(&importer.dependencies),
+                            dev_dependencies: encode_root_dependencies(&importer.dev_dependencies),
+                            links: importer.links.clone(),
                         },
                     )
                 })
@@ -119,6 +155,12 @@ impl From<&Lockfile> for BinLockfile {
                                 BinField::Single(value) => BinBinField::Single(value.clone()),
                                 BinField::Map(map) => BinBinField::Map(map.clone()),
                             }),
+                            os: package.os.clone(),
+                            cpu: package.cpu.clone(),
+                            libc: package.libc.clone(),
+                            engines: package.engines.clone(),
+                            deprecated: package.deprecated.clone(),
+                            has_install_script: package.has_install_script,
                         },
                     )
                 })
@@ -131,23 +173,33 @@ impl From<BinLockfile> for Lockfile {
     fn from(binary: BinLockfile) -> Self {
         Self {
             version: binary.version,
+            settings: LockSettings {
+                package_extensions_checksum: binary.settings.package_extensions_checksum,
+                supported_architectures: SupportedArchitectures {
+                    os: binary.settings.supported_os,
+                    cpu: binary.settings.supported_cpu,
+                    libc: binary.settings.supported_libc,
+                },
+                resolution_mode: binary.settings.resolution_mode,
+                before: binary.settings.before,
+            },
             root: LockRoot {
-                dependencies: binary
-                    .root
-                    .into_iter()
-                    .map(|(name, dep)| {
-                        (
-                            name,
-                            LockRootDependency {
-                                requested: dep.requested,
-                                package: dep.package,
-                                version: dep.version,
-                                optional: dep.optional,
-                            },
-                        )
-                    })
-                    .collect(),
+                dependencies: decode_root_dependencies(binary.root),
             },
+            importers: binary
+                .importers
+                .into_iter()
+                .map(|(key, importer)| {
+                    (
+                        key,
+                        LockImporter {
+                            dependencies: decode_root_dependencies(importer.dependencies),
+                            dev_dependencies: decode_root_dependencies(importer.dev_dependencies),
+                            links: importer.links,
+                        },
+                    )
+                })
+                .collect(),
             packages: binary
                 .packages
                 .into_iter()
@@ -176,6 +228,12 @@ impl From<BinLockfile> for Lockfile {
                                 BinBinField::Single(value) => BinField::Single(value),
                                 BinBinField::Map(map) => BinField::Map(map),
                             }),
+                            os: package.os,
+                            cpu: package.cpu,
+                            libc: package.libc,
+                            engines: package.engines,
+                            deprecated: package.deprecated,
+                            has_install_script: package.has_install_script,
                         },
                     )
                 })
@@ -184,6 +242,44 @@ impl From<BinLockfile> for Lockfile {
     }
 }
 
+fn encode_root_dependencies(
+    dependencies: &BTreeMap<String, LockRootDependency>,
+) -> BTreeMap<String, BinRootDependency> {
+    dependencies
+        .iter()
+        .map(|(name, dep)| {
+            (
+                name.clone(),
+                BinRootDependency {
+                    requested: dep.requested.clone(),
+                    package: dep.package.clone(),
+                    version: dep.version.clone(),
+                    optional: dep.optional,
+                },
+            )
+        })
+        .collect()
+}
+
+fn decode_root_dependencies(
+    dependencies: BTreeMap<String, BinRootDependency>,
+) -> BTreeMap<String, LockRootDependency> {
+    dependencies
+        .into_iter()
+        .map(|(name, dep)| {
+            (
+                name,
+                LockRootDependency {
+                    requested: dep.requested,
+                    package: dep.package,
+                    version: dep.version,
+                    optional: dep.optional,
+                },
+            )
+        })
+        .collect()
+}
+
 pub(super) fn sidecar_path(yaml_path: &Path) -> PathBuf {
     yaml_path.with_extension("bin")
 }
@@ -233,7 +329,9 @@ pub(super) fn decode_sidecar(bytes: &[u8], expected_yaml_hash: [u8; 32]) -> Opti
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::lockfile::types::{LockPackage, LockRoot, LockRootDependency, Lockfile};
+    use crate::lockfile::types::{
+        LockImporter, LockPackage, LockRoot, LockRootDependency, Lockfile,
+    };
     use crate::project::BinField;
     use crate::registry::BundledDependencies;
     use std::collections::BTreeMap;
@@ -241,16 +339,18 @@ mod tests {
     fn sample_lockfile() -> Lockfile {
         Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: BTreeMap::new(),
             },
+            importers: BTreeMap::new(),
             packages: BTreeMap::new(),
         }
     }
 
     /// A lockfile shaped like real ones: root deps with skipped
-    /// (`None`/`false`) fields, packages with and without bins,
-    /// untagged-enum values in both variants.
+    /// (`None`/`false`) fields, workspace importers, packages with and
+    /// without bins, untagged-enum values in both variants.
     fn realistic_lockfile() -> Lockfile {
         let mut root_deps = BTreeMap::new();
         root_deps.insert(
@@ -288,6 +388,12 @@ mod tests {
                 bundled_dependencies: None,
                 has_bin: false,
                 bin: None,
+                os: Vec::new(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: None,
+                has_install_script: false,
             },
         );
         packages.insert(
@@ -302,6 +408,12 @@ mod tests {
                 bundled_dependencies: Some(BundledDependencies::List(vec!["vendored".to_string()])),
                 has_bin: true,
                 bin: Some(BinField::Single("cli.js".to_string())),
+                os: Vec::new(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: None,
+                has_install_script: false,
             },
         );
         packages.insert(
@@ -319,14 +431,43 @@ mod tests {
                     "multi".to_string(),
                     "bin/multi.js".to_string(),
                 )]))),
+                os: Vec::new(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: None,
+                has_install_script: false,
+            },
+        );
+
+        let mut importers = BTreeMap::new();
+        importers.insert(
+            ".".to_string(),
+            LockImporter {
+                dependencies: BTreeMap::from([("is-odd".to_string(), root_deps["is-odd"].clone())]),
+                dev_dependencies: BTreeMap::new(),
+                links: BTreeMap::from([("app".to_string(), "packages/app".to_string())]),
+            },
+        );
+        importers.insert(
+            "packages/app".to_string(),
+            LockImporter {
+                dependencies: BTreeMap::new(),
+                dev_dependencies: BTreeMap::from([(
+                    "aliased".to_string(),
+                    root_deps["aliased"].clone(),
+                )]),
+                links: BTreeMap::new(),
             },
         );
 
         Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: root_deps,
             },
+            importers,
             packages,
         }
     }
@@ -2,8 +2,9 @@ mod binary;
 mod read;
 mod write;
 
-pub use read::read;
-pub use write::write;
+pub use read::{parse, read};
+pub(crate) use write::write_lockfile;
+pub use write::{write, write_with_importers};
 
 #[cfg(test)]
 mod tests;
@@ -1,3 +1,4 @@
+use super::super::conflict::has_conflict_markers;
 use super::super::types::{LOCKFILE_VERSION, Lockfile};
 use super::binary::{decode_sidecar, sidecar_path, yaml_hash};
 use crate::{Result, SnpmError};
@@ -26,9 +27,19 @@ pub fn read(path: &Path) -> Result<Lockfile> {
         path: path.to_path_buf(),
         reason: error.to_string(),
     })?;
+    parse(path, yaml)
+}
+
+/// Parses lockfile YAML that did not come straight from disk, such as a
+/// revision read out of git. `path` is only used in errors.
+pub fn parse(path: &Path, yaml: &str) -> Result<Lockfile> {
     let lockfile: Lockfile = serde_yaml::from_str(yaml).map_err(|error| SnpmError::Lockfile {
         path: path.to_path_buf(),
-        reason: error.to_string(),
+        reason: if has_conflict_markers(yaml) {
+            "it has unresolved merge conflicts. Run `snpm install` to reconcile them".to_string()
+        } else {
+            error.to_string()
+        },
     })?;
     check_version(&lockfile, path)?;
     Ok(lockfile)
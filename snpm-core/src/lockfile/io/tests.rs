@@ -1,4 +1,7 @@
-use super::{read, write};
+use super::{read, write, write_with_importers};
+use crate::config::ResolutionMode;
+use crate::lockfile::{LockImporter, LockRootDependency, LockSettings};
+use crate::platform::SupportedArchitectures;
 use crate::project::BinField;
 use crate::resolve::{PackageId, ResolutionGraph, ResolutionRoot, ResolvedPackage, RootDependency};
 
@@ -19,6 +22,12 @@ fn write_and_read_round_trip() {
         bundled_dependencies: None,
         has_bin: true,
         bin: Some(BinField::Single("cli.js".to_string())),
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
 
     let graph = ResolutionGraph {
@@ -37,7 +46,7 @@ fn write_and_read_round_trip() {
     let dir = tempfile::tempdir().unwrap();
     let path = dir.path().join("snpm-lock.yaml");
 
-    write(&path, &graph, &BTreeMap::new()).unwrap();
+    write(&path, &graph, &BTreeMap::new(), &LockSettings::default()).unwrap();
     let lockfile = read(&path).unwrap();
 
     assert_eq!(lockfile.version, 1);
@@ -68,6 +77,12 @@ fn aliased_root_dependency_round_trips_with_real_package_identity() {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
 
     let graph = ResolutionGraph {
@@ -86,7 +101,7 @@ fn aliased_root_dependency_round_trips_with_real_package_identity() {
     let dir = tempfile::tempdir().unwrap();
     let path = dir.path().join("snpm-lock.yaml");
 
-    write(&path, &graph, &BTreeMap::new()).unwrap();
+    write(&path, &graph, &BTreeMap::new(), &LockSettings::default()).unwrap();
     let lockfile = read(&path).unwrap();
 
     let root_dep = &lockfile.root.dependencies["my-alias"];
@@ -127,6 +142,12 @@ fn write_emits_binary_sidecar_alongside_yaml() {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     let graph = ResolutionGraph {
         root: ResolutionRoot {
@@ -143,7 +164,7 @@ fn write_emits_binary_sidecar_alongside_yaml() {
 
     let dir = tempfile::tempdir().unwrap();
     let path = dir.path().join("snpm-lock.yaml");
-    write(&path, &graph, &BTreeMap::new()).unwrap();
+    write(&path, &graph, &BTreeMap::new(), &LockSettings::default()).unwrap();
 
     assert!(path.is_file(), "yaml lockfile should be written");
     assert!(
@@ -171,6 +192,12 @@ fn read_falls_back_to_yaml_when_sidecar_is_stale() {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     let graph = ResolutionGraph {
         root: ResolutionRoot {
@@ -184,7 +211,7 @@ fn read_falls_back_to_yaml_when_sidecar_is_stale() {
         },
         packages: BTreeMap::from([(id, pkg)]),
     };
-    write(&path, &graph, &BTreeMap::new()).unwrap();
+    write(&path, &graph, &BTreeMap::new(), &LockSettings::default()).unwrap();
 
     // Hand-edit the YAML so its hash no longer matches the sidecar. The
     // sidecar's bincode payload still describes the original tarball; the
@@ -261,6 +288,12 @@ fn write_then_read_round_trips_repeatedly_via_sidecar() {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     let graph = ResolutionGraph {
         root: ResolutionRoot {
@@ -279,7 +312,7 @@ fn write_then_read_round_trips_repeatedly_via_sidecar() {
     let path = dir.path().join("snpm-lock.yaml");
 
     for _ in 0..5 {
-        write(&path, &graph, &BTreeMap::new()).unwrap();
+        write(&path, &graph, &BTreeMap::new(), &LockSettings::default()).unwrap();
         let lockfile = read(&path).unwrap();
         assert_eq!(lockfile.packages.len(), 1);
         let entry = &lockfile.packages["round-trip@1.0.0"];
@@ -305,6 +338,12 @@ fn whitespace_only_yaml_edit_invalidates_sidecar() {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     let graph = ResolutionGraph {
         root: ResolutionRoot {
@@ -321,7 +360,7 @@ fn whitespace_only_yaml_edit_invalidates_sidecar() {
 
     let dir = tempfile::tempdir().unwrap();
     let path = dir.path().join("snpm-lock.yaml");
-    write(&path, &graph, &BTreeMap::new()).unwrap();
+    write(&path, &graph, &BTreeMap::new(), &LockSettings::default()).unwrap();
 
     // Append a trailing newline (whitespace-only). The YAML still parses to
     // the same Lockfile, but the bytes differ — sidecar must be rejected.
@@ -332,7 +371,13 @@ fn whitespace_only_yaml_edit_invalidates_sidecar() {
     let lockfile = read(&path).unwrap();
     assert_eq!(lockfile.packages.len(), 1);
     // Round-tripping again should resync the sidecar.
-    write(&path, &lockfile_to_graph(&lockfile), &BTreeMap::new()).unwrap();
+    write(
+        &path,
+        &lockfile_to_graph(&lockfile),
+        &BTreeMap::new(),
+        &LockSettings::default(),
+    )
+    .unwrap();
     let again = read(&path).unwrap();
     assert_eq!(again.packages.len(), 1);
 }
@@ -378,6 +423,12 @@ fn lockfile_to_graph(lockfile: &crate::lockfile::types::Lockfile) -> ResolutionG
                 bundled_dependencies: entry.bundled_dependencies.clone(),
                 has_bin: entry.has_bin,
                 bin: entry.bin.clone(),
+                os: Vec::new(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: None,
+                has_install_script: false,
             };
             (id, pkg)
         })
@@ -389,3 +440,199 @@ fn lockfile_to_graph(lockfile: &crate::lockfile::types::Lockfile) -> ResolutionG
         packages,
     }
 }
+
+#[test]
+fn write_with_importers_fills_versions_from_linked_packages() {
+    let id = PackageId {
+        name: "shared".to_string(),
+        version: "1.4.0".to_string(),
+    };
+    let graph = ResolutionGraph {
+        root: ResolutionRoot {
+            dependencies: BTreeMap::from([(
+                "shared".to_string(),
+                RootDependency {
+                    requested: "^1.2.0".to_string(),
+                    resolved: id.clone(),
+                },
+            )]),
+        },
+        packages: BTreeMap::from([(
+            id.clone(),
+            ResolvedPackage {
+                id,
+                tarball: "https://example.com/shared-1.4.0.tgz".to_string(),
+                integrity: None,
+                dependencies: BTreeMap::new(),
+                peer_dependencies: BTreeMap::new(),
+                bundled_dependencies: None,
+                has_bin: false,
+                bin: None,
+                os: Vec::new(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: None,
+                has_install_script: false,
+            },
+        )]),
+    };
+    let importers = BTreeMap::from([(
+        "packages/app".to_string(),
+        LockImporter {
+            dependencies: BTreeMap::from([(
+                "shared".to_string(),
+                LockRootDependency {
+                    requested: "^1.0.0".to_string(),
+                    package: None,
+                    version: None,
+                    optional: false,
+                },
+            )]),
+            dev_dependencies: BTreeMap::new(),
+            links: BTreeMap::from([("lib".to_string(), "packages/lib".to_string())]),
+        },
+    )]);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snpm-lock.yaml");
+
+    write_with_importers(
+        &path,
+        &graph,
+        &BTreeMap::new(),
+        &importers,
+        &LockSettings::default(),
+    )
+    .unwrap();
+    let yaml = std::fs::read_to_string(&path).unwrap();
+    assert!(yaml.contains("importers:"));
+
+    let lockfile = read(&path).unwrap();
+    let app = &lockfile.importers["packages/app"];
+    assert_eq!(app.dependencies["shared"].requested, "^1.0.0");
+    assert_eq!(app.dependencies["shared"].version.as_deref(), Some("1.4.0"));
+    assert_eq!(app.links["lib"], "packages/lib");
+
+    // The YAML path must agree with the sidecar.
+    std::fs::remove_file(path.with_extension("bin")).unwrap();
+    assert_eq!(read(&path).unwrap(), lockfile);
+}
+
+#[test]
+fn lockfiles_without_importers_still_read() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snpm-lock.yaml");
+    std::fs::write(
+        &path,
+        "version: 1\nroot:\n  dependencies: {}\npackages: {}\n",
+    )
+    .unwrap();
+
+    let lockfile = read(&path).unwrap();
+    assert!(lockfile.importers.is_empty());
+}
+
+#[test]
+fn settings_round_trip_through_yaml_and_sidecar() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snpm-lock.yaml");
+    let graph = ResolutionGraph {
+        root: ResolutionRoot {
+            dependencies: BTreeMap::new(),
+        },
+        packages: BTreeMap::new(),
+    };
+    let settings = LockSettings {
+        package_extensions_checksum: Some("sha256-abc".to_string()),
+        supported_architectures: SupportedArchitectures {
+            os: vec!["current".to_string(), "linux".to_string()],
+            cpu: vec!["arm64".to_string()],
+            libc: Vec::new(),
+        },
+        resolution_mode: ResolutionMode::TimeBased,
+        before: Some("2024-05-01T00:00:00Z".to_string()),
+    };
+
+    write(&path, &graph, &BTreeMap::new(), &settings).unwrap();
+    let yaml = std::fs::read_to_string(&path).unwrap();
+    assert!(yaml.contains("packageExtensionsChecksum: sha256-abc"));
+    assert!(yaml.contains("supportedArchitectures:"));
+    assert!(yaml.contains("resolutionMode: time-based"));
+    assert!(yaml.contains("before: 2024-05-01T00:00:00Z"));
+    assert!(!yaml.contains("libc:"));
+    assert_eq!(read(&path).unwrap().settings, settings);
+
+    std::fs::remove_file(path.with_extension("bin")).unwrap();
+    assert_eq!(read(&path).unwrap().settings, settings);
+
+    write(&path, &graph, &BTreeMap::new(), &LockSettings::default()).unwrap();
+    assert!(
+        !std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("settings:")
+    );
+}
+
+#[test]
+fn package_metadata_round_trips_through_yaml_and_sidecar() {
+    let id = PackageId {
+        name: "native".to_string(),
+        version: "1.0.0".to_string(),
+    };
+    let graph = ResolutionGraph {
+        root: ResolutionRoot {
+            dependencies: BTreeMap::new(),
+        },
+        packages: BTreeMap::from([(
+            id.clone(),
+            ResolvedPackage {
+                id,
+                tarball: "https://example.com/native-1.0.0.tgz".to_string(),
+                integrity: None,
+                dependencies: BTreeMap::new(),
+                peer_dependencies: BTreeMap::new(),
+                bundled_dependencies: None,
+                has_bin: false,
+                bin: None,
+                os: vec!["linux".to_string()],
+                cpu: vec!["x64".to_string()],
+                libc: vec!["glibc".to_string()],
+                engines: BTreeMap::from([("node".to_string(), ">=18".to_string())]),
+                deprecated: Some("use native@2".to_string()),
+                has_install_script: true,
+            },
+        )]),
+    };
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snpm-lock.yaml");
+    write(&path, &graph, &BTreeMap::new(), &LockSettings::default()).unwrap();
+
+    let lockfile = read(&path).unwrap();
+    let package = &lockfile.packages["native@1.0.0"];
+    assert_eq!(package.os, ["linux"]);
+    assert_eq!(package.cpu, ["x64"]);
+    assert_eq!(package.libc, ["glibc"]);
+    assert_eq!(package.engines["node"], ">=18");
+    assert_eq!(package.deprecated.as_deref(), Some("use native@2"));
+    assert!(package.has_install_script);
+
+    std::fs::remove_file(path.with_extension("bin")).unwrap();
+    assert_eq!(read(&path).unwrap(), lockfile);
+}
+
+#[test]
+fn packages_without_platform_constraints_still_read() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snpm-lock.yaml");
+    std::fs::write(
+        &path,
+        "version: 1\nroot:\n  dependencies: {}\npackages:\n  foo@1.0.0:\n    name: foo\n    version: 1.0.0\n    tarball: https://example.com/foo-1.0.0.tgz\n    dependencies: {}\n",
+    )
+    .unwrap();
+
+    let package = &read(&path).unwrap().packages["foo@1.0.0"];
+    assert!(package.os.is_empty() && package.cpu.is_empty() && package.libc.is_empty());
+    assert!(package.engines.is_empty());
+}
@@ -1,5 +1,8 @@
 use super::super::keys::package_key;
-use super::super::types::{LOCKFILE_VERSION, LockPackage, LockRoot, LockRootDependency, Lockfile};
+use super::super::types::{
+    LOCKFILE_VERSION, LockImporter, LockPackage, LockRoot, LockRootDependency, LockSettings,
+    Lockfile,
+};
 use super::binary::{encode_sidecar, sidecar_path, yaml_hash};
 use crate::resolve::ResolutionGraph;
 use crate::{Result, SnpmError};
@@ -12,15 +15,36 @@ pub fn write(
     path: &Path,
     graph: &ResolutionGraph,
     optional_root_specs: &BTreeMap<String, String>,
+    settings: &LockSettings,
+) -> Result<()> {
+    write_with_importers(path, graph, optional_root_specs, &BTreeMap::new(), settings)
+}
+
+/// Like [`write`], but also records per-project `importers`. Importer
+/// entries only need their declared specs; each is resolved to the graph
+/// package the workspace linker links into that project.
+pub fn write_with_importers(
+    path: &Path,
+    graph: &ResolutionGraph,
+    optional_root_specs: &BTreeMap<String, String>,
+    importers: &BTreeMap<String, LockImporter>,
+    settings: &LockSettings,
 ) -> Result<()> {
     let lockfile = Lockfile {
         version: LOCKFILE_VERSION,
+        settings: settings.clone(),
         root: LockRoot {
             dependencies: build_root_dependencies(graph, optional_root_specs),
         },
+        importers: build_importers(graph, importers),
         packages: build_packages(graph),
     };
 
+    write_lockfile(path, &lockfile)
+}
+
+/// Serializes `lockfile` as-is to `path`, with its binary sidecar.
+pub(crate) fn write_lockfile(path: &Path, lockfile: &Lockfile) -> Result<()> {
     let data = serde_yaml::to_string(&lockfile).map_err(|source| SnpmError::LockfileWrite {
         path: path.to_path_buf(),
         source,
@@ -34,7 +58,7 @@ pub fn write(
     // Write the binary sidecar next to the YAML. We intentionally swallow any
     // error from this path: the sidecar is a fast-load cache and never the
     // source of truth. A read can always fall back to the YAML.
-    if let Some(bytes) = encode_sidecar(&lockfile, yaml_hash(data.as_bytes())) {
+    if let Some(bytes) = encode_sidecar(lockfile, yaml_hash(data.as_bytes())) {
         let _ = fs::write(sidecar_path(path), bytes);
     }
 
@@ -73,6 +97,48 @@ fn build_root_dependencies(
     root_dependencies
 }
 
+fn build_importers(
+    graph: &ResolutionGraph,
+    importers: &BTreeMap<String, LockImporter>,
+) -> BTreeMap<String, LockImporter> {
+    importers
+        .iter()
+        .map(|(key, importer)| {
+            (
+                key.clone(),
+                LockImporter {
+                    dependencies: resolve_importer_block(graph, &importer.dependencies),
+                    dev_dependencies: resolve_importer_block(graph, &importer.dev_dependencies),
+                    links: importer.links.clone(),
+                },
+            )
+        })
+        .collect()
+}
+
+fn resolve_importer_block(
+    graph: &ResolutionGraph,
+    block: &BTreeMap<String, LockRootDependency>,
+) -> BTreeMap<String, LockRootDependency> {
+    block
+        .iter()
+        .map(|(name, dep)| {
+            let resolved = graph.linked_package(name).map(|package| &package.id);
+            (
+                name.clone(),
+                LockRootDependency {
+                    requested: dep.requested.clone(),
+                    package: resolved
+                        .filter(|id| id.name != *name)
+                        .map(|id| id.name.clone()),
+                    version: resolved.map(|id| id.version.clone()),
+                    optional: dep.optional,
+                },
+            )
+        })
+        .collect()
+}
+
 fn build_packages(graph: &ResolutionGraph) -> BTreeMap<String, LockPackage> {
     let mut packages = BTreeMap::new();
 
@@ -95,6 +161,12 @@ fn build_packages(graph: &ResolutionGraph) -> BTreeMap<String, LockPackage> {
                 bundled_dependencies: package.bundled_dependencies.clone(),
                 has_bin: package.has_bin,
                 bin: package.bin.clone(),
+                os: package.os.clone(),
+                cpu: package.cpu.clone(),
+                libc: package.libc.clone(),
+                engines: package.engines.clone(),
+                deprecated: package.deprecated.clone(),
+                has_install_script: package.has_install_script,
             },
         );
     }
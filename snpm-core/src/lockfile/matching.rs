@@ -42,6 +42,7 @@ mod tests {
     fn root_specs_match_accepts_unresolved_optional_roots() {
         let lockfile = Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: BTreeMap::from([
                     (
@@ -64,6 +65,7 @@ mod tests {
                     ),
                 ]),
             },
+            importers: BTreeMap::new(),
             packages: BTreeMap::new(),
         };
 
@@ -77,6 +79,7 @@ mod tests {
     fn root_specs_match_rejects_different_count() {
         let lockfile = Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: BTreeMap::from([(
                     "a".to_string(),
@@ -88,6 +91,7 @@ mod tests {
                     },
                 )]),
             },
+            importers: BTreeMap::new(),
             packages: BTreeMap::new(),
         };
 
@@ -102,6 +106,7 @@ mod tests {
     fn root_specs_match_rejects_different_range() {
         let lockfile = Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: BTreeMap::from([(
                     "a".to_string(),
@@ -113,6 +118,7 @@ mod tests {
                     },
                 )]),
             },
+            importers: BTreeMap::new(),
             packages: BTreeMap::new(),
         };
 
@@ -124,6 +130,7 @@ mod tests {
     fn root_specs_match_rejects_unresolved_required() {
         let lockfile = Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: BTreeMap::from([(
                     "a".to_string(),
@@ -135,6 +142,7 @@ mod tests {
                     },
                 )]),
             },
+            importers: BTreeMap::new(),
             packages: BTreeMap::new(),
         };
 
@@ -146,9 +154,11 @@ mod tests {
     fn root_specs_match_rejects_missing_dep() {
         let lockfile = Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: BTreeMap::new(),
             },
+            importers: BTreeMap::new(),
             packages: BTreeMap::new(),
         };
 
@@ -1,5 +1,7 @@
 mod compat;
+mod conflict;
 mod graph;
+mod importers;
 mod io;
 mod keys;
 mod matching;
@@ -9,7 +11,12 @@ pub use compat::{
     CompatibleLockfile, CompatibleLockfileKind, detect_compatible_lockfile,
     read_compatible_lockfile,
 };
+pub use conflict::{
+    ConflictResolution, ReconciledDependency, Reconciliation, has_conflict_markers,
+    resolve_conflicts,
+};
 pub use graph::to_graph;
-pub use io::{read, write};
+pub use importers::{importer_drift, importer_key};
+pub use io::{parse, read, write, write_with_importers};
 pub use matching::root_specs_match;
-pub use types::{LockPackage, LockRoot, LockRootDependency, Lockfile};
+pub use types::{LockImporter, LockPackage, LockRoot, LockRootDependency, LockSettings, Lockfile};
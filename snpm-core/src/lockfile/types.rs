@@ -1,3 +1,5 @@
+use crate::config::ResolutionMode;
+use crate::platform::SupportedArchitectures;
 use crate::project::BinField;
 use crate::registry::BundledDependencies;
 use serde::{Deserialize, Serialize};
@@ -21,6 +23,31 @@ pub struct LockRoot {
     pub dependencies: BTreeMap<String, LockRootDependency>,
 }
 
+/// One workspace project's slice of the lockfile, keyed in
+/// [`Lockfile::importers`] by the project's path relative to the
+/// workspace root (`.` for the root itself).
+///
+/// `root` is the flattened view the resolver works from; importers keep
+/// the per-project view so filtered installs, `why` and frozen checks can
+/// tell which project declared what.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockImporter {
+    /// Production and optional dependencies. Optional entries carry
+    /// `optional: true`, the same as in [`LockRoot`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, LockRootDependency>,
+    #[serde(
+        default,
+        skip_serializing_if = "BTreeMap::is_empty",
+        rename = "devDependencies"
+    )]
+    pub dev_dependencies: BTreeMap<String, LockRootDependency>,
+    /// Workspace siblings this project links to, mapped to the sibling's
+    /// importer key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LockPackage {
     pub name: String,
@@ -47,12 +74,75 @@ pub struct LockPackage {
     pub has_bin: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bin: Option<BinField>,
+    /// Platform constraints copied from the registry metadata, so a
+    /// lockfile install can skip packages for other platforms offline.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub os: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cpu: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub libc: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub engines: BTreeMap<String, String>,
+    /// The registry's deprecation message for this version, kept so the
+    /// install summary can report it without refetching metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+    /// Whether the registry reports a preinstall, install or postinstall
+    /// script, so `lockfile diff` can flag new ones without a registry.
+    #[serde(default, skip_serializing_if = "is_false", rename = "hasInstallScript")]
+    pub has_install_script: bool,
+}
+
+/// Inputs besides the manifests that shaped the resolution. A lockfile
+/// whose settings differ from the current ones is re-resolved rather than
+/// installed as-is.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockSettings {
+    /// Digest of the `packageExtensions` applied while resolving.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "packageExtensionsChecksum"
+    )]
+    pub package_extensions_checksum: Option<String>,
+    /// `supportedArchitectures` targets besides the host, as configured
+    /// (`current` is kept as a token so the lockfile reads the same on
+    /// every machine).
+    #[serde(
+        default,
+        skip_serializing_if = "SupportedArchitectures::is_empty",
+        rename = "supportedArchitectures"
+    )]
+    pub supported_architectures: SupportedArchitectures,
+    /// The `resolutionMode` the versions were picked under. Left out for
+    /// the default, `highest`.
+    #[serde(
+        default,
+        skip_serializing_if = "ResolutionMode::is_highest",
+        rename = "resolutionMode"
+    )]
+    pub resolution_mode: ResolutionMode,
+    /// The `--before` cutoff, in UTC, when versions published after it
+    /// were left out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+}
+
+impl LockSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &LockSettings::default()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Lockfile {
     pub version: u32,
+    #[serde(default, skip_serializing_if = "LockSettings::is_empty")]
+    pub settings: LockSettings,
     pub root: LockRoot,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub importers: BTreeMap<String, LockImporter>,
     pub packages: BTreeMap<String, LockPackage>,
 }
 
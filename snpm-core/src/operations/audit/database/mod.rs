@@ -0,0 +1,162 @@
+mod osv;
+mod sync;
+
+pub use sync::{AdvisoryDbSyncReport, sync_advisory_database};
+
+use super::types::BulkAuditResponse;
+use crate::{Result, SnpmError, console};
+
+use osv::OsvRecord;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Reads the OSV records under `database` (any directory of `.json`
+/// files, such as an unpacked osv.dev or GitHub advisory dump) and returns
+/// those affecting `packages`, shaped like the registry's bulk advisory
+/// response so the rest of the audit is the same.
+pub(super) fn load_bulk_advisories(
+    database: &Path,
+    packages: &BTreeMap<String, Vec<String>>,
+) -> Result<BulkAuditResponse> {
+    if !database.is_dir() {
+        return Err(SnpmError::AuditDatabase {
+            path: database.to_path_buf(),
+            reason: "not found; run `snpm audit db sync --from <archive>` to create it".to_string(),
+        });
+    }
+
+    let files = json_files(database)?;
+    let records: Vec<OsvRecord> = files
+        .par_iter()
+        .filter_map(|path| {
+            let parsed = fs::read(path)
+                .map_err(|error| error.to_string())
+                .and_then(|data| {
+                    serde_json::from_slice::<OsvRecord>(&data).map_err(|error| error.to_string())
+                });
+            match parsed {
+                Ok(record) => Some(record),
+                Err(error) => {
+                    console::verbose(&format!("skipping advisory {}: {error}", path.display()));
+                    None
+                }
+            }
+        })
+        .collect();
+
+    if records.is_empty() {
+        return Err(SnpmError::AuditDatabase {
+            path: database.to_path_buf(),
+            reason: "contains no OSV advisories".to_string(),
+        });
+    }
+
+    let mut response = BulkAuditResponse::new();
+    for record in records.iter().filter(|record| record.withdrawn.is_none()) {
+        let affected: BTreeSet<&str> = record
+            .npm_packages()
+            .filter(|name| packages.contains_key(*name))
+            .collect();
+
+        for package in affected {
+            if let Some(advisory) = record.bulk_advisory(package) {
+                response
+                    .entry(package.to_string())
+                    .or_default()
+                    .push(advisory);
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+fn json_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let entries = fs::read_dir(&dir).map_err(|source| SnpmError::ReadFile {
+            path: dir.clone(),
+            source,
+        })?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+
+            if file_type.is_dir() {
+                stack.push(path);
+            } else if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                files.push(path);
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::load_bulk_advisories;
+
+    use std::collections::BTreeMap;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn loads_matching_advisories_and_skips_withdrawn_ones() {
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("advisories/2024");
+        fs::create_dir_all(&nested).unwrap();
+        let advisory = |id: &str, name: &str, withdrawn: bool| {
+            let withdrawn = if withdrawn {
+                r#""withdrawn": "2024-01-02T00:00:00Z","#
+            } else {
+                ""
+            };
+            format!(
+                r#"{{"id": "{id}", {withdrawn} "affected": [{{
+                    "package": {{"ecosystem": "npm", "name": "{name}"}},
+                    "ranges": [{{"type": "SEMVER", "events": [{{"introduced": "0"}}, {{"fixed": "1.0.1"}}]}}]
+                }}]}}"#
+            )
+        };
+        fs::write(
+            nested.join("GHSA-1.json"),
+            advisory("GHSA-1", "left", false),
+        )
+        .unwrap();
+        fs::write(nested.join("GHSA-2.json"), advisory("GHSA-2", "left", true)).unwrap();
+        fs::write(
+            dir.path().join("GHSA-3.json"),
+            advisory("GHSA-3", "right", false),
+        )
+        .unwrap();
+        fs::write(dir.path().join("broken.json"), "{").unwrap();
+
+        let packages = BTreeMap::from([("left".to_string(), vec!["1.0.0".to_string()])]);
+        let response = load_bulk_advisories(dir.path(), &packages).unwrap();
+
+        assert_eq!(response.len(), 1);
+        assert_eq!(response["left"].len(), 1);
+        assert_eq!(response["left"][0].vulnerable_versions, "<1.0.1");
+    }
+
+    #[test]
+    fn missing_database_is_an_error() {
+        let dir = tempdir().unwrap();
+
+        let error =
+            load_bulk_advisories(&dir.path().join("missing"), &BTreeMap::new()).unwrap_err();
+
+        assert!(error.to_string().contains("audit db sync"));
+    }
+}
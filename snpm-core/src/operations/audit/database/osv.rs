@@ -0,0 +1,316 @@
+use super::super::types::{BulkAdvisory, BulkCwe};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// One record of an OSV dump (https://ossf.github.io/osv-schema/), with
+/// only the fields an audit needs.
+#[derive(Debug, Deserialize)]
+pub(super) struct OsvRecord {
+    pub id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    details: Option<String>,
+    #[serde(default)]
+    pub withdrawn: Option<String>,
+    #[serde(default)]
+    affected: Vec<OsvAffected>,
+    #[serde(default)]
+    references: Vec<OsvReference>,
+    #[serde(default)]
+    database_specific: Option<OsvDatabaseSpecific>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvAffected {
+    #[serde(default)]
+    package: Option<OsvPackage>,
+    #[serde(default)]
+    ranges: Vec<OsvRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<OsvEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvEvent {
+    #[serde(default)]
+    introduced: Option<String>,
+    #[serde(default)]
+    fixed: Option<String>,
+    #[serde(default)]
+    last_affected: Option<String>,
+    #[serde(default)]
+    limit: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvReference {
+    #[serde(default, rename = "type")]
+    kind: String,
+    url: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OsvDatabaseSpecific {
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    cwe_ids: Vec<String>,
+}
+
+impl OsvRecord {
+    /// Names of the npm packages this record affects.
+    pub(super) fn npm_packages(&self) -> impl Iterator<Item = &str> {
+        self.affected
+            .iter()
+            .filter_map(|affected| affected.package.as_ref())
+            .filter(|package| package.ecosystem.eq_ignore_ascii_case("npm"))
+            .map(|package| package.name.as_str())
+    }
+
+    /// The record as the registry's bulk endpoint would report it for
+    /// `package`. `None` when none of its ranges affect `package`.
+    pub(super) fn bulk_advisory(&self, package: &str) -> Option<BulkAdvisory> {
+        let ranges: Vec<String> = self
+            .affected
+            .iter()
+            .filter(|affected| {
+                affected.package.as_ref().is_some_and(|candidate| {
+                    candidate.ecosystem.eq_ignore_ascii_case("npm") && candidate.name == package
+                })
+            })
+            .flat_map(affected_ranges)
+            .collect();
+        if ranges.is_empty() {
+            return None;
+        }
+
+        let database_specific = self.database_specific.as_ref();
+        let severity = database_specific
+            .and_then(|specific| specific.severity.as_deref())
+            .map(|severity| match severity.to_ascii_lowercase().as_str() {
+                "medium" => "moderate".to_string(),
+                other => other.to_string(),
+            })
+            // OSV records carry no severity label outside the GitHub
+            // database; report those at the middle of the scale.
+            .unwrap_or_else(|| "moderate".to_string());
+        let cwe_ids = database_specific
+            .map(|specific| specific.cwe_ids.clone())
+            .unwrap_or_default();
+
+        Some(BulkAdvisory {
+            id: numeric_id(&self.id, package),
+            url: Some(self.advisory_url()),
+            title: self.title(),
+            severity,
+            vulnerable_versions: ranges.join(" || "),
+            cwe: (!cwe_ids.is_empty()).then_some(BulkCwe::Many(cwe_ids)),
+            cves: self
+                .identifiers()
+                .filter(|id| id.starts_with("CVE-"))
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+
+    fn identifiers(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.id.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+
+    fn title(&self) -> String {
+        self.summary
+            .as_deref()
+            .or_else(|| {
+                self.details
+                    .as_deref()
+                    .and_then(|details| details.lines().next())
+            })
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or(&self.id)
+            .trim()
+            .to_string()
+    }
+
+    /// The GitHub advisory page when there is one, so the GHSA id can be
+    /// read back from it like for registry advisories.
+    fn advisory_url(&self) -> String {
+        if let Some(reference) = self.references.iter().find(|reference| {
+            reference.kind.eq_ignore_ascii_case("ADVISORY")
+                && reference.url.contains("github.com/advisories/")
+        }) {
+            return reference.url.clone();
+        }
+
+        match self.identifiers().find(|id| id.starts_with("GHSA-")) {
+            Some(ghsa) => format!("https://github.com/advisories/{ghsa}"),
+            None => format!("https://osv.dev/vulnerability/{}", self.id),
+        }
+    }
+}
+
+/// The semver ranges one `affected` entry describes. OSV lists events in
+/// order: each `introduced` opens a range that the next `fixed`,
+/// `last_affected` or `limit` closes. Explicit `versions` are only used
+/// when there are no ranges.
+fn affected_ranges(affected: &OsvAffected) -> Vec<String> {
+    let mut ranges = Vec::new();
+
+    for range in &affected.ranges {
+        if !matches!(range.kind.as_str(), "SEMVER" | "ECOSYSTEM") {
+            continue;
+        }
+
+        let mut introduced: Option<&str> = None;
+        for event in &range.events {
+            if let Some(version) = &event.introduced {
+                introduced = Some(version);
+            } else if let Some(upper) = event
+                .fixed
+                .as_ref()
+                .or(event.limit.as_ref())
+                .map(|version| format!("<{version}"))
+                .or_else(|| {
+                    event
+                        .last_affected
+                        .as_ref()
+                        .map(|version| format!("<={version}"))
+                })
+                && let Some(lower) = introduced.take()
+            {
+                ranges.push(match lower {
+                    "0" => upper,
+                    lower => format!(">={lower} {upper}"),
+                });
+            }
+        }
+
+        if let Some(lower) = introduced {
+            ranges.push(match lower {
+                "0" => "*".to_string(),
+                lower => format!(">={lower}"),
+            });
+        }
+    }
+
+    if ranges.is_empty() {
+        ranges.extend(affected.versions.iter().cloned());
+    }
+
+    ranges
+}
+
+/// Advisories from the registry carry numeric ids; OSV ids are strings.
+/// A stable number derived from the OSV id and package keeps the two
+/// interchangeable in reports (it fits in 53 bits for JSON consumers).
+fn numeric_id(id: &str, package: &str) -> u64 {
+    let digest = Sha256::digest(format!("{id}\0{package}"));
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes) >> 11
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OsvRecord;
+
+    fn record(json: &str) -> OsvRecord {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn converts_osv_events_to_semver_ranges() {
+        let record = record(
+            r#"{
+                "id": "GHSA-xvch-5gv4-984h",
+                "aliases": ["CVE-2021-44906"],
+                "summary": "Prototype Pollution in minimist",
+                "affected": [{
+                    "package": {"ecosystem": "npm", "name": "minimist"},
+                    "ranges": [{
+                        "type": "ECOSYSTEM",
+                        "events": [
+                            {"introduced": "0"}, {"fixed": "0.2.4"},
+                            {"introduced": "1.0.0"}, {"fixed": "1.2.6"}
+                        ]
+                    }]
+                }],
+                "database_specific": {"severity": "CRITICAL", "cwe_ids": ["CWE-1321"]}
+            }"#,
+        );
+
+        let advisory = record.bulk_advisory("minimist").unwrap();
+
+        assert_eq!(advisory.vulnerable_versions, "<0.2.4 || >=1.0.0 <1.2.6");
+        assert_eq!(advisory.severity, "critical");
+        assert_eq!(advisory.title, "Prototype Pollution in minimist");
+        assert_eq!(advisory.cves, vec!["CVE-2021-44906".to_string()]);
+        assert_eq!(
+            advisory.url.as_deref(),
+            Some("https://github.com/advisories/GHSA-xvch-5gv4-984h")
+        );
+        assert!(advisory.id < 1 << 53);
+        assert!(record.bulk_advisory("other").is_none());
+    }
+
+    #[test]
+    fn handles_last_affected_open_ranges_and_explicit_versions() {
+        let record = record(
+            r#"{
+                "id": "OSV-2024-1",
+                "details": "First line\nmore",
+                "affected": [
+                    {
+                        "package": {"ecosystem": "npm", "name": "pkg"},
+                        "ranges": [{
+                            "type": "SEMVER",
+                            "events": [
+                                {"introduced": "1.0.0"}, {"last_affected": "1.4.2"},
+                                {"introduced": "3.0.0"}
+                            ]
+                        }]
+                    },
+                    {
+                        "package": {"ecosystem": "npm", "name": "pkg"},
+                        "versions": ["2.0.1"]
+                    },
+                    {
+                        "package": {"ecosystem": "PyPI", "name": "pkg"},
+                        "versions": ["9.9.9"]
+                    }
+                ]
+            }"#,
+        );
+
+        let advisory = record.bulk_advisory("pkg").unwrap();
+
+        assert_eq!(
+            advisory.vulnerable_versions,
+            ">=1.0.0 <=1.4.2 || >=3.0.0 || 2.0.1"
+        );
+        assert_eq!(advisory.severity, "moderate");
+        assert_eq!(advisory.title, "First line");
+        assert_eq!(
+            advisory.url.as_deref(),
+            Some("https://osv.dev/vulnerability/OSV-2024-1")
+        );
+        assert_eq!(record.npm_packages().collect::<Vec<_>>(), ["pkg", "pkg"]);
+    }
+}
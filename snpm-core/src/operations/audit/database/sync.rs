@@ -0,0 +1,269 @@
+use super::json_files;
+use super::osv::OsvRecord;
+use crate::{Result, SnpmError};
+
+use flate2::read::GzDecoder;
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct AdvisoryDbSyncReport {
+    pub path: PathBuf,
+    /// npm advisories now in the database.
+    pub advisories: usize,
+}
+
+/// Replaces the advisory database at `database` with the npm advisories in
+/// `from`: an OSV dump as a `.zip` (osv.dev's `npm/all.zip`), a tarball
+/// (GitHub's advisory-database archive) or a directory. Other ecosystems
+/// are dropped. The old copy stays in place until the new one is complete.
+pub fn sync_advisory_database(from: &Path, database: &Path) -> Result<AdvisoryDbSyncReport> {
+    let parent = database.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(parent).map_err(|source| SnpmError::WriteFile {
+        path: parent.to_path_buf(),
+        source,
+    })?;
+
+    let name = database
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "advisory-db".to_string());
+    let staging = parent.join(format!(".{name}.sync-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging).map_err(|source| SnpmError::WriteFile {
+        path: staging.clone(),
+        source,
+    })?;
+
+    let imported = import_records(from, &staging).and_then(|ids| {
+        if ids.is_empty() {
+            Err(SnpmError::AuditDatabase {
+                path: from.to_path_buf(),
+                reason: "contains no npm advisories in OSV format".to_string(),
+            })
+        } else {
+            Ok(ids.len())
+        }
+    });
+    let advisories = match imported {
+        Ok(advisories) => advisories,
+        Err(error) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(error);
+        }
+    };
+
+    let previous = parent.join(format!(".{name}.old-{}", std::process::id()));
+    if database.exists() {
+        fs::rename(database, &previous).map_err(|source| SnpmError::WriteFile {
+            path: database.to_path_buf(),
+            source,
+        })?;
+    }
+    if let Err(source) = fs::rename(&staging, database) {
+        let _ = fs::rename(&previous, database);
+        let _ = fs::remove_dir_all(&staging);
+        return Err(SnpmError::WriteFile {
+            path: database.to_path_buf(),
+            source,
+        });
+    }
+    let _ = fs::remove_dir_all(&previous);
+
+    Ok(AdvisoryDbSyncReport {
+        path: database.to_path_buf(),
+        advisories,
+    })
+}
+
+/// Writes each npm record in `from` to `staging` as `<id>.json` and
+/// returns the ids written.
+fn import_records(from: &Path, staging: &Path) -> Result<BTreeSet<String>> {
+    let mut ids = BTreeSet::new();
+    let mut import = |entry: &str, data: Vec<u8>| -> Result<()> {
+        if !entry.ends_with(".json") {
+            return Ok(());
+        }
+        let Ok(record) = serde_json::from_slice::<OsvRecord>(&data) else {
+            return Ok(());
+        };
+        if record.npm_packages().next().is_none() {
+            return Ok(());
+        }
+
+        let path = staging.join(format!("{}.json", file_stem(&record.id)));
+        fs::write(&path, &data).map_err(|source| SnpmError::WriteFile { path, source })?;
+        ids.insert(record.id);
+        Ok(())
+    };
+
+    if from.is_dir() {
+        for path in json_files(from)? {
+            let data = fs::read(&path).map_err(|source| SnpmError::ReadFile {
+                path: path.clone(),
+                source,
+            })?;
+            import(&path.to_string_lossy(), data)?;
+        }
+        return Ok(ids);
+    }
+
+    let archive_error = |reason: String| SnpmError::AuditDatabase {
+        path: from.to_path_buf(),
+        reason,
+    };
+    let open = || {
+        File::open(from).map_err(|source| SnpmError::ReadFile {
+            path: from.to_path_buf(),
+            source,
+        })
+    };
+
+    let mut magic = [0u8; 2];
+    open()?
+        .read_exact(&mut magic)
+        .map_err(|error| archive_error(format!("not an archive: {error}")))?;
+
+    if magic == *b"PK" {
+        let mut archive = zip::ZipArchive::new(BufReader::new(open()?))
+            .map_err(|error| archive_error(error.to_string()))?;
+        for index in 0..archive.len() {
+            let mut file = archive
+                .by_index(index)
+                .map_err(|error| archive_error(error.to_string()))?;
+            if !file.is_file() {
+                continue;
+            }
+            let entry = file.name().to_string();
+            let mut data = Vec::new();
+            file.read_to_end(&mut data)
+                .map_err(|error| archive_error(format!("{entry}: {error}")))?;
+            import(&entry, data)?;
+        }
+        return Ok(ids);
+    }
+
+    let reader: Box<dyn Read> = if magic == [0x1f, 0x8b] {
+        Box::new(GzDecoder::new(BufReader::new(open()?)))
+    } else {
+        Box::new(BufReader::new(open()?))
+    };
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive
+        .entries()
+        .map_err(|error| archive_error(error.to_string()))?
+    {
+        let mut entry = entry.map_err(|error| archive_error(error.to_string()))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut data = Vec::new();
+        entry
+            .read_to_end(&mut data)
+            .map_err(|error| archive_error(format!("{name}: {error}")))?;
+        import(&name, data)?;
+    }
+
+    Ok(ids)
+}
+
+/// Advisory ids are things like `GHSA-xvch-5gv4-984h`; anything that
+/// could escape the database directory is replaced.
+fn file_stem(id: &str) -> String {
+    id.chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() || matches!(character, '-' | '_' | '.') {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sync_advisory_database;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::fs;
+    use std::io::Write;
+    use tar::{Builder, Header};
+    use tempfile::tempdir;
+
+    fn advisory(id: &str, ecosystem: &str) -> String {
+        format!(
+            r#"{{"id": "{id}", "affected": [{{
+                "package": {{"ecosystem": "{ecosystem}", "name": "pkg"}},
+                "ranges": [{{"type": "SEMVER", "events": [{{"introduced": "0"}}, {{"fixed": "1.0.1"}}]}}]
+            }}]}}"#
+        )
+    }
+
+    #[test]
+    fn imports_npm_advisories_from_a_tarball_and_replaces_the_old_copy() {
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("advisory-database.tar.gz");
+        let mut builder = Builder::new(Vec::new());
+        for (path, content) in [
+            (
+                "advisory-database-main/advisories/github-reviewed/GHSA-1.json",
+                advisory("GHSA-1", "npm"),
+            ),
+            (
+                "advisory-database-main/advisories/github-reviewed/GHSA-2.json",
+                advisory("GHSA-2", "PyPI"),
+            ),
+            (
+                "advisory-database-main/README.md",
+                "# advisories".to_string(),
+            ),
+        ] {
+            let mut header = Header::new_gnu();
+            header.set_path(path).unwrap();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, content.as_bytes()).unwrap();
+        }
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&builder.into_inner().unwrap()).unwrap();
+        fs::write(&archive, encoder.finish().unwrap()).unwrap();
+
+        let database = dir.path().join("data/advisory-db");
+        fs::create_dir_all(&database).unwrap();
+        fs::write(database.join("STALE.json"), advisory("STALE", "npm")).unwrap();
+
+        let report = sync_advisory_database(&archive, &database).unwrap();
+
+        assert_eq!(report.advisories, 1);
+        assert!(database.join("GHSA-1.json").is_file());
+        assert!(!database.join("GHSA-2.json").exists());
+        assert!(!database.join("STALE.json").exists());
+        assert_eq!(fs::read_dir(dir.path().join("data")).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn keeps_the_old_copy_when_the_archive_has_no_npm_advisories() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("dump");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("PYSEC-1.json"), advisory("PYSEC-1", "PyPI")).unwrap();
+
+        let database = dir.path().join("advisory-db");
+        fs::create_dir_all(&database).unwrap();
+        fs::write(database.join("GHSA-1.json"), advisory("GHSA-1", "npm")).unwrap();
+
+        assert!(sync_advisory_database(&source, &database).is_err());
+        assert!(database.join("GHSA-1.json").is_file());
+    }
+}
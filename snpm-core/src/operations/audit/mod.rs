@@ -1,13 +1,20 @@
+mod database;
 mod filter;
 mod request;
 mod sarif;
 mod service;
+mod signatures;
+mod suppressions;
 mod types;
 
+pub use database::{AdvisoryDbSyncReport, sync_advisory_database};
 pub use sarif::*;
-pub use service::{audit, audit_workspace, fix};
+pub use service::{audit, audit_workspace, expired_suppressions, fix};
+pub use signatures::audit_signatures;
+pub(crate) use signatures::{SignedPackage, verify_package_signatures};
 pub use types::{
     AuditAction, AuditAdvisory, AuditFinding, AuditMetadata, AuditOptions, AuditResolve,
-    AuditResponse, AuditResult, FixResult, FixableVulnerability, Severity, UnfixableVulnerability,
-    VulnerabilityCounts,
+    AuditResponse, AuditResult, AuditSuppression, FixAction, FixOutcome, FixResult,
+    FixableVulnerability, Severity, SignatureAuditResult, SignatureIssue, SuppressedAdvisory,
+    UnfixableVulnerability, VulnerabilityCounts,
 };
@@ -203,6 +203,7 @@ mod tests {
     fn sample_lockfile() -> Lockfile {
         Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: BTreeMap::from([
                     (
@@ -234,6 +235,7 @@ mod tests {
                     ),
                 ]),
             },
+            importers: BTreeMap::new(),
             packages: BTreeMap::from([
                 (
                     "prod-root@1.0.0".to_string(),
@@ -250,6 +252,12 @@ mod tests {
                         bundled_dependencies: None,
                         has_bin: false,
                         bin: None,
+                        os: Vec::new(),
+                        cpu: Vec::new(),
+                        libc: Vec::new(),
+                        engines: BTreeMap::new(),
+                        deprecated: None,
+                        has_install_script: false,
                     },
                 ),
                 (
@@ -267,6 +275,12 @@ mod tests {
                         bundled_dependencies: None,
                         has_bin: false,
                         bin: None,
+                        os: Vec::new(),
+                        cpu: Vec::new(),
+                        libc: Vec::new(),
+                        engines: BTreeMap::new(),
+                        deprecated: None,
+                        has_install_script: false,
                     },
                 ),
                 (
@@ -284,6 +298,12 @@ mod tests {
                         bundled_dependencies: None,
                         has_bin: false,
                         bin: None,
+                        os: Vec::new(),
+                        cpu: Vec::new(),
+                        libc: Vec::new(),
+                        engines: BTreeMap::new(),
+                        deprecated: None,
+                        has_install_script: false,
                     },
                 ),
                 (
@@ -298,6 +318,12 @@ mod tests {
                         bundled_dependencies: None,
                         has_bin: false,
                         bin: None,
+                        os: Vec::new(),
+                        cpu: Vec::new(),
+                        libc: Vec::new(),
+                        engines: BTreeMap::new(),
+                        deprecated: None,
+                        has_install_script: false,
                     },
                 ),
             ]),
@@ -1,8 +1,9 @@
 use super::super::filter::is_unfixable;
-use super::super::types::{AuditResult, Severity};
+use super::super::types::{AuditAdvisory, AuditResult, AuditSuppression, Severity};
 use super::types::{
     SarifArtifactLocation, SarifDriver, SarifLocation, SarifMessage, SarifPhysicalLocation,
-    SarifReport, SarifResult, SarifRule, SarifRuleConfig, SarifRun, SarifTool,
+    SarifReport, SarifResult, SarifRule, SarifRuleConfig, SarifRun, SarifSuppression,
+    SarifSuppressionProperties, SarifTool,
 };
 
 const SARIF_SCHEMA: &str = "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json";
@@ -13,52 +14,17 @@ impl AuditResult {
         let mut results = Vec::new();
 
         for advisory in &self.advisories {
-            let rule_id = format!("SNPM-{}", advisory.id);
-            let level = sarif_level(advisory.severity);
-
-            rules.push(SarifRule {
-                id: rule_id.clone(),
-                name: advisory.title.clone(),
-                short_description: SarifMessage {
-                    text: format!(
-                        "{} in {} ({})",
-                        advisory.title, advisory.module_name, advisory.severity
-                    ),
-                },
-                full_description: SarifMessage {
-                    text: advisory.overview.clone(),
-                },
-                help_uri: advisory.url.clone(),
-                default_configuration: SarifRuleConfig {
-                    level: level.to_string(),
-                },
-            });
-
-            for finding in &advisory.findings {
-                for path in &finding.paths {
-                    results.push(SarifResult {
-                        rule_id: rule_id.clone(),
-                        level: level.to_string(),
-                        message: SarifMessage {
-                            text: format!(
-                                "{} {} has {} vulnerability: {}. Fix: upgrade to {}",
-                                advisory.module_name,
-                                finding.version,
-                                advisory.severity,
-                                advisory.title,
-                                fix_text(&advisory.patched_versions),
-                            ),
-                        },
-                        locations: vec![SarifLocation {
-                            physical_location: SarifPhysicalLocation {
-                                artifact_location: SarifArtifactLocation {
-                                    uri: format!("package.json#{}", path.replace('>', "/")),
-                                },
-                            },
-                        }],
-                    });
-                }
-            }
+            push_advisory(&mut rules, &mut results, advisory, None);
+        }
+        // Suppressed findings stay in the report, marked as accepted, so
+        // code scanning shows them as dismissed with the justification.
+        for suppressed in &self.suppressed {
+            push_advisory(
+                &mut rules,
+                &mut results,
+                &suppressed.advisory,
+                Some(&suppressed.suppression),
+            );
         }
 
         SarifReport {
@@ -83,10 +49,82 @@ impl AuditResult {
             "vulnerabilities": self.counts,
             "totalPackages": self.total_packages,
             "advisories": self.advisories,
+            "suppressed": self.suppressed,
+            "expiredSuppressions": self.expired_suppressions,
         })
     }
 }
 
+fn push_advisory(
+    rules: &mut Vec<SarifRule>,
+    results: &mut Vec<SarifResult>,
+    advisory: &AuditAdvisory,
+    suppression: Option<&AuditSuppression>,
+) {
+    let rule_id = format!("SNPM-{}", advisory.id);
+    let level = sarif_level(advisory.severity);
+
+    if !rules.iter().any(|rule| rule.id == rule_id) {
+        rules.push(SarifRule {
+            id: rule_id.clone(),
+            name: advisory.title.clone(),
+            short_description: SarifMessage {
+                text: format!(
+                    "{} in {} ({})",
+                    advisory.title, advisory.module_name, advisory.severity
+                ),
+            },
+            full_description: SarifMessage {
+                text: advisory.overview.clone(),
+            },
+            help_uri: advisory.url.clone(),
+            default_configuration: SarifRuleConfig {
+                level: level.to_string(),
+            },
+        });
+    }
+
+    let suppressions: Vec<SarifSuppression> = suppression
+        .map(|suppression| SarifSuppression {
+            kind: "external".to_string(),
+            status: "accepted".to_string(),
+            justification: suppression.reason.clone(),
+            properties: SarifSuppressionProperties {
+                owner: suppression.owner.clone(),
+                expires: suppression.expires.clone(),
+            },
+        })
+        .into_iter()
+        .collect();
+
+    for finding in &advisory.findings {
+        for path in &finding.paths {
+            results.push(SarifResult {
+                rule_id: rule_id.clone(),
+                level: level.to_string(),
+                message: SarifMessage {
+                    text: format!(
+                        "{} {} has {} vulnerability: {}. Fix: upgrade to {}",
+                        advisory.module_name,
+                        finding.version,
+                        advisory.severity,
+                        advisory.title,
+                        fix_text(&advisory.patched_versions),
+                    ),
+                },
+                locations: vec![SarifLocation {
+                    physical_location: SarifPhysicalLocation {
+                        artifact_location: SarifArtifactLocation {
+                            uri: format!("package.json#{}", path.replace('>', "/")),
+                        },
+                    },
+                }],
+                suppressions: suppressions.clone(),
+            });
+        }
+    }
+}
+
 fn sarif_level(severity: Severity) -> &'static str {
     match severity {
         Severity::Critical | Severity::High => "error",
@@ -102,3 +140,78 @@ fn fix_text(patched_versions: &str) -> String {
         patched_versions.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::types::{
+        AuditAdvisory, AuditFinding, AuditResult, AuditSuppression, Severity, SuppressedAdvisory,
+        VulnerabilityCounts,
+    };
+
+    #[test]
+    fn suppressed_findings_are_reported_as_accepted() {
+        let advisory = AuditAdvisory {
+            id: 7,
+            created: None,
+            updated: None,
+            title: "ReDoS".to_string(),
+            module_name: "semver".to_string(),
+            cves: Vec::new(),
+            vulnerable_versions: "<7.5.2".to_string(),
+            patched_versions: ">=7.5.2".to_string(),
+            overview: String::new(),
+            recommendation: String::new(),
+            severity: Severity::High,
+            cwe: None,
+            github_advisory_id: None,
+            url: None,
+            findings: vec![AuditFinding {
+                version: "7.5.1".to_string(),
+                paths: vec!["a>semver".to_string()],
+                dev: false,
+                optional: false,
+                bundled: false,
+            }],
+        };
+        let result = AuditResult {
+            advisories: vec![advisory.clone()],
+            counts: VulnerabilityCounts::default(),
+            total_packages: 2,
+            project_name: "app".to_string(),
+            workspace_member: None,
+            suppressed: vec![SuppressedAdvisory {
+                advisory: AuditAdvisory {
+                    findings: vec![AuditFinding {
+                        paths: vec!["b>semver".to_string()],
+                        ..advisory.findings[0].clone()
+                    }],
+                    ..advisory
+                },
+                suppression: AuditSuppression {
+                    id: "7".to_string(),
+                    package: None,
+                    path: Some("b".to_string()),
+                    reason: "Only parses our own ranges".to_string(),
+                    owner: "@tools".to_string(),
+                    expires: "2027-01-01".to_string(),
+                },
+            }],
+            expired_suppressions: Vec::new(),
+        };
+
+        let sarif = serde_json::to_value(result.to_sarif()).unwrap();
+        let run = &sarif["runs"][0];
+
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+        assert!(run["results"][0].get("suppressions").is_none());
+        assert_eq!(
+            run["results"][1]["suppressions"][0],
+            serde_json::json!({
+                "kind": "external",
+                "status": "accepted",
+                "justification": "Only parses our own ranges",
+                "properties": {"owner": "@tools", "expires": "2027-01-01"}
+            })
+        );
+    }
+}
@@ -59,6 +59,22 @@ pub struct SarifResult {
     pub level: String,
     pub message: SarifMessage,
     pub locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<SarifSuppression>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifSuppression {
+    pub kind: String,
+    pub status: String,
+    pub justification: String,
+    pub properties: SarifSuppressionProperties,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifSuppressionProperties {
+    pub owner: String,
+    pub expires: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
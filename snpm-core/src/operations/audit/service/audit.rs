@@ -1,25 +1,37 @@
+use super::super::database::load_bulk_advisories;
 use super::super::filter::{calculate_counts, filter_advisories};
-use super::super::request::{AuditPathIndex, build_audit_path_index, build_audit_request};
-use super::super::types::{AuditAdvisory, AuditFinding, AuditOptions, AuditResult, Severity};
+use super::super::request::{
+    AuditPathIndex, AuditRequest, build_audit_path_index, build_audit_request,
+};
+use super::super::suppressions::{Suppressions, apply_suppressions, load_suppressions};
+use super::super::types::{
+    AuditAdvisory, AuditFinding, AuditOptions, AuditResult, AuditSuppression, BulkAuditResponse,
+    BulkCwe, Severity,
+};
 use crate::lockfile::{self, Lockfile};
 use crate::{Project, Result, SnpmConfig, SnpmError, Workspace, http};
-use serde::Deserialize;
 use snpm_semver::{RangeSet, parse_version};
 
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use time::OffsetDateTime;
 
 pub async fn audit(
     config: &SnpmConfig,
     project: &Project,
     options: &AuditOptions,
 ) -> Result<AuditResult> {
-    let lockfile = read_audit_lockfile(&project.root.join("snpm-lock.yaml"))?;
+    // Like overrides, the lockfile and suppressions are shared by the
+    // whole workspace.
+    let root = install_root(project)?;
+    let lockfile = read_audit_lockfile(&root.join("snpm-lock.yaml"))?;
     let project_name = project
         .manifest
         .name
         .clone()
         .unwrap_or_else(|| "unknown".to_string());
     let dev_root_names = project.manifest.dev_dependencies.keys().cloned().collect();
+    let suppressions = read_suppressions(&root)?;
     audit_lockfile(
         config,
         &lockfile,
@@ -27,10 +39,30 @@ pub async fn audit(
         None,
         &dev_root_names,
         options,
+        suppressions,
     )
     .await
 }
 
+/// Where `project` keeps `snpm-lock.yaml`, overrides and suppressions:
+/// the workspace root when it belongs to one, its own root otherwise.
+pub(super) fn install_root(project: &Project) -> Result<PathBuf> {
+    Ok(Workspace::discover(&project.root)?
+        .map(|workspace| workspace.root)
+        .unwrap_or_else(|| project.root.clone()))
+}
+
+/// The `snpm-audit.yaml` entries past their `expires` date for the
+/// install root containing `cwd`. Checked up front so that `audit --fix`
+/// refuses to run with stale suppressions just like a plain audit.
+pub fn expired_suppressions(cwd: &Path) -> Result<Vec<AuditSuppression>> {
+    let root = match Workspace::discover(cwd)? {
+        Some(workspace) => workspace.root,
+        None => Project::discover(cwd)?.root,
+    };
+    Ok(read_suppressions(&root)?.expired)
+}
+
 pub async fn audit_workspace(
     config: &SnpmConfig,
     workspace: &Workspace,
@@ -43,6 +75,7 @@ pub async fn audit_workspace(
         .and_then(|project| project.manifest.name.clone())
         .unwrap_or_else(|| "workspace".to_string());
     let dev_root_names = workspace_dev_root_names(workspace);
+    let suppressions = read_suppressions(&workspace.root)?;
 
     let result = audit_lockfile(
         config,
@@ -51,6 +84,7 @@ pub async fn audit_workspace(
         Some(".".to_string()),
         &dev_root_names,
         options,
+        suppressions,
     )
     .await?;
 
@@ -64,15 +98,45 @@ pub(super) async fn audit_lockfile(
     workspace_member: Option<String>,
     dev_root_names: &HashSet<String>,
     options: &AuditOptions,
+    suppressions: Suppressions,
 ) -> Result<AuditResult> {
-    let client = http::create_client()?;
     let request = build_audit_request(lockfile, dev_root_names, options);
+    let bulk_response = match &options.database {
+        Some(database) => load_bulk_advisories(database, &request.request.packages)?,
+        None => fetch_bulk_advisories(config, &request.request).await?,
+    };
+    let vulnerable_names = bulk_response.keys().cloned().collect::<HashSet<_>>();
+    let path_index = build_audit_path_index(lockfile, dev_root_names, options, &vulnerable_names);
+    let (advisories, suppressed) = apply_suppressions(
+        filter_advisories(
+            &build_audit_advisories(&bulk_response, &path_index),
+            options,
+        ),
+        &suppressions.active,
+    );
+
+    Ok(AuditResult {
+        counts: calculate_counts(&advisories),
+        advisories,
+        total_packages: request.total_packages,
+        project_name: project_name.to_string(),
+        workspace_member,
+        suppressed,
+        expired_suppressions: suppressions.expired,
+    })
+}
+
+async fn fetch_bulk_advisories(
+    config: &SnpmConfig,
+    request: &AuditRequest,
+) -> Result<BulkAuditResponse> {
+    let client = http::create_client()?;
     let audit_url = audit_url(config);
 
     let mut request_builder = client
         .post(&audit_url)
         .header("Content-Type", "application/json")
-        .json(&request.request);
+        .json(request);
 
     if let Some(header_value) = config.authorization_header_for_url(&audit_url) {
         request_builder = request_builder.header("Authorization", header_value);
@@ -101,27 +165,12 @@ pub(super) async fn audit_lockfile(
     }
 
     let body = response.text().await.unwrap_or_default();
-    let bulk_response: BulkAuditResponse =
-        serde_json::from_str(&body).map_err(|error| SnpmError::AuditFailed {
-            reason: format!("Registry returned invalid bulk audit response: {error}"),
-        })?;
-    let vulnerable_names = bulk_response.keys().cloned().collect::<HashSet<_>>();
-    let path_index = build_audit_path_index(lockfile, dev_root_names, options, &vulnerable_names);
-    let advisories = filter_advisories(
-        &build_audit_advisories(&bulk_response, &path_index),
-        options,
-    );
-
-    Ok(AuditResult {
-        counts: calculate_counts(&advisories),
-        advisories,
-        total_packages: request.total_packages,
-        project_name: project_name.to_string(),
-        workspace_member,
+    serde_json::from_str(&body).map_err(|error| SnpmError::AuditFailed {
+        reason: format!("Registry returned invalid bulk audit response: {error}"),
     })
 }
 
-fn read_audit_lockfile(path: &std::path::Path) -> Result<Lockfile> {
+pub(in crate::operations::audit) fn read_audit_lockfile(path: &Path) -> Result<Lockfile> {
     if !path.exists() {
         return Err(SnpmError::AuditLockfileRequired);
     }
@@ -129,6 +178,10 @@ fn read_audit_lockfile(path: &std::path::Path) -> Result<Lockfile> {
     lockfile::read(path)
 }
 
+fn read_suppressions(root: &Path) -> Result<Suppressions> {
+    load_suppressions(root, OffsetDateTime::now_utc().date())
+}
+
 fn workspace_dev_root_names(workspace: &Workspace) -> HashSet<String> {
     workspace
         .projects
@@ -142,28 +195,6 @@ fn audit_url(config: &SnpmConfig) -> String {
     format!("{registry}/-/npm/v1/security/advisories/bulk")
 }
 
-type BulkAuditResponse = HashMap<String, Vec<BulkAdvisory>>;
-
-#[derive(Debug, Clone, Deserialize)]
-struct BulkAdvisory {
-    id: u64,
-    #[serde(default)]
-    url: Option<String>,
-    #[serde(default)]
-    title: String,
-    severity: String,
-    vulnerable_versions: String,
-    #[serde(default)]
-    cwe: Option<BulkCwe>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-#[serde(untagged)]
-enum BulkCwe {
-    One(String),
-    Many(Vec<String>),
-}
-
 fn build_audit_advisories(
     bulk_response: &BulkAuditResponse,
     path_index: &AuditPathIndex,
@@ -192,7 +223,7 @@ fn build_audit_advisories(
                     updated: None,
                     title: advisory.title.clone(),
                     module_name: module_name.clone(),
-                    cves: Vec::new(),
+                    cves: advisory.cves.clone(),
                     vulnerable_versions: advisory.vulnerable_versions.clone(),
                     patched_versions: infer_patched_versions(&advisory.vulnerable_versions)
                         .unwrap_or_default(),
@@ -286,21 +317,42 @@ fn derive_github_advisory_id(url: &str) -> Option<String> {
     Some(format!("GHSA-{suffix}"))
 }
 
-impl BulkCwe {
-    fn as_string(&self) -> Option<String> {
-        match self {
-            BulkCwe::One(value) if !value.is_empty() => Some(value.clone()),
-            BulkCwe::Many(values) if !values.is_empty() => Some(values.join(", ")),
-            _ => None,
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
+    use super::super::super::types::BulkAdvisory;
     use super::*;
     use crate::lockfile::{LockPackage, LockRoot, LockRootDependency, Lockfile};
     use std::collections::BTreeMap;
+    use std::fs;
+
+    #[test]
+    fn expired_suppressions_reads_the_workspace_root_file_from_a_member() {
+        let dir = tempfile::tempdir().unwrap();
+        let member = dir.path().join("packages/app");
+        fs::create_dir_all(&member).unwrap();
+        fs::write(
+            dir.path().join("snpm-workspace.yaml"),
+            "packages:\n  - packages/*\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("package.json"), r#"{"name":"root"}"#).unwrap();
+        fs::write(member.join("package.json"), r#"{"name":"app"}"#).unwrap();
+        fs::write(
+            dir.path().join("snpm-audit.yaml"),
+            "suppressions:\n  - id: GHSA-old\n    reason: reviewed\n    owner: security\n    expires: 2000-01-01\n  - id: GHSA-new\n    reason: reviewed\n    owner: security\n    expires: 9999-12-31\n",
+        )
+        .unwrap();
+
+        let expired = expired_suppressions(&member).unwrap();
+
+        assert_eq!(
+            expired
+                .iter()
+                .map(|suppression| suppression.id.as_str())
+                .collect::<Vec<_>>(),
+            ["GHSA-old"]
+        );
+    }
 
     #[test]
     fn infer_patched_versions_handles_lt_and_lte_ranges() {
@@ -328,6 +380,7 @@ mod tests {
                     "CWE-79".to_string(),
                     "CWE-89".to_string(),
                 ])),
+                cves: Vec::new(),
             }],
         )]);
         let path_index = BTreeMap::from([(
@@ -368,6 +421,7 @@ mod tests {
     fn audit_request_total_packages_matches_unique_versions() {
         let lockfile = Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: BTreeMap::from([(
                     "shared".to_string(),
@@ -379,6 +433,7 @@ mod tests {
                     },
                 )]),
             },
+            importers: BTreeMap::new(),
             packages: BTreeMap::from([(
                 "shared@1.0.0".to_string(),
                 LockPackage {
@@ -391,6 +446,12 @@ mod tests {
                     bundled_dependencies: None,
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             )]),
         };
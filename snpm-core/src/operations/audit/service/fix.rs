@@ -1,41 +1,777 @@
 use super::super::filter::is_unfixable;
-use super::super::types::{AuditOptions, FixResult, FixableVulnerability, UnfixableVulnerability};
-use super::audit::audit;
-use crate::{Project, Result, SnpmConfig};
+use super::super::types::{
+    AuditAdvisory, AuditOptions, FixAction, FixOutcome, FixResult, FixableVulnerability,
+    UnfixableVulnerability,
+};
+use super::audit::{audit, install_root, read_audit_lockfile};
+use crate::config::OfflineMode;
+use crate::lockfile;
+use crate::operations::install::{
+    FrozenLockfileMode, InstallOptions, current_spec, install, is_registry_unresolvable_spec,
+    rewrite_spec_preserving_operator,
+};
+use crate::project::Manifest;
+use crate::protocols::jsr::tarball_protocol;
+use crate::registry::{RegistryPackage, fetch_package_with_offline};
+use crate::resolve::{PackageId, ResolutionGraph};
+use crate::workspace::OverridesConfig;
+use crate::{Project, Result, SnpmConfig, console, http};
 
+use futures::future::join_all;
+use snpm_semver::{RangeSet, Version, parse_version};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+/// Audits `project` and works out, for every finding with a patched
+/// version, the smallest change that moves off the vulnerable version: a
+/// direct dependency's range is raised when a patched version fits it,
+/// anything else gets an override scoped to the range its dependents
+/// request. Unless `dry_run`, the changes are written, the project is
+/// re-resolved keeping every other locked version, and audited again.
 pub async fn fix(
     config: &SnpmConfig,
-    project: &Project,
+    project: &mut Project,
     options: &AuditOptions,
+    dry_run: bool,
 ) -> Result<FixResult> {
-    let audit_result = audit(config, project, options).await?;
+    let before = audit(config, project, options).await?;
+    let root = install_root(project)?;
+    let lockfile_path = root.join("snpm-lock.yaml");
+    let graph = lockfile::to_graph(&read_audit_lockfile(&lockfile_path)?);
+    let groups = findings_by_version(&before.advisories);
+    // An audit against a local database stays off the network where the
+    // metadata cache already has what the plan needs.
+    let offline_mode = match options.database {
+        Some(_) => OfflineMode::PreferOffline,
+        None => config.offline_mode,
+    };
+    let packuments = fetch_packuments(config, &graph, &groups, offline_mode).await?;
+
     let mut fixable = Vec::new();
     let mut unfixable = Vec::new();
+    let mut actions = BTreeSet::new();
 
-    for advisory in &audit_result.advisories {
-        if is_unfixable(&advisory.patched_versions) {
-            for finding in &advisory.findings {
-                unfixable.push(UnfixableVulnerability {
-                    package: advisory.module_name.clone(),
-                    version: finding.version.clone(),
-                    advisory_id: advisory.id,
-                    severity: advisory.severity,
-                    reason: "No patched version available".to_string(),
-                });
+    for ((package, version), advisories) in &groups {
+        let planned = if advisories
+            .iter()
+            .any(|advisory| is_unfixable(&advisory.patched_versions))
+        {
+            Err("No patched version available".to_string())
+        } else {
+            vulnerable_ranges(advisories).and_then(|vulnerable| {
+                plan_fix(
+                    &project.manifest,
+                    &graph,
+                    package,
+                    version,
+                    &vulnerable,
+                    &packuments,
+                )
+            })
+        };
+
+        match planned {
+            Ok(plan) => {
+                for advisory in advisories {
+                    fixable.push(FixableVulnerability {
+                        package: package.clone(),
+                        from_version: version.clone(),
+                        to_version: plan.patched.clone(),
+                        advisory_id: advisory.id,
+                        severity: advisory.severity,
+                    });
+                }
+                actions.extend(plan.actions);
+            }
+            Err(reason) => {
+                for advisory in advisories {
+                    unfixable.push(UnfixableVulnerability {
+                        package: package.clone(),
+                        version: version.clone(),
+                        advisory_id: advisory.id,
+                        severity: advisory.severity,
+                        reason: reason.clone(),
+                    });
+                }
             }
-            continue;
         }
+    }
+
+    let actions = dedupe_overrides(actions);
+    if dry_run || actions.is_empty() {
+        return Ok(FixResult {
+            fixable,
+            unfixable,
+            actions,
+            outcomes: Vec::new(),
+        });
+    }
+
+    apply_actions(project, &root, &actions)?;
+    install(
+        config,
+        project,
+        InstallOptions {
+            requested: Vec::new(),
+            dev: false,
+            include_dev: true,
+            // Seeded from the old lockfile, so only what the changes
+            // touch is resolved again.
+            frozen_lockfile: FrozenLockfileMode::Fix,
+            strict_no_lockfile: false,
+            force: false,
+            lockfile_only: false,
+            silent_summary: true,
+        },
+    )
+    .await?;
+
+    let after = audit(config, project, options).await?;
+    let graph = lockfile::to_graph(&read_audit_lockfile(&lockfile_path)?);
+    let outcomes = fixable
+        .iter()
+        .map(|vulnerability| FixOutcome {
+            package: vulnerability.package.clone(),
+            advisory_id: vulnerability.advisory_id,
+            severity: vulnerability.severity,
+            before: vulnerability.from_version.clone(),
+            after: graph
+                .packages
+                .keys()
+                .filter(|id| id.name == vulnerability.package)
+                .map(|id| id.version.clone())
+                .collect(),
+            fixed: !after.advisories.iter().any(|advisory| {
+                advisory.id == vulnerability.advisory_id && !advisory.findings.is_empty()
+            }),
+        })
+        .collect();
+
+    Ok(FixResult {
+        fixable,
+        unfixable,
+        actions,
+        outcomes,
+    })
+}
+
+#[derive(Debug)]
+struct FixPlan {
+    /// The patched version the first change lands on.
+    patched: String,
+    actions: Vec<FixAction>,
+}
 
+type FindingGroups<'a> = BTreeMap<(String, String), Vec<&'a AuditAdvisory>>;
+
+/// Every vulnerable `(package, version)` with the advisories it falls
+/// under, so one change covers all of them.
+fn findings_by_version(advisories: &[AuditAdvisory]) -> FindingGroups<'_> {
+    let mut groups: FindingGroups<'_> = BTreeMap::new();
+    for advisory in advisories {
         for finding in &advisory.findings {
-            fixable.push(FixableVulnerability {
-                package: advisory.module_name.clone(),
-                from_version: finding.version.clone(),
-                to_version: advisory.patched_versions.clone(),
-                advisory_id: advisory.id,
-                severity: advisory.severity,
-            });
+            groups
+                .entry((advisory.module_name.clone(), finding.version.clone()))
+                .or_default()
+                .push(advisory);
+        }
+    }
+    groups
+}
+
+fn vulnerable_ranges(advisories: &[&AuditAdvisory]) -> std::result::Result<Vec<RangeSet>, String> {
+    advisories
+        .iter()
+        .map(|advisory| {
+            RangeSet::parse(&advisory.vulnerable_versions).map_err(|error| {
+                format!(
+                    "Cannot read vulnerable range {}: {error}",
+                    advisory.vulnerable_versions
+                )
+            })
+        })
+        .collect()
+}
+
+/// Registry metadata for each vulnerable package and for every package
+/// that depends on one, which tells what range it requests. Each comes
+/// from the registry that served its locked tarball. Packages whose
+/// metadata can't be fetched are left out and reported as unfixable.
+async fn fetch_packuments(
+    config: &SnpmConfig,
+    graph: &ResolutionGraph,
+    groups: &FindingGroups<'_>,
+    offline_mode: OfflineMode,
+) -> Result<HashMap<String, RegistryPackage>> {
+    let mut names = BTreeMap::new();
+    for (package, version) in groups.keys() {
+        let id = PackageId {
+            name: package.clone(),
+            version: version.clone(),
+        };
+        if let Some(locked) = graph.packages.get(&id) {
+            names.insert(package.clone(), tarball_protocol(&locked.tarball));
+        }
+        for (parent, _) in dependents(graph, package, version) {
+            names.insert(parent.id.name.clone(), tarball_protocol(&parent.tarball));
+        }
+    }
+
+    let client = http::create_client()?;
+    let fetched = join_all(names.into_iter().map(|(name, protocol)| {
+        let client = &client;
+        async move {
+            let package =
+                fetch_package_with_offline(config, client, &name, &protocol, offline_mode).await;
+            (name, package)
+        }
+    }))
+    .await;
+
+    Ok(fetched
+        .into_iter()
+        .filter_map(|(name, package)| match package {
+            Ok(package) => Some((name, package)),
+            Err(error) => {
+                console::verbose(&format!("audit fix: no metadata for {name}: {error}"));
+                None
+            }
+        })
+        .collect())
+}
+
+/// Packages in `graph` that depend on `name@version`, with the name of
+/// the edge (which differs from `name` for an aliased dependency).
+fn dependents<'a>(
+    graph: &'a ResolutionGraph,
+    name: &'a str,
+    version: &'a str,
+) -> impl Iterator<Item = (&'a crate::resolve::ResolvedPackage, &'a str)> {
+    graph.packages.values().filter_map(move |package| {
+        package
+            .dependencies
+            .iter()
+            .find(|(_, id)| id.name == name && id.version == version)
+            .map(|(edge, _)| (package, edge.as_str()))
+    })
+}
+
+fn plan_fix(
+    manifest: &Manifest,
+    graph: &ResolutionGraph,
+    name: &str,
+    version: &str,
+    vulnerable: &[RangeSet],
+    packuments: &HashMap<String, RegistryPackage>,
+) -> std::result::Result<FixPlan, String> {
+    let package = packuments
+        .get(name)
+        .ok_or_else(|| format!("Registry metadata for {name} is unavailable"))?;
+    let installed =
+        parse_version(version).map_err(|_| format!("{name}@{version} is not a semver version"))?;
+    let candidates = patched_versions(package, vulnerable, &installed);
+    let lowest = candidates.first().ok_or_else(|| {
+        format!("No published version of {name} above {version} is outside the vulnerable range")
+    })?;
+    let lowest_within =
+        |range: &RangeSet| candidates.iter().find(|candidate| range.matches(candidate));
+
+    let mut patched = None;
+    let mut actions = Vec::new();
+
+    let direct = graph
+        .root
+        .dependencies
+        .get(name)
+        .is_some_and(|dep| dep.resolved.name == name && dep.resolved.version == version);
+    if direct
+        && let Some(spec) = current_spec(manifest, name)
+        && !is_registry_unresolvable_spec(spec)
+    {
+        match RangeSet::parse(spec).ok().as_ref().and_then(lowest_within) {
+            Some(candidate) => {
+                actions.push(FixAction::BumpDependency {
+                    package: name.to_string(),
+                    from: spec.to_string(),
+                    to: rewrite_spec_preserving_operator(spec, &candidate.to_string()),
+                });
+                patched.get_or_insert_with(|| candidate.to_string());
+            }
+            None => {
+                actions.push(FixAction::Override {
+                    selector: format!("{name}@{spec}"),
+                    range: format!("^{lowest}"),
+                });
+                patched.get_or_insert_with(|| lowest.to_string());
+            }
+        }
+    }
+
+    for (parent, edge) in dependents(graph, name, version) {
+        let label = format!("{}@{}", parent.id.name, parent.id.version);
+        if edge != name {
+            return Err(format!("{label} depends on it through the alias {edge}"));
+        }
+
+        let requested = packuments
+            .get(&parent.id.name)
+            .and_then(|package| package.versions.get(&parent.id.version))
+            .and_then(|meta| {
+                meta.dependencies
+                    .get(edge)
+                    .or_else(|| meta.optional_dependencies.get(edge))
+            })
+            .ok_or_else(|| format!("Cannot tell which range {label} requests"))?;
+        if is_registry_unresolvable_spec(requested) {
+            return Err(format!("{label} requests it as {requested}"));
+        }
+
+        let candidate = RangeSet::parse(requested)
+            .ok()
+            .as_ref()
+            .and_then(lowest_within);
+        let range = match candidate {
+            Some(candidate) => rewrite_spec_preserving_operator(requested, &candidate.to_string()),
+            None => format!("^{lowest}"),
+        };
+        patched.get_or_insert_with(|| candidate.unwrap_or(lowest).to_string());
+        actions.push(FixAction::Override {
+            selector: format!("{name}@{requested}"),
+            range,
+        });
+    }
+
+    match patched {
+        Some(patched) => Ok(FixPlan { patched, actions }),
+        None => Err(format!(
+            "{name}@{version} is not a dependency of this project"
+        )),
+    }
+}
+
+/// Published, non-prerelease versions above `installed` and outside every
+/// vulnerable range, lowest first. Older releases are never a fix: they
+/// would move the package backwards, possibly across a major.
+fn patched_versions(
+    package: &RegistryPackage,
+    vulnerable: &[RangeSet],
+    installed: &Version,
+) -> Vec<Version> {
+    let mut versions: Vec<Version> = package
+        .versions
+        .keys()
+        .filter_map(|version| parse_version(version).ok())
+        .filter(|version| version.pre.is_empty() && version > installed)
+        .filter(|version| !vulnerable.iter().any(|range| range.matches(version)))
+        .collect();
+    versions.sort();
+    versions
+}
+
+/// One override per selector; when two findings ask for different ranges
+/// under the same selector the higher one wins.
+fn dedupe_overrides(actions: BTreeSet<FixAction>) -> Vec<FixAction> {
+    let mut overrides: BTreeMap<String, String> = BTreeMap::new();
+    let mut deduped = Vec::new();
+    for action in actions {
+        match action {
+            FixAction::Override { selector, range } => {
+                let floor = |range: &str| RangeSet::parse(range).ok()?.min_version();
+                match overrides.get(selector.as_str()) {
+                    Some(kept) if floor(kept) >= floor(&range) => {}
+                    _ => {
+                        overrides.insert(selector, range);
+                    }
+                }
+            }
+            bump => deduped.push(bump),
         }
     }
+    deduped.extend(
+        overrides
+            .into_iter()
+            .map(|(selector, range)| FixAction::Override { selector, range }),
+    );
+    deduped
+}
+
+/// Writes `actions`: range bumps to `project`'s manifest, overrides to
+/// `snpm-overrides.yaml` under `root`, where installs read them.
+fn apply_actions(project: &mut Project, root: &Path, actions: &[FixAction]) -> Result<()> {
+    let mut manifest = project.manifest.clone();
+    let mut bumped = false;
+    let mut overrides = BTreeMap::new();
+
+    for action in actions {
+        match action {
+            FixAction::BumpDependency { package, from, to } => {
+                for dependencies in [
+                    &mut manifest.dependencies,
+                    &mut manifest.dev_dependencies,
+                    &mut manifest.optional_dependencies,
+                ] {
+                    if let Some(spec) = dependencies.get_mut(package)
+                        && spec == from
+                    {
+                        *spec = to.clone();
+                        bumped = true;
+                    }
+                }
+            }
+            FixAction::Override { selector, range } => {
+                overrides.insert(selector.clone(), range.clone());
+            }
+        }
+    }
+
+    if !overrides.is_empty() {
+        OverridesConfig::add(root, &overrides)?;
+    }
+
+    if bumped {
+        project.write_manifest(&manifest)?;
+        project.manifest = manifest;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FixAction, apply_actions, plan_fix};
+    use crate::Project;
+    use crate::project::Manifest;
+    use crate::registry::RegistryPackage;
+    use crate::resolve::{
+        PackageId, ResolutionGraph, ResolutionRoot, ResolvedPackage, RootDependency,
+    };
+
+    use snpm_semver::RangeSet;
+    use std::collections::{BTreeMap, HashMap};
+    use std::fs;
+
+    fn id(name: &str, version: &str) -> PackageId {
+        PackageId {
+            name: name.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    fn resolved(id: PackageId, dependencies: &[(&str, PackageId)]) -> ResolvedPackage {
+        ResolvedPackage {
+            id,
+            tarball: String::new(),
+            integrity: None,
+            dependencies: dependencies
+                .iter()
+                .map(|(name, id)| (name.to_string(), id.clone()))
+                .collect(),
+            peer_dependencies: BTreeMap::new(),
+            bundled_dependencies: None,
+            has_bin: false,
+            bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
+        }
+    }
+
+    fn packument(versions: &[(&str, &[(&str, &str)])]) -> RegistryPackage {
+        let versions: serde_json::Map<String, serde_json::Value> = versions
+            .iter()
+            .map(|(version, dependencies)| {
+                let dependencies: BTreeMap<_, _> = dependencies.iter().copied().collect();
+                (
+                    version.to_string(),
+                    serde_json::json!({
+                        "version": version,
+                        "dependencies": dependencies,
+                        "dist": { "tarball": format!("https://registry.test/{version}.tgz") },
+                    }),
+                )
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({ "versions": versions })).unwrap()
+    }
+
+    /// `app` depends on `direct@^1.0.0` (locked at 1.0.0), which depends on
+    /// `deep@~2.1.0` (locked at 2.1.0).
+    fn fixture() -> (Manifest, ResolutionGraph, HashMap<String, RegistryPackage>) {
+        let manifest: Manifest =
+            serde_json::from_str(r#"{"name":"app","dependencies":{"direct":"^1.0.0"}}"#).unwrap();
+        let graph = ResolutionGraph {
+            root: ResolutionRoot {
+                dependencies: BTreeMap::from([(
+                    "direct".to_string(),
+                    RootDependency {
+                        requested: "^1.0.0".to_string(),
+                        resolved: id("direct", "1.0.0"),
+                    },
+                )]),
+            },
+            packages: BTreeMap::from([
+                (
+                    id("direct", "1.0.0"),
+                    resolved(id("direct", "1.0.0"), &[("deep", id("deep", "2.1.0"))]),
+                ),
+                (id("deep", "2.1.0"), resolved(id("deep", "2.1.0"), &[])),
+            ]),
+        };
+        let packuments = HashMap::from([
+            (
+                "direct".to_string(),
+                packument(&[
+                    ("1.0.0", &[("deep", "~2.1.0")]),
+                    ("1.0.1", &[("deep", "~2.1.0")]),
+                    ("2.0.0", &[]),
+                ]),
+            ),
+            (
+                "deep".to_string(),
+                packument(&[
+                    ("2.1.0", &[]),
+                    ("2.1.4", &[]),
+                    ("2.2.0", &[]),
+                    ("3.0.0-beta.1", &[]),
+                ]),
+            ),
+        ]);
+        (manifest, graph, packuments)
+    }
+
+    fn ranges(range: &str) -> Vec<RangeSet> {
+        vec![RangeSet::parse(range).unwrap()]
+    }
 
-    Ok(FixResult { fixable, unfixable })
+    #[test]
+    fn bumps_direct_dependency_within_its_range() {
+        let (manifest, graph, packuments) = fixture();
+
+        let plan = plan_fix(
+            &manifest,
+            &graph,
+            "direct",
+            "1.0.0",
+            &ranges("<1.0.1"),
+            &packuments,
+        )
+        .unwrap();
+
+        assert_eq!(plan.patched, "1.0.1");
+        assert_eq!(
+            plan.actions,
+            vec![FixAction::BumpDependency {
+                package: "direct".to_string(),
+                from: "^1.0.0".to_string(),
+                to: "^1.0.1".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn overrides_direct_dependency_when_no_patch_fits_its_range() {
+        let (manifest, graph, packuments) = fixture();
+
+        let plan = plan_fix(
+            &manifest,
+            &graph,
+            "direct",
+            "1.0.0",
+            &ranges("<2.0.0"),
+            &packuments,
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan.actions,
+            vec![FixAction::Override {
+                selector: "direct@^1.0.0".to_string(),
+                range: "^2.0.0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn scopes_transitive_override_to_the_requested_range() {
+        let (manifest, graph, packuments) = fixture();
+
+        let plan = plan_fix(
+            &manifest,
+            &graph,
+            "deep",
+            "2.1.0",
+            &ranges("<2.1.3"),
+            &packuments,
+        )
+        .unwrap();
+        assert_eq!(
+            plan.actions,
+            vec![FixAction::Override {
+                selector: "deep@~2.1.0".to_string(),
+                range: "~2.1.4".to_string(),
+            }]
+        );
+
+        let plan = plan_fix(
+            &manifest,
+            &graph,
+            "deep",
+            "2.1.0",
+            &ranges("<2.2.0"),
+            &packuments,
+        )
+        .unwrap();
+        assert_eq!(
+            plan.actions,
+            vec![FixAction::Override {
+                selector: "deep@~2.1.0".to_string(),
+                range: "^2.2.0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_findings_without_a_published_patch() {
+        let (manifest, graph, packuments) = fixture();
+
+        let reason = plan_fix(
+            &manifest,
+            &graph,
+            "deep",
+            "2.1.0",
+            &ranges("<3.0.0"),
+            &packuments,
+        )
+        .unwrap_err();
+
+        assert!(reason.contains("No published version of deep"));
+    }
+
+    #[test]
+    fn never_plans_a_version_older_than_the_installed_one() {
+        let manifest: Manifest =
+            serde_json::from_str(r#"{"name":"app","dependencies":{"pkg":"1.0.0"}}"#).unwrap();
+        let graph = ResolutionGraph {
+            root: ResolutionRoot {
+                dependencies: BTreeMap::from([(
+                    "pkg".to_string(),
+                    RootDependency {
+                        requested: "1.0.0".to_string(),
+                        resolved: id("pkg", "1.0.0"),
+                    },
+                )]),
+            },
+            packages: BTreeMap::from([(id("pkg", "1.0.0"), resolved(id("pkg", "1.0.0"), &[]))]),
+        };
+        let mut packuments = HashMap::from([(
+            "pkg".to_string(),
+            packument(&[("0.9.0", &[]), ("1.0.0", &[])]),
+        )]);
+
+        let reason = plan_fix(
+            &manifest,
+            &graph,
+            "pkg",
+            "1.0.0",
+            &ranges(">=1.0.0 <1.0.5"),
+            &packuments,
+        )
+        .unwrap_err();
+        assert!(reason.contains("No published version of pkg"), "{reason}");
+
+        packuments.insert(
+            "pkg".to_string(),
+            packument(&[("0.9.0", &[]), ("1.0.0", &[]), ("1.0.5", &[])]),
+        );
+        let plan = plan_fix(
+            &manifest,
+            &graph,
+            "pkg",
+            "1.0.0",
+            &ranges(">=1.0.0 <1.0.5"),
+            &packuments,
+        )
+        .unwrap();
+        assert_eq!(plan.patched, "1.0.5");
+        assert_eq!(
+            plan.actions,
+            vec![FixAction::Override {
+                selector: "pkg@1.0.0".to_string(),
+                range: "^1.0.5".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn apply_actions_bumps_the_manifest_and_adds_overrides_at_the_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let member = dir.path().join("packages/app");
+        fs::create_dir_all(&member).unwrap();
+        fs::write(
+            member.join("package.json"),
+            r#"{"name":"app","dependencies":{"direct":"^1.0.0"},"devDependencies":{"other":"^3.0.0"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("snpm-overrides.yaml"),
+            "overrides:\n  # reviewed\n  left-pad: 1.3.0\n",
+        )
+        .unwrap();
+        let mut project = Project::from_manifest_path(member.join("package.json")).unwrap();
+
+        apply_actions(
+            &mut project,
+            dir.path(),
+            &[
+                FixAction::BumpDependency {
+                    package: "direct".to_string(),
+                    from: "^1.0.0".to_string(),
+                    to: "^1.0.1".to_string(),
+                },
+                FixAction::Override {
+                    selector: "deep@~2.1.0".to_string(),
+                    range: "~2.1.4".to_string(),
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(project.manifest.dependencies["direct"], "^1.0.1");
+        let written = Project::from_manifest_path(member.join("package.json")).unwrap();
+        assert_eq!(written.manifest.dependencies["direct"], "^1.0.1");
+        assert_eq!(written.manifest.dev_dependencies["other"], "^3.0.0");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("snpm-overrides.yaml")).unwrap(),
+            "overrides:\n  # reviewed\n  left-pad: 1.3.0\n  deep@~2.1.0: ~2.1.4\n"
+        );
+        assert!(!member.join("snpm-overrides.yaml").exists());
+    }
+
+    #[test]
+    fn apply_actions_leaves_a_changed_spec_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"name":"app","dependencies":{"direct":"^1.2.0"}}"#,
+        )
+        .unwrap();
+        let mut project = Project::from_manifest_path(dir.path().join("package.json")).unwrap();
+
+        apply_actions(
+            &mut project,
+            dir.path(),
+            &[FixAction::BumpDependency {
+                package: "direct".to_string(),
+                from: "^1.0.0".to_string(),
+                to: "^1.0.1".to_string(),
+            }],
+        )
+        .unwrap();
+
+        assert_eq!(project.manifest.dependencies["direct"], "^1.2.0");
+        assert!(!dir.path().join("snpm-overrides.yaml").exists());
+    }
 }
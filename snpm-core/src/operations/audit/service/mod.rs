@@ -1,5 +1,6 @@
 mod audit;
 mod fix;
 
-pub use audit::{audit, audit_workspace};
+pub(super) use audit::read_audit_lockfile;
+pub use audit::{audit, audit_workspace, expired_suppressions};
 pub use fix::fix;
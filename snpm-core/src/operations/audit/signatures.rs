@@ -0,0 +1,456 @@
+//! `audit signatures`: checks each locked registry package's
+//! `dist.signatures` against the signing keys its registry publishes.
+//! Installs run the same check over the resolved graph under
+//! `verify-signatures`.
+
+use super::service::read_audit_lockfile;
+use super::types::{SignatureAuditResult, SignatureIssue};
+use crate::config::OfflineMode;
+use crate::protocols::encode_package_name;
+use crate::protocols::jsr::is_jsr_tarball;
+use crate::registry::{
+    RegistryKey, RegistryPackage, RegistryProtocol, SignatureCheck, fetch_registry_keys,
+    verify_signatures,
+};
+use crate::{Result, SnpmConfig, SnpmError, http};
+
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+use std::path::Path;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+/// A locked or resolved package whose signature can be checked.
+pub(crate) struct SignedPackage<'a> {
+    pub name: &'a str,
+    pub version: &'a str,
+    pub tarball: &'a str,
+    pub integrity: Option<&'a str>,
+}
+
+/// A version and the integrity it was locked with.
+type LockedVersion<'a> = (&'a str, Option<&'a str>);
+
+enum Outcome {
+    Verified,
+    Unchecked,
+    Missing(SignatureIssue),
+    Invalid(SignatureIssue),
+}
+
+/// Checks the registry signature of every package in the lockfile at
+/// `root`.
+pub async fn audit_signatures(config: &SnpmConfig, root: &Path) -> Result<SignatureAuditResult> {
+    let lockfile = read_audit_lockfile(&root.join("snpm-lock.yaml"))?;
+    let client = http::create_client()?;
+    let packages = lockfile.packages.values().map(|package| SignedPackage {
+        name: &package.name,
+        version: &package.version,
+        tarball: &package.tarball,
+        integrity: package.integrity.as_deref(),
+    });
+
+    verify_package_signatures(config, &client, packages).await
+}
+
+pub(crate) async fn verify_package_signatures<'a>(
+    config: &SnpmConfig,
+    client: &reqwest::Client,
+    packages: impl IntoIterator<Item = SignedPackage<'a>>,
+) -> Result<SignatureAuditResult> {
+    let mut by_package: BTreeMap<(String, &str), Vec<LockedVersion<'_>>> = BTreeMap::new();
+    for package in packages.into_iter().filter(is_registry_package) {
+        by_package
+            .entry((
+                tarball_registry(package.name, package.tarball),
+                package.name,
+            ))
+            .or_default()
+            .push((package.version, package.integrity));
+    }
+
+    let mut keys: BTreeMap<&str, Vec<RegistryKey>> = BTreeMap::new();
+    for (registry, _) in by_package.keys() {
+        if let Entry::Vacant(entry) = keys.entry(registry.as_str()) {
+            let registry_keys = fetch_registry_keys(config, client, registry).await?;
+            entry.insert(registry_keys);
+        }
+    }
+
+    let keys = &keys;
+    let concurrency = crate::store::registry_task_concurrency(config);
+    let outcomes: Vec<Vec<Outcome>> = stream::iter(&by_package)
+        .map(|((registry, name), versions)| async move {
+            let registry_keys = &keys[registry.as_str()];
+            if registry_keys.is_empty() {
+                return Ok(versions.iter().map(|_| Outcome::Unchecked).collect());
+            }
+
+            let package = fetch_packument(config, client, name, registry).await?;
+            Ok::<_, crate::SnpmError>(
+                versions
+                    .iter()
+                    .map(|&(version, integrity)| {
+                        check_version(&package, name, version, integrity, registry, registry_keys)
+                    })
+                    .collect(),
+            )
+        })
+        .buffer_unordered(concurrency)
+        .try_collect()
+        .await?;
+
+    let mut result = SignatureAuditResult::default();
+    for outcome in outcomes.into_iter().flatten() {
+        match outcome {
+            Outcome::Verified => result.verified += 1,
+            Outcome::Unchecked => result.unchecked += 1,
+            Outcome::Missing(issue) => result.missing.push(issue),
+            Outcome::Invalid(issue) => result.invalid.push(issue),
+        }
+    }
+    result
+        .missing
+        .sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    result
+        .invalid
+        .sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+
+    Ok(result)
+}
+
+/// `name`'s packument from `registry`, the one whose keys sign its
+/// tarballs. The registry configured for `name` goes through the metadata
+/// cache; any other (a lockfile written against a mirror, say) is asked
+/// directly, since that cache is keyed by name alone.
+async fn fetch_packument(
+    config: &SnpmConfig,
+    client: &reqwest::Client,
+    name: &str,
+    registry: &str,
+) -> Result<RegistryPackage> {
+    if config
+        .registry_url_for_package_name(name)
+        .trim_end_matches('/')
+        == registry
+    {
+        return crate::registry::fetch_package(config, client, name, &RegistryProtocol::npm())
+            .await;
+    }
+
+    if matches!(config.offline_mode, OfflineMode::Offline) {
+        return Err(SnpmError::OfflineRequired {
+            resource: format!("package metadata for {name} from {registry}"),
+        });
+    }
+
+    let url = format!("{registry}/{}", encode_package_name(name));
+    let mut request = client.get(&url).header("accept", "application/json");
+    if let Some(header) = config.authorization_header_for_url(&url) {
+        request = request.header("authorization", header);
+    }
+    let http_error = |source| SnpmError::Http {
+        url: url.clone(),
+        source,
+    };
+    request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(http_error)?
+        .json::<RegistryPackage>()
+        .await
+        .map_err(http_error)
+}
+
+fn check_version(
+    package: &RegistryPackage,
+    name: &str,
+    version: &str,
+    locked_integrity: Option<&str>,
+    registry: &str,
+    keys: &[RegistryKey],
+) -> Outcome {
+    let issue = |reason: &str| SignatureIssue {
+        name: name.to_string(),
+        version: version.to_string(),
+        registry: registry.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let Some(registry_version) = package.versions.get(version) else {
+        return Outcome::Missing(issue("version is not in the registry's metadata"));
+    };
+    // The locked integrity is what gets installed, so it is what the
+    // signature has to cover; the registry's own is a fallback for
+    // lockfiles that do not record one.
+    let Some(integrity) = locked_integrity.or(registry_version.dist.integrity.as_deref()) else {
+        return Outcome::Missing(issue("no integrity to check a signature against"));
+    };
+    let published = package
+        .time
+        .get(version)
+        .and_then(|time| OffsetDateTime::parse(time, &Rfc3339).ok());
+
+    match verify_signatures(
+        name,
+        version,
+        integrity,
+        &registry_version.dist.signatures,
+        keys,
+        published,
+    ) {
+        SignatureCheck::Verified => Outcome::Verified,
+        SignatureCheck::Missing => Outcome::Missing(issue("registry has no signature")),
+        SignatureCheck::Invalid { reason } => Outcome::Invalid(issue(&reason)),
+    }
+}
+
+/// Only packages fetched from an npm registry carry registry signatures;
+/// git, file and jsr packages are left out.
+fn is_registry_package(package: &SignedPackage<'_>) -> bool {
+    (package.tarball.starts_with("http://") || package.tarball.starts_with("https://"))
+        && !is_jsr_tarball(package.tarball)
+}
+
+/// The registry that served `tarball`, which is the one whose keys signed
+/// it: the URL before `/<name>/-/` for the usual npm layout, otherwise the
+/// tarball's origin.
+fn tarball_registry(name: &str, tarball: &str) -> String {
+    let encoded = name.replacen('/', "%2f", 1);
+    let registry = [name, encoded.as_str()].iter().find_map(|name| {
+        tarball
+            .find(&format!("/{name}/-/"))
+            .map(|end| tarball[..end].to_string())
+    });
+
+    registry
+        .or_else(|| {
+            reqwest::Url::parse(tarball)
+                .ok()
+                .map(|url| url.origin().ascii_serialization())
+        })
+        .unwrap_or_else(|| tarball.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SignedPackage, tarball_registry, verify_package_signatures};
+    use crate::config::SnpmConfig;
+    use crate::registry::{RegistrySignature, TestSigner};
+
+    use serde_json::json;
+    use tempfile::tempdir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// A stand-in registry serving `keys` and one packument per name.
+    async fn serve_registry(
+        keys: serde_json::Value,
+        packuments: Vec<(&'static str, serde_json::Value)>,
+    ) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut request = vec![0; 2048];
+                let read = socket.read(&mut request).await.unwrap();
+                let request = String::from_utf8_lossy(&request[..read]);
+                let body = if request.starts_with("GET /-/npm/v1/keys ") {
+                    keys.to_string()
+                } else {
+                    packuments
+                        .iter()
+                        .find(|(name, _)| request.starts_with(&format!("GET /{name} ")))
+                        .map(|(_, packument)| packument.to_string())
+                        .unwrap_or_default()
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    fn packument(name: &str, signatures: &[RegistrySignature]) -> serde_json::Value {
+        json!({
+            "versions": {
+                "1.0.0": {
+                    "version": "1.0.0",
+                    "dist": {
+                        "tarball": format!("https://registry.example/{name}/-/{name}-1.0.0.tgz"),
+                        "integrity": "sha512-registry",
+                        "signatures": signatures,
+                    }
+                }
+            },
+            "dist-tags": { "latest": "1.0.0" }
+        })
+    }
+
+    #[tokio::test]
+    async fn sorts_packages_into_verified_missing_and_invalid() {
+        let signer = TestSigner::generate("SHA256:local");
+        let keys = json!({ "keys": [signer.public_key()] });
+        let packuments = vec![
+            (
+                "signed",
+                packument(
+                    "signed",
+                    &[signer.sign("signed", "1.0.0", "sha512-registry")],
+                ),
+            ),
+            ("unsigned", packument("unsigned", &[])),
+            (
+                "tampered",
+                packument(
+                    "tampered",
+                    &[signer.sign("tampered", "1.0.0", "sha512-registry")],
+                ),
+            ),
+        ];
+        let registry = serve_registry(keys, packuments).await;
+
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().to_path_buf(),
+            cache_dir: dir.path().join("cache"),
+            default_registry: registry.clone(),
+            ..SnpmConfig::for_tests()
+        };
+        let tarball = format!("{registry}/pkg.tgz");
+        let packages = [
+            ("signed", None),
+            ("unsigned", None),
+            ("tampered", Some("sha512-swapped")),
+        ];
+        let result = verify_package_signatures(
+            &config,
+            &reqwest::Client::new(),
+            packages.iter().map(|(name, integrity)| SignedPackage {
+                name,
+                version: "1.0.0",
+                tarball: &tarball,
+                integrity: *integrity,
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.verified, 1);
+        assert_eq!(result.missing.len(), 1);
+        assert_eq!(result.missing[0].name, "unsigned");
+        assert_eq!(result.invalid.len(), 1);
+        assert_eq!(result.invalid[0].name, "tampered");
+        assert!(result.invalid[0].reason.contains("does not match"));
+    }
+
+    #[tokio::test]
+    async fn registries_without_keys_are_not_checked() {
+        let registry = serve_registry(json!({ "keys": [] }), Vec::new()).await;
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().to_path_buf(),
+            cache_dir: dir.path().join("cache"),
+            default_registry: registry.clone(),
+            ..SnpmConfig::for_tests()
+        };
+        let tarball = format!("{registry}/pkg.tgz");
+
+        let result = verify_package_signatures(
+            &config,
+            &reqwest::Client::new(),
+            [
+                SignedPackage {
+                    name: "private",
+                    version: "1.0.0",
+                    tarball: &tarball,
+                    integrity: None,
+                },
+                SignedPackage {
+                    name: "from-git",
+                    version: "1.0.0",
+                    tarball: "git+https://example.com/repo.git",
+                    integrity: None,
+                },
+            ],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.unchecked, 1);
+        assert_eq!(result.verified, 0);
+        assert!(result.missing.is_empty() && result.invalid.is_empty());
+    }
+
+    #[tokio::test]
+    async fn metadata_comes_from_the_registry_that_signed_the_tarball() {
+        let signer = TestSigner::generate("SHA256:mirror");
+        let mirror = serve_registry(
+            json!({ "keys": [signer.public_key()] }),
+            vec![(
+                "signed",
+                packument(
+                    "signed",
+                    &[signer.sign("signed", "1.0.0", "sha512-registry")],
+                ),
+            )],
+        )
+        .await;
+        let configured = serve_registry(json!({ "keys": [] }), Vec::new()).await;
+
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().to_path_buf(),
+            cache_dir: dir.path().join("cache"),
+            default_registry: configured,
+            ..SnpmConfig::for_tests()
+        };
+        let tarball = format!("{mirror}/signed/-/signed-1.0.0.tgz");
+
+        let result = verify_package_signatures(
+            &config,
+            &reqwest::Client::new(),
+            [SignedPackage {
+                name: "signed",
+                version: "1.0.0",
+                tarball: &tarball,
+                integrity: None,
+            }],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.verified, 1);
+    }
+
+    #[test]
+    fn keys_come_from_the_registry_that_served_the_tarball() {
+        assert_eq!(
+            tarball_registry(
+                "lodash",
+                "https://registry.npmjs.org/lodash/-/lodash-4.17.21.tgz"
+            ),
+            "https://registry.npmjs.org"
+        );
+        assert_eq!(
+            tarball_registry(
+                "@scope/pkg",
+                "https://mirror.example/npm/@scope/pkg/-/pkg-1.0.0.tgz"
+            ),
+            "https://mirror.example/npm"
+        );
+        assert_eq!(
+            tarball_registry("pkg", "https://cdn.example:8443/files/pkg.tgz"),
+            "https://cdn.example:8443"
+        );
+    }
+}
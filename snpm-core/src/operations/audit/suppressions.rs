@@ -0,0 +1,308 @@
+use super::types::{AuditAdvisory, AuditFinding, AuditSuppression, SuppressedAdvisory};
+use crate::{Result, SnpmError};
+
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use time::Date;
+use time::format_description::well_known::Iso8601;
+
+pub(super) const SUPPRESSIONS_FILE: &str = "snpm-audit.yaml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SuppressionsFile {
+    #[serde(default)]
+    suppressions: Vec<AuditSuppression>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Suppressions {
+    pub active: Vec<AuditSuppression>,
+    pub expired: Vec<AuditSuppression>,
+}
+
+/// Reads `snpm-audit.yaml` under `root`, splitting its entries into those
+/// still in effect on `today` and those past their `expires` date.
+pub(super) fn load_suppressions(root: &Path, today: Date) -> Result<Suppressions> {
+    let path = root.join(SUPPRESSIONS_FILE);
+    if !path.is_file() {
+        return Ok(Suppressions::default());
+    }
+
+    let invalid = |reason: String| SnpmError::AuditSuppressions {
+        path: path.clone(),
+        reason,
+    };
+
+    let data = fs::read_to_string(&path).map_err(|source| SnpmError::ReadFile {
+        path: path.clone(),
+        source,
+    })?;
+    let file: SuppressionsFile = if data.trim().is_empty() {
+        SuppressionsFile::default()
+    } else {
+        serde_yaml::from_str(&data).map_err(|error| invalid(error.to_string()))?
+    };
+
+    let mut suppressions = Suppressions::default();
+
+    for (index, suppression) in file.suppressions.into_iter().enumerate() {
+        let entry = format!("suppression {} ({})", index + 1, suppression.id.trim());
+        if suppression.id.trim().is_empty() {
+            return Err(invalid(format!("suppression {} has no id", index + 1)));
+        }
+        if suppression.reason.trim().is_empty() {
+            return Err(invalid(format!("{entry} has no reason")));
+        }
+        if suppression.owner.trim().is_empty() {
+            return Err(invalid(format!("{entry} has no owner")));
+        }
+
+        let expires = Date::parse(suppression.expires.trim(), &Iso8601::DATE).map_err(|_| {
+            invalid(format!(
+                "{entry} expires on {:?}, expected a YYYY-MM-DD date",
+                suppression.expires
+            ))
+        })?;
+
+        if expires < today {
+            suppressions.expired.push(suppression);
+        } else {
+            suppressions.active.push(suppression);
+        }
+    }
+
+    Ok(suppressions)
+}
+
+/// Moves the findings the suppressions cover out of `advisories`. A
+/// suppression scoped to a path only takes the matching paths, so an
+/// advisory can end up both reported and suppressed.
+pub(super) fn apply_suppressions(
+    advisories: Vec<AuditAdvisory>,
+    suppressions: &[AuditSuppression],
+) -> (Vec<AuditAdvisory>, Vec<SuppressedAdvisory>) {
+    let mut reported = Vec::new();
+    let mut suppressed = Vec::new();
+
+    for mut advisory in advisories {
+        for suppression in suppressions {
+            if !covers_advisory(suppression, &advisory) {
+                continue;
+            }
+
+            let (covered, remaining) = split_findings(&advisory.findings, suppression);
+            if covered.is_empty() {
+                continue;
+            }
+
+            suppressed.push(SuppressedAdvisory {
+                advisory: AuditAdvisory {
+                    findings: covered,
+                    ..advisory.clone()
+                },
+                suppression: suppression.clone(),
+            });
+            advisory.findings = remaining;
+            if advisory.findings.is_empty() {
+                break;
+            }
+        }
+
+        if !advisory.findings.is_empty() {
+            reported.push(advisory);
+        }
+    }
+
+    (reported, suppressed)
+}
+
+fn covers_advisory(suppression: &AuditSuppression, advisory: &AuditAdvisory) -> bool {
+    if let Some(package) = &suppression.package
+        && package.trim() != advisory.module_name
+    {
+        return false;
+    }
+
+    let id = suppression.id.trim();
+    advisory
+        .github_advisory_id
+        .as_deref()
+        .is_some_and(|ghsa| ghsa.eq_ignore_ascii_case(id))
+        || advisory.cves.iter().any(|cve| cve.eq_ignore_ascii_case(id))
+        || advisory.id.to_string() == id
+}
+
+/// Splits `findings` into the paths `suppression` covers and the rest,
+/// dropping findings left without paths.
+fn split_findings(
+    findings: &[AuditFinding],
+    suppression: &AuditSuppression,
+) -> (Vec<AuditFinding>, Vec<AuditFinding>) {
+    let Some(scope) = &suppression.path else {
+        return (findings.to_vec(), Vec::new());
+    };
+    let scope: Vec<&str> = scope.split('>').map(str::trim).collect();
+
+    let mut covered = Vec::new();
+    let mut remaining = Vec::new();
+    for finding in findings {
+        let (inside, outside): (Vec<String>, Vec<String>) =
+            finding.paths.iter().cloned().partition(|path| {
+                let segments: Vec<&str> = path.split('>').collect();
+                segments.starts_with(&scope)
+            });
+
+        if !inside.is_empty() {
+            covered.push(AuditFinding {
+                paths: inside,
+                ..finding.clone()
+            });
+        }
+        if !outside.is_empty() {
+            remaining.push(AuditFinding {
+                paths: outside,
+                ..finding.clone()
+            });
+        }
+    }
+
+    (covered, remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::types::{AuditAdvisory, AuditFinding, AuditSuppression, Severity};
+    use super::{apply_suppressions, load_suppressions};
+
+    use std::fs;
+    use tempfile::tempdir;
+    use time::{Date, Month};
+
+    fn date(year: i32, month: Month, day: u8) -> Date {
+        Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    fn advisory(paths: &[&str]) -> AuditAdvisory {
+        AuditAdvisory {
+            id: 1_097_000,
+            created: None,
+            updated: None,
+            title: "Prototype Pollution".to_string(),
+            module_name: "minimist".to_string(),
+            cves: vec!["CVE-2021-44906".to_string()],
+            vulnerable_versions: "<1.2.6".to_string(),
+            patched_versions: ">=1.2.6".to_string(),
+            overview: String::new(),
+            recommendation: String::new(),
+            severity: Severity::Critical,
+            cwe: None,
+            github_advisory_id: Some("GHSA-xvch-5gv4-984h".to_string()),
+            url: None,
+            findings: vec![AuditFinding {
+                version: "1.2.5".to_string(),
+                paths: paths.iter().map(|path| path.to_string()).collect(),
+                dev: false,
+                optional: false,
+                bundled: false,
+            }],
+        }
+    }
+
+    fn suppression(id: &str, path: Option<&str>) -> AuditSuppression {
+        AuditSuppression {
+            id: id.to_string(),
+            package: Some("minimist".to_string()),
+            path: path.map(str::to_string),
+            reason: "CLI args are never user input".to_string(),
+            owner: "@platform".to_string(),
+            expires: "2026-12-31".to_string(),
+        }
+    }
+
+    #[test]
+    fn splits_expired_entries_and_rejects_incomplete_ones() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("snpm-audit.yaml"),
+            r#"suppressions:
+  - id: GHSA-xvch-5gv4-984h
+    package: minimist
+    reason: CLI args are never user input
+    owner: "@platform"
+    expires: 2026-10-16
+  - id: CVE-2020-1234
+    reason: Dev tooling only
+    owner: jane
+    expires: 2026-10-15
+"#,
+        )
+        .unwrap();
+
+        let suppressions = load_suppressions(dir.path(), date(2026, Month::October, 16)).unwrap();
+        assert_eq!(suppressions.active.len(), 1);
+        assert_eq!(suppressions.active[0].package.as_deref(), Some("minimist"));
+        assert_eq!(suppressions.expired.len(), 1);
+        assert_eq!(suppressions.expired[0].id, "CVE-2020-1234");
+
+        fs::write(
+            dir.path().join("snpm-audit.yaml"),
+            "suppressions:\n  - id: CVE-2020-1234\n    reason: ''\n    owner: jane\n    expires: 2026-10-15\n",
+        )
+        .unwrap();
+        let error = load_suppressions(dir.path(), date(2026, Month::October, 16)).unwrap_err();
+        assert!(error.to_string().contains("has no reason"));
+
+        fs::write(
+            dir.path().join("snpm-audit.yaml"),
+            "suppressions:\n  - id: CVE-2020-1234\n    reason: r\n    owner: jane\n    expires: soon\n",
+        )
+        .unwrap();
+        assert!(load_suppressions(dir.path(), date(2026, Month::October, 16)).is_err());
+    }
+
+    #[test]
+    fn suppresses_by_any_advisory_id() {
+        for id in ["ghsa-xvch-5gv4-984h", "CVE-2021-44906", "1097000"] {
+            let (reported, suppressed) =
+                apply_suppressions(vec![advisory(&["cli>minimist"])], &[suppression(id, None)]);
+
+            assert!(reported.is_empty(), "{id}");
+            assert_eq!(suppressed.len(), 1);
+        }
+
+        let mut other_package = suppression("CVE-2021-44906", None);
+        other_package.package = Some("yargs".to_string());
+        let (reported, suppressed) =
+            apply_suppressions(vec![advisory(&["cli>minimist"])], &[other_package]);
+        assert_eq!(reported.len(), 1);
+        assert!(suppressed.is_empty());
+    }
+
+    #[test]
+    fn path_scope_only_takes_matching_paths() {
+        let (reported, suppressed) = apply_suppressions(
+            vec![advisory(&[
+                "cli>minimist",
+                "app>cli>minimist",
+                "cli-extra>minimist",
+            ])],
+            &[suppression("CVE-2021-44906", Some("cli"))],
+        );
+
+        assert_eq!(suppressed.len(), 1);
+        assert_eq!(
+            suppressed[0].advisory.findings[0].paths,
+            vec!["cli>minimist".to_string()]
+        );
+        assert_eq!(reported.len(), 1);
+        assert_eq!(
+            reported[0].findings[0].paths,
+            vec![
+                "app>cli>minimist".to_string(),
+                "cli-extra>minimist".to_string()
+            ]
+        );
+    }
+}
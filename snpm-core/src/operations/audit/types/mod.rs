@@ -5,9 +5,12 @@ mod wire;
 
 pub use counts::VulnerabilityCounts;
 pub use results::{
-    AuditOptions, AuditResult, FixResult, FixableVulnerability, UnfixableVulnerability,
+    AuditOptions, AuditResult, AuditSuppression, FixAction, FixOutcome, FixResult,
+    FixableVulnerability, SignatureAuditResult, SignatureIssue, SuppressedAdvisory,
+    UnfixableVulnerability,
 };
 pub use severity::Severity;
 pub use wire::{
     AuditAction, AuditAdvisory, AuditFinding, AuditMetadata, AuditResolve, AuditResponse,
 };
+pub(in crate::operations::audit) use wire::{BulkAdvisory, BulkAuditResponse, BulkCwe};
@@ -1,4 +1,6 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 use super::counts::VulnerabilityCounts;
 use super::severity::Severity;
@@ -13,6 +15,9 @@ pub struct AuditOptions {
     pub ignore_cves: HashSet<String>,
     pub ignore_ghsas: HashSet<String>,
     pub ignore_unfixable: bool,
+    /// Match against a local OSV advisory dump instead of asking the
+    /// registry.
+    pub database: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -22,23 +27,87 @@ pub struct AuditResult {
     pub total_packages: usize,
     pub project_name: String,
     pub workspace_member: Option<String>,
+    /// Findings `snpm-audit.yaml` accepts; left out of `advisories` and
+    /// `counts`.
+    pub suppressed: Vec<SuppressedAdvisory>,
+    /// Entries of `snpm-audit.yaml` past their `expires` date. They
+    /// suppress nothing and fail the audit until renewed or removed.
+    pub expired_suppressions: Vec<AuditSuppression>,
+}
+
+/// An accepted advisory from `snpm-audit.yaml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditSuppression {
+    /// GHSA id, CVE or the registry's numeric advisory id.
+    pub id: String,
+    /// Only findings in this package.
+    #[serde(default)]
+    pub package: Option<String>,
+    /// Only findings reached through this dependency path, written like
+    /// the audit's paths (`webpack>terser`) and matched as a prefix.
+    #[serde(default)]
+    pub path: Option<String>,
+    pub reason: String,
+    pub owner: String,
+    /// Last day the suppression applies, as `YYYY-MM-DD`.
+    pub expires: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SuppressedAdvisory {
+    /// The advisory with only the findings the suppression covers.
+    pub advisory: AuditAdvisory,
+    pub suppression: AuditSuppression,
 }
 
 #[derive(Debug, Clone)]
 pub struct FixResult {
     pub fixable: Vec<FixableVulnerability>,
     pub unfixable: Vec<UnfixableVulnerability>,
+    /// The changes that fix `fixable`, each listed once.
+    pub actions: Vec<FixAction>,
+    /// Each fixable finding after re-resolving. Empty for a dry run.
+    pub outcomes: Vec<FixOutcome>,
 }
 
 #[derive(Debug, Clone)]
 pub struct FixableVulnerability {
     pub package: String,
     pub from_version: String,
+    /// Lowest published version outside every advisory's vulnerable range.
     pub to_version: String,
     pub advisory_id: u64,
     pub severity: Severity,
 }
 
+/// The smallest change that moves a vulnerable package to a patched
+/// version.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FixAction {
+    /// Raise the range of a direct dependency in `package.json`.
+    BumpDependency {
+        package: String,
+        from: String,
+        to: String,
+    },
+    /// Add `selector` (`name@<requested range>`) to `snpm-overrides.yaml`,
+    /// for a package whose dependents request a range with no patched
+    /// version in it, or that only comes in transitively.
+    Override { selector: String, range: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct FixOutcome {
+    pub package: String,
+    pub advisory_id: u64,
+    pub severity: Severity,
+    pub before: String,
+    /// Versions of `package` in the lockfile after the fix.
+    pub after: Vec<String>,
+    pub fixed: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct UnfixableVulnerability {
     pub package: String,
@@ -47,3 +116,21 @@ pub struct UnfixableVulnerability {
     pub severity: Severity,
     pub reason: String,
 }
+
+/// Outcome of checking registry signatures over a lockfile or graph.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SignatureAuditResult {
+    pub verified: usize,
+    /// Packages from registries that publish no signing keys.
+    pub unchecked: usize,
+    pub missing: Vec<SignatureIssue>,
+    pub invalid: Vec<SignatureIssue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SignatureIssue {
+    pub name: String,
+    pub version: String,
+    pub registry: String,
+    pub reason: String,
+}
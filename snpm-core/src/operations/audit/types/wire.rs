@@ -96,3 +96,40 @@ pub struct AuditMetadata {
     #[serde(default, rename = "totalDependencies")]
     pub total_dependencies: u64,
 }
+
+/// Response of the registry's bulk advisory endpoint: advisories keyed by
+/// package name. Local advisory databases are read into the same shape.
+pub(in crate::operations::audit) type BulkAuditResponse = HashMap<String, Vec<BulkAdvisory>>;
+
+#[derive(Debug, Clone, Deserialize)]
+pub(in crate::operations::audit) struct BulkAdvisory {
+    pub id: u64,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub title: String,
+    pub severity: String,
+    pub vulnerable_versions: String,
+    #[serde(default)]
+    pub cwe: Option<BulkCwe>,
+    /// Not part of the registry response; filled from OSV aliases.
+    #[serde(default)]
+    pub cves: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(in crate::operations::audit) enum BulkCwe {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl BulkCwe {
+    pub(in crate::operations::audit) fn as_string(&self) -> Option<String> {
+        match self {
+            BulkCwe::One(value) if !value.is_empty() => Some(value.clone()),
+            BulkCwe::Many(values) if !values.is_empty() => Some(values.join(", ")),
+            _ => None,
+        }
+    }
+}
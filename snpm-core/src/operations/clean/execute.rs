@@ -15,6 +15,8 @@ pub fn execute(config: &SnpmConfig, options: &CleanOptions) -> Result<CleanSumma
             console::step("Removing cached packages...");
             remove_directory_contents(&packages_directory)?;
         }
+        // Without packages, every pooled file is unreferenced.
+        remove_directory_contents(&config.files_dir())?;
     }
 
     if options.metadata {
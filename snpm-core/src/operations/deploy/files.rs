@@ -0,0 +1,35 @@
+use crate::copying::clone_or_copy_file;
+use crate::operations::pack::collect_pack_files;
+use crate::operations::publish::rewrite::rewritten_manifest_bytes;
+use crate::{Project, Result, SnpmError};
+
+use std::fs;
+use std::path::Path;
+
+/// Copies the files `snpm pack` would ship for `project` into `dest`.
+/// The manifest gets the same `workspace:`/`catalog:` rewrite a published
+/// tarball would, so the copy stands on its own outside the monorepo.
+pub(super) fn copy_project_files(project: &Project, dest: &Path) -> Result<usize> {
+    let files = collect_pack_files(project)?;
+    let manifest = rewritten_manifest_bytes(project)?;
+
+    for file in &files {
+        let to = dest.join(&file.relative_path);
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).map_err(|source| SnpmError::WriteFile {
+                path: parent.to_path_buf(),
+                source,
+            })?;
+        }
+
+        match manifest.as_deref() {
+            Some(bytes) if file.absolute_path == project.manifest_path => {
+                fs::write(&to, bytes).map_err(|source| SnpmError::WriteFile { path: to, source })?
+            }
+            _ => clone_or_copy_file(&file.absolute_path, &to)
+                .map_err(|source| SnpmError::WriteFile { path: to, source })?,
+        }
+    }
+
+    Ok(files.len())
+}
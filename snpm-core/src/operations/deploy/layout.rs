@@ -0,0 +1,188 @@
+use crate::lockfile::{LockImporter, LockRootDependency};
+use crate::resolve::{PackageId, ResolutionGraph};
+use crate::{Result, SnpmError};
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum DeployNode {
+    Package(PackageId),
+    /// A workspace sibling, injected as a copy of its packed files.
+    /// Identified by its importer key.
+    Workspace(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Placement {
+    /// The `node_modules` directory the entry lives in, relative to the
+    /// deploy target.
+    pub(super) scope_dir: PathBuf,
+    pub(crate) name: String,
+    pub(crate) node: DeployNode,
+}
+
+impl Placement {
+    pub(crate) fn path(&self) -> PathBuf {
+        self.scope_dir.join(&self.name)
+    }
+}
+
+struct Scope {
+    dir: PathBuf,
+    parent: Option<usize>,
+    entries: BTreeMap<String, DeployNode>,
+}
+
+/// Lays out the production closure of `root_key` as a plain nested
+/// `node_modules` tree that Node's resolver can walk without symlinks.
+///
+/// Everything is hoisted to the top level unless a different version of
+/// the same name is already visible from the dependent, in which case it
+/// nests under the dependent's own `node_modules`. Packages are processed
+/// breadth-first and each one's dependencies are placed together, so a
+/// nested placement can never change what an already-placed package
+/// resolves to.
+pub(crate) fn plan_layout(
+    graph: &ResolutionGraph,
+    importers: &BTreeMap<String, LockImporter>,
+    root_key: &str,
+) -> Result<Vec<Placement>> {
+    let mut scopes = vec![Scope {
+        dir: PathBuf::from("node_modules"),
+        parent: None,
+        entries: BTreeMap::new(),
+    }];
+    let mut placements = Vec::new();
+    let mut queue = VecDeque::new();
+
+    let root = DeployNode::Workspace(root_key.to_string());
+    for (name, node) in node_dependencies(graph, importers, &root)? {
+        place(&mut scopes, &mut placements, &mut queue, 0, name, node);
+    }
+
+    while let Some((index, scope)) = queue.pop_front() {
+        let placement: Placement = placements[index].clone();
+        let mut own_scope = None;
+
+        for (name, node) in node_dependencies(graph, importers, &placement.node)? {
+            match visible(&scopes, own_scope.unwrap_or(scope), &name) {
+                Some(existing) if *existing == node => {}
+                Some(_) => {
+                    let target = match own_scope {
+                        Some(target) => target,
+                        None => {
+                            scopes.push(Scope {
+                                dir: placement.path().join("node_modules"),
+                                parent: Some(scope),
+                                entries: BTreeMap::new(),
+                            });
+                            own_scope = Some(scopes.len() - 1);
+                            scopes.len() - 1
+                        }
+                    };
+                    place(&mut scopes, &mut placements, &mut queue, target, name, node);
+                }
+                None => place(&mut scopes, &mut placements, &mut queue, 0, name, node),
+            }
+        }
+    }
+
+    Ok(placements)
+}
+
+fn place(
+    scopes: &mut [Scope],
+    placements: &mut Vec<Placement>,
+    queue: &mut VecDeque<(usize, usize)>,
+    scope: usize,
+    name: String,
+    node: DeployNode,
+) {
+    scopes[scope].entries.insert(name.clone(), node.clone());
+    placements.push(Placement {
+        scope_dir: scopes[scope].dir.clone(),
+        name,
+        node,
+    });
+    queue.push_back((placements.len() - 1, scope));
+}
+
+fn visible<'a>(scopes: &'a [Scope], mut scope: usize, name: &str) -> Option<&'a DeployNode> {
+    loop {
+        if let Some(node) = scopes[scope].entries.get(name) {
+            return Some(node);
+        }
+        scope = scopes[scope].parent?;
+    }
+}
+
+fn node_dependencies(
+    graph: &ResolutionGraph,
+    importers: &BTreeMap<String, LockImporter>,
+    node: &DeployNode,
+) -> Result<Vec<(String, DeployNode)>> {
+    match node {
+        DeployNode::Package(id) => {
+            let package = graph
+                .packages
+                .get(id)
+                .ok_or_else(|| SnpmError::GraphMissing {
+                    name: id.name.clone(),
+                    version: id.version.clone(),
+                })?;
+
+            Ok(package
+                .dependencies
+                .iter()
+                .map(|(name, id)| (name.clone(), DeployNode::Package(id.clone())))
+                .collect())
+        }
+        DeployNode::Workspace(key) => {
+            let importer = importers.get(key).ok_or_else(|| SnpmError::DeployFailed {
+                reason: format!("workspace project {key} is not part of the workspace"),
+            })?;
+
+            let mut dependencies = Vec::new();
+            for (name, dep) in &importer.dependencies {
+                match importer_package(graph, name, dep) {
+                    Some(id) => dependencies.push((name.clone(), DeployNode::Package(id))),
+                    None if dep.optional => {}
+                    None => {
+                        return Err(SnpmError::DeployFailed {
+                            reason: format!(
+                                "{name} (required by {key}) is missing from snpm-lock.yaml. Run `snpm install` first."
+                            ),
+                        });
+                    }
+                }
+            }
+
+            for (name, sibling) in &importer.links {
+                dependencies.push((name.clone(), DeployNode::Workspace(sibling.clone())));
+            }
+
+            Ok(dependencies)
+        }
+    }
+}
+
+/// The package an importer entry resolved to: the version the lockfile
+/// records for this member, or the flattened root's for importers that
+/// carry none. `None` when the graph doesn't hold it, as with optional
+/// packages for another platform.
+fn importer_package(
+    graph: &ResolutionGraph,
+    name: &str,
+    dep: &LockRootDependency,
+) -> Option<PackageId> {
+    let id = match &dep.version {
+        Some(version) => PackageId {
+            name: dep.package.clone().unwrap_or_else(|| name.to_string()),
+            version: version.clone(),
+        },
+        None => graph.root.dependencies.get(name)?.resolved.clone(),
+    };
+
+    graph.packages.contains_key(&id).then_some(id)
+}
@@ -0,0 +1,219 @@
+mod files;
+mod layout;
+
+use crate::linker::bins::link_bins;
+use crate::linker::fs::copy_dir;
+use crate::lockfile;
+use crate::operations::install::effective_config::{ConfigOverrides, effective_config};
+use crate::operations::install::supported_architectures::load_workspace_supported_architectures;
+use crate::operations::install::utils::{installable_graph, materialize_store};
+use crate::operations::install::workspace::collect_workspace_importers;
+use crate::operations::patch as patch_ops;
+use crate::resolve::{PackageId, ResolutionGraph};
+use crate::{Project, Result, SnpmConfig, SnpmError, Workspace, console, http, lifecycle, patch};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use files::copy_project_files;
+// `lockfile export` lays out npm and bun lockfiles with the same hoisting.
+pub(crate) use layout::{DeployNode, Placement, plan_layout};
+
+#[derive(Debug)]
+pub struct DeployResult {
+    pub target: PathBuf,
+    pub file_count: usize,
+    pub package_count: usize,
+    /// Workspace siblings copied in place of `workspace:` links.
+    pub injected: Vec<String>,
+    pub blocked_scripts: Vec<String>,
+}
+
+/// Produces a self-contained copy of `project` in `target`: its packed
+/// files plus a real `node_modules` holding the production dependency
+/// closure from the workspace lockfile. Nothing links back into the store
+/// or to other members, so the directory can be copied straight into an
+/// image.
+pub async fn deploy(
+    config: &SnpmConfig,
+    workspace: &Workspace,
+    project: &Project,
+    target: &Path,
+) -> Result<DeployResult> {
+    let lockfile_path = workspace.root.join("snpm-lock.yaml");
+    if !lockfile_path.is_file() {
+        return Err(SnpmError::Lockfile {
+            path: lockfile_path,
+            reason: "snpm-lock.yaml is missing. Run `snpm install` first.".into(),
+        });
+    }
+
+    let targets = load_workspace_supported_architectures(config, workspace);
+    let config = &*effective_config(
+        config,
+        ConfigOverrides {
+            supported_architectures: Some(&targets),
+            ..ConfigOverrides::default()
+        },
+    );
+    let lockfile = lockfile::read(&lockfile_path)?;
+    let graph = lockfile::to_graph(&lockfile);
+    let graph = installable_graph(config, &graph);
+    let importers = locked_importers(workspace, &lockfile)?;
+    let root_key = lockfile::importer_key(&workspace.root, &project.root);
+    let placements = plan_layout(&graph, &importers, &root_key)?;
+
+    prepare_target(target)?;
+
+    console::step("Copying project files");
+    let file_count = copy_project_files(project, target)?;
+
+    let package_ids: BTreeSet<&PackageId> = placements
+        .iter()
+        .filter_map(|placement| match &placement.node {
+            DeployNode::Package(id) => Some(id),
+            DeployNode::Workspace(_) => None,
+        })
+        .collect();
+    let closure = ResolutionGraph {
+        root: graph.root.clone(),
+        packages: graph
+            .packages
+            .iter()
+            .filter(|(id, _)| package_ids.contains(id))
+            .map(|(id, package)| (id.clone(), package.clone()))
+            .collect(),
+    };
+
+    console::step("Fetching packages");
+    let client = http::create_client()?;
+    let store_paths = materialize_store(config, &closure, &client).await?;
+    let patches = collect_patches(workspace)?;
+
+    console::step_with_count("Copying dependencies", placements.len());
+    let mut injected = BTreeSet::new();
+    for placement in &placements {
+        let dest = target.join(placement.path());
+
+        match &placement.node {
+            DeployNode::Package(id) => {
+                let store_path = store_paths.get(id).ok_or_else(|| SnpmError::StoreMissing {
+                    name: id.name.clone(),
+                    version: id.version.clone(),
+                })?;
+                copy_dir(store_path, &dest)?;
+
+                if let Some(patch_path) = patches.get(&(id.name.clone(), id.version.clone())) {
+                    patch::apply_patch(&dest, patch_path)?;
+                }
+            }
+            DeployNode::Workspace(key) => {
+                let sibling = workspace
+                    .projects
+                    .iter()
+                    .find(|candidate| {
+                        lockfile::importer_key(&workspace.root, &candidate.root) == *key
+                    })
+                    .ok_or_else(|| SnpmError::DeployFailed {
+                        reason: format!("workspace project {key} is not part of the workspace"),
+                    })?;
+                copy_project_files(sibling, &dest)?;
+                injected.insert(placement.name.clone());
+            }
+        }
+    }
+
+    for placement in &placements {
+        link_bins(
+            &target.join(placement.path()),
+            &target.join(&placement.scope_dir),
+            &placement.name,
+        )?;
+    }
+
+    let blocked_scripts = lifecycle::run_install_scripts(config, Some(workspace), target)?;
+
+    Ok(DeployResult {
+        target: target.to_path_buf(),
+        file_count,
+        package_count: package_ids.len(),
+        injected: injected.into_iter().collect(),
+        blocked_scripts,
+    })
+}
+
+/// The members' production specs and workspace links, from the manifests,
+/// with each dependency pinned to the version the lockfile's importer
+/// entry for that member records.
+fn locked_importers(
+    workspace: &Workspace,
+    lockfile: &lockfile::Lockfile,
+) -> Result<BTreeMap<String, lockfile::LockImporter>> {
+    let mut importers = collect_workspace_importers(workspace, false, &BTreeMap::new())?;
+    for (key, importer) in &mut importers {
+        let Some(locked) = lockfile.importers.get(key) else {
+            continue;
+        };
+        for (name, dep) in &mut importer.dependencies {
+            if let Some(locked) = locked.dependencies.get(name) {
+                dep.package = locked.package.clone();
+                dep.version = locked.version.clone();
+            }
+        }
+    }
+
+    Ok(importers)
+}
+
+fn prepare_target(target: &Path) -> Result<()> {
+    if target.exists() {
+        if !target.is_dir() {
+            return Err(SnpmError::DeployFailed {
+                reason: format!("{} exists and is not a directory", target.display()),
+            });
+        }
+
+        let mut entries = fs::read_dir(target).map_err(|source| SnpmError::ReadFile {
+            path: target.to_path_buf(),
+            source,
+        })?;
+        if entries.next().is_some() {
+            return Err(SnpmError::DeployFailed {
+                reason: format!("target directory {} is not empty", target.display()),
+            });
+        }
+    }
+
+    fs::create_dir_all(target).map_err(|source| SnpmError::WriteFile {
+        path: target.to_path_buf(),
+        source,
+    })
+}
+
+fn collect_patches(workspace: &Workspace) -> Result<BTreeMap<(String, String), PathBuf>> {
+    let mut patches = BTreeMap::new();
+
+    for project in &workspace.projects {
+        for (name, version, patch_path) in patch_ops::get_patches_to_apply(project)? {
+            let key = (name, version);
+            if let Some(existing) = patches.get(&key)
+                && *existing != patch_path
+            {
+                return Err(SnpmError::WorkspaceConfig {
+                    path: workspace.root.clone(),
+                    reason: format!(
+                        "conflicting patches configured for {}@{} across workspace projects",
+                        key.0, key.1
+                    ),
+                });
+            }
+            patches.insert(key, patch_path);
+        }
+    }
+
+    Ok(patches)
+}
+
+#[cfg(test)]
+mod tests;
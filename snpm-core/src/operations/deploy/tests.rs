@@ -0,0 +1,277 @@
+use super::files::copy_project_files;
+use super::layout::{DeployNode, plan_layout};
+use super::prepare_target;
+use crate::Workspace;
+use crate::lockfile::{LockImporter, LockRootDependency};
+use crate::resolve::{PackageId, ResolutionGraph, ResolutionRoot, ResolvedPackage, RootDependency};
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tempfile::tempdir;
+
+fn id(name: &str, version: &str) -> PackageId {
+    PackageId {
+        name: name.to_string(),
+        version: version.to_string(),
+    }
+}
+
+fn package(name: &str, version: &str, deps: &[(&str, &str)]) -> (PackageId, ResolvedPackage) {
+    (
+        id(name, version),
+        ResolvedPackage {
+            id: id(name, version),
+            tarball: String::new(),
+            integrity: None,
+            dependencies: deps
+                .iter()
+                .map(|(dep, version)| (dep.to_string(), id(dep, version)))
+                .collect(),
+            peer_dependencies: BTreeMap::new(),
+            bundled_dependencies: None,
+            has_bin: false,
+            bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
+        },
+    )
+}
+
+fn root_dep(name: &str, version: &str) -> (String, RootDependency) {
+    (
+        name.to_string(),
+        RootDependency {
+            requested: format!("^{version}"),
+            resolved: id(name, version),
+        },
+    )
+}
+
+fn spec(requested: &str) -> LockRootDependency {
+    LockRootDependency {
+        requested: requested.to_string(),
+        package: None,
+        version: None,
+        optional: false,
+    }
+}
+
+fn placed(placements: &[super::layout::Placement]) -> Vec<(PathBuf, DeployNode)> {
+    placements
+        .iter()
+        .map(|placement| (placement.path(), placement.node.clone()))
+        .collect()
+}
+
+#[test]
+fn plan_layout_hoists_and_nests_conflicting_versions() {
+    let graph = ResolutionGraph {
+        root: ResolutionRoot {
+            dependencies: BTreeMap::from([root_dep("a", "1.0.0"), root_dep("c", "2.0.0")]),
+        },
+        packages: BTreeMap::from([
+            package("a", "1.0.0", &[("b", "1.0.0"), ("c", "1.0.0")]),
+            package("b", "1.0.0", &[("c", "1.0.0")]),
+            package("c", "1.0.0", &[]),
+            package("c", "2.0.0", &[]),
+        ]),
+    };
+    let importers = BTreeMap::from([(
+        "packages/app".to_string(),
+        LockImporter {
+            dependencies: BTreeMap::from([
+                ("a".to_string(), spec("^1.0.0")),
+                ("c".to_string(), spec("^2.0.0")),
+            ]),
+            ..LockImporter::default()
+        },
+    )]);
+
+    let placements = placed(&plan_layout(&graph, &importers, "packages/app").unwrap());
+
+    assert_eq!(
+        placements,
+        vec![
+            (
+                PathBuf::from("node_modules/a"),
+                DeployNode::Package(id("a", "1.0.0"))
+            ),
+            (
+                PathBuf::from("node_modules/c"),
+                DeployNode::Package(id("c", "2.0.0"))
+            ),
+            (
+                PathBuf::from("node_modules/b"),
+                DeployNode::Package(id("b", "1.0.0"))
+            ),
+            (
+                PathBuf::from("node_modules/a/node_modules/c"),
+                DeployNode::Package(id("c", "1.0.0"))
+            ),
+            (
+                PathBuf::from("node_modules/b/node_modules/c"),
+                DeployNode::Package(id("c", "1.0.0"))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn plan_layout_injects_workspace_siblings_with_their_dependencies() {
+    let graph = ResolutionGraph {
+        root: ResolutionRoot {
+            dependencies: BTreeMap::from([root_dep("a", "1.0.0")]),
+        },
+        packages: BTreeMap::from([package("a", "1.0.0", &[])]),
+    };
+    let importers = BTreeMap::from([
+        (
+            "packages/app".to_string(),
+            LockImporter {
+                links: BTreeMap::from([("lib".to_string(), "packages/lib".to_string())]),
+                ..LockImporter::default()
+            },
+        ),
+        (
+            "packages/lib".to_string(),
+            LockImporter {
+                dependencies: BTreeMap::from([("a".to_string(), spec("^1.0.0"))]),
+                ..LockImporter::default()
+            },
+        ),
+    ]);
+
+    let placements = placed(&plan_layout(&graph, &importers, "packages/app").unwrap());
+
+    assert_eq!(
+        placements,
+        vec![
+            (
+                PathBuf::from("node_modules/lib"),
+                DeployNode::Workspace("packages/lib".to_string())
+            ),
+            (
+                PathBuf::from("node_modules/a"),
+                DeployNode::Package(id("a", "1.0.0"))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn plan_layout_uses_the_version_the_importer_records() {
+    let graph = ResolutionGraph {
+        root: ResolutionRoot {
+            dependencies: BTreeMap::from([root_dep("a", "2.0.0")]),
+        },
+        packages: BTreeMap::from([package("a", "1.0.0", &[]), package("a", "2.0.0", &[])]),
+    };
+    let importers = BTreeMap::from([(
+        "packages/app".to_string(),
+        LockImporter {
+            dependencies: BTreeMap::from([(
+                "a".to_string(),
+                LockRootDependency {
+                    version: Some("1.0.0".to_string()),
+                    ..spec("^1.0.0")
+                },
+            )]),
+            ..LockImporter::default()
+        },
+    )]);
+
+    let placements = placed(&plan_layout(&graph, &importers, "packages/app").unwrap());
+
+    assert_eq!(
+        placements,
+        vec![(
+            PathBuf::from("node_modules/a"),
+            DeployNode::Package(id("a", "1.0.0"))
+        )]
+    );
+}
+
+#[test]
+fn plan_layout_errors_on_dependency_missing_from_lockfile() {
+    let graph = ResolutionGraph {
+        root: ResolutionRoot {
+            dependencies: BTreeMap::new(),
+        },
+        packages: BTreeMap::new(),
+    };
+    let importers = BTreeMap::from([(
+        ".".to_string(),
+        LockImporter {
+            dependencies: BTreeMap::from([("a".to_string(), spec("^1.0.0"))]),
+            ..LockImporter::default()
+        },
+    )]);
+
+    let error = plan_layout(&graph, &importers, ".").unwrap_err();
+    assert!(error.to_string().contains("a (required by .)"));
+}
+
+#[test]
+fn prepare_target_rejects_non_empty_directory() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("stale.txt"), "").unwrap();
+
+    assert!(prepare_target(dir.path()).is_err());
+    assert!(prepare_target(&dir.path().join("fresh")).is_ok());
+    assert!(dir.path().join("fresh").is_dir());
+}
+
+fn write_manifest(root: &Path, manifest: serde_json::Value) {
+    fs::create_dir_all(root).unwrap();
+    fs::write(
+        root.join("package.json"),
+        serde_json::to_string_pretty(&manifest).unwrap(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn copy_project_files_rewrites_workspace_specs() {
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    fs::write(
+        root.join("snpm-workspace.yaml"),
+        "packages:\n  - packages/*\n",
+    )
+    .unwrap();
+    write_manifest(
+        &root.join("packages/lib"),
+        serde_json::json!({ "name": "lib", "version": "1.4.0" }),
+    );
+    write_manifest(
+        &root.join("packages/app"),
+        serde_json::json!({
+            "name": "app",
+            "version": "1.0.0",
+            "dependencies": { "lib": "workspace:^" }
+        }),
+    );
+    fs::write(root.join("packages/app/index.js"), "module.exports = 1;\n").unwrap();
+
+    let workspace = Workspace::discover(root).unwrap().unwrap();
+    let app = workspace
+        .projects
+        .iter()
+        .find(|project| project.manifest.name.as_deref() == Some("app"))
+        .unwrap();
+
+    let out = root.join("out");
+    let count = copy_project_files(app, &out).unwrap();
+
+    assert_eq!(count, 2);
+    assert!(out.join("index.js").is_file());
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&fs::read(out.join("package.json")).unwrap()).unwrap();
+    assert_eq!(manifest["dependencies"]["lib"], "^1.4.0");
+}
@@ -35,6 +35,9 @@ pub(super) async fn resolve_dlx_graph(
         None,
         None,
         None,
+        None,
+        None,
+        false,
         offline_mode,
         {
             let store_config = store_config.clone();
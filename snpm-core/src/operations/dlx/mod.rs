@@ -7,13 +7,13 @@ use crate::config::OfflineMode;
 use environment::prepare_dlx_environment;
 use execute::run_bin;
 
-/// Run a package binary with default online mode.
+/// Run a package binary in the configured offline mode.
 pub async fn dlx(
     config: &crate::SnpmConfig,
     package_spec: String,
     arguments: Vec<String>,
 ) -> Result<()> {
-    dlx_with_offline(config, package_spec, arguments, OfflineMode::Online).await
+    dlx_with_offline(config, package_spec, arguments, config.offline_mode).await
 }
 
 /// Run a package binary respecting offline mode.
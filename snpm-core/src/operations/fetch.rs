@@ -0,0 +1,317 @@
+use crate::lockfile::{self, LockImporter, LockRootDependency};
+use crate::operations::install::effective_config::{ConfigOverrides, effective_config};
+use crate::operations::install::utils::{
+    installable_graph, load_graph_snapshot, materialize_store,
+};
+use crate::operations::install::workspace::collect_workspace_importers;
+use crate::resolve::{PackageId, ResolutionGraph};
+use crate::{Project, Result, SnpmConfig, SnpmError, Workspace, console, http};
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Which importer dependency blocks `fetch` downloads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FetchScope {
+    #[default]
+    All,
+    Production,
+    Development,
+}
+
+#[derive(Debug)]
+pub struct FetchResult {
+    pub lockfile_path: PathBuf,
+    pub package_count: usize,
+}
+
+/// Downloads every package locked in `<root>/snpm-lock.yaml` into the
+/// store without resolving anything or touching `node_modules`, so a later
+/// `install --frozen-lockfile` can run without the network.
+pub async fn fetch(config: &SnpmConfig, root: &Path, scope: FetchScope) -> Result<FetchResult> {
+    let lockfile_path = root.join("snpm-lock.yaml");
+    if !lockfile_path.is_file() {
+        return Err(SnpmError::Lockfile {
+            path: lockfile_path,
+            reason: "snpm-lock.yaml is missing. Run `snpm install --lockfile-only` first.".into(),
+        });
+    }
+
+    let lockfile = lockfile::read(&lockfile_path)?;
+    let graph = match load_graph_snapshot(root, &lockfile_path) {
+        Some(snapshot) => snapshot.graph,
+        None => lockfile::to_graph(&lockfile),
+    };
+
+    let graph = if scope == FetchScope::All {
+        graph
+    } else {
+        let importers = if lockfile.importers.is_empty() {
+            manifest_importers(root)?
+        } else {
+            lockfile.importers.clone()
+        };
+
+        if importers.is_empty() {
+            console::warn(
+                "snpm-lock.yaml does not record which dependencies are dev-only; fetching every locked package",
+            );
+            graph
+        } else {
+            scoped_graph(&graph, &importers, scope == FetchScope::Development)
+        }
+    };
+
+    // Packages for platforms outside the lockfile's supportedArchitectures
+    // would never be linked here, so they are not downloaded either.
+    let targets = config
+        .supported_architectures
+        .clone()
+        .or(&lockfile.settings.supported_architectures);
+    let config = &*effective_config(
+        config,
+        ConfigOverrides {
+            supported_architectures: Some(&targets),
+            ..ConfigOverrides::default()
+        },
+    );
+    let graph = installable_graph(config, &graph);
+
+    let client = http::create_client()?;
+    let paths = materialize_store(config, &graph, &client).await?;
+
+    Ok(FetchResult {
+        lockfile_path,
+        package_count: paths.len(),
+    })
+}
+
+/// Lockfiles without importers (standalone projects, and workspaces
+/// locked before importers were recorded) are split by the manifests
+/// instead: every workspace project the way install collects them, or the
+/// project's own `package.json`.
+fn manifest_importers(root: &Path) -> Result<BTreeMap<String, LockImporter>> {
+    if let Some(workspace) = Workspace::discover(root)?
+        && workspace.root == root
+    {
+        // Only the dependency names decide the split, so overrides, which
+        // rewrite ranges, are left out.
+        return collect_workspace_importers(&workspace, true, &BTreeMap::new());
+    }
+
+    let manifest_path = root.join("package.json");
+    if !manifest_path.is_file() {
+        return Ok(BTreeMap::new());
+    }
+
+    let manifest = Project::from_manifest_path(manifest_path)?.manifest;
+    let spec = |requested: &String, optional: bool| LockRootDependency {
+        requested: requested.clone(),
+        package: None,
+        version: None,
+        optional,
+    };
+
+    let mut dependencies = BTreeMap::new();
+    for (name, requested) in &manifest.dependencies {
+        dependencies.insert(name.clone(), spec(requested, false));
+    }
+    for (name, requested) in &manifest.optional_dependencies {
+        dependencies.insert(name.clone(), spec(requested, true));
+    }
+
+    let dev_dependencies = manifest
+        .dev_dependencies
+        .iter()
+        .filter(|(name, _)| !dependencies.contains_key(*name))
+        .map(|(name, requested)| (name.clone(), spec(requested, false)))
+        .collect();
+
+    Ok(BTreeMap::from([(
+        ".".to_string(),
+        LockImporter {
+            dependencies,
+            dev_dependencies,
+            links: BTreeMap::new(),
+        },
+    )]))
+}
+
+/// Keeps only the packages reachable from the importers' production
+/// roots, or their dev roots when `dev` is set.
+fn scoped_graph(
+    graph: &ResolutionGraph,
+    importers: &BTreeMap<String, LockImporter>,
+    dev: bool,
+) -> ResolutionGraph {
+    let mut queue: VecDeque<&PackageId> = importers
+        .values()
+        .flat_map(|importer| {
+            if dev {
+                importer.dev_dependencies.keys()
+            } else {
+                importer.dependencies.keys()
+            }
+        })
+        .filter_map(|name| graph.root.dependencies.get(name))
+        .map(|root| &root.resolved)
+        .collect();
+
+    let mut reachable = BTreeSet::new();
+    while let Some(id) = queue.pop_front() {
+        if !reachable.insert(id) {
+            continue;
+        }
+        if let Some(package) = graph.packages.get(id) {
+            queue.extend(package.dependencies.values());
+        }
+    }
+
+    ResolutionGraph {
+        root: graph.root.clone(),
+        packages: graph
+            .packages
+            .iter()
+            .filter(|(id, _)| reachable.contains(id))
+            .map(|(id, package)| (id.clone(), package.clone()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolve::{ResolutionRoot, ResolvedPackage, RootDependency};
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn id(name: &str) -> PackageId {
+        PackageId {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+        }
+    }
+
+    fn package(name: &str, deps: &[&str]) -> (PackageId, ResolvedPackage) {
+        (
+            id(name),
+            ResolvedPackage {
+                id: id(name),
+                tarball: String::new(),
+                integrity: None,
+                dependencies: deps.iter().map(|dep| (dep.to_string(), id(dep))).collect(),
+                peer_dependencies: BTreeMap::new(),
+                bundled_dependencies: None,
+                has_bin: false,
+                bin: None,
+                os: Vec::new(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: None,
+                has_install_script: false,
+            },
+        )
+    }
+
+    fn root_dep(name: &str) -> (String, RootDependency) {
+        (
+            name.to_string(),
+            RootDependency {
+                requested: "^1.0.0".to_string(),
+                resolved: id(name),
+            },
+        )
+    }
+
+    fn names(graph: &ResolutionGraph) -> Vec<&str> {
+        graph.packages.keys().map(|id| id.name.as_str()).collect()
+    }
+
+    #[test]
+    fn scoped_graph_splits_production_and_dev_closures() {
+        let graph = ResolutionGraph {
+            root: ResolutionRoot {
+                dependencies: BTreeMap::from([root_dep("app-dep"), root_dep("test-dep")]),
+            },
+            packages: BTreeMap::from([
+                package("app-dep", &["shared"]),
+                package("test-dep", &["shared", "test-only"]),
+                package("shared", &[]),
+                package("test-only", &[]),
+            ]),
+        };
+
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{
+                "name": "app",
+                "dependencies": { "app-dep": "^1.0.0" },
+                "devDependencies": { "test-dep": "^1.0.0" }
+            }"#,
+        )
+        .unwrap();
+        let importers = manifest_importers(dir.path()).unwrap();
+
+        assert_eq!(
+            names(&scoped_graph(&graph, &importers, false)),
+            vec!["app-dep", "shared"]
+        );
+        assert_eq!(
+            names(&scoped_graph(&graph, &importers, true)),
+            vec!["shared", "test-dep", "test-only"]
+        );
+    }
+
+    #[test]
+    fn manifest_importers_cover_every_workspace_project() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        fs::write(
+            root.join("snpm-workspace.yaml"),
+            "packages:\n  - packages/*\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("package.json"),
+            r#"{ "name": "root", "devDependencies": { "test-dep": "^1.0.0" } }"#,
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("packages/app")).unwrap();
+        fs::write(
+            root.join("packages/app/package.json"),
+            r#"{ "name": "app", "dependencies": { "app-dep": "^1.0.0" } }"#,
+        )
+        .unwrap();
+
+        let importers = manifest_importers(&root).unwrap();
+
+        assert!(importers["."].dev_dependencies.contains_key("test-dep"));
+        assert!(
+            importers["packages/app"]
+                .dependencies
+                .contains_key("app-dep")
+        );
+    }
+
+    #[test]
+    fn manifest_importers_are_empty_without_package_json() {
+        let dir = tempdir().unwrap();
+        assert!(manifest_importers(dir.path()).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn fetch_requires_a_lockfile() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+
+        let error = fetch(&config, dir.path(), FetchScope::All)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("snpm-lock.yaml is missing"));
+    }
+}
@@ -35,6 +35,7 @@ pub async fn install_global(config: &SnpmConfig, packages: Vec<String>) -> Resul
             frozen_lockfile: FrozenLockfileMode::Prefer,
             strict_no_lockfile: false,
             force: false,
+            lockfile_only: false,
             silent_summary: false,
         },
     )
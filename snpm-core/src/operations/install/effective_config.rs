@@ -0,0 +1,152 @@
+//! The config an install actually runs with: the loaded `.snpmrc` and
+//! environment settings, with what the project or workspace configures on
+//! top.
+//!
+//! `trustPolicy` in `snpm-workspace.yaml` wins over `trust-policy` from
+//! `.snpmrc` or the environment; the workspace's `trustPolicyExclude` adds
+//! to `trust-policy-exclude`. `resolutionMode` and `supportedArchitectures`
+//! are loaded by their own modules and passed in.
+
+use crate::config::ResolutionMode;
+use crate::platform::{EngineTargets, SupportedArchitectures};
+use crate::{SnpmConfig, Workspace};
+
+use std::borrow::Cow;
+
+/// What to apply on top of the loaded config. `None` keeps its value.
+#[derive(Default)]
+pub(crate) struct ConfigOverrides<'a> {
+    /// Platforms to resolve and install optional packages for.
+    pub(crate) supported_architectures: Option<&'a SupportedArchitectures>,
+    pub(crate) resolution_mode: Option<ResolutionMode>,
+    /// The workspace whose trust policy applies.
+    pub(crate) workspace: Option<&'a Workspace>,
+    /// Runtime versions `engines` are checked against.
+    pub(crate) engine_targets: Option<&'a EngineTargets>,
+}
+
+/// `config` with `overrides` applied, borrowed when they change nothing.
+pub(crate) fn effective_config<'a>(
+    config: &'a SnpmConfig,
+    overrides: ConfigOverrides<'_>,
+) -> Cow<'a, SnpmConfig> {
+    let supported_architectures = overrides
+        .supported_architectures
+        .filter(|targets| **targets != config.supported_architectures);
+    let resolution_mode = overrides
+        .resolution_mode
+        .filter(|mode| *mode != config.resolution_mode);
+    let engine_targets = overrides
+        .engine_targets
+        .filter(|targets| **targets != config.engine_targets);
+    let trust_policy = overrides.workspace.filter(|workspace| {
+        workspace
+            .config
+            .trust_policy
+            .is_some_and(|policy| policy != config.trust_policy)
+            || workspace
+                .config
+                .trust_policy_exclude
+                .iter()
+                .any(|entry| !config.trust_policy_exclude.contains(entry))
+    });
+
+    if supported_architectures.is_none()
+        && resolution_mode.is_none()
+        && engine_targets.is_none()
+        && trust_policy.is_none()
+    {
+        return Cow::Borrowed(config);
+    }
+
+    let mut effective = config.clone();
+    if let Some(targets) = supported_architectures {
+        effective.supported_architectures = targets.clone();
+    }
+    if let Some(mode) = resolution_mode {
+        effective.resolution_mode = mode;
+    }
+    if let Some(targets) = engine_targets {
+        effective.engine_targets = targets.clone();
+    }
+    if let Some(workspace) = trust_policy {
+        effective.trust_policy = workspace.config.trust_policy.unwrap_or(config.trust_policy);
+        effective
+            .trust_policy_exclude
+            .extend(workspace.config.trust_policy_exclude.iter().cloned());
+    }
+    Cow::Owned(effective)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfigOverrides, effective_config};
+    use crate::Workspace;
+    use crate::config::{ResolutionMode, SnpmConfig, TrustPolicy};
+    use crate::workspace::types::WorkspaceConfig;
+
+    use std::borrow::Cow;
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    fn workspace(yaml: &str) -> Workspace {
+        Workspace {
+            root: PathBuf::from("/workspace"),
+            projects: Vec::new(),
+            config: serde_yaml::from_str::<WorkspaceConfig>(yaml).unwrap(),
+        }
+    }
+
+    #[test]
+    fn workspace_policy_wins_and_exclusions_merge() {
+        let config = SnpmConfig {
+            trust_policy_exclude: BTreeSet::from(["from-rc".to_string()]),
+            ..SnpmConfig::for_tests()
+        };
+        let workspace = workspace(
+            "packages: []\ntrustPolicy: no-downgrade\ntrustPolicyExclude:\n  - from-yaml@1.0.0\n",
+        );
+
+        let config = effective_config(
+            &config,
+            ConfigOverrides {
+                workspace: Some(&workspace),
+                resolution_mode: Some(ResolutionMode::Lowest),
+                ..ConfigOverrides::default()
+            },
+        );
+
+        assert_eq!(config.trust_policy, TrustPolicy::NoDowngrade);
+        assert_eq!(
+            config.trust_policy_exclude,
+            BTreeSet::from(["from-rc".to_string(), "from-yaml@1.0.0".to_string()])
+        );
+        assert_eq!(config.resolution_mode, ResolutionMode::Lowest);
+    }
+
+    #[test]
+    fn borrows_when_nothing_changes() {
+        let config = SnpmConfig {
+            trust_policy: TrustPolicy::NoDowngrade,
+            ..SnpmConfig::for_tests()
+        };
+        let workspace = workspace("packages: []\n");
+
+        assert!(matches!(
+            effective_config(
+                &config,
+                ConfigOverrides {
+                    workspace: Some(&workspace),
+                    resolution_mode: Some(config.resolution_mode),
+                    supported_architectures: Some(&config.supported_architectures),
+                    engine_targets: Some(&config.engine_targets),
+                }
+            ),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            effective_config(&config, ConfigOverrides::default()),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -0,0 +1,286 @@
+use crate::resolve::{PackageId, ResolutionGraph};
+use crate::{Project, Result, SnpmConfig, Workspace};
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::super::project_install::{dedupe_project_lockfile, install};
+use super::super::utils::{FrozenLockfileMode, InstallOptions};
+use super::super::workspace::{dedupe_workspace_lockfile, install_workspace};
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DedupedPackage {
+    pub name: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DedupeResult {
+    /// Packages that were locked at more versions than they now need.
+    pub collapsed: Vec<DedupedPackage>,
+    /// How many package entries left the lockfile overall, including
+    /// transitive dependencies that were only reachable through a dropped
+    /// version.
+    pub removed: usize,
+    /// Whether deduplication changes the graph a regular install would
+    /// lock, leaving aside any drift between the lockfile and manifests.
+    pub changed: bool,
+}
+
+/// Re-resolves the project lockfile preferring already-locked versions
+/// and, unless `check` is set, writes the reduced lockfile and relinks
+/// `node_modules` from it.
+pub async fn dedupe(
+    config: &SnpmConfig,
+    project: &mut Project,
+    check: bool,
+) -> Result<DedupeResult> {
+    let (before, after) = dedupe_project_lockfile(config, project, !check).await?;
+    let result = dedupe_report(&before, &after);
+
+    if result.changed && !check {
+        let options = InstallOptions {
+            requested: Vec::new(),
+            dev: false,
+            include_dev: true,
+            frozen_lockfile: FrozenLockfileMode::Prefer,
+            strict_no_lockfile: false,
+            force: false,
+            lockfile_only: false,
+            silent_summary: false,
+        };
+        install(config, project, options).await?;
+    }
+
+    Ok(result)
+}
+
+/// Workspace form of [`dedupe`], operating on the shared lockfile.
+pub async fn dedupe_workspace(
+    config: &SnpmConfig,
+    workspace: &mut Workspace,
+    check: bool,
+) -> Result<DedupeResult> {
+    let (before, after) = dedupe_workspace_lockfile(config, workspace, !check).await?;
+    let result = dedupe_report(&before, &after);
+
+    if result.changed && !check {
+        install_workspace(
+            config,
+            workspace,
+            true,
+            FrozenLockfileMode::Prefer,
+            false,
+            false,
+            false,
+        )
+        .await?;
+    }
+
+    Ok(result)
+}
+
+fn dedupe_report(before: &ResolutionGraph, after: &ResolutionGraph) -> DedupeResult {
+    let before_versions = versions_by_name(before);
+    let after_versions = versions_by_name(after);
+
+    let collapsed = before_versions
+        .iter()
+        .filter(|(_, versions)| versions.len() > 1)
+        .filter_map(|(name, versions)| {
+            let remaining = after_versions.get(name)?;
+            (remaining.len() < versions.len()).then(|| DedupedPackage {
+                name: name.to_string(),
+                before: versions.iter().map(|version| version.to_string()).collect(),
+                after: remaining
+                    .iter()
+                    .map(|version| version.to_string())
+                    .collect(),
+            })
+        })
+        .collect();
+
+    DedupeResult {
+        collapsed,
+        removed: before.packages.len().saturating_sub(after.packages.len()),
+        changed: graph_edges(before) != graph_edges(after),
+    }
+}
+
+fn versions_by_name(graph: &ResolutionGraph) -> BTreeMap<&str, BTreeSet<&str>> {
+    let mut versions: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for id in graph.packages.keys() {
+        versions
+            .entry(id.name.as_str())
+            .or_default()
+            .insert(id.version.as_str());
+    }
+    versions
+}
+
+type GraphEdges<'a> = (
+    BTreeMap<&'a str, &'a PackageId>,
+    BTreeMap<&'a PackageId, &'a BTreeMap<String, PackageId>>,
+);
+
+fn graph_edges(graph: &ResolutionGraph) -> GraphEdges<'_> {
+    (
+        graph
+            .root
+            .dependencies
+            .iter()
+            .map(|(name, dep)| (name.as_str(), &dep.resolved))
+            .collect(),
+        graph
+            .packages
+            .iter()
+            .map(|(id, package)| (id, &package.dependencies))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolve::{ResolutionRoot, ResolvedPackage, RootDependency};
+
+    use crate::operations::install::test_support::{lockfile_only_options, serve_registry};
+
+    use std::fs;
+
+    fn id(name: &str, version: &str) -> PackageId {
+        PackageId {
+            name: name.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    type PackageSpec<'a> = (&'a str, &'a str, &'a [(&'a str, &'a str)]);
+
+    fn graph(root: &[(&str, &str)], packages: &[PackageSpec<'_>]) -> ResolutionGraph {
+        ResolutionGraph {
+            root: ResolutionRoot {
+                dependencies: root
+                    .iter()
+                    .map(|(name, version)| {
+                        (
+                            name.to_string(),
+                            RootDependency {
+                                requested: "^1.0.0".to_string(),
+                                resolved: id(name, version),
+                            },
+                        )
+                    })
+                    .collect(),
+            },
+            packages: packages
+                .iter()
+                .map(|(name, version, deps)| {
+                    (
+                        id(name, version),
+                        ResolvedPackage {
+                            id: id(name, version),
+                            tarball: String::new(),
+                            integrity: None,
+                            dependencies: deps
+                                .iter()
+                                .map(|(dep, version)| (dep.to_string(), id(dep, version)))
+                                .collect(),
+                            peer_dependencies: BTreeMap::new(),
+                            bundled_dependencies: None,
+                            has_bin: false,
+                            bin: None,
+                            os: Vec::new(),
+                            cpu: Vec::new(),
+                            libc: Vec::new(),
+                            engines: BTreeMap::new(),
+                            deprecated: None,
+                            has_install_script: false,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn dedupe_report_lists_collapsed_versions_and_dropped_entries() {
+        let before = graph(
+            &[("a", "1.0.0"), ("c", "1.2.0")],
+            &[
+                ("a", "1.0.0", &[("c", "1.0.0")]),
+                ("c", "1.0.0", &[("d", "1.0.0")]),
+                ("c", "1.2.0", &[]),
+                ("d", "1.0.0", &[]),
+            ],
+        );
+        let after = graph(
+            &[("a", "1.0.0"), ("c", "1.2.0")],
+            &[("a", "1.0.0", &[("c", "1.2.0")]), ("c", "1.2.0", &[])],
+        );
+
+        let report = dedupe_report(&before, &after);
+
+        assert!(report.changed);
+        assert_eq!(report.removed, 2);
+        assert_eq!(
+            report.collapsed,
+            vec![DedupedPackage {
+                name: "c".to_string(),
+                before: vec!["1.0.0".to_string(), "1.2.0".to_string()],
+                after: vec!["1.2.0".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn dedupe_report_is_unchanged_for_identical_graphs() {
+        let current = graph(&[("a", "1.0.0")], &[("a", "1.0.0", &[])]);
+
+        let report = dedupe_report(&current, &current.clone());
+
+        assert!(!report.changed);
+        assert_eq!(report.removed, 0);
+        assert!(report.collapsed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn check_ignores_a_lockfile_that_is_behind_the_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("app");
+        fs::create_dir_all(&root).unwrap();
+        let manifest = |dependencies: &str| {
+            fs::write(
+                root.join("package.json"),
+                format!(r#"{{ "name": "app", "version": "1.0.0", "dependencies": {{ {dependencies} }} }}"#),
+            )
+            .unwrap();
+        };
+        manifest(r#""foo": "^1.0.0""#);
+
+        let config = SnpmConfig {
+            cache_dir: dir.path().join("cache"),
+            data_dir: dir.path().join("data"),
+            default_registry: serve_registry(&[("foo", ""), ("bar", "")]).await,
+            registry_concurrency: 4,
+            ..SnpmConfig::for_tests()
+        };
+        let mut project = Project::from_manifest_path(root.join("package.json")).unwrap();
+        install(
+            &config,
+            &mut project,
+            lockfile_only_options(FrozenLockfileMode::No),
+        )
+        .await
+        .unwrap();
+
+        manifest(r#""foo": "^1.0.0", "bar": "^1.0.0""#);
+        let mut project = Project::from_manifest_path(root.join("package.json")).unwrap();
+        let result = dedupe(&config, &mut project, true).await.unwrap();
+
+        assert!(!result.changed);
+        assert_eq!(result.removed, 0);
+        assert!(result.collapsed.is_empty());
+    }
+}
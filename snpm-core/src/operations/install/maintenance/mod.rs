@@ -1,5 +1,10 @@
+mod dedupe;
 mod mutations;
 mod outdated;
 
+pub use dedupe::{DedupeResult, DedupedPackage, dedupe, dedupe_workspace};
+pub(crate) use mutations::{
+    current_spec, is_registry_unresolvable_spec, rewrite_spec_preserving_operator,
+};
 pub use mutations::{remove, upgrade};
-pub use outdated::outdated;
+pub use outdated::{deprecated, outdated};
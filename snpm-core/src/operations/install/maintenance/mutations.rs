@@ -1,9 +1,10 @@
 use crate::console;
 use crate::registry::RegistryProtocol;
-use crate::{Project, Result, SnpmConfig};
+use crate::{Project, Result, SnpmConfig, Workspace};
 
 use std::collections::BTreeMap;
 
+use super::super::effective_config::{ConfigOverrides, effective_config};
 use super::super::manifest::{is_special_protocol_spec, parse_spec};
 use super::super::project_install::install;
 use super::super::utils::{FrozenLockfileMode, InstallOptions};
@@ -181,6 +182,17 @@ async fn upgrade_to_latest(
         packages.iter().map(|spec| parse_spec(spec).0).collect()
     };
 
+    // The trust policy, like the install that follows, takes the
+    // workspace's trustPolicy into account; under no-downgrade it also
+    // makes the fetch below ask for full packuments with publish times.
+    let workspace = Workspace::discover(&project.root)?;
+    let registry_config = effective_config(
+        config,
+        ConfigOverrides {
+            workspace: workspace.as_ref(),
+            ..ConfigOverrides::default()
+        },
+    );
     let client = crate::http::create_client()?;
     let npm = RegistryProtocol::npm();
     let mut manifest = project.manifest.clone();
@@ -193,8 +205,12 @@ async fn upgrade_to_latest(
             continue;
         }
 
-        match crate::registry::fetch_package(config, &client, &name, &npm).await {
+        match crate::registry::fetch_package(&registry_config, &client, &name, &npm).await {
             Ok(package) => {
+                let package = match config.before {
+                    Some(cutoff) => crate::version::published_before(package, cutoff),
+                    None => package,
+                };
                 // Route the dist-tag through select_version so the
                 // configured minimum package age applies here too — writing
                 // a too-young version to the manifest would make the
@@ -204,6 +220,7 @@ async fn upgrade_to_latest(
                     "latest",
                     &package,
                     config.min_package_age_days,
+                    registry_config.no_downgrade_exclusions(),
                     force,
                 ) {
                     Ok(meta) => {
@@ -254,7 +271,10 @@ fn collect_upgradable_names(manifest: &crate::project::Manifest, include_dev: bo
     names
 }
 
-fn current_spec<'a>(manifest: &'a crate::project::Manifest, name: &str) -> Option<&'a str> {
+pub(crate) fn current_spec<'a>(
+    manifest: &'a crate::project::Manifest,
+    name: &str,
+) -> Option<&'a str> {
     manifest
         .dependencies
         .get(name)
@@ -266,7 +286,7 @@ fn current_spec<'a>(manifest: &'a crate::project::Manifest, name: &str) -> Optio
 /// Specs whose target isn't a plain registry range: special protocols
 /// (git/link/workspace/npm-alias/…) plus `file:`, which
 /// `is_special_protocol_spec` doesn't cover.
-fn is_registry_unresolvable_spec(spec: &str) -> bool {
+pub(crate) fn is_registry_unresolvable_spec(spec: &str) -> bool {
     is_special_protocol_spec(spec) || spec.starts_with("file:")
 }
 
@@ -326,7 +346,7 @@ fn update_manifest_entry(
 /// caret (or complex) ranges get the caret default. Blindly writing
 /// `^<wanted>` would silently widen a tilde or exact constraint to all
 /// of the new major.
-fn rewrite_spec_preserving_operator(current: &str, wanted: &str) -> String {
+pub(crate) fn rewrite_spec_preserving_operator(current: &str, wanted: &str) -> String {
     let current = current.trim();
     if let Some(rest) = current.strip_prefix('~')
         && !rest.is_empty()
@@ -373,6 +393,7 @@ async fn reinstall(
             frozen_lockfile,
             strict_no_lockfile,
             force,
+            lockfile_only: false,
             silent_summary: false,
         },
     )
@@ -5,7 +5,7 @@ mod results;
 use crate::console;
 use crate::http;
 use crate::registry::RegistryProtocol;
-use crate::resolve;
+use crate::resolve::{self, ResolutionGraph};
 use crate::{Project, Result, SnpmConfig, SnpmError, Workspace};
 
 use reqwest::Client;
@@ -13,10 +13,23 @@ use std::collections::BTreeMap;
 use std::time::Instant;
 
 use config::{load_catalog, load_overrides};
-use manifest::{build_root_dependencies, build_root_protocols, resolve_manifest_dependencies};
+use manifest::{
+    ResolvedManifestDependencies, build_root_dependencies, build_root_protocols,
+    resolve_manifest_dependencies,
+};
 use results::{build_outdated_entries, read_current_versions};
 
-use super::super::utils::OutdatedEntry;
+use super::super::utils::{DeprecatedPackage, OutdatedEntry, deprecated_packages};
+
+/// The project's manifest resolved fresh from the registry, without the
+/// lockfile: what an install of the current ranges would pick today.
+struct ProjectResolution {
+    workspace: Option<Workspace>,
+    registry_client: Client,
+    manifest: ResolvedManifestDependencies,
+    root_protocols: BTreeMap<String, RegistryProtocol>,
+    graph: ResolutionGraph,
+}
 
 pub async fn outdated(
     config: &SnpmConfig,
@@ -24,48 +37,13 @@ pub async fn outdated(
     include_dev: bool,
     force: bool,
 ) -> Result<Vec<OutdatedEntry>> {
-    let workspace = Workspace::discover(&project.root)?;
-    let registry_client = http::create_client()?;
-    let overrides = load_overrides(project, workspace.as_ref())?;
-    let catalog = load_catalog(project, workspace.as_ref())?;
-    let resolved_manifest =
-        resolve_manifest_dependencies(project, workspace.as_ref(), catalog.as_ref())?;
-
-    let root_dependencies = build_root_dependencies(
-        project,
-        workspace.as_ref(),
-        &resolved_manifest.dependencies,
-        &resolved_manifest.development_dependencies,
-        include_dev,
-    )?;
-    let root_protocols = build_root_protocols(&root_dependencies, &resolved_manifest.protocols);
-
-    console::verbose(&format!(
-        "outdated: resolving {} root deps (include_dev={} force={})",
-        root_dependencies.len(),
-        include_dev,
-        force
-    ));
-
-    let resolve_started = Instant::now();
-    let graph = resolve::resolve(
-        config,
-        &registry_client,
-        &root_dependencies,
-        &root_protocols,
-        config.min_package_age_days,
-        force,
-        Some(&overrides),
-        None,
-        |_package| async { Ok::<(), SnpmError>(()) },
-    )
-    .await?;
-
-    console::verbose(&format!(
-        "outdated: resolve completed in {:.3}s (packages={})",
-        resolve_started.elapsed().as_secs_f64(),
-        graph.packages.len()
-    ));
+    let ProjectResolution {
+        workspace,
+        registry_client,
+        manifest: resolved_manifest,
+        root_protocols,
+        graph,
+    } = resolve_project(config, project, include_dev, force).await?;
 
     let current_versions = read_current_versions(project, workspace.as_ref())?;
     let mut entries = build_outdated_entries(
@@ -117,6 +95,86 @@ pub async fn outdated(
     Ok(entries)
 }
 
+/// Deprecated packages among what the manifest resolves to, direct
+/// dependencies first.
+pub async fn deprecated(
+    config: &SnpmConfig,
+    project: &Project,
+    include_dev: bool,
+    force: bool,
+) -> Result<Vec<DeprecatedPackage>> {
+    let resolution = resolve_project(config, project, include_dev, force).await?;
+    let mut packages = deprecated_packages(&resolution.graph);
+    packages.sort_by(|left, right| {
+        right
+            .direct
+            .cmp(&left.direct)
+            .then_with(|| left.name.cmp(&right.name))
+            .then_with(|| left.version.cmp(&right.version))
+    });
+
+    Ok(packages)
+}
+
+async fn resolve_project(
+    config: &SnpmConfig,
+    project: &Project,
+    include_dev: bool,
+    force: bool,
+) -> Result<ProjectResolution> {
+    let workspace = Workspace::discover(&project.root)?;
+    let registry_client = http::create_client()?;
+    let overrides = load_overrides(project, workspace.as_ref())?;
+    let catalog = load_catalog(project, workspace.as_ref())?;
+    let manifest = resolve_manifest_dependencies(project, workspace.as_ref(), catalog.as_ref())?;
+
+    let root_dependencies = build_root_dependencies(
+        project,
+        workspace.as_ref(),
+        &manifest.dependencies,
+        &manifest.development_dependencies,
+        include_dev,
+    )?;
+    let root_protocols = build_root_protocols(&root_dependencies, &manifest.protocols);
+
+    console::verbose(&format!(
+        "outdated: resolving {} root deps (include_dev={} force={})",
+        root_dependencies.len(),
+        include_dev,
+        force
+    ));
+
+    let resolve_started = Instant::now();
+    let graph = resolve::resolve(
+        config,
+        &registry_client,
+        &root_dependencies,
+        &root_protocols,
+        config.min_package_age_days,
+        force,
+        Some(&overrides),
+        None,
+        None,
+        None,
+        |_package| async { Ok::<(), SnpmError>(()) },
+    )
+    .await?;
+
+    console::verbose(&format!(
+        "outdated: resolve completed in {:.3}s (packages={})",
+        resolve_started.elapsed().as_secs_f64(),
+        graph.packages.len()
+    ));
+
+    Ok(ProjectResolution {
+        workspace,
+        registry_client,
+        manifest,
+        root_protocols,
+        graph,
+    })
+}
+
 fn version_is_newer(candidate: &str, baseline: &str) -> bool {
     match (
         snpm_semver::parse_version(candidate),
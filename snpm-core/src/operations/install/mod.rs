@@ -1,12 +1,25 @@
 mod maintenance;
 mod project_install;
 
+pub(crate) mod effective_config;
 pub mod manifest;
 pub(crate) mod overrides;
+pub(crate) mod package_extensions;
+pub(crate) mod peer_dependency_rules;
+pub(crate) mod resolution_mode;
+pub(crate) mod supported_architectures;
 pub mod utils;
 pub mod workspace;
 
-pub use maintenance::{outdated, remove, upgrade};
+#[cfg(test)]
+pub(crate) mod test_support;
+
+pub use maintenance::{
+    DedupeResult, DedupedPackage, dedupe, dedupe_workspace, deprecated, outdated, remove, upgrade,
+};
+pub(crate) use maintenance::{
+    current_spec, is_registry_unresolvable_spec, rewrite_spec_preserving_operator,
+};
 pub use manifest::*;
 pub use project_install::install;
 pub use utils::*;
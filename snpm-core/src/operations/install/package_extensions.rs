@@ -0,0 +1,171 @@
+//! `packageExtensions` declared by the project or workspace, shared by
+//! every loader.
+//!
+//! Extensions come from `snpm-workspace.yaml` and from `snpm.packageExtensions`
+//! in the root manifest (the workspace root, or the project itself when
+//! there is no workspace). Like overrides, member manifests are ignored.
+
+use crate::resolve::PackageExtensions;
+use crate::resolve::extensions::validate_package_extensions;
+use crate::{Project, Result, SnpmError, Workspace};
+
+/// Collect the extensions that apply when installing `project`, which
+/// are the workspace's when it belongs to one.
+pub(crate) fn load_package_extensions(
+    project: &Project,
+    workspace: Option<&Workspace>,
+) -> Result<PackageExtensions> {
+    if let Some(workspace) = workspace {
+        return load_workspace_package_extensions(workspace);
+    }
+
+    let mut extensions = PackageExtensions::new();
+    merge_manifest_extensions(project, &mut extensions)?;
+    Ok(extensions)
+}
+
+/// `snpm-workspace.yaml` extensions, then the root manifest's. For the
+/// same selector the manifest entry replaces the workspace file's.
+pub(crate) fn load_workspace_package_extensions(
+    workspace: &Workspace,
+) -> Result<PackageExtensions> {
+    let mut extensions = workspace.config.package_extensions.clone();
+
+    if let Some(root_project) = workspace
+        .projects
+        .iter()
+        .find(|project| project.root == workspace.root)
+    {
+        merge_manifest_extensions(root_project, &mut extensions)?;
+    }
+
+    Ok(extensions)
+}
+
+fn merge_manifest_extensions(project: &Project, extensions: &mut PackageExtensions) -> Result<()> {
+    let Some(snpm) = &project.manifest.snpm else {
+        return Ok(());
+    };
+
+    validate_package_extensions(&snpm.package_extensions).map_err(|error| {
+        SnpmError::ManifestInvalid {
+            path: project.manifest_path.clone(),
+            reason: format!("packageExtensions: {error}"),
+        }
+    })?;
+    extensions.extend(snpm.package_extensions.clone());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lockfile::{self, LockPackage, LockRoot, LockRootDependency, Lockfile};
+    use crate::operations::install::install;
+    use crate::operations::install::test_support::{lockfile_only_options, serve_registry};
+    use crate::operations::install::utils::FrozenLockfileMode;
+    use crate::{Project, SnpmConfig};
+
+    use std::collections::BTreeMap;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn changed_package_extensions_force_re_resolution() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("app");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("package.json"),
+            r#"{
+  "name": "app",
+  "version": "1.0.0",
+  "dependencies": { "foo": "^1.0.0" },
+  "snpm": {
+    "packageExtensions": { "foo@1": { "dependencies": { "bar": "^1.0.0" } } }
+  }
+}"#,
+        )
+        .unwrap();
+
+        let registry = serve_registry(&[("foo", ""), ("bar", "")]).await;
+        let config = SnpmConfig {
+            cache_dir: dir.path().join("cache"),
+            data_dir: dir.path().join("data"),
+            default_registry: registry.clone(),
+            registry_concurrency: 4,
+            ..SnpmConfig::for_tests()
+        };
+
+        // Written before the extension existed: foo has no dependencies.
+        let stale = Lockfile {
+            version: 1,
+            settings: Default::default(),
+            root: LockRoot {
+                dependencies: BTreeMap::from([(
+                    "foo".to_string(),
+                    LockRootDependency {
+                        requested: "^1.0.0".to_string(),
+                        package: None,
+                        version: Some("1.0.0".to_string()),
+                        optional: false,
+                    },
+                )]),
+            },
+            importers: BTreeMap::new(),
+            packages: BTreeMap::from([(
+                "foo@1.0.0".to_string(),
+                LockPackage {
+                    name: "foo".to_string(),
+                    version: "1.0.0".to_string(),
+                    tarball: format!("{registry}/foo/-/foo-1.0.0.tgz"),
+                    integrity: None,
+                    dependencies: BTreeMap::new(),
+                    peer_dependencies: BTreeMap::new(),
+                    bundled_dependencies: None,
+                    has_bin: false,
+                    bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
+                },
+            )]),
+        };
+        let lockfile_path = root.join("snpm-lock.yaml");
+        fs::write(&lockfile_path, serde_yaml::to_string(&stale).unwrap()).unwrap();
+
+        let mut project = Project::from_manifest_path(root.join("package.json")).unwrap();
+        let error = install(
+            &config,
+            &mut project,
+            lockfile_only_options(FrozenLockfileMode::Frozen),
+        )
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains("packageExtensions changed"));
+
+        install(
+            &config,
+            &mut project,
+            lockfile_only_options(FrozenLockfileMode::Prefer),
+        )
+        .await
+        .unwrap();
+
+        let written = lockfile::read(&lockfile_path).unwrap();
+        assert_eq!(
+            written.packages["foo@1.0.0"].dependencies["bar"],
+            "bar@1.0.0"
+        );
+        assert!(written.packages.contains_key("bar@1.0.0"));
+        assert!(
+            written
+                .settings
+                .package_extensions_checksum
+                .is_some_and(|checksum| checksum.starts_with("sha256-"))
+        );
+    }
+}
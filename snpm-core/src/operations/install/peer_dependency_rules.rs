@@ -0,0 +1,59 @@
+//! `peerDependencyRules` declared by the project or workspace, shared by
+//! every loader.
+//!
+//! Rules come from `snpm-workspace.yaml` and from `snpm.peerDependencyRules`
+//! in the root manifest (the workspace root, or the project itself when
+//! there is no workspace). Member manifests are ignored.
+
+use crate::resolve::PeerDependencyRules;
+use crate::resolve::peers::validate_peer_dependency_rules;
+use crate::{Project, Result, SnpmError, Workspace};
+
+/// Collect the rules that apply when installing `project`, which are the
+/// workspace's when it belongs to one.
+pub(crate) fn load_peer_dependency_rules(
+    project: &Project,
+    workspace: Option<&Workspace>,
+) -> Result<PeerDependencyRules> {
+    if let Some(workspace) = workspace {
+        return load_workspace_peer_dependency_rules(workspace);
+    }
+
+    let mut rules = PeerDependencyRules::default();
+    merge_manifest_rules(project, &mut rules)?;
+    Ok(rules)
+}
+
+/// `snpm-workspace.yaml` rules, then the root manifest's. Globs from both
+/// apply; for the same `allowedVersions` key the manifest wins.
+pub(crate) fn load_workspace_peer_dependency_rules(
+    workspace: &Workspace,
+) -> Result<PeerDependencyRules> {
+    let mut rules = workspace.config.peer_dependency_rules.clone();
+
+    if let Some(root_project) = workspace
+        .projects
+        .iter()
+        .find(|project| project.root == workspace.root)
+    {
+        merge_manifest_rules(root_project, &mut rules)?;
+    }
+
+    Ok(rules)
+}
+
+fn merge_manifest_rules(project: &Project, rules: &mut PeerDependencyRules) -> Result<()> {
+    let Some(snpm) = &project.manifest.snpm else {
+        return Ok(());
+    };
+
+    validate_peer_dependency_rules(&snpm.peer_dependency_rules).map_err(|error| {
+        SnpmError::ManifestInvalid {
+            path: project.manifest_path.clone(),
+            reason: format!("peerDependencyRules: {error}"),
+        }
+    })?;
+    rules.extend(&snpm.peer_dependency_rules);
+
+    Ok(())
+}
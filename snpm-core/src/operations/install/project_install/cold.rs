@@ -13,6 +13,23 @@ use std::time::Instant;
 use tokio::sync::Semaphore;
 use tokio::task::JoinHandle;
 
+/// Workspace member names mapped to their roots, so `workspace:` specs
+/// resolve to local sources.
+pub(super) fn workspace_sources(plan: &ProjectInstallPlan) -> Option<BTreeMap<String, String>> {
+    plan.workspace.as_ref().map(|workspace| {
+        workspace
+            .projects
+            .iter()
+            .filter_map(|project| {
+                Some((
+                    project.manifest.name.clone()?,
+                    project.root.to_string_lossy().into_owned(),
+                ))
+            })
+            .collect()
+    })
+}
+
 pub(super) async fn resolve_cold_install(
     config: &SnpmConfig,
     registry_client: &reqwest::Client,
@@ -36,18 +53,7 @@ pub(super) async fn resolve_cold_install(
     let client_clone = registry_client.clone();
     let progress_count = Arc::new(AtomicUsize::new(0));
     let progress_total = Arc::new(AtomicUsize::new(root_dependencies.len()));
-    let workspace_sources = plan.workspace.as_ref().map(|workspace| {
-        workspace
-            .projects
-            .iter()
-            .filter_map(|project| {
-                Some((
-                    project.manifest.name.clone()?,
-                    project.root.to_string_lossy().into_owned(),
-                ))
-            })
-            .collect::<BTreeMap<_, _>>()
-    });
+    let workspace_sources = workspace_sources(plan);
 
     let graph = if let Some(seed_graph) = existing_graph {
         resolve::resolve_with_optional_roots_with_seed(
@@ -59,6 +65,8 @@ pub(super) async fn resolve_cold_install(
             config.min_package_age_days,
             force,
             Some(&plan.overrides),
+            Some(&plan.package_extensions),
+            Some(&plan.peer_dependency_rules),
             workspace_sources.as_ref(),
             Some(seed_graph),
             move |package| {
@@ -106,6 +114,8 @@ pub(super) async fn resolve_cold_install(
             config.min_package_age_days,
             force,
             Some(&plan.overrides),
+            Some(&plan.package_extensions),
+            Some(&plan.peer_dependency_rules),
             workspace_sources.as_ref(),
             move |package| {
                 let config = config_clone.clone();
@@ -0,0 +1,95 @@
+use super::cold::workspace_sources;
+use super::plan::prepare_install_plan;
+use crate::console;
+use crate::lockfile;
+use crate::operations::install::effective_config::{ConfigOverrides, effective_config};
+use crate::operations::install::utils::{FrozenLockfileMode, InstallOptions};
+use crate::resolve::{self, ResolutionGraph};
+use crate::{Project, Result, SnpmConfig, SnpmError, http};
+
+/// Re-resolves the project's lockfile preferring versions it already
+/// locks, returning the graph a regular install would lock and the
+/// deduplicated one. The lockfile is rewritten only when `write` is set.
+pub(in crate::operations::install) async fn dedupe_project_lockfile(
+    config: &SnpmConfig,
+    project: &Project,
+    write: bool,
+) -> Result<(ResolutionGraph, ResolutionGraph)> {
+    let options = InstallOptions {
+        requested: Vec::new(),
+        dev: false,
+        include_dev: true,
+        frozen_lockfile: FrozenLockfileMode::Prefer,
+        strict_no_lockfile: false,
+        force: false,
+        lockfile_only: false,
+        silent_summary: true,
+    };
+    let plan = prepare_install_plan(config, project, &options)?;
+    let config = &*effective_config(
+        config,
+        ConfigOverrides {
+            supported_architectures: Some(&plan.supported_architectures),
+            resolution_mode: Some(plan.lock_settings.resolution_mode),
+            workspace: plan.workspace.as_ref(),
+            ..ConfigOverrides::default()
+        },
+    );
+
+    if !plan.lockfile_path.is_file() {
+        return Err(SnpmError::Lockfile {
+            path: plan.lockfile_path,
+            reason: "snpm-lock.yaml is missing. Run `snpm install` first.".into(),
+        });
+    }
+
+    let existing = lockfile::to_graph(&lockfile::read(&plan.lockfile_path)?);
+    let client = http::create_client()?;
+
+    console::step("Resolving dependencies");
+    // Measure dedupe against what a regular install would lock from the
+    // current manifests, so manifest drift is not reported as duplication.
+    let baseline = resolve::resolve_with_optional_roots_with_seed(
+        config,
+        &client,
+        &plan.root_dependencies,
+        &plan.root_protocols,
+        &plan.optional_root_names,
+        config.min_package_age_days,
+        false,
+        Some(&plan.overrides),
+        Some(&plan.package_extensions),
+        Some(&plan.peer_dependency_rules),
+        workspace_sources(&plan).as_ref(),
+        Some(&existing),
+        |_package| async move { Ok(()) },
+    )
+    .await?;
+    let deduped = resolve::resolve_deduped(
+        config,
+        &client,
+        &plan.root_dependencies,
+        &plan.root_protocols,
+        &plan.optional_root_names,
+        config.min_package_age_days,
+        false,
+        Some(&plan.overrides),
+        Some(&plan.package_extensions),
+        Some(&plan.peer_dependency_rules),
+        workspace_sources(&plan).as_ref(),
+        &baseline,
+        |_package| async move { Ok(()) },
+    )
+    .await?;
+
+    if write {
+        lockfile::write(
+            &plan.lockfile_path,
+            &deduped,
+            &plan.root_specs.optional,
+            &plan.lock_settings,
+        )?;
+    }
+
+    Ok((baseline, deduped))
+}
@@ -11,7 +11,8 @@ use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use crate::operations::install::utils::{
-    IntegrityState, build_project_integrity_state, can_any_scripts_run, write_integrity_file,
+    IntegrityState, build_project_integrity_state, can_any_scripts_run, installable_graph,
+    write_integrity_file,
 };
 use crate::operations::install::workspace::link_local_workspace_deps;
 
@@ -25,12 +26,15 @@ pub(super) fn finalize_install(
     precomputed_integrity: Option<IntegrityState>,
 ) -> Result<()> {
     let link_start = Instant::now();
+    // Only linking skips packages for other platforms; the integrity
+    // hash below still covers the whole lockfile graph.
+    let installable = installable_graph(config, graph);
 
     linker::link(
         config,
         plan.workspace.as_ref(),
         project,
-        graph,
+        &installable,
         store_paths,
         include_dev,
     )?;
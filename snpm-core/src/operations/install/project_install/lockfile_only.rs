@@ -0,0 +1,165 @@
+use super::cold::workspace_sources;
+use super::plan::ProjectInstallPlan;
+use super::state::{
+    lock_settings_changed, pinned_root_dependencies_for_fix, read_existing_graph_seed,
+    read_lockfile_for_fix,
+};
+use crate::console;
+use crate::lockfile;
+use crate::operations::install::utils::{
+    FrozenLockfileMode, InstallOptions, ensure_direct_not_deprecated,
+};
+use crate::resolve::{self, ResolutionGraph};
+use crate::{Project, Result, SnpmConfig, http};
+
+/// Resolves the project and writes `snpm-lock.yaml` without downloading
+/// tarballs or touching `node_modules`. The existing lockfile seeds the
+/// resolver, so entries that still satisfy the manifest stay put and
+/// usually need no registry round-trip.
+pub(super) async fn resolve_lockfile_only(
+    config: &SnpmConfig,
+    project: &Project,
+    plan: &ProjectInstallPlan,
+    options: &InstallOptions,
+) -> Result<ResolutionGraph> {
+    let (root_dependencies, seed_graph) = match options.frozen_lockfile {
+        FrozenLockfileMode::No => (plan.root_dependencies.clone(), None),
+        FrozenLockfileMode::Fix => (
+            pinned_root_dependencies_for_fix(plan, config)?,
+            read_lockfile_for_fix(plan, config)
+                .ok()
+                .map(|lockfile| lockfile::to_graph(&lockfile)),
+        ),
+        FrozenLockfileMode::Frozen | FrozenLockfileMode::Prefer => (
+            plan.root_dependencies.clone(),
+            read_existing_graph_seed(config, project, plan),
+        ),
+    };
+    let seed_graph =
+        seed_graph.filter(|_| !lock_settings_changed(plan, options.frozen_lockfile, None));
+
+    console::step("Resolving dependencies");
+    let client = http::create_client()?;
+    let graph = resolve::resolve_with_optional_roots_with_seed(
+        config,
+        &client,
+        &root_dependencies,
+        &plan.root_protocols,
+        &plan.optional_root_names,
+        config.min_package_age_days,
+        options.force,
+        Some(&plan.overrides),
+        Some(&plan.package_extensions),
+        Some(&plan.peer_dependency_rules),
+        workspace_sources(plan).as_ref(),
+        seed_graph.as_ref(),
+        |_package| async move { Ok(()) },
+    )
+    .await?;
+    ensure_direct_not_deprecated(config, &graph)?;
+
+    lockfile::write(
+        &plan.lockfile_path,
+        &graph,
+        &plan.root_specs.optional,
+        &plan.lock_settings,
+    )?;
+    console::step("Saved lockfile");
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lockfile::{self, LockPackage, LockRoot, LockRootDependency, Lockfile};
+    use crate::operations::install::install;
+    use crate::operations::install::utils::{FrozenLockfileMode, InstallOptions};
+    use crate::{Project, SnpmConfig};
+
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn lockfile_only_writes_lockfile_without_touching_node_modules() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join("package.json"),
+            r#"{ "name": "app", "version": "1.0.0", "dependencies": { "foo": "^1.0.0" } }"#,
+        )
+        .unwrap();
+
+        let existing = Lockfile {
+            version: 1,
+            settings: Default::default(),
+            root: LockRoot {
+                dependencies: BTreeMap::from([(
+                    "foo".to_string(),
+                    LockRootDependency {
+                        requested: "^1.0.0".to_string(),
+                        package: None,
+                        version: Some("1.2.3".to_string()),
+                        optional: false,
+                    },
+                )]),
+            },
+            importers: BTreeMap::new(),
+            packages: BTreeMap::from([(
+                "foo@1.2.3".to_string(),
+                LockPackage {
+                    name: "foo".to_string(),
+                    version: "1.2.3".to_string(),
+                    tarball: "https://registry.example/foo-1.2.3.tgz".to_string(),
+                    integrity: None,
+                    dependencies: BTreeMap::new(),
+                    peer_dependencies: BTreeMap::new(),
+                    bundled_dependencies: None,
+                    has_bin: false,
+                    bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
+                },
+            )]),
+        };
+        fs::write(
+            root.join("snpm-lock.yaml"),
+            serde_yaml::to_string(&existing).unwrap(),
+        )
+        .unwrap();
+
+        let data = tempdir().unwrap();
+        let mut config = SnpmConfig::for_tests();
+        config.data_dir = data.path().join("data");
+        config.cache_dir = data.path().join("cache");
+
+        let mut project = Project::from_manifest_path(root.join("package.json")).unwrap();
+        let options = InstallOptions {
+            requested: Vec::new(),
+            dev: false,
+            include_dev: true,
+            frozen_lockfile: FrozenLockfileMode::Prefer,
+            strict_no_lockfile: false,
+            force: false,
+            lockfile_only: true,
+            silent_summary: true,
+        };
+
+        let result = install(&config, &mut project, options).await.unwrap();
+
+        assert_eq!(result.package_count, 1);
+        assert!(!root.join("node_modules").exists());
+        assert!(!config.data_dir.exists());
+
+        let written = lockfile::read(&root.join("snpm-lock.yaml")).unwrap();
+        assert_eq!(
+            written.root.dependencies["foo"].version.as_deref(),
+            Some("1.2.3")
+        );
+    }
+}
@@ -1,5 +1,7 @@
 mod cold;
+mod dedupe;
 mod finalize;
+mod lockfile_only;
 mod patches;
 mod plan;
 mod report;
@@ -10,10 +12,16 @@ use crate::{Project, Result, SnpmConfig};
 
 use std::time::Instant;
 
+use super::effective_config::{ConfigOverrides, effective_config};
 use super::manifest::build_project_root_specs;
 use super::manifest::write_manifest;
-use super::utils::{InstallOptions, InstallResult, InstallScenario};
+use super::utils::{
+    InstallOptions, InstallResult, InstallScenario, load_engine_targets, print_deprecations,
+    reconcile_lockfile_conflicts, record_lockfile_only_project, record_store_project,
+};
+pub(super) use dedupe::dedupe_project_lockfile;
 use finalize::{finalize_install, run_install_scripts};
+use lockfile_only::resolve_lockfile_only;
 use plan::{prepare_install_plan, validate_frozen_lockfile};
 use report::print_install_changes;
 use state::resolve_install_state;
@@ -35,7 +43,17 @@ pub async fn install(
         options.force,
     ));
 
-    let plan = prepare_install_plan(project, &options)?;
+    let plan = prepare_install_plan(config, project, &options)?;
+    let engine_targets = load_engine_targets(config, &project.root).await;
+    let config = &*effective_config(
+        config,
+        ConfigOverrides {
+            supported_architectures: Some(&plan.supported_architectures),
+            resolution_mode: Some(plan.lock_settings.resolution_mode),
+            workspace: plan.workspace.as_ref(),
+            engine_targets: Some(&engine_targets),
+        },
+    );
 
     console::verbose(&format!(
         "workspace_root={} overrides={} catalog_local={}",
@@ -60,8 +78,56 @@ pub async fn install(
         plan.is_fresh_install
     ));
 
+    reconcile_lockfile_conflicts(
+        &plan.lockfile_path,
+        &plan.root_specs.required,
+        &plan.root_specs.optional,
+        options.frozen_lockfile,
+    )?;
     validate_frozen_lockfile(config, &options, &plan)?;
 
+    if options.lockfile_only {
+        let installed_lockfile_hash = crate::store::lockfile_hash(&plan.lockfile_path);
+        let graph = resolve_lockfile_only(config, project, &plan, &options).await?;
+        write_manifest(
+            project,
+            &graph,
+            &plan.additions,
+            options.dev,
+            plan.workspace.as_ref(),
+            plan.catalog.as_ref(),
+            config.effective_save_prefix(),
+        )?;
+        // Nothing is linked, but a later prune must not free what an
+        // earlier install of this project linked.
+        let store_root = plan.store_root(project);
+        if store_root.join("node_modules").is_dir() {
+            record_lockfile_only_project(
+                config,
+                store_root,
+                &plan.lockfile_path,
+                installed_lockfile_hash,
+            );
+        }
+
+        console::clear_steps(2);
+        print_install_changes(&graph, &plan, &options);
+
+        let package_count = graph.packages.len();
+        if !options.silent_summary {
+            console::info(&format!(
+                "Updated {} ({package_count} packages) without installing",
+                plan.lockfile_path.display()
+            ));
+        }
+        print_deprecations(&graph);
+
+        return Ok(InstallResult {
+            package_count,
+            elapsed_seconds: started.elapsed().as_secs_f32(),
+        });
+    }
+
     let resolved = resolve_install_state(config, project, &plan, &options).await?;
     if let Err(error) = crate::store::persist_store_residency_index(config, &resolved.store_paths) {
         console::verbose(&format!("failed to persist store residency index: {error}"));
@@ -131,11 +197,14 @@ pub async fn install(
             &lockfile_source_path,
             &current_root_specs.required,
             &current_root_specs.optional,
+            &plan.lock_settings,
             &resolved.graph,
             options.include_dev,
         )?;
     }
 
+    record_store_project(config, plan.store_root(project), &plan.lockfile_path);
+
     console::clear_steps(step_count_for_install(
         resolved.scenario,
         resolved.wrote_lockfile,
@@ -149,6 +218,12 @@ pub async fn install(
         console::summary(package_count, elapsed_seconds);
     }
 
+    // A hot install changed nothing, so it does not repeat the summary the
+    // install that produced this tree already printed.
+    if !early_exit {
+        print_deprecations(&resolved.graph);
+    }
+
     console::verbose(&format!(
         "install completed in {:.3}s (packages={} store_paths={} additions={} is_fresh_install={} blocked_scripts={})",
         elapsed_seconds,
@@ -5,6 +5,7 @@ use crate::{Result, SnpmConfig, SnpmError};
 
 use super::super::super::utils::FrozenLockfileMode;
 use super::super::super::utils::InstallOptions;
+use super::super::super::utils::{diff_lock_settings, ensure_lock_settings_unchanged};
 
 pub(in crate::operations::install::project_install) fn validate_frozen_lockfile(
     config: &SnpmConfig,
@@ -49,7 +50,10 @@ pub(in crate::operations::install::project_install) fn validate_frozen_lockfile(
         });
     }
 
-    Ok(())
+    ensure_lock_settings_unchanged(
+        &plan.lockfile_path,
+        diff_lock_settings(&existing.settings, &plan.lock_settings),
+    )
 }
 
 fn read_frozen_lockfile(
@@ -1,15 +1,23 @@
-use crate::{Project, Result, Workspace};
+use crate::{Project, Result, SnpmConfig, Workspace};
 
-use super::super::super::utils::InstallOptions;
+use super::super::super::utils::{InstallOptions, before_setting};
 use super::config::{load_catalog, load_overrides};
 use super::manifest::{
     build_root_protocols, build_root_specs, collect_additions, merge_root_dependencies,
     resolve_manifest_specs,
 };
 use super::types::ProjectInstallPlan;
-use crate::lockfile;
+use crate::lockfile::{self, LockSettings};
+use crate::operations::install::package_extensions::load_package_extensions;
+use crate::operations::install::peer_dependency_rules::load_peer_dependency_rules;
+use crate::operations::install::resolution_mode::load_resolution_mode;
+use crate::operations::install::supported_architectures::{
+    configured_supported_architectures, load_supported_architectures,
+};
+use crate::resolve::extensions::package_extensions_checksum;
 
 pub(in crate::operations::install::project_install) fn prepare_install_plan(
+    config: &SnpmConfig,
     project: &Project,
     options: &InstallOptions,
 ) -> Result<ProjectInstallPlan> {
@@ -17,6 +25,15 @@ pub(in crate::operations::install::project_install) fn prepare_install_plan(
     let workspace = Workspace::discover(&project.root)?;
     let catalog = load_catalog(project, workspace.as_ref())?;
     let overrides = load_overrides(project, workspace.as_ref())?;
+    let package_extensions = load_package_extensions(project, workspace.as_ref())?;
+    let peer_dependency_rules = load_peer_dependency_rules(project, workspace.as_ref())?;
+    let supported_architectures = load_supported_architectures(config, project, workspace.as_ref());
+    let lock_settings = LockSettings {
+        package_extensions_checksum: package_extensions_checksum(&package_extensions),
+        supported_architectures: configured_supported_architectures(project, workspace.as_ref()),
+        resolution_mode: load_resolution_mode(config, workspace.as_ref()),
+        before: before_setting(config),
+    };
 
     let resolved_manifest = resolve_manifest_specs(project, workspace.as_ref(), catalog.as_ref())?;
     let root_specs = build_root_specs(
@@ -53,6 +70,10 @@ pub(in crate::operations::install::project_install) fn prepare_install_plan(
         workspace,
         catalog,
         overrides,
+        package_extensions,
+        lock_settings,
+        peer_dependency_rules,
+        supported_architectures,
         additions,
         local_deps: resolved_manifest.local_deps,
         local_dev_deps: resolved_manifest.local_dev_deps,
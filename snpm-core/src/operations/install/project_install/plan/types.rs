@@ -1,14 +1,24 @@
+use crate::lockfile::LockSettings;
+use crate::platform::SupportedArchitectures;
 use crate::registry::RegistryProtocol;
+use crate::resolve::{PackageExtensions, PeerDependencyRules};
 use crate::workspace::CatalogConfig;
-use crate::{Workspace, lockfile::CompatibleLockfile, operations::install::manifest::RootSpecSet};
+use crate::{
+    Project, Workspace, lockfile::CompatibleLockfile, operations::install::manifest::RootSpecSet,
+};
 
 use std::collections::{BTreeMap, BTreeSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub(in crate::operations::install::project_install) struct ProjectInstallPlan {
     pub(in crate::operations::install::project_install) workspace: Option<Workspace>,
     pub(in crate::operations::install::project_install) catalog: Option<CatalogConfig>,
     pub(in crate::operations::install::project_install) overrides: BTreeMap<String, String>,
+    pub(in crate::operations::install::project_install) package_extensions: PackageExtensions,
+    pub(in crate::operations::install::project_install) lock_settings: LockSettings,
+    pub(in crate::operations::install::project_install) peer_dependency_rules: PeerDependencyRules,
+    pub(in crate::operations::install::project_install) supported_architectures:
+        SupportedArchitectures,
     pub(in crate::operations::install::project_install) additions: BTreeMap<String, String>,
     pub(in crate::operations::install::project_install) local_deps: BTreeSet<String>,
     pub(in crate::operations::install::project_install) local_dev_deps: BTreeSet<String>,
@@ -33,6 +43,17 @@ impl ProjectInstallPlan {
             .unwrap_or_else(|| "<none>".to_string())
     }
 
+    /// The directory recorded in the store's project registry: the
+    /// workspace root when `project` belongs to one.
+    pub(in crate::operations::install::project_install) fn store_root<'a>(
+        &'a self,
+        project: &'a Project,
+    ) -> &'a Path {
+        self.workspace
+            .as_ref()
+            .map_or(project.root.as_path(), |workspace| workspace.root.as_path())
+    }
+
     pub(in crate::operations::install::project_install) fn lockfile_source_label(&self) -> String {
         if self.lockfile_path.is_file() {
             return self.lockfile_path.display().to_string();
@@ -4,6 +4,7 @@ use crate::SnpmError;
 use crate::console;
 use crate::lockfile;
 use crate::operations::install::utils::FrozenLockfileMode;
+use crate::resolve::query::override_excludes_version;
 use crate::resolve::{PackageId, ResolutionGraph};
 use crate::{Project, Result, SnpmConfig};
 
@@ -12,8 +13,9 @@ use std::path::PathBuf;
 use std::time::Instant;
 
 use crate::operations::install::utils::{
-    InstallOptions, InstallScenario, IntegrityState, ScenarioResult, detect_install_scenario,
-    load_graph_snapshot, materialize_missing_packages, validate_graph_min_package_age,
+    InstallOptions, InstallScenario, IntegrityState, ScenarioResult, changed_resolve_settings,
+    check_engines, detect_install_scenario, ensure_direct_not_deprecated, load_graph_snapshot,
+    materialize_missing_packages, validate_graph_min_package_age, verify_graph_signatures,
 };
 
 pub(super) struct ResolvedInstall {
@@ -31,14 +33,14 @@ pub(super) async fn resolve_install_state(
     plan: &ProjectInstallPlan,
     options: &InstallOptions,
 ) -> Result<ResolvedInstall> {
+    crate::store::ensure_store_layout(config).await?;
+
     let planned_root_dependencies = if matches!(options.frozen_lockfile, FrozenLockfileMode::Fix) {
         pinned_root_dependencies_for_fix(plan, config)?
     } else {
         plan.root_dependencies.clone()
     };
 
-    let explicit_lockfile_seed = explicit_lockfile_seed(config, project, plan, options);
-
     let scenario_result = if options.include_dev
         && plan.additions.is_empty()
         && !matches!(
@@ -60,6 +62,25 @@ pub(super) async fn resolve_install_state(
         ScenarioResult::cold()
     };
 
+    // A graph resolved under other settings (say, an edited
+    // packageExtensions) can neither be installed as-is nor seed the
+    // resolver, or its stale dependency lists would survive. The hash the
+    // scenario recorded answers this on a Hot install without reading the
+    // lockfile.
+    let settings_changed = lock_settings_changed(
+        plan,
+        options.frozen_lockfile,
+        scenario_result.lock_settings_hash.as_deref(),
+    );
+    let (scenario_result, explicit_lockfile_seed) = if settings_changed {
+        (ScenarioResult::cold(), None)
+    } else {
+        (
+            scenario_result,
+            explicit_lockfile_seed(config, project, plan, options),
+        )
+    };
+
     let ScenarioResult {
         cache_check,
         graph,
@@ -74,9 +95,11 @@ pub(super) async fn resolve_install_state(
     // Building a reqwest client sets up rustls + a DNS resolver, which is
     // a few milliseconds we don't want on the common Hot no-op that never
     // touches the network. Create it only for scenarios that actually
-    // need it: an age check, or any non-Hot (cache/download/resolve) path.
-    let needs_network =
-        config.min_package_age_days.is_some() || !matches!(scenario, InstallScenario::Hot);
+    // need it: an age or signature check, or any non-Hot
+    // (cache/download/resolve) path.
+    let needs_network = config.min_package_age_days.is_some()
+        || config.verify_signatures
+        || !matches!(scenario, InstallScenario::Hot);
     let registry_client = if needs_network {
         Some(crate::http::create_client()?)
     } else {
@@ -85,7 +108,7 @@ pub(super) async fn resolve_install_state(
     let client = || {
         registry_client
             .as_ref()
-            .expect("registry client is initialized for age-checks and non-hot installs")
+            .expect("registry client is initialized for registry checks and non-hot installs")
     };
 
     // The age check only reaches the network when a minimum age is
@@ -164,22 +187,28 @@ pub(super) async fn resolve_install_state(
 
             store_paths = resolved_store_paths;
 
-            if options.include_dev {
-                lockfile::write(&plan.lockfile_path, &graph, &plan.root_specs.optional)?;
-                wrote_lockfile = true;
-            }
-
             console::step_with_count("Resolved, downloaded and extracted", store_paths.len());
             graph
         }
     };
 
-    if options.include_dev
-        && !wrote_lockfile
-        && plan.compatible_lockfile.is_some()
-        && !plan.lockfile_path.is_file()
-    {
-        lockfile::write(&plan.lockfile_path, &graph, &plan.root_specs.optional)?;
+    // Checked before the lockfile is saved, so a failing install leaves
+    // the lockfile in step with package.json.
+    ensure_direct_not_deprecated(config, &graph)?;
+    check_engines(config, &graph)?;
+    if config.verify_signatures {
+        verify_graph_signatures(config, client(), &graph).await?;
+    }
+
+    let needs_lockfile = matches!(scenario, InstallScenario::Cold)
+        || (plan.compatible_lockfile.is_some() && !plan.lockfile_path.is_file());
+    if options.include_dev && needs_lockfile {
+        lockfile::write(
+            &plan.lockfile_path,
+            &graph,
+            &plan.root_specs.optional,
+            &plan.lock_settings,
+        )?;
         wrote_lockfile = true;
     }
 
@@ -197,7 +226,7 @@ pub(super) async fn resolve_install_state(
     })
 }
 
-fn pinned_root_dependencies_for_fix(
+pub(super) fn pinned_root_dependencies_for_fix(
     plan: &ProjectInstallPlan,
     config: &SnpmConfig,
 ) -> Result<BTreeMap<String, String>> {
@@ -216,7 +245,9 @@ fn pinned_root_dependencies_for_fix(
             continue;
         }
 
-        if let Some(version) = dep.version.as_ref() {
+        if let Some(version) = dep.version.as_ref()
+            && !override_excludes_version(name, version, Some(&plan.overrides))
+        {
             root_dependencies.insert(name.clone(), version.clone());
         }
     }
@@ -230,7 +261,9 @@ fn pinned_root_dependencies_for_fix(
             continue;
         }
 
-        if let Some(version) = dep.version.as_ref() {
+        if let Some(version) = dep.version.as_ref()
+            && !override_excludes_version(name, version, Some(&plan.overrides))
+        {
             root_dependencies.insert(name.clone(), version.clone());
         }
     }
@@ -238,6 +271,38 @@ fn pinned_root_dependencies_for_fix(
     Ok(root_dependencies)
 }
 
+/// Whether the lockfile on disk was resolved under settings other than
+/// the plan's. `recorded` is the settings hash stored with the graph the
+/// scenario picked, if any. Logs which settings changed so `--verbose`
+/// explains the re-resolve. A frozen install never re-resolves:
+/// `validate_frozen_lockfile` already rejected changed settings from the
+/// lockfile it read.
+pub(super) fn lock_settings_changed(
+    plan: &ProjectInstallPlan,
+    frozen_lockfile: FrozenLockfileMode,
+    recorded: Option<&str>,
+) -> bool {
+    if plan.is_fresh_install || matches!(frozen_lockfile, FrozenLockfileMode::Frozen) {
+        return false;
+    }
+
+    let changed = changed_resolve_settings(
+        &plan.lockfile_path,
+        recorded,
+        &plan.lock_settings,
+        &plan.supported_architectures,
+    );
+    if changed.is_empty() {
+        return false;
+    }
+
+    console::verbose(&format!(
+        "{} changed since the lockfile was written; re-resolving",
+        changed.join(", ")
+    ));
+    true
+}
+
 fn explicit_lockfile_seed(
     config: &SnpmConfig,
     project: &Project,
@@ -257,7 +322,7 @@ fn explicit_lockfile_seed(
     read_existing_graph_seed(config, project, plan)
 }
 
-fn read_existing_graph_seed(
+pub(super) fn read_existing_graph_seed(
     config: &SnpmConfig,
     project: &Project,
     plan: &ProjectInstallPlan,
@@ -278,7 +343,7 @@ fn read_existing_graph_seed(
         .ok()
 }
 
-fn read_lockfile_for_fix(
+pub(super) fn read_lockfile_for_fix(
     plan: &ProjectInstallPlan,
     config: &SnpmConfig,
 ) -> Result<crate::lockfile::Lockfile> {
@@ -355,6 +420,7 @@ mod tests {
             frozen_lockfile,
             strict_no_lockfile: false,
             force: false,
+            lockfile_only: false,
             silent_summary: false,
         }
     }
@@ -371,6 +437,10 @@ mod tests {
             workspace: None,
             catalog: None,
             overrides: BTreeMap::new(),
+            package_extensions: BTreeMap::new(),
+            lock_settings: Default::default(),
+            peer_dependency_rules: Default::default(),
+            supported_architectures: Default::default(),
             additions: BTreeMap::new(),
             local_deps: BTreeSet::new(),
             local_dev_deps: BTreeSet::new(),
@@ -389,6 +459,7 @@ mod tests {
     fn write_lockfile(path: &std::path::Path, deps: Vec<LockfileDep<'_>>) {
         let lockfile = Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: deps
                     .into_iter()
@@ -405,6 +476,7 @@ mod tests {
                     })
                     .collect(),
             },
+            importers: BTreeMap::new(),
             packages: BTreeMap::new(),
         };
 
@@ -0,0 +1,18 @@
+//! `resolutionMode`: which matching version of a range the resolver picks.
+//!
+//! `resolutionMode` in `snpm-workspace.yaml` wins over `resolution-mode`
+//! from `.snpmrc` or the environment (already in `config`), the same way
+//! the workspace's `hoisting` does.
+
+use crate::config::ResolutionMode;
+use crate::{SnpmConfig, Workspace};
+
+/// The mode that applies when installing with `workspace`, if any.
+pub(crate) fn load_resolution_mode(
+    config: &SnpmConfig,
+    workspace: Option<&Workspace>,
+) -> ResolutionMode {
+    workspace
+        .and_then(|workspace| workspace.config.resolution_mode)
+        .unwrap_or(config.resolution_mode)
+}
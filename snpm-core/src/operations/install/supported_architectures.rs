@@ -0,0 +1,164 @@
+//! `supportedArchitectures`: the platforms whose optional packages are
+//! resolved besides the host's.
+//!
+//! Targets come from `snpm-workspace.yaml` and from
+//! `snpm.supportedArchitectures` in the root manifest (the workspace root,
+//! or the project itself when there is no workspace). `--os`, `--cpu` and
+//! `--libc` (already in `config`) replace the matching list for the run,
+//! but are not recorded in the lockfile.
+
+use crate::platform::SupportedArchitectures;
+use crate::{Project, SnpmConfig, Workspace};
+
+/// Targets that apply when installing `project`, which are the
+/// workspace's when it belongs to one.
+pub(crate) fn load_supported_architectures(
+    config: &SnpmConfig,
+    project: &Project,
+    workspace: Option<&Workspace>,
+) -> SupportedArchitectures {
+    config
+        .supported_architectures
+        .clone()
+        .or(&configured_supported_architectures(project, workspace))
+}
+
+/// Command-line targets, then the root manifest's, then
+/// `snpm-workspace.yaml`'s, list by list.
+pub(crate) fn load_workspace_supported_architectures(
+    config: &SnpmConfig,
+    workspace: &Workspace,
+) -> SupportedArchitectures {
+    config
+        .supported_architectures
+        .clone()
+        .or(&configured_workspace_supported_architectures(workspace))
+}
+
+/// The targets the project or workspace configures, leaving out
+/// `--os`, `--cpu` and `--libc`. This is what the lockfile records, so a
+/// one-off cross-platform install does not change its settings.
+pub(crate) fn configured_supported_architectures(
+    project: &Project,
+    workspace: Option<&Workspace>,
+) -> SupportedArchitectures {
+    match workspace {
+        Some(workspace) => configured_workspace_supported_architectures(workspace),
+        None => manifest_targets(project),
+    }
+}
+
+pub(crate) fn configured_workspace_supported_architectures(
+    workspace: &Workspace,
+) -> SupportedArchitectures {
+    let manifest = workspace
+        .projects
+        .iter()
+        .find(|project| project.root == workspace.root)
+        .map(manifest_targets)
+        .unwrap_or_default();
+
+    manifest.or(&workspace.config.supported_architectures)
+}
+
+fn manifest_targets(project: &Project) -> SupportedArchitectures {
+    project
+        .manifest
+        .snpm
+        .as_ref()
+        .map(|snpm| snpm.supported_architectures.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lockfile;
+    use crate::operations::install::install;
+    use crate::operations::install::test_support::{lockfile_only_options, serve_registry};
+    use crate::operations::install::utils::FrozenLockfileMode;
+    use crate::platform::SupportedArchitectures;
+    use crate::{Project, SnpmConfig};
+
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn supported_architectures_lock_optional_packages_for_other_platforms() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("app");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("package.json"),
+            r#"{ "name": "app", "version": "1.0.0", "dependencies": { "native": "^1.0.0" } }"#,
+        )
+        .unwrap();
+
+        let host = SnpmConfig {
+            cache_dir: dir.path().join("cache"),
+            data_dir: dir.path().join("data"),
+            default_registry: serve_registry(&[
+                ("native", r#""optionalDependencies":{"native-aix":"1.0.0"}"#),
+                ("native-aix", r#""os":["aix"]"#),
+            ])
+            .await,
+            registry_concurrency: 4,
+            ..SnpmConfig::for_tests()
+        };
+        let cross = SnpmConfig {
+            supported_architectures: SupportedArchitectures {
+                os: vec!["current".to_string(), "aix".to_string()],
+                ..SupportedArchitectures::default()
+            },
+            ..host.clone()
+        };
+        let lockfile_path = root.join("snpm-lock.yaml");
+        let mut project = Project::from_manifest_path(root.join("package.json")).unwrap();
+
+        install(
+            &host,
+            &mut project,
+            lockfile_only_options(FrozenLockfileMode::No),
+        )
+        .await
+        .unwrap();
+        let written = lockfile::read(&lockfile_path).unwrap();
+        assert!(!written.packages.contains_key("native-aix@1.0.0"));
+
+        install(
+            &cross,
+            &mut project,
+            lockfile_only_options(FrozenLockfileMode::Prefer),
+        )
+        .await
+        .unwrap();
+        let written = lockfile::read(&lockfile_path).unwrap();
+        assert_eq!(written.packages["native-aix@1.0.0"].os, ["aix"]);
+        // Command-line targets are a one-off and stay out of the settings.
+        assert!(written.settings.supported_architectures.is_empty());
+        install(
+            &host,
+            &mut project,
+            lockfile_only_options(FrozenLockfileMode::Frozen),
+        )
+        .await
+        .unwrap();
+
+        fs::write(
+            root.join("package.json"),
+            r#"{ "name": "app", "version": "1.0.0", "dependencies": { "native": "^1.0.0" }, "snpm": { "supportedArchitectures": { "os": ["current", "aix"] } } }"#,
+        )
+        .unwrap();
+        let mut project = Project::from_manifest_path(root.join("package.json")).unwrap();
+        let error = install(
+            &host,
+            &mut project,
+            lockfile_only_options(FrozenLockfileMode::Frozen),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("supportedArchitectures changed"),
+            "{error}"
+        );
+    }
+}
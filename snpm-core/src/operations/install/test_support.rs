@@ -0,0 +1,65 @@
+//! Fixtures shared by the install tests: a throwaway registry and the
+//! options for a lockfile-only install.
+
+use super::{FrozenLockfileMode, InstallOptions};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Serves `1.0.0` of each `(name, fields)` package until the test ends and
+/// returns the registry URL. `fields` are extra JSON members of the version,
+/// e.g. `"deprecated":"use bar"`; anything else gets a 404.
+pub(crate) async fn serve_registry(packages: &'static [(&'static str, &'static str)]) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                return;
+            };
+            let mut request = vec![0; 2048];
+            let read = socket.read(&mut request).await.unwrap();
+            let request = String::from_utf8_lossy(&request[..read]);
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let package = packages
+                .iter()
+                .find(|(name, _)| path.strip_prefix('/') == Some(*name));
+
+            let response = match package {
+                Some((name, fields)) => {
+                    let fields = if fields.is_empty() {
+                        String::new()
+                    } else {
+                        format!(",{fields}")
+                    };
+                    let body = format!(
+                        r#"{{"versions":{{"1.0.0":{{"version":"1.0.0"{fields},"dist":{{"tarball":"http://{addr}/{name}/-/{name}-1.0.0.tgz"}}}}}},"dist-tags":{{"latest":"1.0.0"}}}}"#
+                    );
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                }
+                None => "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                    .to_string(),
+            };
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    format!("http://{addr}")
+}
+
+/// Options for `snpm install --lockfile-only` with the given frozen mode.
+pub(crate) fn lockfile_only_options(frozen_lockfile: FrozenLockfileMode) -> InstallOptions {
+    InstallOptions {
+        requested: Vec::new(),
+        dev: false,
+        include_dev: true,
+        frozen_lockfile,
+        strict_no_lockfile: false,
+        force: false,
+        lockfile_only: true,
+        silent_summary: true,
+    }
+}
@@ -1,4 +1,3 @@
-use crate::config::OfflineMode;
 use crate::registry::RegistryProtocol;
 use crate::resolve::{ResolutionGraph, ResolvedPackage};
 use crate::version::version_age_days;
@@ -64,7 +63,7 @@ async fn validate_package_versions_min_age(
         client,
         name,
         protocol,
-        OfflineMode::Online,
+        config.offline_mode,
     )
     .await?;
 
@@ -96,11 +95,7 @@ fn should_check_registry_age(package: &ResolvedPackage) -> bool {
 }
 
 fn age_check_protocol(package: &ResolvedPackage) -> RegistryProtocol {
-    if package.tarball.contains("npm.jsr.io/") {
-        RegistryProtocol::jsr()
-    } else {
-        RegistryProtocol::npm()
-    }
+    crate::protocols::jsr::tarball_protocol(&package.tarball)
 }
 
 #[cfg(test)]
@@ -130,6 +125,12 @@ mod tests {
             bundled_dependencies: None,
             has_bin: false,
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         }
     }
 
@@ -198,6 +199,12 @@ mod tests {
                     bundled_dependencies: None,
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             )]),
         }
@@ -0,0 +1,73 @@
+use super::FrozenLockfileMode;
+use crate::Result;
+use crate::console;
+use crate::lockfile::{self, LockRootDependency, ReconciledDependency, Reconciliation};
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Reconciles git merge conflict markers left in `snpm-lock.yaml` before
+/// install reads it, and prints what was decided. A frozen install never
+/// rewrites the lockfile, so it leaves the markers for `read` to report.
+pub(crate) fn reconcile_lockfile_conflicts(
+    lockfile_path: &Path,
+    required_root: &BTreeMap<String, String>,
+    optional_root: &BTreeMap<String, String>,
+    frozen_lockfile: FrozenLockfileMode,
+) -> Result<()> {
+    if matches!(frozen_lockfile, FrozenLockfileMode::Frozen) {
+        return Ok(());
+    }
+
+    let mut root_specs = required_root.clone();
+    root_specs.extend(
+        optional_root
+            .iter()
+            .map(|(name, spec)| (name.clone(), spec.clone())),
+    );
+
+    let Some(resolution) = lockfile::resolve_conflicts(lockfile_path, &root_specs)? else {
+        return Ok(());
+    };
+
+    console::info(&format!(
+        "Resolved merge conflicts in {}",
+        lockfile_path.display()
+    ));
+    for dependency in &resolution.reconciled {
+        console::info(&format!("  {}", describe(dependency)));
+    }
+
+    Ok(())
+}
+
+fn describe(dependency: &ReconciledDependency) -> String {
+    let name = &dependency.name;
+    match dependency.outcome {
+        Reconciliation::KeptOurs => format!(
+            "{name}: kept {} (ours) over {} (theirs)",
+            locked(dependency.ours.as_ref()),
+            locked(dependency.theirs.as_ref())
+        ),
+        Reconciliation::KeptTheirs => format!(
+            "{name}: kept {} (theirs) over {} (ours)",
+            locked(dependency.theirs.as_ref()),
+            locked(dependency.ours.as_ref())
+        ),
+        Reconciliation::Reresolve => format!(
+            "{name}: re-resolving, package.json no longer matches {} (ours) or {} (theirs)",
+            locked(dependency.ours.as_ref()),
+            locked(dependency.theirs.as_ref())
+        ),
+    }
+}
+
+fn locked(dependency: Option<&LockRootDependency>) -> String {
+    match dependency {
+        Some(dependency) => match &dependency.version {
+            Some(version) => format!("{} -> {version}", dependency.requested),
+            None => dependency.requested.clone(),
+        },
+        None => "nothing".to_string(),
+    }
+}
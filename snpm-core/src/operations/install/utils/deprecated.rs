@@ -0,0 +1,264 @@
+use crate::console;
+use crate::operations::why::{DependencyPaths, WhyHop, WhyPath};
+use crate::resolve::{PackageId, ResolutionGraph};
+use crate::{Result, SnpmConfig, SnpmError};
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A resolved package whose registry metadata marks it deprecated.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeprecatedPackage {
+    pub name: String,
+    pub version: String,
+    pub message: String,
+    /// Whether a manifest depends on this version directly.
+    pub direct: bool,
+    /// How it got installed, e.g. `rimraf@3.0.2 > glob@7.2.3`: the
+    /// shortest path from a direct dependency in the why index.
+    pub path: String,
+}
+
+pub(crate) fn deprecated_packages(graph: &ResolutionGraph) -> Vec<DeprecatedPackage> {
+    let deprecated: Vec<(&PackageId, &String)> = graph
+        .packages
+        .values()
+        .filter_map(|package| {
+            package
+                .deprecated
+                .as_ref()
+                .map(|message| (&package.id, message))
+        })
+        .collect();
+
+    if deprecated.is_empty() {
+        return Vec::new();
+    }
+
+    let direct: BTreeSet<&PackageId> = graph
+        .root
+        .dependencies
+        .values()
+        .map(|dep| &dep.resolved)
+        .collect();
+    let paths = DependencyPaths::new(graph);
+
+    deprecated
+        .into_iter()
+        .map(|(id, message)| DeprecatedPackage {
+            name: id.name.clone(),
+            version: id.version.clone(),
+            message: message.clone(),
+            direct: direct.contains(id),
+            path: format_path(id, paths.shortest(id)),
+        })
+        .collect()
+}
+
+/// Prints the deprecated packages in `graph`, grouped by message so a
+/// package pulled in at several versions (or a family of packages sharing
+/// one notice) is reported once.
+pub(crate) fn print_deprecations(graph: &ResolutionGraph) {
+    let deprecated = deprecated_packages(graph);
+    if deprecated.is_empty() {
+        return;
+    }
+
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for package in deprecated {
+        groups
+            .entry(package.message)
+            .or_default()
+            .push(package.path);
+    }
+
+    let groups: Vec<(String, Vec<String>)> = groups.into_iter().collect();
+    println!();
+    console::deprecations(&groups);
+}
+
+/// With `fail-on-deprecated` set, a direct dependency that resolved to a
+/// deprecated version fails the install. Transitive ones are only reported.
+pub(crate) fn ensure_direct_not_deprecated(
+    config: &SnpmConfig,
+    graph: &ResolutionGraph,
+) -> Result<()> {
+    if !config.fail_on_deprecated {
+        return Ok(());
+    }
+
+    let lines: Vec<String> = graph
+        .root
+        .dependencies
+        .iter()
+        .filter_map(|(name, dep)| {
+            let message = graph.packages.get(&dep.resolved)?.deprecated.as_ref()?;
+            Some(format!("  {name}@{}: {message}", dep.resolved.version))
+        })
+        .collect();
+
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    Err(SnpmError::DeprecatedDependencies {
+        packages: lines.join("\n"),
+    })
+}
+
+fn format_path(target: &PackageId, path: Option<WhyPath>) -> String {
+    let mut chain: Vec<String> = path
+        .map(|path| {
+            path.hops
+                .iter()
+                .rev()
+                .filter_map(|hop| match hop {
+                    WhyHop::Package { name, version, .. } => Some(format!("{name}@{version}")),
+                    WhyHop::Root { .. } => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    chain.push(format!("{}@{}", target.name, target.version));
+    chain.join(" > ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deprecated_packages, ensure_direct_not_deprecated};
+    use crate::config::SnpmConfig;
+    use crate::operations::install::install;
+    use crate::operations::install::test_support::{lockfile_only_options, serve_registry};
+    use crate::operations::install::utils::FrozenLockfileMode;
+    use crate::resolve::{
+        PackageId, ResolutionGraph, ResolutionRoot, ResolvedPackage, RootDependency,
+    };
+    use crate::{Project, SnpmError};
+
+    use std::collections::BTreeMap;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn id(name: &str) -> PackageId {
+        PackageId {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+        }
+    }
+
+    fn package(
+        name: &str,
+        deprecated: Option<&str>,
+        dependencies: &[&str],
+    ) -> (PackageId, ResolvedPackage) {
+        (
+            id(name),
+            ResolvedPackage {
+                id: id(name),
+                tarball: format!("https://example.com/{name}.tgz"),
+                integrity: None,
+                dependencies: dependencies
+                    .iter()
+                    .map(|dep| (dep.to_string(), id(dep)))
+                    .collect(),
+                peer_dependencies: BTreeMap::new(),
+                bundled_dependencies: None,
+                has_bin: false,
+                bin: None,
+                os: Vec::new(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: deprecated.map(str::to_string),
+                has_install_script: false,
+            },
+        )
+    }
+
+    fn graph(app_deprecated: Option<&str>) -> ResolutionGraph {
+        ResolutionGraph {
+            root: ResolutionRoot {
+                dependencies: BTreeMap::from([(
+                    "app".to_string(),
+                    RootDependency {
+                        requested: "^1.0.0".to_string(),
+                        resolved: id("app"),
+                    },
+                )]),
+            },
+            packages: BTreeMap::from([
+                package("app", app_deprecated, &["rimraf"]),
+                package("rimraf", None, &["glob"]),
+                package(
+                    "glob",
+                    Some("Glob versions prior to v9 are no longer supported"),
+                    &[],
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn reports_transitive_deprecations_with_their_path() {
+        let deprecated = deprecated_packages(&graph(None));
+
+        assert_eq!(deprecated.len(), 1);
+        assert_eq!(deprecated[0].name, "glob");
+        assert!(!deprecated[0].direct);
+        assert_eq!(deprecated[0].path, "app@1.0.0 > rimraf@1.0.0 > glob@1.0.0");
+    }
+
+    #[test]
+    fn fail_on_deprecated_only_rejects_direct_dependencies() {
+        let config = SnpmConfig {
+            fail_on_deprecated: true,
+            ..SnpmConfig::for_tests()
+        };
+
+        assert!(ensure_direct_not_deprecated(&config, &graph(None)).is_ok());
+        assert!(
+            ensure_direct_not_deprecated(&SnpmConfig::for_tests(), &graph(Some("use next")))
+                .is_ok()
+        );
+
+        let error = ensure_direct_not_deprecated(&config, &graph(Some("use next"))).unwrap_err();
+        assert!(
+            matches!(error, SnpmError::DeprecatedDependencies { packages } if packages == "  app@1.0.0: use next")
+        );
+    }
+
+    #[tokio::test]
+    async fn failing_deprecation_check_leaves_the_lockfile_alone() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("app");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("package.json"),
+            r#"{ "name": "app", "version": "1.0.0", "dependencies": { "foo": "^1.0.0" } }"#,
+        )
+        .unwrap();
+
+        let registry = serve_registry(&[("foo", r#""deprecated":"use bar""#)]).await;
+        let config = SnpmConfig {
+            cache_dir: dir.path().join("cache"),
+            data_dir: dir.path().join("data"),
+            default_registry: registry.clone(),
+            registry_concurrency: 4,
+            fail_on_deprecated: true,
+            ..SnpmConfig::for_tests()
+        };
+
+        let mut project = Project::from_manifest_path(root.join("package.json")).unwrap();
+        let error = install(
+            &config,
+            &mut project,
+            lockfile_only_options(FrozenLockfileMode::Prefer),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error.to_string().contains("foo@1.0.0"), "{error}");
+        assert!(!root.join("snpm-lock.yaml").exists());
+    }
+}
@@ -0,0 +1,205 @@
+//! Checking dependency `engines` fields against the project's runtimes.
+//!
+//! Node comes from the project's pin (`node::discover::discover_pinned`).
+//! A `.node-version` / `.nvmrc` pin is the version snpm runs scripts with,
+//! resolved offline through aliases and the cached release index. The
+//! project's own `engines.node` is a range it claims to support, so the
+//! lowest version it allows is used. npm is the release bundled with that
+//! Node, when the cached index knows it.
+
+use super::installable::installable_graph;
+use crate::node::discover::{PinnedNodeSource, discover_pinned};
+use crate::node::index::read_cached_index;
+use crate::node::resolve::resolve_spec;
+use crate::platform::EngineTargets;
+use crate::resolve::ResolutionGraph;
+use crate::{Result, SnpmConfig, SnpmError, console};
+
+use snpm_semver::{RangeSet, Version, parse_version};
+use std::path::Path;
+
+/// Targets for installing the project (or workspace) rooted at `root`.
+/// The snpm version already in `config` is kept.
+pub(crate) async fn load_engine_targets(config: &SnpmConfig, root: &Path) -> EngineTargets {
+    let node = pinned_node_version(config, root).await;
+    let npm = node
+        .as_ref()
+        .and_then(|node| bundled_npm_version(config, node));
+
+    EngineTargets {
+        node,
+        npm,
+        snpm: config.engine_targets.snpm.clone(),
+    }
+}
+
+async fn pinned_node_version(config: &SnpmConfig, root: &Path) -> Option<Version> {
+    let pin = match discover_pinned(root) {
+        Ok(pin) => pin?,
+        Err(error) => {
+            console::verbose(&format!("engines: failed to read the Node pin: {error}"));
+            return None;
+        }
+    };
+
+    if matches!(pin.source, PinnedNodeSource::File(_))
+        && let Ok(resolved) = resolve_spec(config, &pin.spec, false).await
+    {
+        return parse_version(&resolved.normalized).ok();
+    }
+
+    RangeSet::parse(&pin.spec).ok()?.min_version()
+}
+
+fn bundled_npm_version(config: &SnpmConfig, node: &Version) -> Option<Version> {
+    let releases = read_cached_index(config)?;
+    let wanted = format!("v{node}");
+    let release = releases
+        .into_iter()
+        .find(|release| release.version == wanted)?;
+    parse_version(release.npm.as_deref()?).ok()
+}
+
+/// Checks every package installed here against `config.engine_targets`,
+/// using the `engines` recorded in the graph (and so in the lockfile).
+/// Mismatches are a warning, or an error under `engine-strict`.
+pub(crate) fn check_engines(config: &SnpmConfig, graph: &ResolutionGraph) -> Result<()> {
+    let targets = &config.engine_targets;
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    let graph = installable_graph(config, graph);
+    let lines: Vec<String> = graph
+        .packages
+        .values()
+        .filter_map(|package| {
+            let mismatches = targets.mismatches(&package.engines);
+            if mismatches.is_empty() {
+                return None;
+            }
+
+            let wanted: Vec<String> = mismatches.iter().map(ToString::to_string).collect();
+            Some(format!(
+                "  {}@{} requires {}",
+                package.id.name,
+                package.id.version,
+                wanted.join(", ")
+            ))
+        })
+        .collect();
+
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let packages = lines.join("\n");
+    if config.engine_strict {
+        return Err(SnpmError::EngineIncompatible { packages });
+    }
+
+    let noun = if lines.len() == 1 {
+        "package does"
+    } else {
+        "packages do"
+    };
+    console::warn(&format!(
+        "{} {noun} not support this project's engines:\n{packages}",
+        lines.len()
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_engines, load_engine_targets};
+    use crate::SnpmError;
+    use crate::config::SnpmConfig;
+    use crate::platform::EngineTargets;
+    use crate::resolve::{PackageId, ResolutionGraph, ResolutionRoot, ResolvedPackage};
+
+    use snpm_semver::Version;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn graph(node_range: &str) -> ResolutionGraph {
+        let id = PackageId {
+            name: "modern".to_string(),
+            version: "2.0.0".to_string(),
+        };
+        ResolutionGraph {
+            root: ResolutionRoot {
+                dependencies: BTreeMap::new(),
+            },
+            packages: BTreeMap::from([(
+                id.clone(),
+                ResolvedPackage {
+                    id,
+                    tarball: "https://example.com/modern-2.0.0.tgz".to_string(),
+                    integrity: None,
+                    dependencies: BTreeMap::new(),
+                    peer_dependencies: BTreeMap::new(),
+                    bundled_dependencies: None,
+                    has_bin: false,
+                    bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::from([("node".to_string(), node_range.to_string())]),
+                    deprecated: None,
+                    has_install_script: false,
+                },
+            )]),
+        }
+    }
+
+    fn config(engine_strict: bool) -> SnpmConfig {
+        SnpmConfig {
+            engine_strict,
+            engine_targets: EngineTargets {
+                node: Some(Version::new(18, 19, 0)),
+                ..Default::default()
+            },
+            ..SnpmConfig::for_tests()
+        }
+    }
+
+    #[test]
+    fn incompatible_engines_warn_unless_engine_strict() {
+        assert!(check_engines(&config(false), &graph(">=20")).is_ok());
+        assert!(check_engines(&config(true), &graph(">=18")).is_ok());
+        assert!(check_engines(&SnpmConfig::for_tests(), &graph(">=20")).is_ok());
+
+        let error = check_engines(&config(true), &graph(">=20")).unwrap_err();
+        assert!(matches!(
+            error,
+            SnpmError::EngineIncompatible { packages }
+                if packages == "  modern@2.0.0 requires node >=20 (have 18.19.0)"
+        ));
+    }
+
+    #[tokio::test]
+    async fn node_target_comes_from_the_pin() {
+        let data = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: data.path().to_path_buf(),
+            cache_dir: data.path().join("cache"),
+            ..SnpmConfig::for_tests()
+        };
+
+        let project = tempdir().unwrap();
+        fs::write(
+            project.path().join("package.json"),
+            r#"{"name":"app","engines":{"node":">=18.12 <21"}}"#,
+        )
+        .unwrap();
+        let targets = load_engine_targets(&config, project.path()).await;
+        assert_eq!(targets.node, Some(Version::new(18, 12, 0)));
+
+        fs::write(project.path().join(".nvmrc"), "v20.11.1\n").unwrap();
+        let targets = load_engine_targets(&config, project.path()).await;
+        assert_eq!(targets.node, Some(Version::new(20, 11, 1)));
+        assert_eq!(targets.npm, None);
+    }
+}
@@ -9,7 +9,7 @@ use std::path::{Path, PathBuf};
 use std::time::UNIX_EPOCH;
 
 const GRAPH_SNAPSHOT_FILE: &str = ".snpm-graph-snapshot.bin";
-const GRAPH_SNAPSHOT_VERSION: u32 = 1;
+const GRAPH_SNAPSHOT_VERSION: u32 = 2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct GraphSnapshot {
@@ -186,6 +186,12 @@ mod tests {
                     bundled_dependencies: None,
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             )]),
         }
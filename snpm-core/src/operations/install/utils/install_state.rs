@@ -1,9 +1,12 @@
 use super::graph_snapshot::root_specs_hash;
+use super::installable::installable_graph;
 use super::layout_state::{
     LayoutCheck, build_project_layout_hash, build_workspace_layout_hash, capture_project_checks,
     capture_workspace_checks, install_state_path, package_path_ready,
 };
+use super::lock_settings::lock_settings_hash;
 use super::snapshot_graph::SnapshotGraph;
+use crate::lockfile::LockSettings;
 use crate::resolve::ResolutionGraph;
 use crate::{Project, Result, SnpmConfig, SnpmError, Workspace};
 
@@ -18,7 +21,11 @@ use std::time::UNIX_EPOCH;
 // `DiskInstallStateFile`/`SnapshotGraph` mirror (tagged enums) because
 // bincode cannot round-trip the untagged enums the real graph embeds.
 // The old v1 files (which never read back) are ignored on version bump.
-const INSTALL_STATE_VERSION: u32 = 2;
+// v3: snapshot packages carry their os/cpu/libc/engines constraints,
+// deprecation message and whether they have an install script, and the
+// state records a hash of the lockfile settings the graph was resolved
+// under.
+const INSTALL_STATE_VERSION: u32 = 3;
 const LEGACY_GRAPH_SNAPSHOT_FILE: &str = ".snpm-graph-snapshot.bin";
 static NEXT_TMP_WRITE_ID: AtomicU64 = AtomicU64::new(0);
 
@@ -33,6 +40,7 @@ struct InstallStateFile {
 struct GraphSnapshotState {
     source: SnapshotSource,
     root_specs_hash: String,
+    lock_settings_hash: String,
     graph: ResolutionGraph,
 }
 
@@ -45,6 +53,7 @@ struct DiskInstallStateFile {
     version: u32,
     source: SnapshotSource,
     root_specs_hash: String,
+    lock_settings_hash: String,
     graph: SnapshotGraph,
     layout: LayoutSnapshot,
 }
@@ -55,6 +64,7 @@ impl DiskInstallStateFile {
             version: state.version,
             source: state.graph_snapshot.source.clone(),
             root_specs_hash: state.graph_snapshot.root_specs_hash.clone(),
+            lock_settings_hash: state.graph_snapshot.lock_settings_hash.clone(),
             graph: SnapshotGraph::from(&state.graph_snapshot.graph),
             layout: state.layout.clone(),
         }
@@ -66,6 +76,7 @@ impl DiskInstallStateFile {
             graph_snapshot: GraphSnapshotState {
                 source: self.source,
                 root_specs_hash: self.root_specs_hash,
+                lock_settings_hash: self.lock_settings_hash,
                 graph: self.graph.into(),
             },
             layout: self.layout,
@@ -107,6 +118,8 @@ pub(crate) struct CachedInstallState {
     pub(crate) graph: ResolutionGraph,
     pub(crate) root_specs_matches: bool,
     pub(crate) layout_valid: bool,
+    /// `lock_settings_hash` of the settings `graph` was resolved under.
+    pub(crate) lock_settings_hash: String,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -117,6 +130,7 @@ pub(crate) fn write_project_install_state(
     source_path: &Path,
     required_root: &BTreeMap<String, String>,
     optional_root: &BTreeMap<String, String>,
+    lock_settings: &LockSettings,
     graph: &ResolutionGraph,
     include_dev: bool,
 ) -> Result<()> {
@@ -125,6 +139,7 @@ pub(crate) fn write_project_install_state(
         graph_snapshot: GraphSnapshotState {
             source: SnapshotSource::capture(source_path)?,
             root_specs_hash: root_specs_hash(required_root, optional_root),
+            lock_settings_hash: lock_settings_hash(lock_settings),
             graph: graph.clone(),
         },
         layout: LayoutSnapshot {
@@ -141,12 +156,14 @@ pub(crate) fn write_project_install_state(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn write_workspace_install_state(
     config: &SnpmConfig,
     workspace: &Workspace,
     source_path: &Path,
     required_root: &BTreeMap<String, String>,
     optional_root: &BTreeMap<String, String>,
+    lock_settings: &LockSettings,
     graph: &ResolutionGraph,
     include_dev: bool,
 ) -> Result<()> {
@@ -155,6 +172,7 @@ pub(crate) fn write_workspace_install_state(
         graph_snapshot: GraphSnapshotState {
             source: SnapshotSource::capture(source_path)?,
             root_specs_hash: root_specs_hash(required_root, optional_root),
+            lock_settings_hash: lock_settings_hash(lock_settings),
             graph: graph.clone(),
         },
         layout: LayoutSnapshot {
@@ -206,6 +224,7 @@ pub(crate) fn load_project_install_state(
         graph: state.graph_snapshot.graph,
         root_specs_matches,
         layout_valid,
+        lock_settings_hash: state.graph_snapshot.lock_settings_hash,
     })
 }
 
@@ -237,6 +256,7 @@ pub(crate) fn load_workspace_install_state(
         graph: state.graph_snapshot.graph,
         root_specs_matches,
         layout_valid,
+        lock_settings_hash: state.graph_snapshot.lock_settings_hash,
     })
 }
 
@@ -259,6 +279,7 @@ pub(crate) fn load_project_install_state_fast(
         graph: state.graph_snapshot.graph,
         root_specs_matches,
         layout_valid,
+        lock_settings_hash: state.graph_snapshot.lock_settings_hash,
     })
 }
 
@@ -280,6 +301,7 @@ pub(crate) fn load_workspace_install_state_fast(
         graph: state.graph_snapshot.graph,
         root_specs_matches,
         layout_valid,
+        lock_settings_hash: state.graph_snapshot.lock_settings_hash,
     })
 }
 
@@ -463,11 +485,11 @@ fn fast_project_layout_valid(
         return false;
     }
 
-    let link_paths =
-        match project_link_paths(project, workspace, &state.graph_snapshot.graph, include_dev) {
-            Ok(paths) => paths,
-            Err(_) => return false,
-        };
+    let graph = installable_graph(config, &state.graph_snapshot.graph);
+    let link_paths = match project_link_paths(project, workspace, &graph, include_dev) {
+        Ok(paths) => paths,
+        Err(_) => return false,
+    };
 
     validate_fast_layout_checks(
         &state.layout.checks,
@@ -499,15 +521,12 @@ fn fast_workspace_layout_valid(
     let mut node_modules_roots = Vec::with_capacity(workspace.projects.len() + 1);
     node_modules_roots.push(workspace.root.join("node_modules"));
 
+    let graph = installable_graph(config, &state.graph_snapshot.graph);
     let mut link_paths = BTreeSet::new();
     for project in &workspace.projects {
         node_modules_roots.push(project.root.join("node_modules"));
-        let project_paths = match project_link_paths(
-            project,
-            Some(workspace),
-            &state.graph_snapshot.graph,
-            include_dev,
-        ) {
+        let project_paths = match project_link_paths(project, Some(workspace), &graph, include_dev)
+        {
             Ok(paths) => paths,
             Err(_) => return false,
         };
@@ -538,11 +557,10 @@ fn validate_fast_layout_checks(
     for check in checks {
         match check {
             StoredLayoutCheck::Mtime { path, .. }
-                if is_fast_boundary_mtime(path, node_modules_roots, virtual_store_roots) =>
+                if is_fast_boundary_mtime(path, node_modules_roots, virtual_store_roots)
+                    && !check.validate() =>
             {
-                if !check.validate() {
-                    return false;
-                }
+                return false;
             }
             StoredLayoutCheck::Exists { path } if link_paths.contains(path) => {
                 if !check.validate() {
@@ -703,6 +721,7 @@ mod tests {
     use crate::Project;
     use crate::Workspace;
     use crate::config::{HoistingMode, SnpmConfig};
+    use crate::lockfile::LockSettings;
     use crate::project::Manifest;
     use crate::resolve::{
         PackageId, ResolutionGraph, ResolutionRoot, ResolvedPackage, RootDependency,
@@ -748,6 +767,12 @@ mod tests {
                 ignored_built_dependencies: Vec::new(),
                 disable_global_virtual_store_for_packages: None,
                 hoisting: None,
+                package_extensions: Default::default(),
+                peer_dependency_rules: Default::default(),
+                supported_architectures: Default::default(),
+                resolution_mode: None,
+                trust_policy: None,
+                trust_policy_exclude: Vec::new(),
             },
         }
     }
@@ -777,6 +802,12 @@ mod tests {
                     // failed to deserialize, silently killing the fast path.
                     has_bin: true,
                     bin: Some(crate::project::BinField::Single("cli.js".to_string())),
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             )]),
         }
@@ -821,6 +852,7 @@ mod tests {
             &lockfile_path,
             &BTreeMap::from([("dep".to_string(), "^1.0.0".to_string())]),
             &BTreeMap::new(),
+            &LockSettings::default(),
             &graph,
             true,
         )
@@ -909,6 +941,7 @@ mod tests {
             &lockfile_path,
             &BTreeMap::from([("dep".to_string(), "^1.0.0".to_string())]),
             &BTreeMap::new(),
+            &LockSettings::default(),
             &graph,
             true,
         )
@@ -981,6 +1014,7 @@ mod tests {
             &lockfile_path,
             &BTreeMap::from([("dep".to_string(), "^1.0.0".to_string())]),
             &BTreeMap::new(),
+            &LockSettings::default(),
             &graph,
             true,
         )
@@ -1054,6 +1088,7 @@ mod tests {
             &lockfile_path,
             &BTreeMap::from([("dep".to_string(), "^1.0.0".to_string())]),
             &BTreeMap::new(),
+            &LockSettings::default(),
             &graph,
             true,
         )
@@ -1128,6 +1163,7 @@ mod tests {
             &lockfile_path,
             &BTreeMap::from([("dep".to_string(), "^1.0.0".to_string())]),
             &BTreeMap::new(),
+            &LockSettings::default(),
             &graph,
             true,
         )
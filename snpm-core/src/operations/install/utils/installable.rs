@@ -0,0 +1,180 @@
+use crate::SnpmConfig;
+use crate::resolve::{PackageId, ResolutionGraph};
+
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+/// The part of `graph` that gets fetched and linked here: packages whose
+/// recorded `os`/`cpu`/`libc` match no `supportedArchitectures` target are
+/// left out, along with anything only they depend on.
+///
+/// The full graph is still what gets written to the lockfile, so a
+/// lockfile resolved on another platform keeps that platform's packages.
+/// Packages from lockfiles that predate the platform fields carry no
+/// constraints and are always kept.
+pub(crate) fn installable_graph<'a>(
+    config: &SnpmConfig,
+    graph: &'a ResolutionGraph,
+) -> Cow<'a, ResolutionGraph> {
+    let supported = &config.supported_architectures;
+    let mut removed: BTreeSet<PackageId> = graph
+        .packages
+        .values()
+        .filter(|package| !supported.supports(&package.os, &package.cpu, &package.libc))
+        .map(|package| package.id.clone())
+        .collect();
+
+    if removed.is_empty() {
+        return Cow::Borrowed(graph);
+    }
+
+    let depended_on: BTreeSet<&PackageId> = graph
+        .packages
+        .values()
+        .flat_map(|package| package.dependencies.values())
+        .chain(graph.root.dependencies.values().map(|dep| &dep.resolved))
+        .collect();
+
+    // A package that loses every dependent went in only for the removed
+    // ones. Packages nothing depended on to begin with (workspace-only
+    // entries) are left alone.
+    loop {
+        let still_depended_on: BTreeSet<&PackageId> = graph
+            .packages
+            .iter()
+            .filter(|(id, _)| !removed.contains(*id))
+            .flat_map(|(_, package)| package.dependencies.values())
+            .chain(graph.root.dependencies.values().map(|dep| &dep.resolved))
+            .collect();
+
+        let orphans: Vec<PackageId> = graph
+            .packages
+            .keys()
+            .filter(|id| !removed.contains(*id))
+            .filter(|id| depended_on.contains(id) && !still_depended_on.contains(id))
+            .cloned()
+            .collect();
+
+        if orphans.is_empty() {
+            break;
+        }
+        removed.extend(orphans);
+    }
+
+    let mut installable = graph.clone();
+    installable.packages.retain(|id, _| !removed.contains(id));
+    for package in installable.packages.values_mut() {
+        package
+            .dependencies
+            .retain(|_, dependency| !removed.contains(dependency));
+    }
+    installable
+        .root
+        .dependencies
+        .retain(|_, dep| !removed.contains(&dep.resolved));
+
+    Cow::Owned(installable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::installable_graph;
+    use crate::config::SnpmConfig;
+    use crate::platform::SupportedArchitectures;
+    use crate::resolve::{
+        PackageId, ResolutionGraph, ResolutionRoot, ResolvedPackage, RootDependency,
+    };
+
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    fn id(name: &str) -> PackageId {
+        PackageId {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+        }
+    }
+
+    fn package(name: &str, os: &[&str], dependencies: &[&str]) -> (PackageId, ResolvedPackage) {
+        (
+            id(name),
+            ResolvedPackage {
+                id: id(name),
+                tarball: format!("https://example.com/{name}.tgz"),
+                integrity: None,
+                dependencies: dependencies
+                    .iter()
+                    .map(|dep| (dep.to_string(), id(dep)))
+                    .collect(),
+                peer_dependencies: BTreeMap::new(),
+                bundled_dependencies: None,
+                has_bin: false,
+                bin: None,
+                os: os.iter().map(ToString::to_string).collect(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: None,
+                has_install_script: false,
+            },
+        )
+    }
+
+    fn graph() -> ResolutionGraph {
+        ResolutionGraph {
+            root: ResolutionRoot {
+                dependencies: BTreeMap::from([(
+                    "app-deps".to_string(),
+                    RootDependency {
+                        requested: "^1.0.0".to_string(),
+                        resolved: id("app-deps"),
+                    },
+                )]),
+            },
+            packages: BTreeMap::from([
+                package("app-deps", &[], &["native-a", "native-b", "shared"]),
+                package("native-a", &["plan9"], &["only-a", "shared"]),
+                package("native-b", &["!plan9"], &[]),
+                package("only-a", &[], &[]),
+                package("shared", &[], &[]),
+                package("unreferenced", &[], &[]),
+            ]),
+        }
+    }
+
+    fn config(os: &[&str]) -> SnpmConfig {
+        SnpmConfig {
+            supported_architectures: SupportedArchitectures {
+                os: os.iter().map(ToString::to_string).collect(),
+                ..Default::default()
+            },
+            ..SnpmConfig::for_tests()
+        }
+    }
+
+    #[test]
+    fn drops_unsupported_packages_and_what_only_they_need() {
+        let graph = graph();
+        let installable = installable_graph(&config(&[]), &graph);
+
+        let names: Vec<&str> = installable
+            .packages
+            .keys()
+            .map(|id| id.name.as_str())
+            .collect();
+        assert_eq!(names, ["app-deps", "native-b", "shared", "unreferenced"]);
+        assert!(
+            !installable.packages[&id("app-deps")]
+                .dependencies
+                .contains_key("native-a")
+        );
+    }
+
+    #[test]
+    fn keeps_everything_when_every_package_is_supported() {
+        let graph = graph();
+        let installable = installable_graph(&config(&["current", "plan9"]), &graph);
+
+        assert!(matches!(installable, Cow::Borrowed(_)));
+    }
+}
@@ -17,6 +17,12 @@ fn make_graph() -> ResolutionGraph {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     ResolutionGraph {
         root: ResolutionRoot {
@@ -58,6 +64,12 @@ fn compute_lockfile_hash_changes_with_different_graph() {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     let graph2 = ResolutionGraph {
         root: ResolutionRoot {
@@ -1,3 +1,4 @@
+use super::installable::installable_graph;
 use crate::config::HoistingMode;
 use crate::linker::fs::package_node_modules;
 use crate::linker::hoist::effective_hoisting;
@@ -113,6 +114,7 @@ pub(crate) fn build_project_layout_hash(
     graph: &ResolutionGraph,
     include_dev: bool,
 ) -> Result<String> {
+    let graph = &*installable_graph(config, graph);
     let mut entries = Vec::new();
     entries.push("kind=project".to_string());
     entries.push(format!(
@@ -133,6 +135,7 @@ pub(crate) fn build_workspace_layout_hash(
     graph: &ResolutionGraph,
     include_dev: bool,
 ) -> Result<String> {
+    let graph = &*installable_graph(config, graph);
     let mut entries = Vec::new();
     entries.push("kind=workspace".to_string());
 
@@ -285,6 +288,7 @@ pub(crate) fn capture_project_checks(
     graph: &ResolutionGraph,
     include_dev: bool,
 ) -> Result<Vec<LayoutCheck>> {
+    let graph = &*installable_graph(config, graph);
     let mut checks = Vec::new();
     let mut seen_links = BTreeSet::new();
     let node_modules = project.root.join("node_modules");
@@ -312,6 +316,7 @@ pub(crate) fn capture_workspace_checks(
     graph: &ResolutionGraph,
     include_dev: bool,
 ) -> Result<Vec<LayoutCheck>> {
+    let graph = &*installable_graph(config, graph);
     let mut checks = Vec::new();
 
     capture_directory_mtime(&workspace.root.join("node_modules"), &mut checks)?;
@@ -721,6 +726,12 @@ mod tests {
             bundled_dependencies: None,
             has_bin: false,
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         };
 
         ResolutionGraph {
@@ -750,6 +761,12 @@ mod tests {
             bundled_dependencies: None,
             has_bin: false,
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         };
         let child_pkg = ResolvedPackage {
             id: child_id.clone(),
@@ -760,6 +777,12 @@ mod tests {
             bundled_dependencies: None,
             has_bin: false,
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         };
 
         ResolutionGraph {
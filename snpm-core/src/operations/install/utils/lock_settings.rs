@@ -0,0 +1,154 @@
+use crate::lockfile::{self, LockSettings};
+use crate::platform::SupportedArchitectures;
+use crate::{Result, SnpmConfig, SnpmError};
+
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use time::UtcOffset;
+use time::format_description::well_known::Rfc3339;
+
+/// The `before` cutoff as the lockfile records it: RFC 3339 in UTC, so
+/// the same moment reads the same whatever offset it was given in.
+pub(crate) fn before_setting(config: &SnpmConfig) -> Option<String> {
+    config
+        .before
+        .and_then(|cutoff| cutoff.to_offset(UtcOffset::UTC).format(&Rfc3339).ok())
+}
+
+/// Digest of `settings`, stored with the install state so an install can
+/// tell that its cached graph was resolved under the current settings
+/// without parsing the lockfile again.
+pub(crate) fn lock_settings_hash(settings: &LockSettings) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(settings).unwrap_or_default());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Names of the settings in `locked` that differ from `current`. A graph
+/// resolved under other settings must not be installed or reused as a
+/// resolver seed.
+pub(crate) fn diff_lock_settings(
+    locked: &LockSettings,
+    current: &LockSettings,
+) -> Vec<&'static str> {
+    let mut changed = Vec::new();
+    if locked.package_extensions_checksum != current.package_extensions_checksum {
+        changed.push("packageExtensions");
+    }
+    if locked.supported_architectures != current.supported_architectures {
+        changed.push("supportedArchitectures");
+    }
+    if locked.resolution_mode != current.resolution_mode {
+        changed.push("resolutionMode");
+    }
+    if locked.before != current.before {
+        changed.push("before");
+    }
+    changed
+}
+
+/// `diff_lock_settings` against the settings behind the graph at hand.
+/// `recorded` is the `lock_settings_hash` stored with it, when it came
+/// from the install state or an already parsed lockfile; a match settles
+/// it without reading anything. Otherwise `snpm-lock.yaml` at
+/// `lockfile_path` is read. A missing lockfile (or a compatible one from
+/// another package manager) records no settings, so it is compared
+/// against the defaults. An unreadable lockfile reports nothing here and
+/// surfaces its error where it is actually read.
+pub(crate) fn changed_lock_settings(
+    lockfile_path: &Path,
+    recorded: Option<&str>,
+    current: &LockSettings,
+) -> Vec<&'static str> {
+    if recorded.is_some_and(|hash| hash == lock_settings_hash(current)) {
+        return Vec::new();
+    }
+
+    let locked = if lockfile_path.is_file() {
+        match lockfile::read(lockfile_path) {
+            Ok(lockfile) => lockfile.settings,
+            Err(_) => return Vec::new(),
+        }
+    } else {
+        LockSettings::default()
+    };
+    diff_lock_settings(&locked, current)
+}
+
+/// `changed_lock_settings`, plus `supportedArchitectures` when `targets`
+/// differ from the recorded ones because of `--os`, `--cpu` or `--libc`.
+/// Those flags are left out of the lockfile, so it cannot say whether it
+/// already covers them; installs that may re-resolve do so.
+pub(crate) fn changed_resolve_settings(
+    lockfile_path: &Path,
+    recorded: Option<&str>,
+    current: &LockSettings,
+    targets: &SupportedArchitectures,
+) -> Vec<&'static str> {
+    let mut changed = changed_lock_settings(lockfile_path, recorded, current);
+    if targets != &current.supported_architectures && !changed.contains(&"supportedArchitectures") {
+        changed.push("supportedArchitectures");
+    }
+    changed
+}
+
+/// Frozen installs cannot re-resolve, so `changed` settings are an error.
+pub(crate) fn ensure_lock_settings_unchanged(
+    lockfile_path: &Path,
+    changed: Vec<&'static str>,
+) -> Result<()> {
+    if changed.is_empty() {
+        return Ok(());
+    }
+
+    Err(SnpmError::Lockfile {
+        path: lockfile_path.to_path_buf(),
+        reason: format!(
+            "{} changed since the lockfile was written; run `snpm install` without --frozen-lockfile to update it",
+            changed.join(", ")
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{changed_lock_settings, lock_settings_hash};
+    use crate::config::ResolutionMode;
+    use crate::lockfile::{LockRoot, LockSettings, Lockfile};
+
+    use std::collections::BTreeMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn matching_recorded_hash_settles_it_without_the_lockfile() {
+        let dir = tempdir().unwrap();
+        let lockfile_path = dir.path().join("snpm-lock.yaml");
+        let lockfile = Lockfile {
+            version: 1,
+            settings: LockSettings {
+                resolution_mode: ResolutionMode::Lowest,
+                ..LockSettings::default()
+            },
+            root: LockRoot {
+                dependencies: BTreeMap::new(),
+            },
+            importers: BTreeMap::new(),
+            packages: BTreeMap::new(),
+        };
+        std::fs::write(&lockfile_path, serde_yaml::to_string(&lockfile).unwrap()).unwrap();
+        let current = LockSettings::default();
+
+        let recorded = lock_settings_hash(&current);
+        assert!(changed_lock_settings(&lockfile_path, Some(&recorded), &current).is_empty());
+
+        let stale = lock_settings_hash(&lockfile.settings);
+        assert_eq!(
+            changed_lock_settings(&lockfile_path, Some(&stale), &current),
+            vec!["resolutionMode"]
+        );
+        assert_eq!(
+            changed_lock_settings(&lockfile_path, None, &current),
+            vec!["resolutionMode"]
+        );
+    }
+}
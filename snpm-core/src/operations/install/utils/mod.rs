@@ -1,15 +1,27 @@
 mod age_policy;
+mod conflicts;
+mod deprecated;
+mod engines;
 mod graph_snapshot;
 mod install_state;
+mod installable;
 mod integrity;
 mod layout_state;
+mod lock_settings;
 mod scenario;
 mod script_policy;
+mod signatures;
 mod snapshot_graph;
 mod store;
 mod types;
 
 pub(crate) use age_policy::validate_graph_min_package_age;
+pub(crate) use conflicts::reconcile_lockfile_conflicts;
+pub use deprecated::DeprecatedPackage;
+pub(crate) use deprecated::{
+    deprecated_packages, ensure_direct_not_deprecated, print_deprecations,
+};
+pub(crate) use engines::{check_engines, load_engine_targets};
 pub(crate) use graph_snapshot::load_graph_snapshot;
 #[cfg(test)]
 pub(crate) use graph_snapshot::write_graph_snapshot;
@@ -17,9 +29,16 @@ pub(crate) use install_state::{
     load_project_install_state, load_project_install_state_fast, load_workspace_install_state,
     load_workspace_install_state_fast, write_project_install_state, write_workspace_install_state,
 };
+pub(crate) use installable::installable_graph;
 pub use integrity::*;
 pub(crate) use layout_state::{check_project_layout_state, check_workspace_layout_state};
+pub(crate) use lock_settings::{
+    before_setting, changed_lock_settings, changed_resolve_settings, diff_lock_settings,
+    ensure_lock_settings_unchanged, lock_settings_hash,
+};
 pub use scenario::detect_install_scenario;
 pub use script_policy::can_any_scripts_run;
+pub(crate) use signatures::verify_graph_signatures;
 pub use store::{check_store_cache, materialize_missing_packages, materialize_store};
+pub(crate) use store::{record_lockfile_only_project, record_store_project};
 pub use types::*;
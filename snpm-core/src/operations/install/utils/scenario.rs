@@ -1,5 +1,6 @@
 use super::integrity::{build_project_integrity_state, check_integrity_file};
 use super::layout_state::check_project_layout_state;
+use super::lock_settings::lock_settings_hash;
 use super::store::check_store_cache;
 use super::types::{InstallScenario, ScenarioResult};
 use super::{load_graph_snapshot, load_project_install_state};
@@ -50,6 +51,7 @@ pub fn detect_install_scenario(
                 graph: Some(state.graph),
                 integrity_state: None,
                 hot_install_state_current: false,
+                lock_settings_hash: Some(state.lock_settings_hash),
             };
         }
 
@@ -64,7 +66,8 @@ pub fn detect_install_scenario(
             state.graph,
             force,
             Some(state.layout_valid),
-        );
+        )
+        .with_lock_settings_hash(state.lock_settings_hash);
     }
 
     if let Some(source_path) = lockfile_source_path(lockfile_path, compatible_lockfile)
@@ -78,6 +81,7 @@ pub fn detect_install_scenario(
                 graph: Some(snapshot.graph),
                 integrity_state: None,
                 hot_install_state_current: false,
+                lock_settings_hash: None,
             };
         }
 
@@ -100,6 +104,7 @@ pub fn detect_install_scenario(
         }
     };
     let graph = lockfile::to_graph(&existing);
+    let settings_hash = lock_settings_hash(&existing.settings);
 
     if !lockfile::root_specs_match(&existing, required_root, optional_root) {
         console::verbose("scenario: Cold (lockfile doesn't match manifest)");
@@ -109,10 +114,12 @@ pub fn detect_install_scenario(
             graph: Some(graph),
             integrity_state: None,
             hot_install_state_current: false,
+            lock_settings_hash: Some(settings_hash),
         };
     }
 
     detect_from_graph(config, project, workspace, graph, force, None)
+        .with_lock_settings_hash(settings_hash)
 }
 
 fn detect_from_graph(
@@ -147,6 +154,7 @@ fn detect_from_graph(
             // Some(..) here means the classification came from a valid
             // install-state file; None means the lockfile fallback.
             hot_install_state_current: cached_layout_valid.is_some(),
+            lock_settings_hash: None,
         };
     }
 
@@ -183,6 +191,7 @@ fn detect_from_graph(
             graph: Some(graph),
             integrity_state: Some(integrity_state),
             hot_install_state_current: false,
+            lock_settings_hash: None,
         };
     }
 
@@ -199,6 +208,7 @@ fn detect_from_graph(
         graph: Some(graph),
         integrity_state: Some(integrity_state),
         hot_install_state_current: false,
+        lock_settings_hash: None,
     }
 }
 
@@ -292,6 +302,12 @@ mod tests {
                     bundled_dependencies: None,
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             )]),
         }
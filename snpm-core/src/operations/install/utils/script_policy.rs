@@ -57,6 +57,12 @@ mod tests {
                 ignored_built_dependencies: Vec::new(),
                 disable_global_virtual_store_for_packages: None,
                 hoisting: None,
+                package_extensions: Default::default(),
+                peer_dependency_rules: Default::default(),
+                supported_architectures: Default::default(),
+                resolution_mode: None,
+                trust_policy: None,
+                trust_policy_exclude: Vec::new(),
             },
         };
         assert!(can_any_scripts_run(&config, Some(&workspace)));
@@ -76,6 +82,12 @@ mod tests {
                 ignored_built_dependencies: vec!["malicious".to_string()],
                 disable_global_virtual_store_for_packages: None,
                 hoisting: None,
+                package_extensions: Default::default(),
+                peer_dependency_rules: Default::default(),
+                supported_architectures: Default::default(),
+                resolution_mode: None,
+                trust_policy: None,
+                trust_policy_exclude: Vec::new(),
             },
         };
         assert!(can_any_scripts_run(&config, Some(&workspace)));
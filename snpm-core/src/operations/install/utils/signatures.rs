@@ -0,0 +1,53 @@
+use super::installable::installable_graph;
+use crate::operations::audit::{SignedPackage, verify_package_signatures};
+use crate::resolve::ResolutionGraph;
+use crate::{Result, SnpmConfig, SnpmError, console};
+
+/// Checks the registry signature of every package installed here; run
+/// under `verify-signatures` before the lockfile is saved. Invalid
+/// signatures fail the install; packages the registry served unsigned are
+/// a warning.
+pub(crate) async fn verify_graph_signatures(
+    config: &SnpmConfig,
+    client: &reqwest::Client,
+    graph: &ResolutionGraph,
+) -> Result<()> {
+    let graph = installable_graph(config, graph);
+    let packages = graph.packages.values().map(|package| SignedPackage {
+        name: &package.id.name,
+        version: &package.id.version,
+        tarball: &package.tarball,
+        integrity: package.integrity.as_deref(),
+    });
+    let result = verify_package_signatures(config, client, packages).await?;
+
+    if !result.invalid.is_empty() {
+        let packages = result
+            .invalid
+            .iter()
+            .map(|issue| format!("  {}@{}: {}", issue.name, issue.version, issue.reason))
+            .collect::<Vec<_>>()
+            .join("\n");
+        return Err(SnpmError::InvalidSignatures { packages });
+    }
+
+    if !result.missing.is_empty() {
+        let packages = result
+            .missing
+            .iter()
+            .map(|issue| format!("  {}@{}", issue.name, issue.version))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let noun = if result.missing.len() == 1 {
+            "package has"
+        } else {
+            "packages have"
+        };
+        console::warn(&format!(
+            "{} {noun} no registry signature:\n{packages}",
+            result.missing.len()
+        ));
+    }
+
+    Ok(())
+}
@@ -41,6 +41,12 @@ struct SnapshotPackage {
     bundled_dependencies: Option<SnapshotBundled>,
     has_bin: bool,
     bin: Option<SnapshotBin>,
+    os: Vec<String>,
+    cpu: Vec<String>,
+    libc: Vec<String>,
+    engines: BTreeMap<String, String>,
+    deprecated: Option<String>,
+    has_install_script: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,6 +133,12 @@ impl From<&ResolvedPackage> for SnapshotPackage {
                 BinField::Single(value) => SnapshotBin::Single(value.clone()),
                 BinField::Map(map) => SnapshotBin::Map(map.clone()),
             }),
+            os: package.os.clone(),
+            cpu: package.cpu.clone(),
+            libc: package.libc.clone(),
+            engines: package.engines.clone(),
+            deprecated: package.deprecated.clone(),
+            has_install_script: package.has_install_script,
         }
     }
 }
@@ -148,6 +160,12 @@ impl From<SnapshotPackage> for ResolvedPackage {
                 SnapshotBin::Single(value) => BinField::Single(value),
                 SnapshotBin::Map(map) => BinField::Map(map),
             }),
+            os: package.os,
+            cpu: package.cpu,
+            libc: package.libc,
+            engines: package.engines,
+            deprecated: package.deprecated,
+            has_install_script: package.has_install_script,
         }
     }
 }
@@ -192,6 +210,12 @@ mod tests {
                         "tool".to_string(),
                         "bin/tool.js".to_string(),
                     )]))),
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             )]),
         };
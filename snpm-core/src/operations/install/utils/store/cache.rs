@@ -1,3 +1,4 @@
+use super::super::installable::installable_graph;
 use super::super::types::CacheCheckResult;
 use crate::resolve::{ResolutionGraph, ResolvedPackage};
 use crate::store::PACKAGE_METADATA_FILE;
@@ -9,6 +10,7 @@ use std::path::PathBuf;
 pub fn check_store_cache(config: &crate::SnpmConfig, graph: &ResolutionGraph) -> CacheCheckResult {
     use rayon::prelude::*;
 
+    let graph = &*installable_graph(config, graph);
     let base = config.packages_dir();
     let store_index = crate::store::load_store_residency_index_lossy(config);
     let packages: Vec<_> = graph.packages.values().collect();
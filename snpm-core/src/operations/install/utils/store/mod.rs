@@ -1,8 +1,10 @@
 mod cache;
 mod materialize;
+mod registry;
 
 pub use cache::check_store_cache;
 pub use materialize::{materialize_missing_packages, materialize_store};
+pub(crate) use registry::{record_lockfile_only_project, record_store_project};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,45 @@
+use crate::SnpmConfig;
+use crate::console;
+use crate::store::{ProjectRecord, record_project};
+
+use std::path::Path;
+
+/// Records `root` in the store's project registry so `store prune` keeps
+/// everything its lockfile references. A failure to record is logged, not
+/// fatal: the next install records the project again.
+pub(crate) fn record_store_project(config: &SnpmConfig, root: &Path, lockfile_path: &Path) {
+    record(
+        config,
+        ProjectRecord::new(root.to_path_buf(), lockfile_path.to_path_buf()),
+    );
+}
+
+/// `record_store_project` for a `--lockfile-only` run, which rewrote the
+/// lockfile but left `node_modules` as it was. `installed_lockfile_hash`
+/// is the lockfile's hash from before the run, so `store prune` sees that
+/// the lockfile moved on and keeps what `node_modules` may still use.
+pub(crate) fn record_lockfile_only_project(
+    config: &SnpmConfig,
+    root: &Path,
+    lockfile_path: &Path,
+    installed_lockfile_hash: Option<String>,
+) {
+    record(
+        config,
+        ProjectRecord {
+            root: root.to_path_buf(),
+            lockfile: lockfile_path.to_path_buf(),
+            lockfile_hash: installed_lockfile_hash,
+        },
+    );
+}
+
+fn record(config: &SnpmConfig, record: ProjectRecord) {
+    let root = record.root.clone();
+    if let Err(error) = record_project(config, record) {
+        console::verbose(&format!(
+            "failed to record {} in the project registry: {error}",
+            root.display()
+        ));
+    }
+}
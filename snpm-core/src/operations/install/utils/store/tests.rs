@@ -37,6 +37,12 @@ fn check_store_cache_uses_detected_package_root() {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     let graph = ResolutionGraph {
         root: ResolutionRoot {
@@ -83,6 +89,12 @@ fn check_store_cache_uses_store_residency_index_when_present() {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     let graph = ResolutionGraph {
         root: ResolutionRoot {
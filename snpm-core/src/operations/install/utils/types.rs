@@ -26,6 +26,8 @@ pub struct InstallOptions {
     pub frozen_lockfile: FrozenLockfileMode,
     pub strict_no_lockfile: bool,
     pub force: bool,
+    /// Resolve and write the lockfile only: no downloads, linking, or scripts.
+    pub lockfile_only: bool,
     pub silent_summary: bool,
 }
 
@@ -225,6 +227,9 @@ pub struct ScenarioResult {
     /// upgrade changed the state format) must still write a fresh state
     /// file or the fast path never engages for that project.
     pub hot_install_state_current: bool,
+    /// `lock_settings_hash` of the settings `graph` was resolved under,
+    /// when the install state or a parsed lockfile recorded them.
+    pub lock_settings_hash: Option<String>,
 }
 
 impl ScenarioResult {
@@ -235,8 +240,18 @@ impl ScenarioResult {
             graph: None,
             integrity_state: None,
             hot_install_state_current: false,
+            lock_settings_hash: None,
         }
     }
+
+    /// Records the settings hash, unless the scenario fell back to Cold
+    /// without a graph it could describe.
+    pub(crate) fn with_lock_settings_hash(mut self, hash: String) -> Self {
+        if self.graph.is_some() {
+            self.lock_settings_hash = Some(hash);
+        }
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,87 @@
+use super::graph::workspace_sources;
+use super::setup::prepare_workspace_install;
+use crate::console;
+use crate::lockfile;
+use crate::operations::install::effective_config::{ConfigOverrides, effective_config};
+use crate::operations::install::utils::FrozenLockfileMode;
+use crate::resolve::{self, ResolutionGraph};
+use crate::{Result, SnpmConfig, SnpmError, Workspace, http};
+
+/// Workspace counterpart of `dedupe_project_lockfile`: re-resolves the
+/// shared lockfile preferring versions it already locks, keeping the
+/// per-project importers intact.
+pub(in crate::operations::install) async fn dedupe_workspace_lockfile(
+    config: &SnpmConfig,
+    workspace: &Workspace,
+    write: bool,
+) -> Result<(ResolutionGraph, ResolutionGraph)> {
+    let setup =
+        prepare_workspace_install(config, workspace, true, FrozenLockfileMode::Prefer, false)?;
+    let config = &*effective_config(
+        config,
+        ConfigOverrides {
+            supported_architectures: Some(&setup.supported_architectures),
+            resolution_mode: Some(setup.lock_settings.resolution_mode),
+            workspace: Some(workspace),
+            ..ConfigOverrides::default()
+        },
+    );
+
+    if !setup.lockfile_path.is_file() {
+        return Err(SnpmError::Lockfile {
+            path: setup.lockfile_path,
+            reason: "snpm-lock.yaml is missing. Run `snpm install` first.".into(),
+        });
+    }
+
+    let existing = lockfile::to_graph(&lockfile::read(&setup.lockfile_path)?);
+    let client = http::create_client()?;
+
+    console::step("Resolving workspace dependencies");
+    // Measure dedupe against what a regular install would lock from the
+    // current manifests, so manifest drift is not reported as duplication.
+    let baseline = resolve::resolve_with_optional_roots_with_seed(
+        config,
+        &client,
+        &setup.root_dependencies,
+        &setup.root_protocols,
+        &setup.optional_root_names,
+        config.min_package_age_days,
+        false,
+        Some(&setup.overrides),
+        Some(&setup.package_extensions),
+        Some(&setup.peer_dependency_rules),
+        Some(&workspace_sources(workspace)),
+        Some(&existing),
+        |_package| async move { Ok(()) },
+    )
+    .await?;
+    let deduped = resolve::resolve_deduped(
+        config,
+        &client,
+        &setup.root_dependencies,
+        &setup.root_protocols,
+        &setup.optional_root_names,
+        config.min_package_age_days,
+        false,
+        Some(&setup.overrides),
+        Some(&setup.package_extensions),
+        Some(&setup.peer_dependency_rules),
+        Some(&workspace_sources(workspace)),
+        &baseline,
+        |_package| async move { Ok(()) },
+    )
+    .await?;
+
+    if write {
+        lockfile::write_with_importers(
+            &setup.lockfile_path,
+            &deduped,
+            &setup.root_specs.optional,
+            &setup.importers,
+            &setup.lock_settings,
+        )?;
+    }
+
+    Ok((baseline, deduped))
+}
@@ -10,7 +10,7 @@ use std::path::{Path, PathBuf};
 
 use super::super::utils::{
     InstallScenario, IntegrityState, build_workspace_integrity_state, can_any_scripts_run,
-    compute_project_patch_hash, write_integrity_path,
+    compute_project_patch_hash, installable_graph, write_integrity_path,
 };
 use super::linking::{
     link_project_dependencies, link_store_dependencies, populate_virtual_store,
@@ -33,24 +33,33 @@ pub(super) fn finalize_workspace_install(
         source,
     })?;
 
+    // Only linking skips packages for other platforms; the integrity
+    // hash below still covers the whole lockfile graph.
+    let installable = installable_graph(config, graph);
     let virtual_store_paths = if matches!(scenario, InstallScenario::Hot) {
         console::step("Validating workspace structure");
-        rebuild_virtual_store_paths(&shared_virtual_store, graph)?
+        rebuild_virtual_store_paths(&shared_virtual_store, &installable)?
     } else {
         console::step("Linking workspace");
         populate_virtual_store(
             &shared_virtual_store,
-            graph,
+            &installable,
             store_paths_map,
             config,
             workspace,
         )?
     };
 
-    link_store_dependencies(&virtual_store_paths, graph)?;
+    link_store_dependencies(&virtual_store_paths, &installable)?;
 
     workspace.projects.par_iter().try_for_each(|project| {
-        link_project_dependencies(project, workspace, graph, &virtual_store_paths, include_dev)
+        link_project_dependencies(
+            project,
+            workspace,
+            &installable,
+            &virtual_store_paths,
+            include_dev,
+        )
     })?;
 
     let patches_applied = apply_workspace_patches(workspace, store_paths_map)?;
@@ -176,6 +185,12 @@ mod tests {
                 ignored_built_dependencies: Vec::new(),
                 disable_global_virtual_store_for_packages: None,
                 hoisting: None,
+                package_extensions: Default::default(),
+                peer_dependency_rules: Default::default(),
+                supported_architectures: Default::default(),
+                resolution_mode: None,
+                trust_policy: None,
+                trust_policy_exclude: Vec::new(),
             },
         };
 
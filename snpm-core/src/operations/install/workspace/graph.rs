@@ -8,8 +8,9 @@ use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use super::super::utils::{
-    InstallScenario, check_store_cache, materialize_missing_packages, materialize_store,
-    validate_graph_min_package_age,
+    InstallScenario, check_engines, check_store_cache, ensure_direct_not_deprecated,
+    materialize_missing_packages, materialize_store, validate_graph_min_package_age,
+    verify_graph_signatures,
 };
 use super::plan::WorkspaceInstallPlan;
 use super::resolution::resolve_workspace_deps;
@@ -39,19 +40,27 @@ pub(super) async fn load_workspace_graph(
             load_warm_partial_graph(config, registry_client, plan).await
         }
         InstallScenario::Cold => {
-            load_cold_graph(config, registry_client, workspace, plan, include_dev, force).await
+            load_cold_graph(config, registry_client, workspace, plan, force).await
         }
     }?;
 
-    if include_dev
-        && !workspace_graph.wrote_lockfile
-        && plan.setup.has_compatible_lockfile()
-        && !plan.setup.lockfile_path.is_file()
-    {
-        lockfile::write(
+    // Checked before the lockfile is saved, so a failing install leaves
+    // the lockfile in step with the manifests.
+    ensure_direct_not_deprecated(config, &workspace_graph.graph)?;
+    check_engines(config, &workspace_graph.graph)?;
+    if config.verify_signatures {
+        verify_graph_signatures(config, registry_client, &workspace_graph.graph).await?;
+    }
+
+    let needs_lockfile = matches!(plan.scenario, InstallScenario::Cold)
+        || (plan.setup.has_compatible_lockfile() && !plan.setup.lockfile_path.is_file());
+    if include_dev && needs_lockfile {
+        lockfile::write_with_importers(
             &plan.setup.lockfile_path,
             &workspace_graph.graph,
             &plan.setup.root_specs.optional,
+            &plan.setup.importers,
+            &plan.setup.lock_settings,
         )?;
         console::step("Saved lockfile");
         workspace_graph.wrote_lockfile = true;
@@ -122,7 +131,6 @@ async fn load_cold_graph(
     registry_client: &Client,
     workspace: &crate::Workspace,
     plan: &WorkspaceInstallPlan,
-    include_dev: bool,
     force: bool,
 ) -> Result<WorkspaceGraphLoad> {
     console::step("Resolving workspace dependencies");
@@ -134,16 +142,7 @@ async fn load_cold_graph(
     };
 
     let mut store_paths_map = BTreeMap::new();
-    let workspace_sources = workspace
-        .projects
-        .iter()
-        .filter_map(|project| {
-            Some((
-                project.manifest.name.clone()?,
-                project.root.to_string_lossy().into_owned(),
-            ))
-        })
-        .collect::<BTreeMap<_, _>>();
+    let workspace_sources = workspace_sources(workspace);
     let graph = resolve_workspace_deps(
         config,
         registry_client,
@@ -152,6 +151,8 @@ async fn load_cold_graph(
         &plan.setup.optional_root_names,
         force,
         Some(&plan.setup.overrides),
+        Some(&plan.setup.package_extensions),
+        Some(&plan.setup.peer_dependency_rules),
         Some(&workspace_sources),
         existing_graph,
         &mut store_paths_map,
@@ -162,24 +163,72 @@ async fn load_cold_graph(
         store_paths_map = materialize_store(config, &graph, registry_client).await?;
     }
 
-    if include_dev {
-        lockfile::write(
-            &plan.setup.lockfile_path,
-            &graph,
-            &plan.setup.root_specs.optional,
-        )?;
-        console::step("Saved lockfile");
-    }
-
     console::step_with_count("Resolved, downloaded and extracted", store_paths_map.len());
 
     Ok(WorkspaceGraphLoad {
         graph,
         store_paths_map,
-        wrote_lockfile: include_dev,
+        wrote_lockfile: false,
     })
 }
 
+/// Resolves the workspace and writes `snpm-lock.yaml` without downloading
+/// tarballs. Whatever graph the planner loaded (lockfile or install
+/// state) seeds the resolver, so unchanged entries stay pinned.
+pub(super) async fn resolve_lockfile_only(
+    config: &SnpmConfig,
+    registry_client: &Client,
+    workspace: &crate::Workspace,
+    plan: &WorkspaceInstallPlan,
+    force: bool,
+) -> Result<ResolutionGraph> {
+    console::step("Resolving workspace dependencies");
+
+    let graph = crate::resolve::resolve_with_optional_roots_with_seed(
+        config,
+        registry_client,
+        &plan.setup.root_dependencies,
+        &plan.setup.root_protocols,
+        &plan.setup.optional_root_names,
+        config.min_package_age_days,
+        force,
+        Some(&plan.setup.overrides),
+        Some(&plan.setup.package_extensions),
+        Some(&plan.setup.peer_dependency_rules),
+        Some(&workspace_sources(workspace)),
+        plan.scenario_graph.as_ref(),
+        |_package| async move { Ok(()) },
+    )
+    .await?;
+    ensure_direct_not_deprecated(config, &graph)?;
+
+    lockfile::write_with_importers(
+        &plan.setup.lockfile_path,
+        &graph,
+        &plan.setup.root_specs.optional,
+        &plan.setup.importers,
+        &plan.setup.lock_settings,
+    )?;
+    console::step("Saved lockfile");
+
+    Ok(graph)
+}
+
+/// Member names mapped to their roots, so `workspace:` specs resolve to
+/// local sources.
+pub(super) fn workspace_sources(workspace: &crate::Workspace) -> BTreeMap<String, String> {
+    workspace
+        .projects
+        .iter()
+        .filter_map(|project| {
+            Some((
+                project.manifest.name.clone()?,
+                project.root.to_string_lossy().into_owned(),
+            ))
+        })
+        .collect()
+}
+
 fn scenario_graph(plan: &WorkspaceInstallPlan, scenario: &str) -> crate::Result<ResolutionGraph> {
     plan.scenario_graph
         .clone()
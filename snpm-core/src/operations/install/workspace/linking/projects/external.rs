@@ -21,27 +21,25 @@ pub(super) fn link_external_deps(
             continue;
         }
 
-        if let Some(root_dep) = graph.root.dependencies.get(name) {
-            let target = virtual_store_paths.get(&root_dep.resolved).ok_or_else(|| {
-                SnpmError::GraphMissing {
-                    name: root_dep.resolved.name.clone(),
-                    version: root_dep.resolved.version.clone(),
-                }
-            })?;
+        if let Some(package) = graph.linked_package(name) {
+            let target =
+                virtual_store_paths
+                    .get(&package.id)
+                    .ok_or_else(|| SnpmError::GraphMissing {
+                        name: package.id.name.clone(),
+                        version: package.id.version.clone(),
+                    })?;
 
             let destination = node_modules.join(name);
             create_symlink(target, &destination)?;
 
-            let package = graph.packages.get(&root_dep.resolved);
-            let result = match package {
-                Some(pkg) if !pkg.has_bin => Ok(()),
-                Some(pkg) => pkg.bin.as_ref().map_or_else(
+            if package.has_bin {
+                let result = package.bin.as_ref().map_or_else(
                     || link_bins(&destination, node_modules, name),
                     |bin| link_known_bins(&destination, node_modules, name, bin),
-                ),
-                None => link_bins(&destination, node_modules, name),
-            };
-            result.ok();
+                );
+                result.ok();
+            }
         }
     }
 
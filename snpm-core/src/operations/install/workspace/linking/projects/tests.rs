@@ -28,6 +28,12 @@ fn collect_workspace_protocol_deps_filters_correctly() {
             ignored_built_dependencies: Vec::new(),
             disable_global_virtual_store_for_packages: None,
             hoisting: None,
+            package_extensions: Default::default(),
+            peer_dependency_rules: Default::default(),
+            supported_architectures: Default::default(),
+            resolution_mode: None,
+            trust_policy: None,
+            trust_policy_exclude: Vec::new(),
         },
     };
     let project = Project {
@@ -83,6 +89,12 @@ fn collect_workspace_protocol_deps_includes_semver_matched_local_packages() {
             ignored_built_dependencies: Vec::new(),
             disable_global_virtual_store_for_packages: None,
             hoisting: None,
+            package_extensions: Default::default(),
+            peer_dependency_rules: Default::default(),
+            supported_architectures: Default::default(),
+            resolution_mode: None,
+            trust_policy: None,
+            trust_policy_exclude: Vec::new(),
         },
     };
     let project = Project {
@@ -1,6 +1,6 @@
 use super::{populate_virtual_store, rebuild_virtual_store_paths};
 use crate::Workspace;
-use crate::config::{AuthScheme, HoistingMode, LinkBackend, SnpmConfig};
+use crate::config::{AuthScheme, HoistingMode, LinkBackend, OfflineMode, SnpmConfig};
 use crate::resolve::{PackageId, ResolutionGraph, ResolutionRoot, ResolvedPackage, RootDependency};
 use crate::workspace::types::WorkspaceConfig;
 
@@ -26,6 +26,12 @@ fn rebuild_virtual_store_paths_scoped_package() {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     let graph = ResolutionGraph {
         root: ResolutionRoot {
@@ -65,6 +71,16 @@ fn populate_virtual_store_keeps_workspace_configured_root_packages_local() {
         frozen_lockfile_default: false,
         always_auth: false,
         registry_concurrency: 64,
+        offline_mode: OfflineMode::Online,
+        supported_architectures: Default::default(),
+        resolution_mode: Default::default(),
+        before: None,
+        fail_on_deprecated: false,
+        engine_strict: false,
+        engine_targets: Default::default(),
+        verify_signatures: false,
+        trust_policy: Default::default(),
+        trust_policy_exclude: BTreeSet::new(),
         verbose: false,
         log_file: None,
         remote_cache_url: None,
@@ -82,6 +98,12 @@ fn populate_virtual_store_keeps_workspace_configured_root_packages_local() {
             ignored_built_dependencies: Vec::new(),
             disable_global_virtual_store_for_packages: Some(vec!["next".to_string()]),
             hoisting: None,
+            package_extensions: Default::default(),
+            peer_dependency_rules: Default::default(),
+            supported_architectures: Default::default(),
+            resolution_mode: None,
+            trust_policy: None,
+            trust_policy_exclude: Vec::new(),
         },
     };
     let id = PackageId {
@@ -97,6 +119,12 @@ fn populate_virtual_store_keeps_workspace_configured_root_packages_local() {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     let graph = ResolutionGraph {
         root: ResolutionRoot {
@@ -1,3 +1,4 @@
+mod dedupe;
 mod finalize;
 mod graph;
 mod linking;
@@ -7,9 +8,12 @@ mod resolution;
 mod root_specs;
 mod setup;
 
+pub(super) use dedupe::dedupe_workspace_lockfile;
 pub use linking::link_local_workspace_deps;
-pub(crate) use root_specs::collect_workspace_root_specs_with_overrides;
 pub(crate) use root_specs::is_local_workspace_dependency;
+pub(crate) use root_specs::{
+    collect_workspace_importers, collect_workspace_root_specs_with_overrides,
+};
 pub use root_specs::{
     collect_workspace_root_deps, collect_workspace_root_specs, insert_workspace_root_dep,
     validate_workspace_spec,
@@ -20,9 +24,13 @@ use crate::{Result, SnpmConfig, Workspace, http};
 
 use std::time::Instant;
 
-use super::utils::InstallResult;
+use super::effective_config::{ConfigOverrides, effective_config};
+use super::utils::{
+    InstallResult, InstallScenario, load_engine_targets, print_deprecations,
+    record_lockfile_only_project, record_store_project,
+};
 use finalize::finalize_workspace_install;
-use graph::load_workspace_graph;
+use graph::{load_workspace_graph, resolve_lockfile_only};
 use plan::plan_workspace_install;
 
 pub async fn install_workspace(
@@ -32,6 +40,7 @@ pub async fn install_workspace(
     frozen_lockfile: super::utils::FrozenLockfileMode,
     strict_no_lockfile: bool,
     force: bool,
+    lockfile_only: bool,
 ) -> Result<InstallResult> {
     let started = Instant::now();
 
@@ -51,6 +60,16 @@ pub async fn install_workspace(
         strict_no_lockfile,
         force,
     )?;
+    let engine_targets = load_engine_targets(config, &workspace.root).await;
+    let config = &*effective_config(
+        config,
+        ConfigOverrides {
+            supported_architectures: Some(&plan.setup.supported_architectures),
+            resolution_mode: Some(plan.setup.lock_settings.resolution_mode),
+            workspace: Some(workspace),
+            engine_targets: Some(&engine_targets),
+        },
+    );
 
     if plan.setup.root_dependencies.is_empty() {
         console::summary(0, 0.0);
@@ -60,6 +79,36 @@ pub async fn install_workspace(
         });
     }
 
+    if lockfile_only {
+        let installed_lockfile_hash = crate::store::lockfile_hash(&plan.setup.lockfile_path);
+        let graph =
+            resolve_lockfile_only(config, &registry_client, workspace, &plan, force).await?;
+        // Nothing is linked, but a later prune must not free what an
+        // earlier install of this workspace linked.
+        if workspace.root.join("node_modules").is_dir() {
+            record_lockfile_only_project(
+                config,
+                &workspace.root,
+                &plan.setup.lockfile_path,
+                installed_lockfile_hash,
+            );
+        }
+        console::clear_steps(2);
+
+        let package_count = graph.packages.len();
+        console::info(&format!(
+            "Updated {} ({package_count} packages) without installing",
+            plan.setup.lockfile_path.display()
+        ));
+        print_deprecations(&graph);
+
+        return Ok(InstallResult {
+            package_count,
+            elapsed_seconds: started.elapsed().as_secs_f32(),
+        });
+    }
+
+    crate::store::ensure_store_layout(config).await?;
     let workspace_graph = load_workspace_graph(
         config,
         &registry_client,
@@ -92,11 +141,14 @@ pub async fn install_workspace(
             &lockfile_source_path,
             &plan.setup.root_specs.required,
             &plan.setup.root_specs.optional,
+            &plan.setup.lock_settings,
             &workspace_graph.graph,
             include_dev,
         )?;
     }
 
+    record_store_project(config, &workspace.root, &plan.setup.lockfile_path);
+
     console::clear_steps(step_count_for_workspace(
         plan.scenario,
         workspace_graph.wrote_lockfile,
@@ -107,6 +159,10 @@ pub async fn install_workspace(
 
     console::summary(package_count, seconds);
 
+    if !matches!(plan.scenario, InstallScenario::Hot) {
+        print_deprecations(&workspace_graph.graph);
+    }
+
     if !blocked_scripts.is_empty() {
         println!();
         console::blocked_scripts(&blocked_scripts);
@@ -118,13 +174,10 @@ pub async fn install_workspace(
     })
 }
 
-fn step_count_for_workspace(
-    scenario: super::utils::InstallScenario,
-    wrote_lockfile: bool,
-) -> usize {
+fn step_count_for_workspace(scenario: InstallScenario, wrote_lockfile: bool) -> usize {
     let load_steps = match scenario {
-        super::utils::InstallScenario::Hot | super::utils::InstallScenario::WarmLinkOnly => 1,
-        super::utils::InstallScenario::WarmPartialCache | super::utils::InstallScenario::Cold => 2,
+        InstallScenario::Hot | InstallScenario::WarmLinkOnly => 1,
+        InstallScenario::WarmPartialCache | InstallScenario::Cold => 2,
     };
 
     load_steps + 1 + usize::from(wrote_lockfile)
@@ -1,5 +1,10 @@
+use crate::console;
 use crate::lockfile;
-use crate::operations::install::utils::{CacheCheckResult, FrozenLockfileMode};
+use crate::operations::install::effective_config::{ConfigOverrides, effective_config};
+use crate::operations::install::utils::{
+    CacheCheckResult, FrozenLockfileMode, changed_lock_settings, changed_resolve_settings,
+    ensure_lock_settings_unchanged, lock_settings_hash, reconcile_lockfile_conflicts,
+};
 use crate::resolve::ResolutionGraph;
 use crate::{Result, SnpmConfig, Workspace};
 use std::collections::BTreeMap;
@@ -65,8 +70,28 @@ pub(super) fn plan_workspace_install(
     strict_no_lockfile: bool,
     force: bool,
 ) -> Result<WorkspaceInstallPlan> {
-    let mut setup =
-        prepare_workspace_install(workspace, include_dev, frozen_lockfile, strict_no_lockfile)?;
+    let mut setup = prepare_workspace_install(
+        config,
+        workspace,
+        include_dev,
+        frozen_lockfile,
+        strict_no_lockfile,
+    )?;
+    let config = &*effective_config(
+        config,
+        ConfigOverrides {
+            supported_architectures: Some(&setup.supported_architectures),
+            resolution_mode: Some(setup.lock_settings.resolution_mode),
+            workspace: Some(workspace),
+            ..ConfigOverrides::default()
+        },
+    );
+    reconcile_lockfile_conflicts(
+        &setup.lockfile_path,
+        &setup.root_specs.required,
+        &setup.root_specs.optional,
+        frozen_lockfile,
+    )?;
     let lockfile_source_path = setup.lockfile_source_path();
     let mut scenario_artifacts = if matches!(frozen_lockfile, FrozenLockfileMode::Fix) {
         let existing_lockfile = read_lockfile_for_fix(&setup, config).ok();
@@ -76,6 +101,9 @@ pub(super) fn plan_workspace_install(
             graph: existing_lockfile.as_ref().map(lockfile::to_graph),
             cache_check: None,
             lockfile_checked: existing_lockfile.is_some(),
+            lock_settings_hash: existing_lockfile
+                .as_ref()
+                .map(|existing| lock_settings_hash(&existing.settings)),
         }
     } else {
         detect_workspace_scenario_early(
@@ -99,11 +127,43 @@ pub(super) fn plan_workspace_install(
         scenario_artifacts.existing_lockfile.take(),
         &setup.root_specs.required,
         &setup.root_specs.optional,
+        &setup.importers,
         scenario_artifacts.lockfile_checked,
     )?;
     scenario_artifacts.scenario = scenario;
     scenario_artifacts.existing_lockfile = existing_lockfile;
 
+    // A lockfile resolved under other settings (say, an edited
+    // packageExtensions) can neither be installed as-is nor seed the
+    // resolver. Fix mode still pins root versions from it below. A frozen
+    // install only compares the recorded settings, since it installs what
+    // the lockfile has either way.
+    if setup.lockfile_path.is_file() || setup.has_compatible_lockfile() {
+        let recorded = scenario_artifacts.lock_settings_hash.as_deref();
+        if matches!(frozen_lockfile, FrozenLockfileMode::Frozen) {
+            ensure_lock_settings_unchanged(
+                &setup.lockfile_path,
+                changed_lock_settings(&setup.lockfile_path, recorded, &setup.lock_settings),
+            )?;
+        } else {
+            let changed = changed_resolve_settings(
+                &setup.lockfile_path,
+                recorded,
+                &setup.lock_settings,
+                &setup.supported_architectures,
+            );
+            if !changed.is_empty() {
+                console::verbose(&format!(
+                    "{} changed since the lockfile was written; re-resolving",
+                    changed.join(", ")
+                ));
+                scenario_artifacts.scenario = InstallScenario::Cold;
+                scenario_artifacts.graph = None;
+                scenario_artifacts.cache_check = None;
+            }
+        }
+    }
+
     if matches!(frozen_lockfile, FrozenLockfileMode::Fix) {
         setup.root_dependencies = pinned_workspace_root_dependencies_for_fix(
             &setup,
@@ -173,6 +233,16 @@ mod tests {
             frozen_lockfile_default: false,
             always_auth: false,
             registry_concurrency: 64,
+            offline_mode: OfflineMode::Online,
+            supported_architectures: Default::default(),
+            resolution_mode: Default::default(),
+            before: None,
+            fail_on_deprecated: false,
+            engine_strict: false,
+            engine_targets: Default::default(),
+            verify_signatures: false,
+            trust_policy: Default::default(),
+            trust_policy_exclude: BTreeSet::new(),
             verbose: false,
             log_file: None,
             remote_cache_url: None,
@@ -206,6 +276,7 @@ mod tests {
 
         let lockfile = Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: BTreeMap::from([
                     (
@@ -228,6 +299,7 @@ mod tests {
                     ),
                 ]),
             },
+            importers: BTreeMap::new(),
             packages: std::collections::BTreeMap::new(),
         };
         write_lockfile(dir.path(), lockfile);
@@ -261,10 +333,15 @@ mod tests {
             lockfile_path: Path::new("/tmp/snpm-lock.yaml").to_path_buf(),
             compatible_lockfile: None,
             overrides: BTreeMap::new(),
+            package_extensions: BTreeMap::new(),
+            lock_settings: Default::default(),
+            peer_dependency_rules: Default::default(),
+            supported_architectures: Default::default(),
             root_specs: RootSpecSet {
                 required: BTreeMap::from([("left".to_string(), "^1.0.0".to_string())]),
                 optional: BTreeMap::from([("right".to_string(), "^2.0.0".to_string())]),
             },
+            importers: BTreeMap::new(),
             root_dependencies: BTreeMap::from([
                 ("left".to_string(), "^1.0.0".to_string()),
                 ("right".to_string(), "^2.0.0".to_string()),
@@ -275,6 +352,7 @@ mod tests {
 
         let lockfile = Lockfile {
             version: 1,
+            settings: Default::default(),
             root: LockRoot {
                 dependencies: BTreeMap::from([
                     (
@@ -297,6 +375,7 @@ mod tests {
                     ),
                 ]),
             },
+            importers: BTreeMap::new(),
             packages: std::collections::BTreeMap::new(),
         };
 
@@ -350,10 +429,15 @@ mod tests {
             lockfile_path: dir.path().join("missing.yaml"),
             compatible_lockfile: None,
             overrides: BTreeMap::new(),
+            package_extensions: BTreeMap::new(),
+            lock_settings: Default::default(),
+            peer_dependency_rules: Default::default(),
+            supported_architectures: Default::default(),
             root_specs: RootSpecSet {
                 required: BTreeMap::new(),
                 optional: BTreeMap::new(),
             },
+            importers: BTreeMap::new(),
             root_dependencies: BTreeMap::new(),
             root_protocols: BTreeMap::new(),
             optional_root_names: BTreeSet::new(),
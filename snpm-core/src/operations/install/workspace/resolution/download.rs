@@ -1,5 +1,5 @@
 use crate::console;
-use crate::resolve::{self, PackageId, ResolutionGraph};
+use crate::resolve::{self, PackageExtensions, PackageId, PeerDependencyRules, ResolutionGraph};
 use crate::store;
 use crate::{Result, SnpmConfig, SnpmError};
 
@@ -20,6 +20,8 @@ pub(crate) async fn resolve_workspace_deps(
     optional_root_names: &BTreeSet<String>,
     force: bool,
     overrides: Option<&BTreeMap<String, String>>,
+    package_extensions: Option<&PackageExtensions>,
+    peer_rules: Option<&PeerDependencyRules>,
     workspace_sources: Option<&BTreeMap<String, String>>,
     existing_graph: Option<&ResolutionGraph>,
     store_paths: &mut BTreeMap<PackageId, PathBuf>,
@@ -51,6 +53,8 @@ pub(crate) async fn resolve_workspace_deps(
                 min_age,
                 force,
                 overrides,
+                package_extensions,
+                peer_rules,
                 workspace_sources,
                 Some(seed_graph),
                 move |package| {
@@ -99,6 +103,8 @@ pub(crate) async fn resolve_workspace_deps(
                 min_age,
                 force,
                 overrides,
+                package_extensions,
+                peer_rules,
                 workspace_sources,
                 move |package| {
                     let config = config_clone.clone();
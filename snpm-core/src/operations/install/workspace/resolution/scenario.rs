@@ -9,7 +9,7 @@ use std::path::Path;
 use super::super::super::utils::{
     CacheCheckResult, InstallScenario, IntegrityState, build_workspace_integrity_state,
     check_integrity_path, check_store_cache, check_workspace_layout_state, load_graph_snapshot,
-    load_workspace_install_state, write_integrity_path,
+    load_workspace_install_state, lock_settings_hash, write_integrity_path,
 };
 
 pub(crate) struct WorkspaceScenarioArtifacts {
@@ -18,6 +18,9 @@ pub(crate) struct WorkspaceScenarioArtifacts {
     pub(crate) graph: Option<ResolutionGraph>,
     pub(crate) cache_check: Option<CacheCheckResult>,
     pub(crate) lockfile_checked: bool,
+    /// `lock_settings_hash` of the settings `graph` was resolved under,
+    /// when the install state or a parsed lockfile recorded them.
+    pub(crate) lock_settings_hash: Option<String>,
 }
 
 impl WorkspaceScenarioArtifacts {
@@ -28,6 +31,7 @@ impl WorkspaceScenarioArtifacts {
             graph: None,
             cache_check: None,
             lockfile_checked,
+            lock_settings_hash: None,
         }
     }
 }
@@ -66,15 +70,18 @@ pub(crate) fn detect_workspace_scenario_early(
         )
     {
         if state.root_specs_matches {
-            return detect_from_graph(
-                workspace,
-                config,
-                state.graph,
-                force,
-                None,
-                true,
-                Some(state.layout_valid),
-            );
+            return WorkspaceScenarioArtifacts {
+                lock_settings_hash: Some(state.lock_settings_hash),
+                ..detect_from_graph(
+                    workspace,
+                    config,
+                    state.graph,
+                    force,
+                    None,
+                    true,
+                    Some(state.layout_valid),
+                )
+            };
         }
 
         return WorkspaceScenarioArtifacts {
@@ -83,6 +90,7 @@ pub(crate) fn detect_workspace_scenario_early(
             graph: Some(state.graph),
             cache_check: None,
             lockfile_checked: true,
+            lock_settings_hash: Some(state.lock_settings_hash),
         };
     }
 
@@ -99,6 +107,7 @@ pub(crate) fn detect_workspace_scenario_early(
     };
 
     let graph = lockfile::to_graph(&existing);
+    let settings_hash = lock_settings_hash(&existing.settings);
     if !lockfile::root_specs_match(&existing, required_root, optional_root) {
         return WorkspaceScenarioArtifacts {
             scenario: InstallScenario::Cold,
@@ -106,10 +115,14 @@ pub(crate) fn detect_workspace_scenario_early(
             graph: Some(graph),
             cache_check: None,
             lockfile_checked: true,
+            lock_settings_hash: Some(settings_hash),
         };
     }
 
-    detect_from_graph(workspace, config, graph, force, Some(existing), true, None)
+    WorkspaceScenarioArtifacts {
+        lock_settings_hash: Some(settings_hash),
+        ..detect_from_graph(workspace, config, graph, force, Some(existing), true, None)
+    }
 }
 
 fn detect_from_graph(
@@ -130,6 +143,7 @@ fn detect_from_graph(
                 graph: Some(graph),
                 cache_check: None,
                 lockfile_checked,
+                lock_settings_hash: None,
             };
         }
     };
@@ -144,6 +158,7 @@ fn detect_from_graph(
             graph: Some(graph),
             cache_check: None,
             lockfile_checked,
+            lock_settings_hash: None,
         };
     }
 
@@ -159,6 +174,7 @@ fn detect_from_graph(
             graph: Some(graph),
             cache_check: None,
             lockfile_checked,
+            lock_settings_hash: None,
         };
     }
     if cache_check.missing.is_empty() {
@@ -168,6 +184,7 @@ fn detect_from_graph(
             graph: Some(graph),
             cache_check: Some(cache_check),
             lockfile_checked,
+            lock_settings_hash: None,
         };
     }
 
@@ -177,6 +194,7 @@ fn detect_from_graph(
         graph: Some(graph),
         cache_check: Some(cache_check),
         lockfile_checked,
+        lock_settings_hash: None,
     }
 }
 
@@ -189,6 +207,7 @@ pub(crate) fn validate_lockfile_matches_manifest(
     lockfile: Option<lockfile::Lockfile>,
     required_root: &BTreeMap<String, String>,
     optional_root: &BTreeMap<String, String>,
+    importers: &BTreeMap<String, lockfile::LockImporter>,
     lockfile_checked: bool,
 ) -> Result<(InstallScenario, Option<lockfile::Lockfile>)> {
     if let Some(ref existing) = lockfile
@@ -205,6 +224,26 @@ pub(crate) fn validate_lockfile_matches_manifest(
         return Ok((InstallScenario::Cold, lockfile));
     }
 
+    // The flattened root can match while an individual project's specs
+    // moved (e.g. a member adds a dependency another member already has),
+    // so the per-project importers are checked separately.
+    if let Some(ref existing) = lockfile {
+        let drifted = lockfile::importer_drift(existing, importers);
+        if !drifted.is_empty() {
+            if matches!(frozen_lockfile, FrozenLockfileMode::Frozen) {
+                return Err(crate::SnpmError::Lockfile {
+                    path: lockfile_path.to_path_buf(),
+                    reason: format!(
+                        "dependencies of {} do not match the existing lockfile when using frozen-lockfile",
+                        drifted.join(", ")
+                    ),
+                });
+            }
+
+            return Ok((InstallScenario::Cold, lockfile));
+        }
+    }
+
     if strict_no_lockfile
         && matches!(frozen_lockfile, FrozenLockfileMode::Frozen)
         && !lockfile_checked
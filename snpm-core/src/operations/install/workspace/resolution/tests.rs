@@ -16,6 +16,7 @@ use super::super::super::utils::InstallScenario;
 fn validate_lockfile_matches_returns_cold_on_mismatch() {
     let lockfile = lockfile::Lockfile {
         version: 1,
+        settings: Default::default(),
         root: lockfile::LockRoot {
             dependencies: BTreeMap::from([(
                 "a".to_string(),
@@ -27,6 +28,7 @@ fn validate_lockfile_matches_returns_cold_on_mismatch() {
                 },
             )]),
         },
+        importers: BTreeMap::new(),
         packages: BTreeMap::new(),
     };
 
@@ -39,6 +41,7 @@ fn validate_lockfile_matches_returns_cold_on_mismatch() {
         Some(lockfile),
         &required,
         &BTreeMap::new(),
+        &BTreeMap::new(),
         true,
     )
     .expect("frozen lockfile should not fail in prefer mode");
@@ -50,6 +53,7 @@ fn validate_lockfile_matches_returns_cold_on_mismatch() {
 fn validate_lockfile_matches_preserves_scenario_on_match() {
     let lockfile = lockfile::Lockfile {
         version: 1,
+        settings: Default::default(),
         root: lockfile::LockRoot {
             dependencies: BTreeMap::from([(
                 "a".to_string(),
@@ -61,6 +65,7 @@ fn validate_lockfile_matches_preserves_scenario_on_match() {
                 },
             )]),
         },
+        importers: BTreeMap::new(),
         packages: BTreeMap::new(),
     };
 
@@ -73,6 +78,7 @@ fn validate_lockfile_matches_preserves_scenario_on_match() {
         Some(lockfile),
         &required,
         &BTreeMap::new(),
+        &BTreeMap::new(),
         true,
     )
     .expect("frozen lockfile should not fail in prefer mode");
@@ -84,6 +90,7 @@ fn validate_lockfile_matches_preserves_scenario_on_match() {
 fn validate_lockfile_matches_fix_mode_rewrites_to_cold_on_root_mismatch() {
     let lockfile = lockfile::Lockfile {
         version: 1,
+        settings: Default::default(),
         root: lockfile::LockRoot {
             dependencies: BTreeMap::from([(
                 "a".to_string(),
@@ -95,6 +102,7 @@ fn validate_lockfile_matches_fix_mode_rewrites_to_cold_on_root_mismatch() {
                 },
             )]),
         },
+        importers: BTreeMap::new(),
         packages: BTreeMap::new(),
     };
 
@@ -107,6 +115,7 @@ fn validate_lockfile_matches_fix_mode_rewrites_to_cold_on_root_mismatch() {
         Some(lockfile),
         &required,
         &BTreeMap::new(),
+        &BTreeMap::new(),
         true,
     )
     .expect("fix mode should preserve lockfile validation and force cold resolution");
@@ -124,6 +133,7 @@ fn validate_lockfile_matches_frozen_mode_with_strict_missing_lockfile_errors() {
         None,
         &BTreeMap::new(),
         &BTreeMap::new(),
+        &BTreeMap::new(),
         false,
     )
     .expect_err("frozen mode requires lockfile when strict flag is enabled");
@@ -135,6 +145,85 @@ fn validate_lockfile_matches_frozen_mode_with_strict_missing_lockfile_errors() {
     );
 }
 
+#[test]
+fn validate_lockfile_matches_frozen_mode_names_drifted_importers() {
+    let root_dep = lockfile::LockRootDependency {
+        requested: "^1.0.0".to_string(),
+        package: None,
+        version: Some("1.0.0".to_string()),
+        optional: false,
+    };
+    let lockfile = lockfile::Lockfile {
+        version: 1,
+        settings: Default::default(),
+        root: lockfile::LockRoot {
+            dependencies: BTreeMap::from([("a".to_string(), root_dep.clone())]),
+        },
+        importers: BTreeMap::from([
+            (
+                "packages/app".to_string(),
+                lockfile::LockImporter {
+                    dependencies: BTreeMap::from([("a".to_string(), root_dep.clone())]),
+                    ..lockfile::LockImporter::default()
+                },
+            ),
+            (
+                "packages/lib".to_string(),
+                lockfile::LockImporter::default(),
+            ),
+        ]),
+        packages: BTreeMap::new(),
+    };
+
+    // `packages/lib` now also depends on `a`; the flattened root is unchanged.
+    let importers = BTreeMap::from([
+        (
+            "packages/app".to_string(),
+            lockfile::LockImporter {
+                dependencies: BTreeMap::from([("a".to_string(), root_dep.clone())]),
+                ..lockfile::LockImporter::default()
+            },
+        ),
+        (
+            "packages/lib".to_string(),
+            lockfile::LockImporter {
+                dependencies: BTreeMap::from([("a".to_string(), root_dep)]),
+                ..lockfile::LockImporter::default()
+            },
+        ),
+    ]);
+    let required = BTreeMap::from([("a".to_string(), "^1.0.0".to_string())]);
+
+    let error = validate_lockfile_matches_manifest(
+        FrozenLockfileMode::Frozen,
+        std::path::Path::new("snpm-lock.yaml"),
+        false,
+        InstallScenario::WarmLinkOnly,
+        Some(lockfile.clone()),
+        &required,
+        &BTreeMap::new(),
+        &importers,
+        true,
+    )
+    .expect_err("frozen mode should reject importer drift");
+    assert!(error.to_string().contains("packages/lib"));
+    assert!(!error.to_string().contains("packages/app"));
+
+    let (scenario, _) = validate_lockfile_matches_manifest(
+        FrozenLockfileMode::Prefer,
+        std::path::Path::new("snpm-lock.yaml"),
+        false,
+        InstallScenario::WarmLinkOnly,
+        Some(lockfile),
+        &required,
+        &BTreeMap::new(),
+        &importers,
+        true,
+    )
+    .expect("prefer mode should re-resolve instead of failing");
+    assert_eq!(scenario, InstallScenario::Cold);
+}
+
 fn make_config() -> SnpmConfig {
     SnpmConfig::for_tests()
 }
@@ -160,6 +249,12 @@ fn make_workspace(root: &Path) -> Workspace {
             ignored_built_dependencies: Vec::new(),
             disable_global_virtual_store_for_packages: None,
             hoisting: None,
+            package_extensions: Default::default(),
+            peer_dependency_rules: Default::default(),
+            supported_architectures: Default::default(),
+            resolution_mode: None,
+            trust_policy: None,
+            trust_policy_exclude: Vec::new(),
         },
     }
 }
@@ -191,6 +286,12 @@ fn make_graph() -> ResolutionGraph {
                 bundled_dependencies: None,
                 has_bin: false,
                 bin: None,
+                os: Vec::new(),
+                cpu: Vec::new(),
+                libc: Vec::new(),
+                engines: BTreeMap::new(),
+                deprecated: None,
+                has_install_script: false,
             },
         )]),
     }
@@ -1,3 +1,4 @@
+use crate::lockfile::{LockImporter, LockRootDependency, importer_key};
 use crate::{Project, Result, Workspace};
 
 use std::collections::{BTreeMap, BTreeSet};
@@ -67,11 +68,26 @@ fn build_member_root_specs(
     include_dev: bool,
     overrides: &BTreeMap<String, String>,
 ) -> Result<RootSpecSet> {
-    let dependencies = apply_member_specs(&member.manifest.dependencies, workspace, overrides)?;
-    let optional_dependencies =
-        apply_member_specs(&member.manifest.optional_dependencies, workspace, overrides)?;
+    let mut local = BTreeSet::new();
+    let dependencies = apply_member_specs(
+        &member.manifest.dependencies,
+        workspace,
+        overrides,
+        &mut local,
+    )?;
+    let optional_dependencies = apply_member_specs(
+        &member.manifest.optional_dependencies,
+        workspace,
+        overrides,
+        &mut local,
+    )?;
     let development_dependencies = if include_dev {
-        apply_member_specs(&member.manifest.dev_dependencies, workspace, overrides)?
+        apply_member_specs(
+            &member.manifest.dev_dependencies,
+            workspace,
+            overrides,
+            &mut local,
+        )?
     } else {
         BTreeMap::new()
     };
@@ -84,13 +100,92 @@ fn build_member_root_specs(
     ))
 }
 
+/// Per-project importer entries for the lockfile, keyed by each member's
+/// path relative to the workspace root. Specs go through the same catalog,
+/// override and `workspace:` handling as the flattened root; resolved
+/// versions are left for the lockfile writer to fill in.
+pub(crate) fn collect_workspace_importers(
+    workspace: &Workspace,
+    include_dev: bool,
+    overrides: &BTreeMap<String, String>,
+) -> Result<BTreeMap<String, LockImporter>> {
+    let mut importers = BTreeMap::new();
+
+    for member in &workspace.projects {
+        let mut local = BTreeSet::new();
+        let dependencies = apply_member_specs(
+            &member.manifest.dependencies,
+            workspace,
+            overrides,
+            &mut local,
+        )?;
+        let optional_dependencies = apply_member_specs(
+            &member.manifest.optional_dependencies,
+            workspace,
+            overrides,
+            &mut local,
+        )?;
+        let development_dependencies = if include_dev {
+            apply_member_specs(
+                &member.manifest.dev_dependencies,
+                workspace,
+                overrides,
+                &mut local,
+            )?
+        } else {
+            BTreeMap::new()
+        };
+
+        let mut importer = LockImporter::default();
+        for (name, range) in &dependencies {
+            if !optional_dependencies.contains_key(name) {
+                importer
+                    .dependencies
+                    .insert(name.clone(), importer_spec(range, false));
+            }
+        }
+        for (name, range) in &optional_dependencies {
+            importer
+                .dependencies
+                .insert(name.clone(), importer_spec(range, true));
+        }
+        for (name, range) in &development_dependencies {
+            if !importer.dependencies.contains_key(name) {
+                importer
+                    .dev_dependencies
+                    .insert(name.clone(), importer_spec(range, false));
+            }
+        }
+        for name in local {
+            if let Some(project) = workspace.project_by_name(&name) {
+                importer
+                    .links
+                    .insert(name, importer_key(&workspace.root, &project.root));
+            }
+        }
+
+        importers.insert(importer_key(&workspace.root, &member.root), importer);
+    }
+
+    Ok(importers)
+}
+
+fn importer_spec(range: &str, optional: bool) -> LockRootDependency {
+    LockRootDependency {
+        requested: range.to_string(),
+        package: None,
+        version: None,
+        optional,
+    }
+}
+
 fn apply_member_specs(
     manifest_deps: &BTreeMap<String, String>,
     workspace: &Workspace,
     overrides: &BTreeMap<String, String>,
+    local: &mut BTreeSet<String>,
 ) -> Result<BTreeMap<String, String>> {
-    let mut local = BTreeSet::new();
-    let mut applied = apply_specs(manifest_deps, Some(workspace), None, &mut local, None)?;
+    let mut applied = apply_specs(manifest_deps, Some(workspace), None, local, None)?;
 
     for (name, range) in &mut applied {
         if let Some(override_range) = select_override(name, range, overrides) {
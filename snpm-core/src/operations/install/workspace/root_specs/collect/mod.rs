@@ -1,6 +1,8 @@
 mod members;
 mod ranges;
 
-pub(crate) use members::collect_workspace_root_specs_with_overrides;
+pub(crate) use members::{
+    collect_workspace_importers, collect_workspace_root_specs_with_overrides,
+};
 pub use members::{collect_workspace_root_deps, collect_workspace_root_specs};
 pub use ranges::insert_workspace_root_dep;
@@ -1,7 +1,9 @@
 mod collect;
 mod validate;
 
-pub(crate) use collect::collect_workspace_root_specs_with_overrides;
+pub(crate) use collect::{
+    collect_workspace_importers, collect_workspace_root_specs_with_overrides,
+};
 pub use collect::{
     collect_workspace_root_deps, collect_workspace_root_specs, insert_workspace_root_dep,
 };
@@ -26,6 +26,12 @@ fn make_workspace_with_project(name: &str, version: Option<&str>) -> Workspace {
             ignored_built_dependencies: Vec::new(),
             disable_global_virtual_store_for_packages: None,
             hoisting: None,
+            package_extensions: Default::default(),
+            peer_dependency_rules: Default::default(),
+            supported_architectures: Default::default(),
+            resolution_mode: None,
+            trust_policy: None,
+            trust_policy_exclude: Vec::new(),
         },
     }
 }
@@ -132,3 +138,40 @@ fn insert_workspace_root_dep_picks_winner_for_mutually_exclusive_ranges() {
 
     assert_eq!(combined.get("lodash").map(String::as_str), Some("^4.0.0"));
 }
+
+#[test]
+fn collect_workspace_importers_keeps_each_project_view() {
+    let mut ws = make_workspace_with_project("lib", Some("1.0.0"));
+    let app_root = ws.root.join("packages").join("app");
+    ws.projects[0].root = ws.root.join("packages").join("lib");
+    ws.projects.push(crate::Project {
+        root: app_root.clone(),
+        manifest_path: app_root.join("package.json"),
+        manifest: Manifest {
+            name: Some("app".to_string()),
+            dependencies: BTreeMap::from([
+                ("lib".to_string(), "workspace:*".to_string()),
+                ("shared".to_string(), "^1.0.0".to_string()),
+            ]),
+            optional_dependencies: BTreeMap::from([("fsevents".to_string(), "^2.0.0".to_string())]),
+            dev_dependencies: BTreeMap::from([
+                ("shared".to_string(), "^1.5.0".to_string()),
+                ("jest".to_string(), "^29.0.0".to_string()),
+            ]),
+            ..Manifest::default()
+        },
+    });
+
+    let importers = collect_workspace_importers(&ws, true, &BTreeMap::new()).unwrap();
+
+    assert!(importers["packages/lib"].dependencies.is_empty());
+    let app = &importers["packages/app"];
+    assert_eq!(app.links["lib"], "packages/lib");
+    assert_eq!(app.dependencies["shared"].requested, "^1.0.0");
+    assert!(app.dependencies["fsevents"].optional);
+    assert!(!app.dev_dependencies.contains_key("shared"));
+    assert_eq!(app.dev_dependencies["jest"].requested, "^29.0.0");
+
+    let without_dev = collect_workspace_importers(&ws, false, &BTreeMap::new()).unwrap();
+    assert!(without_dev["packages/app"].dev_dependencies.is_empty());
+}
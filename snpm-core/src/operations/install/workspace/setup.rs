@@ -1,8 +1,17 @@
-use crate::lockfile::CompatibleLockfile;
-use crate::operations::install::utils::FrozenLockfileMode;
+use crate::lockfile::{CompatibleLockfile, LockImporter, LockSettings};
+use crate::operations::install::package_extensions::load_workspace_package_extensions;
+use crate::operations::install::peer_dependency_rules::load_workspace_peer_dependency_rules;
+use crate::operations::install::resolution_mode::load_resolution_mode;
+use crate::operations::install::supported_architectures::{
+    configured_workspace_supported_architectures, load_workspace_supported_architectures,
+};
+use crate::operations::install::utils::{FrozenLockfileMode, before_setting};
+use crate::platform::SupportedArchitectures;
 use crate::registry::RegistryProtocol;
+use crate::resolve::extensions::package_extensions_checksum;
+use crate::resolve::{PackageExtensions, PeerDependencyRules};
 use crate::workspace::OverridesConfig;
-use crate::{Result, SnpmError, Workspace};
+use crate::{Result, SnpmConfig, SnpmError, Workspace};
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
@@ -12,13 +21,19 @@ pub(super) struct WorkspaceInstallSetup {
     pub(super) lockfile_path: PathBuf,
     pub(super) compatible_lockfile: Option<CompatibleLockfile>,
     pub(super) overrides: BTreeMap<String, String>,
+    pub(super) package_extensions: PackageExtensions,
+    pub(super) lock_settings: LockSettings,
+    pub(super) peer_dependency_rules: PeerDependencyRules,
+    pub(super) supported_architectures: SupportedArchitectures,
     pub(super) root_specs: RootSpecSet,
+    pub(super) importers: BTreeMap<String, LockImporter>,
     pub(super) root_dependencies: BTreeMap<String, String>,
     pub(super) root_protocols: BTreeMap<String, RegistryProtocol>,
     pub(super) optional_root_names: BTreeSet<String>,
 }
 
 pub(super) fn prepare_workspace_install(
+    config: &SnpmConfig,
     workspace: &Workspace,
     include_dev: bool,
     frozen_lockfile: FrozenLockfileMode,
@@ -42,12 +57,22 @@ pub(super) fn prepare_workspace_install(
     }
 
     let overrides = load_workspace_overrides(workspace)?;
+    let package_extensions = load_workspace_package_extensions(workspace)?;
+    let peer_dependency_rules = load_workspace_peer_dependency_rules(workspace)?;
+    let supported_architectures = load_workspace_supported_architectures(config, workspace);
+    let lock_settings = LockSettings {
+        package_extensions_checksum: package_extensions_checksum(&package_extensions),
+        supported_architectures: configured_workspace_supported_architectures(workspace),
+        resolution_mode: load_resolution_mode(config, Some(workspace)),
+        before: before_setting(config),
+    };
     let root_specs =
         crate::operations::install::workspace::collect_workspace_root_specs_with_overrides(
             workspace,
             include_dev,
             &overrides,
         )?;
+    let importers = super::collect_workspace_importers(workspace, include_dev, &overrides)?;
     let mut root_dependencies = root_specs.required.clone();
     for (name, range) in &root_specs.optional {
         root_dependencies.insert(name.clone(), range.clone());
@@ -56,10 +81,15 @@ pub(super) fn prepare_workspace_install(
     Ok(WorkspaceInstallSetup {
         compatible_lockfile,
         overrides,
+        package_extensions,
+        lock_settings,
+        peer_dependency_rules,
+        supported_architectures,
         optional_root_names: root_specs.optional.keys().cloned().collect(),
         root_protocols: build_root_protocols(&root_dependencies),
         root_dependencies,
         root_specs,
+        importers,
         lockfile_path,
     })
 }
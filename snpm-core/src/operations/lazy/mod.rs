@@ -43,6 +43,7 @@ pub async fn lazy_install_with_mode(
         frozen_lockfile,
         strict_no_lockfile,
         force: false,
+        lockfile_only: false,
         silent_summary: true,
     };
 
@@ -1,6 +1,8 @@
 use crate::lockfile;
 use crate::{Project, SnpmConfig, Workspace};
 
+use super::super::install::effective_config::{ConfigOverrides, effective_config};
+use super::super::install::supported_architectures::load_supported_architectures;
 use super::super::install::{
     build_project_integrity_state, check_integrity_file, check_project_layout_state,
     check_workspace_layout_state, load_project_install_state_fast,
@@ -27,6 +29,17 @@ pub(super) fn check_staleness(
     project: &Project,
     workspace: Option<&Workspace>,
 ) -> StalenessCheck {
+    // Install lays node_modules out for these targets, so the layout
+    // hashes below must be computed for them too.
+    let targets = load_supported_architectures(config, project, workspace);
+    let config = &*effective_config(
+        config,
+        ConfigOverrides {
+            supported_architectures: Some(&targets),
+            ..ConfigOverrides::default()
+        },
+    );
+
     let lockfile_path = workspace
         .map(|workspace| workspace.root.join("snpm-lock.yaml"))
         .unwrap_or_else(|| project.root.join("snpm-lock.yaml"));
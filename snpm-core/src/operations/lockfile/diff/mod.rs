@@ -0,0 +1,269 @@
+use crate::lockfile::{self, LockPackage, Lockfile};
+use crate::{Result, SnpmError};
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Where one side of a diff comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffSource {
+    /// `snpm-lock.yaml` at the project or workspace root, as it is on disk.
+    WorkingTree,
+    File(PathBuf),
+    /// `snpm-lock.yaml` at a git revision (`HEAD`, `main`, `origin/main~3`).
+    Revision(String),
+}
+
+impl DiffSource {
+    pub fn label(&self) -> String {
+        match self {
+            DiffSource::WorkingTree => "working tree".to_string(),
+            DiffSource::File(path) => path.display().to_string(),
+            DiffSource::Revision(revision) => revision.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Upgraded,
+    Downgraded,
+    /// Same version, different `integrity`: the tarball was republished or
+    /// the lockfile was tampered with.
+    IntegrityChanged,
+}
+
+impl ChangeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Upgraded => "upgraded",
+            ChangeKind::Downgraded => "downgraded",
+            ChangeKind::IntegrityChanged => "integrity changed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageChange {
+    pub name: String,
+    pub kind: ChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_integrity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_integrity: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScriptPackage {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockfileDiff {
+    pub from: String,
+    pub to: String,
+    pub changes: Vec<PackageChange>,
+    /// Versions new in `to` that the lockfile records as having an
+    /// install script; they run code on every machine that installs them.
+    pub new_lifecycle_scripts: Vec<ScriptPackage>,
+}
+
+impl LockfileDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.new_lifecycle_scripts.is_empty()
+    }
+}
+
+/// Compares the `snpm-lock.yaml` of the project or workspace at `root`
+/// between two sources, including which newly introduced versions have
+/// install scripts. Works from the two lockfiles alone, without the
+/// registry.
+pub fn diff(root: &Path, from: &DiffSource, to: &DiffSource) -> Result<LockfileDiff> {
+    let old = load(root, from)?;
+    let new = load(root, to)?;
+
+    let introduced: Vec<&LockPackage> = new
+        .packages
+        .iter()
+        .filter(|(key, _)| !old.packages.contains_key(*key))
+        .map(|(_, package)| package)
+        .collect();
+
+    Ok(LockfileDiff {
+        from: from.label(),
+        to: to.label(),
+        changes: compare(&old, &new),
+        new_lifecycle_scripts: packages_with_install_scripts(&introduced),
+    })
+}
+
+/// Package-level changes from `old` to `new`, sorted by name. A name
+/// whose versions changed pairs removed versions with added ones in
+/// order, so a single bump reads as one upgrade or downgrade.
+pub fn compare(old: &Lockfile, new: &Lockfile) -> Vec<PackageChange> {
+    let old_versions = versions_by_name(old);
+    let new_versions = versions_by_name(new);
+    let names: BTreeSet<&str> = old_versions
+        .keys()
+        .chain(new_versions.keys())
+        .copied()
+        .collect();
+
+    let empty = BTreeMap::new();
+    let mut changes = Vec::new();
+
+    for name in names {
+        let before = old_versions.get(name).unwrap_or(&empty);
+        let after = new_versions.get(name).unwrap_or(&empty);
+
+        for (version, package) in before {
+            if let Some(updated) = after.get(version)
+                && package.integrity != updated.integrity
+            {
+                changes.push(PackageChange {
+                    name: name.to_string(),
+                    kind: ChangeKind::IntegrityChanged,
+                    from: Some(version.to_string()),
+                    to: Some(version.to_string()),
+                    from_integrity: package.integrity.clone(),
+                    to_integrity: updated.integrity.clone(),
+                });
+            }
+        }
+
+        let mut removed = sorted_versions(before.keys().filter(|v| !after.contains_key(*v)));
+        let mut added = sorted_versions(after.keys().filter(|v| !before.contains_key(*v)));
+
+        while !removed.is_empty() && !added.is_empty() {
+            let from = removed.remove(0);
+            let to = added.remove(0);
+            let kind = if is_newer(to, from) {
+                ChangeKind::Upgraded
+            } else {
+                ChangeKind::Downgraded
+            };
+            changes.push(change(name, kind, Some(from), Some(to)));
+        }
+        for from in removed {
+            changes.push(change(name, ChangeKind::Removed, Some(from), None));
+        }
+        for to in added {
+            changes.push(change(name, ChangeKind::Added, None, Some(to)));
+        }
+    }
+
+    changes
+}
+
+fn change(name: &str, kind: ChangeKind, from: Option<&str>, to: Option<&str>) -> PackageChange {
+    PackageChange {
+        name: name.to_string(),
+        kind,
+        from: from.map(str::to_string),
+        to: to.map(str::to_string),
+        from_integrity: None,
+        to_integrity: None,
+    }
+}
+
+fn versions_by_name(lockfile: &Lockfile) -> BTreeMap<&str, BTreeMap<&str, &LockPackage>> {
+    let mut versions: BTreeMap<&str, BTreeMap<&str, &LockPackage>> = BTreeMap::new();
+    for package in lockfile.packages.values() {
+        versions
+            .entry(package.name.as_str())
+            .or_default()
+            .insert(package.version.as_str(), package);
+    }
+    versions
+}
+
+/// Oldest first by semver, falling back to string order for versions
+/// that do not parse.
+fn sorted_versions<'a>(versions: impl Iterator<Item = &'a &'a str>) -> Vec<&'a str> {
+    let mut versions: Vec<&str> = versions.copied().collect();
+    versions.sort_by(|left, right| {
+        match (
+            snpm_semver::parse_version(left),
+            snpm_semver::parse_version(right),
+        ) {
+            (Ok(left), Ok(right)) => left.cmp(&right),
+            _ => left.cmp(right),
+        }
+    });
+    versions
+}
+
+fn is_newer(left: &str, right: &str) -> bool {
+    match (
+        snpm_semver::parse_version(left),
+        snpm_semver::parse_version(right),
+    ) {
+        (Ok(left), Ok(right)) => left > right,
+        _ => left > right,
+    }
+}
+
+fn load(root: &Path, source: &DiffSource) -> Result<Lockfile> {
+    let lockfile_path = root.join("snpm-lock.yaml");
+    match source {
+        DiffSource::WorkingTree => {
+            if !lockfile_path.is_file() {
+                return Err(SnpmError::Lockfile {
+                    path: lockfile_path,
+                    reason: "snpm-lock.yaml is missing. Run `snpm install` first.".into(),
+                });
+            }
+            lockfile::read(&lockfile_path)
+        }
+        DiffSource::File(path) => lockfile::read(path),
+        DiffSource::Revision(revision) => {
+            let yaml = read_at_revision(root, revision)?;
+            lockfile::parse(&lockfile_path, &yaml)
+        }
+    }
+}
+
+/// `git show <revision>:./snpm-lock.yaml`, relative to `root` so it works
+/// when the project is a subdirectory of the repository.
+fn read_at_revision(root: &Path, revision: &str) -> Result<String> {
+    crate::git::output(root, &["show", &format!("{revision}:./snpm-lock.yaml")]).map_err(|reason| {
+        SnpmError::Lockfile {
+            path: root.join("snpm-lock.yaml"),
+            reason: format!("cannot read snpm-lock.yaml at {revision}: {reason}"),
+        }
+    })
+}
+
+/// Packages among `packages` that the lockfile records as having an
+/// install script, sorted by name and version.
+fn packages_with_install_scripts(packages: &[&LockPackage]) -> Vec<ScriptPackage> {
+    let mut found: Vec<ScriptPackage> = packages
+        .iter()
+        .filter(|package| package.has_install_script)
+        .map(|package| ScriptPackage {
+            name: package.name.clone(),
+            version: package.version.clone(),
+        })
+        .collect();
+
+    found.sort_by(|left, right| {
+        (left.name.as_str(), left.version.as_str()).cmp(&(right.name.as_str(), &right.version))
+    });
+    found
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,252 @@
+use super::{ChangeKind, DiffSource, compare, diff};
+use crate::lockfile::{LockPackage, LockRoot, Lockfile};
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn package(name: &str, version: &str, integrity: &str, tarball: &str) -> (String, LockPackage) {
+    (
+        format!("{name}@{version}"),
+        LockPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            tarball: tarball.to_string(),
+            integrity: Some(integrity.to_string()),
+            dependencies: BTreeMap::new(),
+            peer_dependencies: BTreeMap::new(),
+            bundled_dependencies: None,
+            has_bin: false,
+            bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
+        },
+    )
+}
+
+fn lockfile(packages: &[(&str, &str, &str)]) -> Lockfile {
+    Lockfile {
+        version: 1,
+        settings: Default::default(),
+        root: LockRoot {
+            dependencies: BTreeMap::new(),
+        },
+        importers: BTreeMap::new(),
+        packages: packages
+            .iter()
+            .map(|(name, version, integrity)| {
+                package(
+                    name,
+                    version,
+                    integrity,
+                    &format!("https://registry.npmjs.org/{name}/-/{name}-{version}.tgz"),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn summary(
+    old: &Lockfile,
+    new: &Lockfile,
+) -> Vec<(String, ChangeKind, Option<String>, Option<String>)> {
+    compare(old, new)
+        .into_iter()
+        .map(|change| (change.name, change.kind, change.from, change.to))
+        .collect()
+}
+
+fn some(value: &str) -> Option<String> {
+    Some(value.to_string())
+}
+
+#[test]
+fn compare_classifies_version_changes() {
+    let old = lockfile(&[
+        ("kept", "1.0.0", "sha512-k"),
+        ("gone", "1.0.0", "sha512-g"),
+        ("up", "1.9.0", "sha512-u"),
+        ("down", "2.0.0", "sha512-d"),
+    ]);
+    let new = lockfile(&[
+        ("kept", "1.0.0", "sha512-k"),
+        ("fresh", "0.1.0", "sha512-f"),
+        ("up", "1.10.0", "sha512-u2"),
+        ("down", "1.5.0", "sha512-d2"),
+    ]);
+
+    assert_eq!(
+        summary(&old, &new),
+        [
+            (
+                "down".into(),
+                ChangeKind::Downgraded,
+                some("2.0.0"),
+                some("1.5.0")
+            ),
+            ("fresh".into(), ChangeKind::Added, None, some("0.1.0")),
+            ("gone".into(), ChangeKind::Removed, some("1.0.0"), None),
+            (
+                "up".into(),
+                ChangeKind::Upgraded,
+                some("1.9.0"),
+                some("1.10.0")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn compare_pairs_multiple_versions_of_one_package() {
+    let old = lockfile(&[("c", "1.0.0", "a"), ("c", "2.0.0", "b")]);
+    let new = lockfile(&[
+        ("c", "1.1.0", "c"),
+        ("c", "2.0.0", "b"),
+        ("c", "3.0.0", "d"),
+    ]);
+
+    assert_eq!(
+        summary(&old, &new),
+        [
+            (
+                "c".into(),
+                ChangeKind::Upgraded,
+                some("1.0.0"),
+                some("1.1.0")
+            ),
+            ("c".into(), ChangeKind::Added, None, some("3.0.0")),
+        ]
+    );
+}
+
+#[test]
+fn compare_reports_integrity_changes_for_the_same_version() {
+    let old = lockfile(&[("left-pad", "1.3.0", "sha512-old")]);
+    let new = lockfile(&[("left-pad", "1.3.0", "sha512-new")]);
+
+    let changes = compare(&old, &new);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::IntegrityChanged);
+    assert_eq!(changes[0].from_integrity.as_deref(), Some("sha512-old"));
+    assert_eq!(changes[0].to_integrity.as_deref(), Some("sha512-new"));
+    assert!(compare(&old, &old).is_empty());
+}
+
+#[test]
+fn json_output_uses_camel_case_fields() {
+    let old = lockfile(&[("left-pad", "1.3.0", "sha512-old")]);
+    let new = lockfile(&[("left-pad", "1.3.0", "sha512-new")]);
+
+    let json = serde_json::to_value(&compare(&old, &new)[0]).unwrap();
+    assert_eq!(json["kind"], "integrity-changed");
+    assert_eq!(json["fromIntegrity"], "sha512-old");
+    assert_eq!(json["toIntegrity"], "sha512-new");
+}
+
+fn git(root: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["-c", "user.name=snpm", "-c", "user.email=snpm@example.com"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(status.status.success(), "git {args:?} failed");
+}
+
+fn write(root: &Path, lockfile: &Lockfile) {
+    fs::write(
+        root.join("snpm-lock.yaml"),
+        serde_yaml::to_string(lockfile).unwrap(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn diffs_the_working_tree_against_a_git_revision() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("repo");
+    fs::create_dir_all(&root).unwrap();
+
+    git(&root, &["init", "-q"]);
+    let mut committed = lockfile(&[]);
+    committed
+        .packages
+        .extend([package("local", "1.0.0", "sha512-a", "file:../local")]);
+    write(&root, &committed);
+    git(&root, &["add", "snpm-lock.yaml"]);
+    git(&root, &["commit", "-q", "-m", "lock"]);
+
+    let mut edited = lockfile(&[]);
+    edited
+        .packages
+        .extend([package("local", "1.1.0", "sha512-b", "file:../local")]);
+    write(&root, &edited);
+
+    let result = diff(
+        &root,
+        &DiffSource::Revision("HEAD".to_string()),
+        &DiffSource::WorkingTree,
+    )
+    .unwrap();
+
+    assert_eq!(result.from, "HEAD");
+    assert_eq!(result.to, "working tree");
+    assert_eq!(result.changes.len(), 1);
+    assert_eq!(result.changes[0].kind, ChangeKind::Upgraded);
+    assert!(result.new_lifecycle_scripts.is_empty());
+
+    let error = diff(
+        &root,
+        &DiffSource::Revision("no-such-ref".to_string()),
+        &DiffSource::WorkingTree,
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("no-such-ref"));
+}
+
+#[test]
+fn flags_newly_introduced_install_scripts() {
+    let dir = tempfile::tempdir().unwrap();
+    let old_path = dir.path().join("old.yaml");
+    let new_path = dir.path().join("new.yaml");
+
+    let mut old = lockfile(&[
+        ("native", "1.0.0", "sha512-a"),
+        ("gyp", "9.0.0", "sha512-g"),
+    ]);
+    let mut new = lockfile(&[
+        ("native", "2.0.0", "sha512-b"),
+        ("gyp", "9.0.0", "sha512-g"),
+    ]);
+    old.packages
+        .get_mut("gyp@9.0.0")
+        .unwrap()
+        .has_install_script = true;
+    new.packages
+        .get_mut("gyp@9.0.0")
+        .unwrap()
+        .has_install_script = true;
+    new.packages
+        .get_mut("native@2.0.0")
+        .unwrap()
+        .has_install_script = true;
+    fs::write(&old_path, serde_yaml::to_string(&old).unwrap()).unwrap();
+    fs::write(&new_path, serde_yaml::to_string(&new).unwrap()).unwrap();
+
+    let result = diff(
+        dir.path(),
+        &DiffSource::File(old_path),
+        &DiffSource::File(new_path),
+    )
+    .unwrap();
+
+    assert_eq!(result.new_lifecycle_scripts.len(), 1);
+    assert_eq!(result.new_lifecycle_scripts[0].name, "native");
+    assert_eq!(result.new_lifecycle_scripts[0].version, "2.0.0");
+}
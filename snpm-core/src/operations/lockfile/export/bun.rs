@@ -0,0 +1,154 @@
+use super::model::{ExportDependency, ExportProject, hoisted_layout, npm_range};
+use crate::operations::deploy::DeployNode;
+use crate::project::BinField;
+use crate::resolve::ResolutionGraph;
+use crate::{Result, SnpmError};
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// Renders a bun text lockfile (`bun.lock`, v1). Package keys follow
+/// bun's hoisted install paths (`parent/child` for nested copies), and
+/// dependency ranges are the exact locked versions.
+///
+/// bun records the registry rather than a tarball URL, so packages locked
+/// to a custom tarball resolve from the default registry under bun.
+pub(super) fn render(
+    graph: &ResolutionGraph,
+    projects: &BTreeMap<String, ExportProject>,
+) -> Result<String> {
+    let mut out = String::from("{\n  \"lockfileVersion\": 1,\n  \"workspaces\": {\n");
+
+    for (key, project) in projects {
+        let path = if key == "." { "" } else { key.as_str() };
+        let _ = writeln!(out, "    {}: {{", quote(path));
+        if let Some(name) = &project.name {
+            let _ = writeln!(out, "      \"name\": {},", quote(name));
+        }
+        if key != "."
+            && let Some(version) = &project.version
+        {
+            let _ = writeln!(out, "      \"version\": {},", quote(version));
+        }
+        write_specs(&mut out, "dependencies", &project.dependencies);
+        write_specs(&mut out, "devDependencies", &project.dev_dependencies);
+        write_specs(
+            &mut out,
+            "optionalDependencies",
+            &project.optional_dependencies,
+        );
+        out.push_str("    },\n");
+    }
+
+    out.push_str("  },\n  \"packages\": {\n");
+
+    let mut entries = BTreeMap::new();
+    for (key, project) in projects {
+        if key != "."
+            && let Some(name) = &project.name
+        {
+            entries.insert(
+                name.clone(),
+                format!("[{}]", quote(&format!("{name}@workspace:{key}"))),
+            );
+        }
+    }
+
+    for placement in hoisted_layout(graph, projects)? {
+        let DeployNode::Package(id) = &placement.node else {
+            continue;
+        };
+        let package = graph
+            .packages
+            .get(id)
+            .ok_or_else(|| SnpmError::GraphMissing {
+                name: id.name.clone(),
+                version: id.version.clone(),
+            })?;
+
+        let mut meta = Vec::new();
+        if !package.dependencies.is_empty() {
+            let dependencies = package
+                .dependencies
+                .iter()
+                .map(|(name, dep)| (name.clone(), npm_range(name, dep)))
+                .collect();
+            meta.push(format!("\"dependencies\": {}", inline_map(&dependencies)));
+        }
+        if !package.peer_dependencies.is_empty() {
+            meta.push(format!(
+                "\"peerDependencies\": {}",
+                inline_map(&package.peer_dependencies)
+            ));
+        }
+        if let Some(bin) = &package.bin {
+            let bins = match bin {
+                BinField::Single(path) => BTreeMap::from([(
+                    id.name.rsplit('/').next().unwrap_or(&id.name).to_string(),
+                    path.clone(),
+                )]),
+                BinField::Map(bins) => bins.clone(),
+            };
+            meta.push(format!("\"bin\": {}", inline_map(&bins)));
+        }
+
+        let meta = if meta.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{ {} }}", meta.join(", "))
+        };
+
+        let mut fields = vec![
+            quote(&format!("{}@{}", id.name, id.version)),
+            quote(""),
+            meta,
+        ];
+        if let Some(integrity) = &package.integrity {
+            fields.push(quote(integrity));
+        }
+
+        let key = placement
+            .path()
+            .to_string_lossy()
+            .replace('\\', "/")
+            .trim_start_matches("node_modules/")
+            .replace("/node_modules/", "/");
+        entries.insert(key, format!("[{}]", fields.join(", ")));
+    }
+
+    for (key, entry) in &entries {
+        let _ = writeln!(out, "    {}: {entry},", quote(key));
+    }
+    out.push_str("  }\n}\n");
+
+    Ok(out)
+}
+
+fn write_specs(out: &mut String, field: &str, dependencies: &BTreeMap<String, ExportDependency>) {
+    if dependencies.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(out, "      \"{field}\": {{");
+    for (name, dependency) in dependencies {
+        let _ = writeln!(
+            out,
+            "        {}: {},",
+            quote(name),
+            quote(&dependency.specifier)
+        );
+    }
+    out.push_str("      },\n");
+}
+
+fn inline_map(map: &BTreeMap<String, String>) -> String {
+    let entries = map
+        .iter()
+        .map(|(key, value)| format!("{}: {}", quote(key), quote(value)))
+        .collect::<Vec<_>>();
+    format!("{{ {} }}", entries.join(", "))
+}
+
+fn quote(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
@@ -0,0 +1,106 @@
+mod bun;
+mod model;
+mod npm;
+mod pnpm;
+mod yarn;
+
+use crate::lockfile::{self, CompatibleLockfileKind};
+use crate::project::Manifest;
+use crate::{Project, Result, SnpmError, Workspace};
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use model::build_projects;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Pnpm,
+    Npm,
+    Yarn,
+    Bun,
+}
+
+impl ExportFormat {
+    /// The foreign lockfile kind this format is read back as.
+    pub fn kind(self) -> CompatibleLockfileKind {
+        match self {
+            ExportFormat::Pnpm => CompatibleLockfileKind::Pnpm,
+            ExportFormat::Npm => CompatibleLockfileKind::Npm,
+            ExportFormat::Yarn => CompatibleLockfileKind::Yarn,
+            ExportFormat::Bun => CompatibleLockfileKind::Bun,
+        }
+    }
+
+    pub fn filename(self) -> &'static str {
+        self.kind().filename()
+    }
+}
+
+#[derive(Debug)]
+pub struct ExportResult {
+    pub path: PathBuf,
+    pub package_count: usize,
+}
+
+/// Writes the `snpm-lock.yaml` at `root` (a project or workspace root)
+/// out as a lockfile for another package manager, next to it. Every
+/// version stays exactly as snpm locked it, so projects still installed
+/// with npm, pnpm, yarn or bun during a migration see the same tree.
+pub fn export(root: &Path, format: ExportFormat) -> Result<ExportResult> {
+    let lockfile_path = root.join("snpm-lock.yaml");
+    if !lockfile_path.is_file() {
+        return Err(SnpmError::Lockfile {
+            path: lockfile_path,
+            reason: "snpm-lock.yaml is missing. Run `snpm install` first.".into(),
+        });
+    }
+
+    let lockfile = lockfile::read(&lockfile_path)?;
+    let graph = lockfile::to_graph(&lockfile);
+    let projects = build_projects(&lockfile_path, &lockfile, &project_manifests(root)?)?;
+
+    let path = root.join(format.filename());
+    let content = match format {
+        ExportFormat::Pnpm => pnpm::render(&path, &graph, &projects)?,
+        ExportFormat::Npm => npm::render(&path, &graph, &projects)?,
+        ExportFormat::Yarn => yarn::render(&graph, &projects),
+        ExportFormat::Bun => bun::render(&graph, &projects)?,
+    };
+
+    fs::write(&path, content).map_err(|source| SnpmError::WriteFile {
+        path: path.clone(),
+        source,
+    })?;
+
+    Ok(ExportResult {
+        path,
+        package_count: graph.packages.len(),
+    })
+}
+
+/// Manifests keyed by importer key: every workspace project when `root`
+/// is a workspace root, otherwise just the project at `root`.
+fn project_manifests(root: &Path) -> Result<BTreeMap<String, Manifest>> {
+    if let Some(workspace) = Workspace::discover(root)?
+        && workspace.root == root
+    {
+        return Ok(workspace
+            .projects
+            .into_iter()
+            .map(|project| {
+                (
+                    lockfile::importer_key(&workspace.root, &project.root),
+                    project.manifest,
+                )
+            })
+            .collect());
+    }
+
+    let project = Project::from_manifest_path(root.join("package.json"))?;
+    Ok(BTreeMap::from([(".".to_string(), project.manifest)]))
+}
+
+#[cfg(test)]
+mod tests;
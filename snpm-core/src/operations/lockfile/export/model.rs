@@ -0,0 +1,267 @@
+use crate::lockfile::{LockImporter, LockRootDependency, Lockfile};
+use crate::operations::deploy::{Placement, plan_layout};
+use crate::project::Manifest;
+use crate::resolve::{PackageId, ResolutionGraph};
+use crate::{Result, SnpmError};
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::Path;
+
+/// One project as the foreign formats see it: the specs its manifest
+/// declares, each tied to what the snpm lockfile resolved it to.
+#[derive(Debug, Clone, Default)]
+pub(super) struct ExportProject {
+    pub(super) name: Option<String>,
+    pub(super) version: Option<String>,
+    pub(super) dependencies: BTreeMap<String, ExportDependency>,
+    pub(super) dev_dependencies: BTreeMap<String, ExportDependency>,
+    pub(super) optional_dependencies: BTreeMap<String, ExportDependency>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ExportDependency {
+    /// The spec exactly as written in `package.json`.
+    pub(super) specifier: String,
+    pub(super) target: ExportTarget,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ExportTarget {
+    Package(PackageId),
+    /// A workspace sibling, by importer key.
+    Workspace(String),
+}
+
+impl ExportProject {
+    pub(super) fn blocks(&self) -> [&BTreeMap<String, ExportDependency>; 3] {
+        [
+            &self.dependencies,
+            &self.dev_dependencies,
+            &self.optional_dependencies,
+        ]
+    }
+}
+
+/// Ties every manifest spec (keyed by importer key) to its entry in the
+/// lockfile. Workspace links come from the lockfile's importers; anything
+/// else must be in the flattened root unless it is optional.
+pub(super) fn build_projects(
+    lockfile_path: &Path,
+    lockfile: &Lockfile,
+    manifests: &BTreeMap<String, Manifest>,
+) -> Result<BTreeMap<String, ExportProject>> {
+    let empty = LockImporter::default();
+    let mut projects = BTreeMap::new();
+
+    for (key, manifest) in manifests {
+        let importer = lockfile.importers.get(key).unwrap_or(&empty);
+        let mut project = ExportProject {
+            name: manifest.name.clone(),
+            version: manifest.version.clone(),
+            ..ExportProject::default()
+        };
+
+        let blocks = [
+            (&manifest.dependencies, &mut project.dependencies, false),
+            (
+                &manifest.optional_dependencies,
+                &mut project.optional_dependencies,
+                true,
+            ),
+        ];
+        for (specs, block, optional) in blocks {
+            for (name, specifier) in specs {
+                if !optional && manifest.optional_dependencies.contains_key(name) {
+                    continue;
+                }
+
+                if let Some(target) =
+                    resolve_target(lockfile_path, lockfile, importer, key, name, specifier)?
+                {
+                    block.insert(
+                        name.clone(),
+                        ExportDependency {
+                            specifier: specifier.clone(),
+                            target,
+                        },
+                    );
+                } else if !optional {
+                    return Err(missing_dependency(lockfile_path, key, name));
+                }
+            }
+        }
+
+        for (name, specifier) in &manifest.dev_dependencies {
+            if manifest.dependencies.contains_key(name)
+                || manifest.optional_dependencies.contains_key(name)
+            {
+                continue;
+            }
+
+            let target = resolve_target(lockfile_path, lockfile, importer, key, name, specifier)?
+                .ok_or_else(|| missing_dependency(lockfile_path, key, name))?;
+            project.dev_dependencies.insert(
+                name.clone(),
+                ExportDependency {
+                    specifier: specifier.clone(),
+                    target,
+                },
+            );
+        }
+
+        projects.insert(key.clone(), project);
+    }
+
+    Ok(projects)
+}
+
+fn resolve_target(
+    lockfile_path: &Path,
+    lockfile: &Lockfile,
+    importer: &LockImporter,
+    key: &str,
+    name: &str,
+    specifier: &str,
+) -> Result<Option<ExportTarget>> {
+    if let Some(sibling) = importer.links.get(name) {
+        return Ok(Some(ExportTarget::Workspace(sibling.clone())));
+    }
+
+    if specifier.starts_with("file:") || specifier.starts_with("link:") {
+        return Err(SnpmError::Lockfile {
+            path: lockfile_path.to_path_buf(),
+            reason: format!(
+                "{name}@{specifier} (declared by {key}) is a local dependency and cannot be exported"
+            ),
+        });
+    }
+
+    Ok(lockfile.root.dependencies.get(name).and_then(|root| {
+        root.version.as_ref().map(|version| {
+            ExportTarget::Package(PackageId {
+                name: root.package.clone().unwrap_or_else(|| name.to_string()),
+                version: version.clone(),
+            })
+        })
+    }))
+}
+
+fn missing_dependency(lockfile_path: &Path, key: &str, name: &str) -> SnpmError {
+    SnpmError::Lockfile {
+        path: lockfile_path.to_path_buf(),
+        reason: format!(
+            "{name} (required by {key}) is missing from snpm-lock.yaml. Run `snpm install` first."
+        ),
+    }
+}
+
+/// Every package reachable from the given roots.
+pub(super) fn closure<'a>(
+    graph: &ResolutionGraph,
+    roots: impl IntoIterator<Item = &'a ExportDependency>,
+) -> BTreeSet<PackageId> {
+    let mut queue: VecDeque<PackageId> = roots
+        .into_iter()
+        .filter_map(|dependency| match &dependency.target {
+            ExportTarget::Package(id) => Some(id.clone()),
+            ExportTarget::Workspace(_) => None,
+        })
+        .collect();
+
+    let mut reachable = BTreeSet::new();
+    while let Some(id) = queue.pop_front() {
+        if let Some(package) = graph.packages.get(&id) {
+            if reachable.contains(&id) {
+                continue;
+            }
+            queue.extend(package.dependencies.values().cloned());
+        }
+        reachable.insert(id);
+    }
+
+    reachable
+}
+
+/// Lays the whole graph out as a hoisted `node_modules` tree, the shape
+/// npm and bun record. Every project's direct dependencies sit at the top
+/// level; the lockfile root is already flattened, so they never clash.
+pub(super) fn hoisted_layout(
+    graph: &ResolutionGraph,
+    projects: &BTreeMap<String, ExportProject>,
+) -> Result<Vec<Placement>> {
+    let mut dependencies = BTreeMap::new();
+    for project in projects.values() {
+        for block in project.blocks() {
+            for (name, dependency) in block {
+                if matches!(dependency.target, ExportTarget::Package(_)) {
+                    dependencies.insert(
+                        name.clone(),
+                        LockRootDependency {
+                            requested: dependency.specifier.clone(),
+                            package: None,
+                            version: None,
+                            optional: false,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    let importers = BTreeMap::from([(
+        ".".to_string(),
+        LockImporter {
+            dependencies,
+            ..LockImporter::default()
+        },
+    )]);
+
+    plan_layout(graph, &importers, ".")
+}
+
+/// How `id` is referenced from a dependency named `name`: the bare
+/// version, or `name@version` when it is installed under an alias.
+pub(super) fn version_ref(name: &str, id: &PackageId) -> String {
+    if id.name == name {
+        id.version.clone()
+    } else {
+        format!("{}@{}", id.name, id.version)
+    }
+}
+
+/// The same, as an npm-style range: `npm:name@version` for aliases.
+pub(super) fn npm_range(name: &str, id: &PackageId) -> String {
+    if id.name == name {
+        id.version.clone()
+    } else {
+        format!("npm:{}@{}", id.name, id.version)
+    }
+}
+
+/// Path from importer `from` to importer `to`, both workspace-relative
+/// keys (`.` for the root).
+pub(super) fn relative_importer_path(from: &str, to: &str) -> String {
+    let split = |key: &str| -> Vec<String> {
+        key.split('/')
+            .filter(|part| !part.is_empty() && *part != ".")
+            .map(str::to_string)
+            .collect()
+    };
+    let from = split(from);
+    let to = split(to);
+
+    let common = from
+        .iter()
+        .zip(&to)
+        .take_while(|(left, right)| left == right)
+        .count();
+
+    let mut parts = vec![".."; from.len() - common];
+    parts.extend(to[common..].iter().map(String::as_str));
+
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
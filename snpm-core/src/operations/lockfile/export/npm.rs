@@ -0,0 +1,204 @@
+use super::model::{ExportDependency, ExportProject, closure, hoisted_layout, npm_range};
+use crate::operations::deploy::DeployNode;
+use crate::project::BinField;
+use crate::registry::BundledDependencies;
+use crate::resolve::{PackageId, ResolutionGraph};
+use crate::{Result, SnpmError};
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NpmLockfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    lockfile_version: u32,
+    requires: bool,
+    packages: BTreeMap<String, NpmPackage>,
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NpmPackage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    integrity: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    link: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    dev: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    optional: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    dev_optional: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    workspaces: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    dependencies: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    dev_dependencies: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    optional_dependencies: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    peer_dependencies: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bundle_dependencies: Option<BundledDependencies>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bin: Option<BinField>,
+}
+
+/// Renders an npm v3 `package-lock.json` with the hoisted tree npm itself
+/// would lay out. Dependency ranges inside package entries are the exact
+/// locked versions, since snpm does not keep the ranges packages declared.
+pub(super) fn render(
+    path: &Path,
+    graph: &ResolutionGraph,
+    projects: &BTreeMap<String, ExportProject>,
+) -> Result<String> {
+    let mut packages = BTreeMap::new();
+
+    let members: Vec<String> = projects.keys().filter(|key| *key != ".").cloned().collect();
+    let mut root = projects.get(".").map(project_entry).unwrap_or_default();
+    root.workspaces = members.clone();
+
+    for key in &members {
+        let project = &projects[key];
+        packages.insert(key.clone(), project_entry(project));
+
+        if let Some(name) = &project.name {
+            packages.insert(
+                format!("node_modules/{name}"),
+                NpmPackage {
+                    resolved: Some(key.clone()),
+                    link: true,
+                    ..NpmPackage::default()
+                },
+            );
+        }
+    }
+
+    let flags = DependencyFlags::new(graph, projects);
+    for placement in hoisted_layout(graph, projects)? {
+        let DeployNode::Package(id) = &placement.node else {
+            continue;
+        };
+        let package = graph
+            .packages
+            .get(id)
+            .ok_or_else(|| SnpmError::GraphMissing {
+                name: id.name.clone(),
+                version: id.version.clone(),
+            })?;
+
+        let (dev, optional, dev_optional) = flags.of(id);
+        packages.insert(
+            placement.path().to_string_lossy().replace('\\', "/"),
+            NpmPackage {
+                name: (placement.name != id.name).then(|| id.name.clone()),
+                version: Some(id.version.clone()),
+                resolved: (!package.tarball.is_empty()).then(|| package.tarball.clone()),
+                integrity: package.integrity.clone(),
+                dev,
+                optional,
+                dev_optional,
+                dependencies: package
+                    .dependencies
+                    .iter()
+                    .map(|(name, dep)| (name.clone(), npm_range(name, dep)))
+                    .collect(),
+                peer_dependencies: package.peer_dependencies.clone(),
+                bundle_dependencies: package.bundled_dependencies.clone(),
+                bin: package.bin.clone(),
+                ..NpmPackage::default()
+            },
+        );
+    }
+
+    let root_project = projects.get(".");
+    packages.insert(String::new(), root);
+
+    let lockfile = NpmLockfile {
+        name: root_project.and_then(|project| project.name.clone()),
+        version: root_project.and_then(|project| project.version.clone()),
+        lockfile_version: 3,
+        requires: true,
+        packages,
+    };
+
+    let mut rendered =
+        serde_json::to_string_pretty(&lockfile).map_err(|error| SnpmError::Lockfile {
+            path: path.to_path_buf(),
+            reason: format!("failed to serialize package-lock.json: {error}"),
+        })?;
+    rendered.push('\n');
+    Ok(rendered)
+}
+
+fn project_entry(project: &ExportProject) -> NpmPackage {
+    let specs = |dependencies: &BTreeMap<String, ExportDependency>| {
+        dependencies
+            .iter()
+            .map(|(name, dependency)| (name.clone(), dependency.specifier.clone()))
+            .collect()
+    };
+
+    NpmPackage {
+        name: project.name.clone(),
+        version: project.version.clone(),
+        dependencies: specs(&project.dependencies),
+        dev_dependencies: specs(&project.dev_dependencies),
+        optional_dependencies: specs(&project.optional_dependencies),
+        ..NpmPackage::default()
+    }
+}
+
+/// npm's `dev` / `optional` / `devOptional` markers, which let
+/// `npm ci --omit=dev` skip packages only reachable from dev roots.
+struct DependencyFlags {
+    required: BTreeSet<PackageId>,
+    production: BTreeSet<PackageId>,
+    development: BTreeSet<PackageId>,
+}
+
+impl DependencyFlags {
+    fn new(graph: &ResolutionGraph, projects: &BTreeMap<String, ExportProject>) -> Self {
+        let roots = |select: fn(&ExportProject) -> &BTreeMap<String, ExportDependency>| {
+            projects
+                .values()
+                .flat_map(move |project| select(project).values())
+        };
+
+        let required = closure(graph, roots(|project| &project.dependencies));
+        let mut production = required.clone();
+        production.extend(closure(
+            graph,
+            roots(|project| &project.optional_dependencies),
+        ));
+
+        Self {
+            required,
+            production,
+            development: closure(graph, roots(|project| &project.dev_dependencies)),
+        }
+    }
+
+    fn of(&self, id: &PackageId) -> (bool, bool, bool) {
+        if self.required.contains(id) {
+            (false, false, false)
+        } else if self.production.contains(id) {
+            let dev_optional = self.development.contains(id);
+            (false, !dev_optional, dev_optional)
+        } else {
+            (true, false, false)
+        }
+    }
+}
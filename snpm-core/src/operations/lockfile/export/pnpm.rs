@@ -0,0 +1,159 @@
+use super::model::{
+    ExportDependency, ExportProject, ExportTarget, relative_importer_path, version_ref,
+};
+use crate::registry::BundledDependencies;
+use crate::resolve::ResolutionGraph;
+use crate::{Result, SnpmError};
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PnpmLockfile {
+    lockfile_version: &'static str,
+    settings: PnpmSettings,
+    importers: BTreeMap<String, PnpmImporter>,
+    packages: BTreeMap<String, PnpmPackage>,
+    snapshots: BTreeMap<String, PnpmSnapshot>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PnpmSettings {
+    auto_install_peers: bool,
+    exclude_links_from_lockfile: bool,
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PnpmImporter {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    dependencies: BTreeMap<String, PnpmImporterDependency>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    optional_dependencies: BTreeMap<String, PnpmImporterDependency>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    dev_dependencies: BTreeMap<String, PnpmImporterDependency>,
+}
+
+#[derive(Serialize)]
+struct PnpmImporterDependency {
+    specifier: String,
+    version: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PnpmPackage {
+    resolution: PnpmResolution,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    peer_dependencies: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    has_bin: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bundled_dependencies: Option<BundledDependencies>,
+}
+
+#[derive(Serialize)]
+struct PnpmResolution {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    integrity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tarball: Option<String>,
+}
+
+#[derive(Default, Serialize)]
+struct PnpmSnapshot {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    dependencies: BTreeMap<String, String>,
+}
+
+/// Renders a pnpm v9 `pnpm-lock.yaml`. Tarball URLs are always written
+/// into `resolution`, so packages from custom registries or URLs install
+/// from the same place snpm locked them to.
+pub(super) fn render(
+    path: &Path,
+    graph: &ResolutionGraph,
+    projects: &BTreeMap<String, ExportProject>,
+) -> Result<String> {
+    let importers = projects
+        .iter()
+        .map(|(key, project)| {
+            let block = |dependencies: &BTreeMap<String, ExportDependency>| {
+                dependencies
+                    .iter()
+                    .map(|(name, dependency)| {
+                        let version = match &dependency.target {
+                            ExportTarget::Package(id) => version_ref(name, id),
+                            ExportTarget::Workspace(sibling) => {
+                                format!("link:{}", relative_importer_path(key, sibling))
+                            }
+                        };
+                        (
+                            name.clone(),
+                            PnpmImporterDependency {
+                                specifier: dependency.specifier.clone(),
+                                version,
+                            },
+                        )
+                    })
+                    .collect()
+            };
+
+            (
+                key.clone(),
+                PnpmImporter {
+                    dependencies: block(&project.dependencies),
+                    optional_dependencies: block(&project.optional_dependencies),
+                    dev_dependencies: block(&project.dev_dependencies),
+                },
+            )
+        })
+        .collect();
+
+    let mut packages = BTreeMap::new();
+    let mut snapshots = BTreeMap::new();
+    for (id, package) in &graph.packages {
+        let key = format!("{}@{}", id.name, id.version);
+
+        packages.insert(
+            key.clone(),
+            PnpmPackage {
+                resolution: PnpmResolution {
+                    integrity: package.integrity.clone(),
+                    tarball: (!package.tarball.is_empty()).then(|| package.tarball.clone()),
+                },
+                peer_dependencies: package.peer_dependencies.clone(),
+                has_bin: package.has_bin,
+                bundled_dependencies: package.bundled_dependencies.clone(),
+            },
+        );
+        snapshots.insert(
+            key,
+            PnpmSnapshot {
+                dependencies: package
+                    .dependencies
+                    .iter()
+                    .map(|(name, dep)| (name.clone(), version_ref(name, dep)))
+                    .collect(),
+            },
+        );
+    }
+
+    let lockfile = PnpmLockfile {
+        lockfile_version: "9.0",
+        settings: PnpmSettings {
+            auto_install_peers: true,
+            exclude_links_from_lockfile: false,
+        },
+        importers,
+        packages,
+        snapshots,
+    };
+
+    serde_yaml::to_string(&lockfile).map_err(|error| SnpmError::Lockfile {
+        path: path.to_path_buf(),
+        reason: format!("failed to serialize pnpm-lock.yaml: {error}"),
+    })
+}
@@ -0,0 +1,282 @@
+use super::model::relative_importer_path;
+use super::{ExportFormat, export};
+use crate::config::SnpmConfig;
+use crate::lockfile::{
+    CompatibleLockfile, CompatibleLockfileKind, Lockfile, read_compatible_lockfile,
+};
+
+use std::fs;
+use std::path::Path;
+
+const FORMATS: [ExportFormat; 4] = [
+    ExportFormat::Pnpm,
+    ExportFormat::Npm,
+    ExportFormat::Yarn,
+    ExportFormat::Bun,
+];
+
+/// A project lockfile with an alias, a scoped package, a peer, a bin, a
+/// nested version conflict, and one dependency of each kind.
+const PROJECT_PNPM_LOCK: &str = r#"lockfileVersion: '9.0'
+importers:
+  .:
+    dependencies:
+      a:
+        specifier: ^1.0.0
+        version: 1.0.0
+      alias:
+        specifier: npm:@scope/real@^1.2.3
+        version: '@scope/real@1.2.3'
+      c:
+        specifier: ^2.0.0
+        version: 2.0.0
+    optionalDependencies:
+      maybe:
+        specifier: ^0.1.0
+        version: 0.1.0
+    devDependencies:
+      tool:
+        specifier: ^3.0.0
+        version: 3.0.0
+packages:
+  a@1.0.0:
+    resolution:
+      integrity: sha512-H0D8ktokFpR1CXnubPWC8tXX0o4YM13gWrxU0FYOD1MChgxlK/CNVgJSql50IQVG82n7u86MEs/HlXsmUv6adQ==
+    peerDependencies:
+      c: '*'
+  '@scope/real@1.2.3':
+    resolution:
+      integrity: sha512-MQBwq0CSACqjP1UOuYaA+mZi5zBfziJ4Q0MKujNP360f4N1KZIQmS1n5sCp4BDOlUs49XD2mQ6d0J0Y8BuR4AA==
+  c@1.0.0:
+    resolution:
+      integrity: sha512-vP4h5igL8RuP29BFyLPlJ3SNHs8Y0cRKm+bNBUEHxEeYjQhlFPbVm+lQxfxynoj2bVjA5XRASTsIsJsy8h8HOw==
+  c@2.0.0:
+    resolution:
+      integrity: sha512-o701FrnPqamggdstaprKgv877w6OELEEwTx6zx/x056NCbY7aASt8Jp91C78uSSwnoijotHHwr9pVL3b4lHwqQ==
+  maybe@0.1.0:
+    resolution:
+      integrity: sha512-evxCQnYH9RcbnbF7OMOVOtXmFJVc7bupz7l6J28/O9g/z+Yb2/x5nKg4ZeRG865HmYpiT9cxbn5W97J8nWfxUA==
+  tool@3.0.0:
+    resolution:
+      integrity: sha512-YrlQBLbrX0OQUQSoH9MsKM3LTj/7UBBa/QPrElfPA0pO220fD5/RXXb6QqDQgyXVSx3oEVqJhnXK3HnaosurZA==
+    hasBin: true
+snapshots:
+  a@1.0.0:
+    dependencies:
+      c: 1.0.0
+      '@scope/real': 1.2.3
+  '@scope/real@1.2.3': {}
+  c@1.0.0: {}
+  c@2.0.0: {}
+  maybe@0.1.0: {}
+  tool@3.0.0: {}
+"#;
+
+const PROJECT_MANIFEST: &str = r#"{
+  "name": "app",
+  "version": "1.0.0",
+  "dependencies": {
+    "a": "^1.0.0",
+    "alias": "npm:@scope/real@^1.2.3",
+    "c": "^2.0.0"
+  },
+  "optionalDependencies": { "maybe": "^0.1.0" },
+  "devDependencies": { "tool": "^3.0.0" }
+}"#;
+
+const WORKSPACE_PNPM_LOCK: &str = r#"lockfileVersion: '9.0'
+importers:
+  .:
+    devDependencies:
+      tool:
+        specifier: ^1.0.0
+        version: 1.0.0
+  packages/app:
+    dependencies:
+      lib:
+        specifier: workspace:*
+        version: link:../lib
+      shared:
+        specifier: ^2.0.0
+        version: 2.0.0
+  packages/lib:
+    dependencies:
+      '@scope/util':
+        specifier: ^1.0.0
+        version: 1.0.0
+packages:
+  tool@1.0.0:
+    resolution:
+      integrity: sha512-YrlQBLbrX0OQUQSoH9MsKM3LTj/7UBBa/QPrElfPA0pO220fD5/RXXb6QqDQgyXVSx3oEVqJhnXK3HnaosurZA==
+  shared@2.0.0:
+    resolution:
+      integrity: sha512-IDo+gkxEwZHGPilvuXd/3tWgPPN7CH3d79hj6KV5+N0SnjoRg+4oYIYf3j/A+iGqtLFiPL4nysO0F8c57XI0kw==
+  '@scope/util@1.0.0':
+    resolution:
+      integrity: sha512-+haKHTntWbvEU4HKW3FxuBO2xM9ObACX4nY0Io2SjikfkY7Jpxc11A9KJnwBmoyH8b36PAz2LYk5coGVekC6CA==
+snapshots:
+  tool@1.0.0: {}
+  shared@2.0.0:
+    dependencies:
+      '@scope/util': 1.0.0
+  '@scope/util@1.0.0': {}
+"#;
+
+fn config() -> SnpmConfig {
+    SnpmConfig::for_tests()
+}
+
+fn import(root: &Path, kind: CompatibleLockfileKind) -> Lockfile {
+    read_compatible_lockfile(
+        &CompatibleLockfile {
+            kind,
+            path: root.join(kind.filename()),
+        },
+        &config(),
+    )
+    .unwrap()
+}
+
+/// Imports the pnpm fixture at `root` and saves it as the native
+/// lockfile, the way `snpm install` migrates a pnpm project.
+fn migrate(root: &Path, pnpm_lock: &str) -> Lockfile {
+    fs::write(root.join("pnpm-lock.yaml"), pnpm_lock).unwrap();
+    let lockfile = import(root, CompatibleLockfileKind::Pnpm);
+    fs::remove_file(root.join("pnpm-lock.yaml")).unwrap();
+    fs::write(
+        root.join("snpm-lock.yaml"),
+        serde_yaml::to_string(&lockfile).unwrap(),
+    )
+    .unwrap();
+    lockfile
+}
+
+/// Drops what `format` cannot carry, so the comparison checks exactly
+/// what a round trip through it should preserve.
+fn comparable(mut lockfile: Lockfile, format: ExportFormat) -> Lockfile {
+    if format != ExportFormat::Pnpm {
+        lockfile.importers.clear();
+    }
+
+    for package in lockfile.packages.values_mut() {
+        // npm and bun record bins by name, which only a recorded `bin` knows.
+        if matches!(format, ExportFormat::Npm | ExportFormat::Bun) && package.bin.is_none() {
+            package.has_bin = false;
+        }
+        package.bin = None;
+        match format {
+            ExportFormat::Yarn => {
+                package.peer_dependencies.clear();
+                package.has_bin = false;
+                package.bundled_dependencies = None;
+            }
+            ExportFormat::Bun => {
+                package.bundled_dependencies = None;
+            }
+            ExportFormat::Pnpm | ExportFormat::Npm => {}
+        }
+    }
+
+    lockfile
+}
+
+fn assert_round_trip(root: &Path, original: &Lockfile) {
+    for format in FORMATS {
+        let result = export(root, format).unwrap();
+        assert_eq!(result.path, root.join(format.filename()));
+
+        let exported = import(root, format.kind());
+        fs::remove_file(&result.path).unwrap();
+
+        assert_eq!(
+            comparable(exported, format),
+            comparable(original.clone(), format),
+            "{format:?} export did not round-trip"
+        );
+    }
+}
+
+#[test]
+fn project_lockfile_round_trips_through_every_format() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("package.json"), PROJECT_MANIFEST).unwrap();
+    let original = migrate(dir.path(), PROJECT_PNPM_LOCK);
+
+    assert_round_trip(dir.path(), &original);
+}
+
+#[test]
+fn workspace_lockfile_round_trips_through_every_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::write(
+        root.join("snpm-workspace.yaml"),
+        "packages:\n  - packages/*\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("package.json"),
+        r#"{ "name": "root", "private": true, "devDependencies": { "tool": "^1.0.0" } }"#,
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("packages/app")).unwrap();
+    fs::write(
+        root.join("packages/app/package.json"),
+        r#"{
+  "name": "app",
+  "version": "1.0.0",
+  "dependencies": { "lib": "workspace:*", "shared": "^2.0.0" }
+}"#,
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("packages/lib")).unwrap();
+    fs::write(
+        root.join("packages/lib/package.json"),
+        r#"{ "name": "lib", "version": "1.0.0", "dependencies": { "@scope/util": "^1.0.0" } }"#,
+    )
+    .unwrap();
+    let original = migrate(root, WORKSPACE_PNPM_LOCK);
+
+    assert_round_trip(root, &original);
+}
+
+#[test]
+fn npm_export_nests_conflicts_and_marks_dev_and_optional_packages() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("package.json"), PROJECT_MANIFEST).unwrap();
+    migrate(dir.path(), PROJECT_PNPM_LOCK);
+
+    let result = export(dir.path(), ExportFormat::Npm).unwrap();
+    let lock: serde_json::Value = serde_json::from_slice(&fs::read(result.path).unwrap()).unwrap();
+    let packages = &lock["packages"];
+
+    assert_eq!(lock["lockfileVersion"], 3);
+    assert_eq!(packages["node_modules/c"]["version"], "2.0.0");
+    assert_eq!(
+        packages["node_modules/a/node_modules/c"]["version"],
+        "1.0.0"
+    );
+    assert_eq!(packages["node_modules/alias"]["name"], "@scope/real");
+    assert_eq!(packages["node_modules/tool"]["dev"], true);
+    assert_eq!(packages["node_modules/maybe"]["optional"], true);
+    assert!(packages["node_modules/a"].get("dev").is_none());
+}
+
+#[test]
+fn export_requires_a_native_lockfile() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("package.json"), PROJECT_MANIFEST).unwrap();
+
+    let error = export(dir.path(), ExportFormat::Pnpm).unwrap_err();
+    assert!(error.to_string().contains("snpm-lock.yaml is missing"));
+}
+
+#[test]
+fn relative_importer_path_walks_between_projects() {
+    assert_eq!(
+        relative_importer_path("packages/app", "packages/lib"),
+        "../lib"
+    );
+    assert_eq!(relative_importer_path(".", "packages/lib"), "packages/lib");
+    assert_eq!(relative_importer_path("packages/app", "."), "../..");
+}
@@ -0,0 +1,89 @@
+use super::model::{ExportProject, ExportTarget, npm_range};
+use crate::resolve::{PackageId, ResolutionGraph};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+/// Renders a yarn classic (v1) `yarn.lock`. Each block lists every
+/// `name@range` that resolves to the package: the manifest specs of the
+/// projects, plus exact-version specs for the edges between packages.
+/// Workspace siblings are not recorded, the same as yarn does.
+pub(super) fn render(
+    graph: &ResolutionGraph,
+    projects: &BTreeMap<String, ExportProject>,
+) -> String {
+    let mut specs: BTreeMap<&PackageId, BTreeSet<String>> = BTreeMap::new();
+
+    for project in projects.values() {
+        for block in project.blocks() {
+            for (name, dependency) in block {
+                if let ExportTarget::Package(id) = &dependency.target {
+                    specs
+                        .entry(id)
+                        .or_default()
+                        .insert(format!("{name}@{}", dependency.specifier));
+                }
+            }
+        }
+    }
+
+    for package in graph.packages.values() {
+        for (name, dep) in &package.dependencies {
+            specs
+                .entry(dep)
+                .or_default()
+                .insert(format!("{name}@{}", npm_range(name, dep)));
+        }
+    }
+
+    let mut blocks = Vec::new();
+    for (id, package) in &graph.packages {
+        let header = match specs.get(id) {
+            Some(specs) => specs.iter().map(|spec| wrap(spec)).collect::<Vec<_>>(),
+            None => vec![wrap(&format!("{}@{}", id.name, id.version))],
+        };
+
+        let mut block = format!("{}:\n", header.join(", "));
+        let _ = writeln!(block, "  version {}", wrap(&id.version));
+        if !package.tarball.is_empty() {
+            let _ = writeln!(block, "  resolved {}", wrap(&package.tarball));
+        }
+        if let Some(integrity) = &package.integrity {
+            let _ = writeln!(block, "  integrity {}", wrap(integrity));
+        }
+        if !package.dependencies.is_empty() {
+            block.push_str("  dependencies:\n");
+            for (name, dep) in &package.dependencies {
+                let _ = writeln!(block, "    {} {}", wrap(name), wrap(&npm_range(name, dep)));
+            }
+        }
+
+        blocks.push(block);
+    }
+    blocks.sort();
+
+    let mut out = String::from(
+        "# THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.\n# yarn lockfile v1\n\n",
+    );
+    for block in blocks {
+        out.push('\n');
+        out.push_str(&block);
+    }
+    out
+}
+
+/// Quotes a key or value the way yarn's own lockfile writer does.
+fn wrap(value: &str) -> String {
+    let needs_quotes = value.starts_with("true")
+        || value.starts_with("false")
+        || !value.starts_with(|c: char| c.is_ascii_alphabetic())
+        || value.contains(|c: char| {
+            c.is_whitespace() || matches!(c, ':' | '\\' | '"' | ',' | '[' | ']')
+        });
+
+    if needs_quotes {
+        serde_json::Value::from(value).to_string()
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,5 @@
+pub mod diff;
+pub mod export;
+
+pub use diff::{ChangeKind, DiffSource, LockfileDiff, PackageChange, ScriptPackage, diff};
+pub use export::{ExportFormat, ExportResult, export};
@@ -1,13 +1,16 @@
 pub mod audit;
 pub mod auth;
 pub mod clean;
+pub mod deploy;
 pub mod dlx;
+pub mod fetch;
 pub mod global;
 pub mod init;
 pub mod install;
 pub mod lazy;
 pub mod licenses;
 pub mod link;
+pub mod lockfile;
 pub mod pack;
 pub mod patch;
 pub mod publish;
@@ -17,8 +20,10 @@ pub mod store;
 pub mod why;
 
 pub use audit::{
-    AuditAdvisory, AuditOptions, AuditResult, FixResult, Severity, VulnerabilityCounts, audit,
-    audit_workspace, fix,
+    AdvisoryDbSyncReport, AuditAdvisory, AuditOptions, AuditResult, AuditSuppression, FixAction,
+    FixOutcome, FixResult, Severity, SignatureAuditResult, SignatureIssue, SuppressedAdvisory,
+    VulnerabilityCounts, audit, audit_signatures, audit_workspace, expired_suppressions, fix,
+    sync_advisory_database,
 };
 pub use auth::{
     AuthResult, AuthType, Credentials, OpenerFn, login, login_with_fallback, logout,
@@ -27,16 +32,22 @@ pub use auth::{
 pub use clean::{
     CleanOptions, CleanSummary, analyze as clean_analyze, execute as clean_execute, format_bytes,
 };
+pub use deploy::{DeployResult, deploy};
 pub use dlx::{dlx, dlx_with_offline};
+pub use fetch::{FetchResult, FetchScope, fetch};
 pub use global::{install_global, remove_global};
 pub use init::{InitOptions, init, init_with_options};
 pub use install::{
-    InstallOptions, InstallResult, OutdatedEntry, install, install_workspace, outdated, remove,
-    upgrade,
+    DedupeResult, DedupedPackage, DeprecatedPackage, InstallOptions, InstallResult, OutdatedEntry,
+    dedupe, dedupe_workspace, deprecated, install, install_workspace, outdated, remove, upgrade,
 };
 pub use lazy::{is_stale, is_stale_with_config, lazy_install};
 pub use licenses::{LicenseEntry, collect_licenses};
 pub use link::{link_global, link_local, unlink_global, unlink_local};
+pub use lockfile::{
+    ChangeKind, DiffSource, ExportFormat, ExportResult, LockfileDiff, PackageChange, ScriptPackage,
+    diff as diff_lockfile, export as export_lockfile,
+};
 pub use pack::{
     PackFile, PackFileReason, PackFinding, PackFindingSeverity, PackInspection, PackResult,
     inspect_pack, pack,
@@ -51,5 +62,8 @@ pub use run::{
     ExecOptions, exec_command, exec_workspace_command, format_filters, project_label, run_script,
     run_script_with_node, run_workspace_scripts, select_workspace_projects,
 };
-pub use store::{StoreStatus, path as store_path, prune as store_prune, status as store_status};
+pub use store::{
+    CorruptPackage, StorePruneReport, StoreStatus, StoreVerifyOptions, StoreVerifyReport,
+    path as store_path, prune as store_prune, status as store_status, verify as store_verify,
+};
 pub use why::{WhyHop, WhyOptions, WhyPackageMatch, WhyPath, WhyResult, why};
@@ -12,14 +12,14 @@ use defaults::collect_default_files;
 use manifest::{add_bin_entries, add_main_entry, add_mandatory_files, collect_manifest_files};
 
 #[derive(Debug, Clone)]
-pub(super) struct CollectedFile {
-    pub(super) absolute_path: PathBuf,
-    pub(super) relative_path: String,
+pub(crate) struct CollectedFile {
+    pub(crate) absolute_path: PathBuf,
+    pub(crate) relative_path: String,
     pub(super) size: u64,
     pub(super) reason: PackFileReason,
 }
 
-pub(super) fn collect_pack_files(project: &Project) -> Result<Vec<CollectedFile>> {
+pub(crate) fn collect_pack_files(project: &Project) -> Result<Vec<CollectedFile>> {
     let mut files = BTreeMap::new();
     add_file(
         &project.root,
@@ -6,7 +6,7 @@ use crate::{Project, Result, SnpmError};
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 
-use collect::collect_pack_files;
+pub(crate) use collect::collect_pack_files;
 use safety::audit_pack;
 use tarball::write_tarball;
 
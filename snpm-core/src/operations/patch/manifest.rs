@@ -21,6 +21,9 @@ pub(super) fn update_manifest_with_patch(
 
     let snpm = manifest.snpm.get_or_insert_with(|| ManifestSnpm {
         overrides: BTreeMap::new(),
+        package_extensions: BTreeMap::new(),
+        peer_dependency_rules: Default::default(),
+        supported_architectures: Default::default(),
         patched_dependencies: None,
         publish: None,
     });
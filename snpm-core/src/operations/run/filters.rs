@@ -2,7 +2,6 @@ use crate::{Project, Result, SnpmError, Workspace};
 
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 #[cfg(test)]
 pub(crate) fn matches_filters(name: &str, filters: &[String]) -> bool {
@@ -356,26 +355,14 @@ fn changed_since(
     packages: &[IndexedPackage<'_>],
     revision: &str,
 ) -> Result<BTreeSet<usize>> {
-    let mut command = Command::new("git");
-    let output = command
-        .arg("-C")
-        .arg(workspace_root)
-        .args(["diff", "--name-only", revision])
-        .output()
-        .map_err(|error| SnpmError::WorkspaceConfig {
+    let output = crate::git::output(workspace_root, &["diff", "--name-only", revision]).map_err(
+        |reason| SnpmError::WorkspaceConfig {
             path: workspace_root.to_path_buf(),
-            reason: format!("failed to run git diff: {error}"),
-        })?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(SnpmError::WorkspaceConfig {
-            path: workspace_root.to_path_buf(),
-            reason: format!("git diff failed: {}", stderr.trim()),
-        });
-    }
+            reason,
+        },
+    )?;
 
-    let changed: Vec<PathBuf> = String::from_utf8_lossy(&output.stdout)
+    let changed: Vec<PathBuf> = output
         .lines()
         .filter_map(|line| {
             let trimmed = line.trim();
@@ -591,6 +578,12 @@ mod tests {
                 ignored_built_dependencies: vec![],
                 disable_global_virtual_store_for_packages: None,
                 hoisting: None,
+                package_extensions: Default::default(),
+                peer_dependency_rules: Default::default(),
+                supported_architectures: Default::default(),
+                resolution_mode: None,
+                trust_policy: None,
+                trust_policy_exclude: Vec::new(),
             },
             projects: vec![
                 Project {
@@ -0,0 +1,156 @@
+mod prune;
+mod verify;
+
+pub use prune::{StorePruneReport, prune};
+pub use verify::{CorruptPackage, StoreVerifyOptions, StoreVerifyReport, verify};
+
+use crate::{Result, SnpmConfig, SnpmError};
+use std::fs;
+use std::path::Path;
+
+pub struct StoreStatus {
+    pub packages_count: usize,
+    /// Bytes under the package directories that are not hard links into
+    /// the file pool (indexes, metadata, unpooled files).
+    pub packages_size: u64,
+    /// Distinct file contents in the content-addressed pool.
+    pub files_count: usize,
+    pub files_size: u64,
+    pub metadata_count: usize,
+    pub metadata_size: u64,
+    pub store_path: String,
+}
+
+pub fn status(config: &SnpmConfig) -> Result<StoreStatus> {
+    let packages_directory = config.packages_dir();
+    let metadata_directory = config.metadata_dir();
+
+    let mut pooled = PooledFiles::default();
+    let (files_count, files_size) = pooled.add_pool(&config.files_dir());
+
+    let packages_count = if packages_directory.exists() {
+        count_entries(&packages_directory)?
+    } else {
+        0
+    };
+    let packages_size = pooled.size_outside(&packages_directory);
+
+    let (metadata_count, metadata_size) = if metadata_directory.exists() {
+        count_entries_and_size(&metadata_directory)?
+    } else {
+        (0, 0)
+    };
+
+    Ok(StoreStatus {
+        packages_count,
+        packages_size,
+        files_count,
+        files_size,
+        metadata_count,
+        metadata_size,
+        store_path: packages_directory.display().to_string(),
+    })
+}
+
+pub fn path(config: &SnpmConfig) -> String {
+    config.packages_dir().display().to_string()
+}
+
+fn count_entries_and_size(directory: &Path) -> Result<(usize, u64)> {
+    Ok((count_entries(directory)?, directory_size(directory)))
+}
+
+fn count_entries(directory: &Path) -> Result<usize> {
+    let entries = fs::read_dir(directory).map_err(|source| SnpmError::ReadFile {
+        path: directory.to_path_buf(),
+        source,
+    })?;
+
+    Ok(entries.filter_map(|entry| entry.ok()).count())
+}
+
+fn directory_size(path: &Path) -> u64 {
+    if path.is_file() {
+        return path.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+    }
+
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| directory_size(&entry.path()))
+        .sum()
+}
+
+/// Sizes the pool once, so files hard-linked to its blobs are not counted
+/// again under the package directories.
+#[derive(Default)]
+struct PooledFiles {
+    #[cfg(unix)]
+    inodes: std::collections::HashSet<(u64, u64)>,
+}
+
+impl PooledFiles {
+    fn add_pool(&mut self, files_dir: &Path) -> (usize, u64) {
+        let mut count = 0;
+        let mut size = 0;
+        self.walk(files_dir, &mut |pooled, metadata| {
+            pooled.remember(metadata);
+            count += 1;
+            size += metadata.len();
+        });
+        (count, size)
+    }
+
+    fn size_outside(&mut self, directory: &Path) -> u64 {
+        let mut size = 0;
+        self.walk(directory, &mut |pooled, metadata| {
+            if !pooled.contains(metadata) {
+                size += metadata.len();
+            }
+        });
+        size
+    }
+
+    fn walk(&mut self, path: &Path, visit: &mut dyn FnMut(&mut Self, &fs::Metadata)) {
+        let Ok(metadata) = fs::symlink_metadata(path) else {
+            return;
+        };
+        if metadata.is_file() {
+            visit(self, &metadata);
+            return;
+        }
+        if !metadata.is_dir() {
+            return;
+        }
+
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            self.walk(&entry.path(), visit);
+        }
+    }
+
+    #[cfg(unix)]
+    fn remember(&mut self, metadata: &fs::Metadata) {
+        use std::os::unix::fs::MetadataExt;
+        self.inodes.insert((metadata.dev(), metadata.ino()));
+    }
+
+    #[cfg(unix)]
+    fn contains(&self, metadata: &fs::Metadata) -> bool {
+        use std::os::unix::fs::MetadataExt;
+        self.inodes.contains(&(metadata.dev(), metadata.ino()))
+    }
+
+    #[cfg(not(unix))]
+    fn remember(&mut self, _metadata: &fs::Metadata) {}
+
+    #[cfg(not(unix))]
+    fn contains(&self, _metadata: &fs::Metadata) -> bool {
+        false
+    }
+}
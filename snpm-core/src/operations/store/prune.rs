@@ -0,0 +1,741 @@
+use crate::lockfile::{self, Lockfile};
+use crate::store::{self, ProjectRecord, StoreBaseline};
+use crate::{Result, SnpmConfig, SnpmError, console};
+
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default)]
+pub struct StorePruneReport {
+    /// `name@version` of every extraction that never completed.
+    pub incomplete: Vec<String>,
+    /// Recorded projects still on disk. `None` when no install has recorded
+    /// a project yet, in which case only incomplete extractions are pruned.
+    pub live_projects: Option<usize>,
+    /// Recorded projects whose directory no longer exists. They are dropped
+    /// from the registry unless this was a dry run.
+    pub dead_projects: Vec<PathBuf>,
+    /// Live projects whose lockfile could not be read. Nothing says which
+    /// stored packages they use, so while any are listed no stored package
+    /// or side-effects cache slot is removed.
+    pub unreadable_lockfiles: Vec<PathBuf>,
+    /// Live projects whose lockfile changed since they last installed, so
+    /// their `node_modules` may use packages it no longer lists. These
+    /// keep every stored package too.
+    pub changed_lockfiles: Vec<PathBuf>,
+    /// `name@version` of every stored package no live project references.
+    pub packages: Vec<String>,
+    pub virtual_store_entries: usize,
+    pub side_effects_slots: usize,
+    /// Unreferenced packages and virtual store entries kept because they
+    /// were in the store before projects were recorded, when a project
+    /// that was never recorded may still use them.
+    pub kept_from_baseline: usize,
+    /// Pooled files no stored package or virtual store entry links to.
+    pub pooled_files: usize,
+    /// Bytes freed, or that would be freed on a dry run.
+    pub reclaimable_bytes: u64,
+}
+
+impl StorePruneReport {
+    pub fn is_empty(&self) -> bool {
+        self.incomplete.is_empty()
+            && self.dead_projects.is_empty()
+            && self.packages.is_empty()
+            && self.virtual_store_entries == 0
+            && self.side_effects_slots == 0
+            && self.pooled_files == 0
+    }
+}
+
+/// Removes incomplete extractions, then everything in the store that the
+/// recorded projects still on disk no longer reference: stored packages,
+/// shared virtual store entries, side-effects cache slots and, last, the
+/// pooled files only those held on to. Packages and entries in the store's
+/// baseline are kept.
+pub fn prune(config: &SnpmConfig, dry_run: bool) -> Result<StorePruneReport> {
+    let mut report = StorePruneReport::default();
+    let mut reclaim = Reclaim::default();
+    let mut removals = Vec::new();
+
+    for (label, path) in incomplete_packages(config)? {
+        reclaim.add_tree(&path);
+        removals.push(path);
+        report.incomplete.push(label);
+    }
+
+    if let Some(records) = store::registered_projects(config) {
+        let (live, dead): (Vec<ProjectRecord>, Vec<ProjectRecord>) =
+            records.into_iter().partition(ProjectRecord::is_live);
+        report.live_projects = Some(live.len());
+        report.dead_projects = dead.into_iter().map(|record| record.root).collect();
+
+        let references = StoreReferences::collect(config, &live);
+        let baseline = store::store_baseline(config);
+        report.unreadable_lockfiles = references.unreadable_lockfiles.clone();
+        report.changed_lockfiles = references.changed_lockfiles.clone();
+
+        for (label, path) in references.unreferenced_packages(config)? {
+            if in_baseline_packages(config, &baseline, &path) {
+                report.kept_from_baseline += 1;
+                continue;
+            }
+            reclaim.add_tree(&path);
+            removals.push(path);
+            report.packages.push(label);
+        }
+        for path in references.unreferenced_virtual_store_entries(config) {
+            if in_baseline_virtual_store(&baseline, &path) {
+                report.kept_from_baseline += 1;
+                continue;
+            }
+            reclaim.add_tree(&path);
+            removals.push(path);
+            report.virtual_store_entries += 1;
+        }
+        for path in references.unreferenced_side_effects_slots(config) {
+            reclaim.add_tree(&path);
+            removals.push(path);
+            report.side_effects_slots += 1;
+        }
+    }
+
+    let orphaned_blobs = reclaim.orphaned_pool_blobs(&config.files_dir());
+    report.pooled_files = orphaned_blobs.len();
+    report.reclaimable_bytes = reclaim.bytes();
+
+    if dry_run {
+        return Ok(report);
+    }
+
+    for path in &removals {
+        if let Err(error) = fs::remove_dir_all(path) {
+            console::verbose(&format!("failed to remove {}: {error}", path.display()));
+        }
+        if let Some(parent) = path.parent() {
+            remove_if_empty(parent);
+        }
+    }
+    for blob in &orphaned_blobs {
+        if Reclaim::is_orphaned_blob(blob) {
+            fs::remove_file(blob).ok();
+        }
+    }
+
+    store::forget_projects(config, &report.dead_projects)?;
+    Ok(report)
+}
+
+fn in_baseline_packages(config: &SnpmConfig, baseline: &StoreBaseline, path: &Path) -> bool {
+    path.strip_prefix(config.packages_dir())
+        .is_ok_and(|relative| baseline.packages.contains(relative))
+}
+
+fn in_baseline_virtual_store(baseline: &StoreBaseline, path: &Path) -> bool {
+    path.file_name().is_some_and(|name| {
+        baseline
+            .virtual_store_entries
+            .contains(name.to_string_lossy().as_ref())
+    })
+}
+
+/// Every directory whose `.snpm` virtual store a store change may have to
+/// update: the recorded projects still on disk and, for workspace roots,
+/// the members their lockfile lists.
+pub(super) fn registered_project_roots(config: &SnpmConfig) -> Vec<PathBuf> {
+    store::registered_projects(config)
+        .unwrap_or_default()
+        .iter()
+        .filter(|record| record.is_live())
+        .flat_map(|record| project_roots(record, lockfile::read(&record.lockfile).ok().as_ref()))
+        .collect()
+}
+
+fn project_roots(record: &ProjectRecord, lockfile: Option<&Lockfile>) -> Vec<PathBuf> {
+    let mut roots = vec![record.root.clone()];
+    if let Some(lockfile) = lockfile {
+        roots.extend(
+            lockfile
+                .importers
+                .keys()
+                .filter(|key| key.as_str() != ".")
+                .map(|key| record.root.join(key)),
+        );
+    }
+    roots
+}
+
+fn incomplete_packages(config: &SnpmConfig) -> Result<Vec<(String, PathBuf)>> {
+    let packages_directory = config.packages_dir();
+    if !packages_directory.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(&packages_directory).map_err(|source| SnpmError::ReadFile {
+        path: packages_directory.clone(),
+        source,
+    })?;
+
+    let mut incomplete = Vec::new();
+    for entry in entries.flatten() {
+        let entry_path = entry.path();
+        if !entry_path.is_dir() {
+            continue;
+        }
+
+        let version_entries = match fs::read_dir(&entry_path) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for version_entry in version_entries.flatten() {
+            let version_path = version_entry.path();
+            if !version_path.is_dir() {
+                continue;
+            }
+
+            let marker = version_path.join(".snpm_complete");
+            if !marker.is_file() {
+                let name = entry_path.file_name().unwrap_or_default().to_string_lossy();
+                let version = version_path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy();
+                incomplete.push((format!("{name}@{version}"), version_path));
+            }
+        }
+    }
+
+    Ok(incomplete)
+}
+
+/// What the live projects keep alive in the store.
+#[derive(Default)]
+struct StoreReferences {
+    /// Stored package directories of every locked package.
+    packages: HashSet<PathBuf>,
+    /// `<name>@<version>` keys of side-effects cache slots (`/` as `__`).
+    side_effects: HashSet<String>,
+    /// Names of shared virtual store entries reachable through symlinks
+    /// from the projects' `node_modules` and `.snpm` directories.
+    virtual_store_entries: HashSet<OsString>,
+    /// Lockfiles of live projects that could not be read.
+    unreadable_lockfiles: Vec<PathBuf>,
+    /// Lockfiles of live projects that changed since their last install.
+    changed_lockfiles: Vec<PathBuf>,
+}
+
+impl StoreReferences {
+    fn collect(config: &SnpmConfig, live: &[ProjectRecord]) -> Self {
+        let packages_dir = config.packages_dir();
+        let virtual_store = config.virtual_store_dir();
+        let mut references = StoreReferences::default();
+        let mut pending = Vec::new();
+
+        for record in live {
+            if record.lockfile_changed() {
+                references.changed_lockfiles.push(record.lockfile.clone());
+            }
+            let lockfile = match lockfile::read(&record.lockfile) {
+                Ok(lockfile) => Some(lockfile),
+                Err(error) => {
+                    // The symlinks below still keep its virtual store
+                    // entries, but their files may link to stored packages
+                    // (file symlinks do), so those all stay.
+                    console::verbose(&format!(
+                        "keeping every stored package for {}: {error}",
+                        record.root.display()
+                    ));
+                    references
+                        .unreadable_lockfiles
+                        .push(record.lockfile.clone());
+                    None
+                }
+            };
+
+            if let Some(lockfile) = &lockfile {
+                for package in lockfile.packages.values() {
+                    references.packages.insert(
+                        packages_dir
+                            .join(store::sanitize_name(&package.name))
+                            .join(&package.version),
+                    );
+                    references.side_effects.insert(format!(
+                        "{}@{}",
+                        package.name.replace('/', "__"),
+                        package.version
+                    ));
+                }
+            }
+
+            for root in project_roots(record, lockfile.as_ref()) {
+                linked_entries(&root.join("node_modules"), &virtual_store, &mut pending);
+                if let Ok(entries) = fs::read_dir(root.join(".snpm")) {
+                    for entry in entries.flatten() {
+                        linked_entries(
+                            &entry.path().join("node_modules"),
+                            &virtual_store,
+                            &mut pending,
+                        );
+                    }
+                }
+            }
+        }
+
+        // Shared entries link their dependencies to other shared entries.
+        while let Some(entry) = pending.pop() {
+            if references.virtual_store_entries.insert(entry.clone()) {
+                linked_entries(
+                    &virtual_store.join(&entry).join("node_modules"),
+                    &virtual_store,
+                    &mut pending,
+                );
+            }
+        }
+
+        references
+    }
+
+    /// Whether some live project may use stored packages its lockfile
+    /// does not tell about.
+    fn keeps_every_package(&self) -> bool {
+        !self.unreadable_lockfiles.is_empty() || !self.changed_lockfiles.is_empty()
+    }
+
+    /// None while `keeps_every_package`.
+    fn unreferenced_packages(&self, config: &SnpmConfig) -> Result<Vec<(String, PathBuf)>> {
+        if self.keeps_every_package() {
+            return Ok(Vec::new());
+        }
+
+        Ok(store::stored_package_dirs(config)?
+            .into_iter()
+            .filter(|package_dir| !self.packages.contains(package_dir))
+            .map(|package_dir| (store::stored_package_label(&package_dir), package_dir))
+            .collect())
+    }
+
+    fn unreferenced_virtual_store_entries(&self, config: &SnpmConfig) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(config.virtual_store_dir()) else {
+            return Vec::new();
+        };
+
+        let mut unreferenced: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name();
+                // Staging directories belong to installs in progress.
+                !name.to_string_lossy().starts_with('.')
+                    && !self.virtual_store_entries.contains(&name)
+            })
+            .map(|entry| entry.path())
+            .collect();
+        unreferenced.sort();
+        unreferenced
+    }
+
+    /// Slots live at `<platform>/<name>@<version>/<input hash>`; a package
+    /// version no live project locks loses every platform's slots. None
+    /// while `keeps_every_package`.
+    fn unreferenced_side_effects_slots(&self, config: &SnpmConfig) -> Vec<PathBuf> {
+        if self.keeps_every_package() {
+            return Vec::new();
+        }
+        let Ok(platforms) = fs::read_dir(config.side_effects_cache_dir()) else {
+            return Vec::new();
+        };
+
+        let mut unreferenced = Vec::new();
+        for platform in platforms.flatten() {
+            let Ok(slots) = fs::read_dir(platform.path()) else {
+                continue;
+            };
+            unreferenced.extend(
+                slots
+                    .flatten()
+                    .filter(|slot| {
+                        !self
+                            .side_effects
+                            .contains(slot.file_name().to_string_lossy().as_ref())
+                    })
+                    .map(|slot| slot.path()),
+            );
+        }
+        unreferenced.sort();
+        unreferenced
+    }
+}
+
+/// Adds the shared virtual store entries the symlinks in `node_modules`
+/// (and its `@scope` directories) point into.
+fn linked_entries(node_modules: &Path, virtual_store: &Path, found: &mut Vec<OsString>) {
+    let Ok(entries) = fs::read_dir(node_modules) else {
+        return;
+    };
+    let Ok(virtual_store) = fs::canonicalize(virtual_store) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+
+        if file_type.is_dir() && entry.file_name().to_string_lossy().starts_with('@') {
+            linked_entries(&path, &virtual_store, found);
+            continue;
+        }
+        if !file_type.is_symlink() {
+            continue;
+        }
+
+        if let Ok(target) = fs::canonicalize(&path)
+            && let Ok(relative) = target.strip_prefix(&virtual_store)
+            && let Some(entry_name) = relative.components().next()
+        {
+            found.push(entry_name.as_os_str().to_os_string());
+        }
+    }
+}
+
+fn remove_if_empty(directory: &Path) {
+    if let Ok(mut remaining) = fs::read_dir(directory)
+        && remaining.next().is_none()
+    {
+        fs::remove_dir(directory).ok();
+    }
+}
+
+/// Tallies the files about to be removed. Store files are hard links into
+/// the file pool and virtual store entries link the same blobs, so bytes
+/// only count once every link to a file goes.
+#[derive(Default)]
+struct Reclaim {
+    /// `(dev, ino)` to the file's size, link count and links being removed.
+    #[cfg(unix)]
+    files: std::collections::HashMap<(u64, u64), (u64, u64, u64)>,
+    #[cfg(not(unix))]
+    bytes: u64,
+}
+
+impl Reclaim {
+    fn add_tree(&mut self, path: &Path) {
+        let Ok(metadata) = fs::symlink_metadata(path) else {
+            return;
+        };
+        if metadata.is_file() {
+            self.add_file(&metadata);
+            return;
+        }
+        if !metadata.is_dir() {
+            return;
+        }
+
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            self.add_tree(&entry.path());
+        }
+    }
+
+    #[cfg(unix)]
+    fn add_file(&mut self, metadata: &fs::Metadata) {
+        use std::os::unix::fs::MetadataExt;
+
+        let file = self
+            .files
+            .entry((metadata.dev(), metadata.ino()))
+            .or_insert((metadata.len(), metadata.nlink(), 0));
+        file.2 += 1;
+    }
+
+    #[cfg(not(unix))]
+    fn add_file(&mut self, metadata: &fs::Metadata) {
+        self.bytes += metadata.len();
+    }
+
+    /// Pool blobs left with no link but their own once the tallied files
+    /// are gone; they are tallied too.
+    #[cfg(unix)]
+    fn orphaned_pool_blobs(&mut self, files_dir: &Path) -> Vec<PathBuf> {
+        use std::os::unix::fs::MetadataExt;
+
+        let Ok(prefixes) = fs::read_dir(files_dir) else {
+            return Vec::new();
+        };
+
+        let mut orphaned = Vec::new();
+        for prefix in prefixes.flatten() {
+            let Ok(blobs) = fs::read_dir(prefix.path()) else {
+                continue;
+            };
+            for blob in blobs.flatten() {
+                let Ok(metadata) = blob.metadata() else {
+                    continue;
+                };
+                if !metadata.is_file() {
+                    continue;
+                }
+
+                let removed = self
+                    .files
+                    .get(&(metadata.dev(), metadata.ino()))
+                    .map_or(0, |file| file.2);
+                if metadata.nlink().saturating_sub(removed) <= 1 {
+                    self.add_file(&metadata);
+                    orphaned.push(blob.path());
+                }
+            }
+        }
+        orphaned.sort();
+        orphaned
+    }
+
+    /// Without link counts there is no telling which blobs are unused.
+    #[cfg(not(unix))]
+    fn orphaned_pool_blobs(&mut self, _files_dir: &Path) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Re-checked right before removal, in case an install linked the blob
+    /// again since the tally.
+    #[cfg(unix)]
+    fn is_orphaned_blob(blob: &Path) -> bool {
+        use std::os::unix::fs::MetadataExt;
+        fs::metadata(blob).is_ok_and(|metadata| metadata.nlink() <= 1)
+    }
+
+    #[cfg(not(unix))]
+    fn is_orphaned_blob(_blob: &Path) -> bool {
+        false
+    }
+
+    #[cfg(unix)]
+    fn bytes(&self) -> u64 {
+        self.files
+            .values()
+            .filter(|(_, links, removed)| removed >= links)
+            .map(|(size, _, _)| size)
+            .sum()
+    }
+
+    #[cfg(not(unix))]
+    fn bytes(&self) -> u64 {
+        self.bytes
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::prune;
+    use crate::config::SnpmConfig;
+    use crate::store::{ProjectRecord, record_project, registered_projects};
+
+    use std::fs;
+    use std::os::unix::fs::symlink;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    const LOCKFILE: &str = r#"version: 1
+root:
+  dependencies:
+    pkg:
+      requested: ^1.0.0
+      version: 1.0.0
+packages:
+  pkg@1.0.0:
+    name: pkg
+    version: 1.0.0
+    tarball: https://registry.npmjs.org/pkg/-/pkg-1.0.0.tgz
+    dependencies: {}
+"#;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn record(config: &SnpmConfig, root: &Path) {
+        record_project(
+            config,
+            ProjectRecord::new(root.to_path_buf(), root.join("snpm-lock.yaml")),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn removes_what_no_live_project_references() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+        let project = dir.path().join("project");
+        let gone = dir.path().join("gone");
+        for root in [&project, &gone] {
+            record(&config, root);
+        }
+
+        let packages = config.packages_dir().join("pkg");
+        for version in ["1.0.0", "2.0.0"] {
+            write(&packages.join(version).join(".snpm_complete"), "");
+        }
+        write(&packages.join("1.0.0/package/index.js"), "one");
+        write(&packages.join("2.0.0/package/index.js"), "two");
+        write(&packages.join("3.0.0/package/index.js"), "partial");
+        let blob = config.files_dir().join("ab/cdef");
+        fs::create_dir_all(blob.parent().unwrap()).unwrap();
+        fs::hard_link(packages.join("2.0.0/package/index.js"), &blob).unwrap();
+
+        let virtual_store = config.virtual_store_dir();
+        write(
+            &virtual_store.join("pkg@1.0.0-aa/node_modules/pkg/index.js"),
+            "one",
+        );
+        write(
+            &virtual_store.join("pkg@2.0.0-bb/node_modules/pkg/index.js"),
+            "two",
+        );
+
+        let side_effects = config.side_effects_cache_dir().join("linux-x64-node20");
+        write(&side_effects.join("pkg@1.0.0/hash/out"), "1");
+        write(&side_effects.join("pkg@2.0.0/hash/out"), "2");
+
+        write(&project.join("snpm-lock.yaml"), LOCKFILE);
+        fs::create_dir_all(project.join("node_modules")).unwrap();
+        symlink(
+            virtual_store.join("pkg@1.0.0-aa/node_modules/pkg"),
+            project.join("node_modules/pkg"),
+        )
+        .unwrap();
+
+        let report = prune(&config, true).unwrap();
+        assert_eq!(report.incomplete, vec!["pkg@3.0.0".to_string()]);
+        assert_eq!(report.kept_from_baseline, 0);
+        assert_eq!(report.live_projects, Some(1));
+        assert_eq!(report.dead_projects, vec![gone.clone()]);
+        assert_eq!(report.packages, vec!["pkg@2.0.0".to_string()]);
+        assert_eq!(report.virtual_store_entries, 1);
+        assert_eq!(report.side_effects_slots, 1);
+        assert_eq!(report.pooled_files, 1);
+        // "partial", the stored and the linked "two" (once), "2".
+        assert_eq!(report.reclaimable_bytes, 7 + 3 + 3 + 1);
+        assert!(packages.join("2.0.0").exists());
+
+        prune(&config, false).unwrap();
+        assert!(packages.join("1.0.0").exists());
+        assert!(!packages.join("2.0.0").exists());
+        assert!(!packages.join("3.0.0").exists());
+        assert!(!blob.exists());
+        assert!(virtual_store.join("pkg@1.0.0-aa").exists());
+        assert!(!virtual_store.join("pkg@2.0.0-bb").exists());
+        assert!(side_effects.join("pkg@1.0.0").exists());
+        assert!(!side_effects.join("pkg@2.0.0").exists());
+        assert_eq!(
+            registered_projects(&config)
+                .unwrap()
+                .into_iter()
+                .map(|record| record.root)
+                .collect::<Vec<_>>(),
+            vec![project]
+        );
+        assert!(prune(&config, false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn keeps_every_stored_package_while_a_live_lockfile_is_unreadable() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+        let project = dir.path().join("project");
+        let broken = dir.path().join("broken");
+        record(&config, &project);
+        record(&config, &broken);
+        write(&project.join("snpm-lock.yaml"), LOCKFILE);
+        write(&broken.join("snpm-lock.yaml"), "packages: [");
+
+        let packages = config.packages_dir().join("pkg");
+        write(&packages.join("2.0.0/.snpm_complete"), "");
+        let side_effects = config.side_effects_cache_dir().join("linux-x64-node20");
+        write(&side_effects.join("pkg@2.0.0/hash/out"), "2");
+
+        let report = prune(&config, false).unwrap();
+        assert_eq!(
+            report.unreadable_lockfiles,
+            vec![broken.join("snpm-lock.yaml")]
+        );
+        assert!(report.packages.is_empty());
+        assert_eq!(report.side_effects_slots, 0);
+        assert!(packages.join("2.0.0").exists());
+        assert!(side_effects.join("pkg@2.0.0").exists());
+    }
+
+    #[test]
+    fn keeps_every_stored_package_while_a_lockfile_is_ahead_of_its_install() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+        let project = dir.path().join("project");
+        write(&project.join("snpm-lock.yaml"), LOCKFILE);
+        record(&config, &project);
+        // Checked out a newer lockfile without installing it.
+        write(
+            &project.join("snpm-lock.yaml"),
+            &LOCKFILE.replace("1.0.0", "2.0.0"),
+        );
+
+        let packages = config.packages_dir().join("pkg");
+        write(&packages.join("1.0.0/.snpm_complete"), "");
+
+        let report = prune(&config, false).unwrap();
+        assert_eq!(
+            report.changed_lockfiles,
+            vec![project.join("snpm-lock.yaml")]
+        );
+        assert!(report.packages.is_empty());
+        assert!(packages.join("1.0.0").exists());
+    }
+
+    #[test]
+    fn keeps_what_was_stored_before_projects_were_recorded() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+
+        // Installed by an snpm that did not record projects.
+        let packages = config.packages_dir().join("pkg");
+        write(&packages.join("1.0.0/.snpm_complete"), "");
+        let virtual_store = config.virtual_store_dir();
+        write(
+            &virtual_store.join("pkg@1.0.0-aa/node_modules/pkg/index.js"),
+            "one",
+        );
+
+        let project = dir.path().join("project");
+        write(
+            &project.join("snpm-lock.yaml"),
+            &LOCKFILE.replace("1.0.0", "2.0.0"),
+        );
+        record(&config, &project);
+        write(&packages.join("2.0.0/.snpm_complete"), "");
+        write(&packages.join("3.0.0/.snpm_complete"), "");
+
+        let report = prune(&config, false).unwrap();
+        assert_eq!(report.packages, vec!["pkg@3.0.0".to_string()]);
+        assert_eq!(report.virtual_store_entries, 0);
+        assert_eq!(report.kept_from_baseline, 2);
+        assert!(packages.join("1.0.0").exists());
+        assert!(packages.join("2.0.0").exists());
+        assert!(virtual_store.join("pkg@1.0.0-aa").exists());
+    }
+}
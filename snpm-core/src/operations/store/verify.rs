@@ -0,0 +1,136 @@
+use super::prune::registered_project_roots;
+use crate::linker::fs::relink_dir;
+use crate::linker::virtual_package_locations;
+use crate::resolve::PackageId;
+use crate::store::{self, PackageHealth, PackageVerification};
+use crate::{Result, SnpmConfig, console, http};
+
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default)]
+pub struct StoreVerifyOptions {
+    /// Re-download corrupt entries and relink the virtual stores using them.
+    pub repair: bool,
+    /// Projects whose `.snpm` virtual store is relinked after a repair, on
+    /// top of the shared virtual store and the projects recorded in the
+    /// store's project registry.
+    pub project_roots: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CorruptPackage {
+    pub name: String,
+    pub version: String,
+    pub problems: Vec<String>,
+    pub repaired: bool,
+    /// Why the repair failed, when it was attempted.
+    pub repair_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StoreVerifyReport {
+    pub checked: usize,
+    /// Entries with neither a file manifest nor a cached tarball.
+    pub unverifiable: usize,
+    pub corrupt: Vec<CorruptPackage>,
+    /// Virtual store package directories relinked to repaired entries.
+    pub relinked: usize,
+}
+
+pub async fn verify(
+    config: &SnpmConfig,
+    options: &StoreVerifyOptions,
+) -> Result<StoreVerifyReport> {
+    store::ensure_store_layout(config).await?;
+    let package_dirs = store::stored_package_dirs(config)?;
+    let verifications: Vec<PackageVerification> = package_dirs
+        .par_iter()
+        .map(|package_dir| store::verify_package(config, package_dir))
+        .collect::<Result<_>>()?;
+
+    let mut report = StoreVerifyReport {
+        checked: verifications.len(),
+        ..StoreVerifyReport::default()
+    };
+
+    let client = if options.repair && verifications.iter().any(PackageVerification::is_corrupt) {
+        Some(http::create_client()?)
+    } else {
+        None
+    };
+
+    for verification in &verifications {
+        let problems = match &verification.health {
+            PackageHealth::Intact(_) => continue,
+            PackageHealth::Unverifiable => {
+                report.unverifiable += 1;
+                continue;
+            }
+            PackageHealth::Corrupt { problems, .. } => problems.clone(),
+        };
+
+        let mut corrupt = CorruptPackage {
+            name: verification.name.clone(),
+            version: verification.version.clone(),
+            problems,
+            repaired: false,
+            repair_error: None,
+        };
+
+        if let Some(client) = &client {
+            match store::repair_package(config, verification, client).await {
+                Ok(root) => {
+                    corrupt.repaired = true;
+                    report.relinked += relink_package(config, options, verification, &root);
+                }
+                Err(error) => corrupt.repair_error = Some(error.to_string()),
+            }
+        }
+
+        report.corrupt.push(corrupt);
+    }
+
+    Ok(report)
+}
+
+/// Relinks every virtual store directory that materialized the repaired
+/// package, which may still hold the corrupt files (hard links share them).
+fn relink_package(
+    config: &SnpmConfig,
+    options: &StoreVerifyOptions,
+    verification: &PackageVerification,
+    root: &Path,
+) -> usize {
+    let id = PackageId {
+        name: verification.name.clone(),
+        version: verification.version.clone(),
+    };
+
+    let mut project_roots = options.project_roots.clone();
+    for root in registered_project_roots(config) {
+        if !project_roots.contains(&root) {
+            project_roots.push(root);
+        }
+    }
+
+    let virtual_stores = std::iter::once(config.virtual_store_dir()).chain(
+        project_roots
+            .iter()
+            .map(|project_root| project_root.join(".snpm")),
+    );
+
+    let mut relinked = 0;
+    for virtual_store in virtual_stores {
+        for location in virtual_package_locations(&virtual_store, &id) {
+            match relink_dir(config, root, &location) {
+                Ok(()) => relinked += 1,
+                Err(error) => {
+                    console::warn(&format!("failed to relink {}: {error}", location.display()))
+                }
+            }
+        }
+    }
+
+    relinked
+}
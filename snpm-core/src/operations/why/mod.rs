@@ -3,5 +3,6 @@ mod pattern;
 mod service;
 mod types;
 
+pub(crate) use service::DependencyPaths;
 pub use service::why;
 pub use types::{WhyHop, WhyOptions, WhyPackageMatch, WhyPath, WhyResult};
@@ -1,18 +1,32 @@
 use crate::lockfile;
 use crate::operations::why::index::{ReverseIndex, build_reverse_index};
 use crate::operations::why::pattern::matches_pattern;
-use crate::resolve::PackageId;
+use crate::resolve::{PackageId, ResolutionGraph};
 use crate::{Project, Result, SnpmError, Workspace};
 
-use std::path::PathBuf;
+use std::collections::{BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
 
 pub(super) fn load_why_context(
     project: &Project,
     patterns: &[String],
 ) -> Result<(ReverseIndex, Vec<PackageId>)> {
-    let lockfile_path = find_lockfile_path(project)?;
+    let (workspace_root, lockfile_path) = find_lockfile_path(project)?;
     let lock = lockfile::read(&lockfile_path)?;
-    let graph = lockfile::to_graph(&lock);
+    let mut graph = lockfile::to_graph(&lock);
+
+    // From inside a workspace member, only explain what that member pulls
+    // in. The workspace root (and lockfiles without importers) keep the
+    // whole-workspace view.
+    if let Some(workspace_root) = workspace_root
+        && workspace_root != project.root
+        && let Some(importer) = lock
+            .importers
+            .get(&lockfile::importer_key(&workspace_root, &project.root))
+    {
+        scope_to_importer(&mut graph, importer);
+    }
+
     let index = build_reverse_index(&graph);
 
     let mut targets: Vec<PackageId> = graph
@@ -30,15 +44,41 @@ pub(super) fn load_why_context(
     Ok((index, targets))
 }
 
-fn find_lockfile_path(project: &Project) -> Result<PathBuf> {
-    let workspace = Workspace::discover(&project.root)?;
-    let lockfile_path = workspace
-        .as_ref()
-        .map(|workspace| workspace.root.join("snpm-lock.yaml"))
-        .unwrap_or_else(|| project.root.join("snpm-lock.yaml"));
+pub(super) fn scope_to_importer(graph: &mut ResolutionGraph, importer: &lockfile::LockImporter) {
+    graph.root.dependencies.retain(|name, _| {
+        importer.dependencies.contains_key(name) || importer.dev_dependencies.contains_key(name)
+    });
+
+    let mut reachable = BTreeSet::new();
+    let mut queue: VecDeque<PackageId> = graph
+        .root
+        .dependencies
+        .values()
+        .map(|dep| dep.resolved.clone())
+        .collect();
+
+    while let Some(id) = queue.pop_front() {
+        if !reachable.insert(id.clone()) {
+            continue;
+        }
+
+        if let Some(package) = graph.packages.get(&id) {
+            queue.extend(package.dependencies.values().cloned());
+        }
+    }
+
+    graph.packages.retain(|id, _| reachable.contains(id));
+}
+
+fn find_lockfile_path(project: &Project) -> Result<(Option<PathBuf>, PathBuf)> {
+    let workspace_root = Workspace::discover(&project.root)?.map(|workspace| workspace.root);
+    let lockfile_path = workspace_root
+        .as_deref()
+        .unwrap_or(Path::new(&project.root))
+        .join("snpm-lock.yaml");
 
     if lockfile_path.is_file() {
-        Ok(lockfile_path)
+        Ok((workspace_root, lockfile_path))
     } else {
         Err(SnpmError::Lockfile {
             path: lockfile_path,
@@ -1,11 +1,13 @@
 mod lookup;
 mod paths;
 
-use super::types::{WhyOptions, WhyResult};
+use super::index::{ReverseIndex, build_reverse_index};
+use super::types::{WhyOptions, WhyPath, WhyResult};
+use crate::resolve::{PackageId, ResolutionGraph};
 use crate::{Project, Result};
 
 use lookup::load_why_context;
-use paths::build_match;
+use paths::{build_match, shortest_path};
 
 pub fn why(project: &Project, patterns: &[String], options: WhyOptions) -> Result<WhyResult> {
     let (index, targets) = load_why_context(project, patterns)?;
@@ -18,5 +20,24 @@ pub fn why(project: &Project, patterns: &[String], options: WhyOptions) -> Resul
     Ok(WhyResult { matches })
 }
 
+/// Answers "how did this get installed" for packages of an in-memory
+/// graph, for reports that want one path per package rather than every
+/// path `why` prints.
+pub(crate) struct DependencyPaths {
+    index: ReverseIndex,
+}
+
+impl DependencyPaths {
+    pub(crate) fn new(graph: &ResolutionGraph) -> Self {
+        Self {
+            index: build_reverse_index(graph),
+        }
+    }
+
+    pub(crate) fn shortest(&self, target: &PackageId) -> Option<WhyPath> {
+        shortest_path(target, &self.index)
+    }
+}
+
 #[cfg(test)]
 mod tests;
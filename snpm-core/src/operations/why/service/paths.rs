@@ -2,7 +2,7 @@ use crate::operations::why::index::ReverseIndex;
 use crate::operations::why::types::{WhyHop, WhyPackageMatch, WhyPath};
 use crate::resolve::PackageId;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, VecDeque};
 
 pub(super) fn build_match(
     target: &PackageId,
@@ -134,3 +134,45 @@ fn dedupe_paths(paths: &mut Vec<WhyPath>) {
     paths.sort();
     paths.dedup();
 }
+
+/// The shortest chain from a root dependency down to `target`, found
+/// breadth-first so it stays cheap on large graphs where enumerating every
+/// path (what `why` prints) would not be. `None` when no root reaches it.
+pub(super) fn shortest_path(target: &PackageId, index: &ReverseIndex) -> Option<WhyPath> {
+    let mut visited = BTreeSet::from([target.clone()]);
+    let mut queue = VecDeque::from([(target.clone(), Vec::new())]);
+
+    while let Some((current, hops)) = queue.pop_front() {
+        if let Some(root) = index
+            .root_parents
+            .get(&current)
+            .and_then(|roots| roots.first())
+        {
+            let mut hops = hops;
+            hops.push(WhyHop::Root {
+                name: root.name.clone(),
+                requested: root.requested.clone(),
+            });
+            return Some(WhyPath {
+                hops,
+                truncated: false,
+            });
+        }
+
+        for parent in index.package_parents.get(&current).into_iter().flatten() {
+            if !visited.insert(parent.parent.clone()) {
+                continue;
+            }
+
+            let mut parent_hops = hops.clone();
+            parent_hops.push(WhyHop::Package {
+                name: parent.parent.name.clone(),
+                version: parent.parent.version.clone(),
+                via: parent.via.clone(),
+            });
+            queue.push_back((parent.parent.clone(), parent_hops));
+        }
+    }
+
+    None
+}
@@ -1,4 +1,6 @@
-use super::paths::{build_match, walk_paths};
+use super::lookup::scope_to_importer;
+use super::paths::{build_match, shortest_path, walk_paths};
+use crate::operations::why::WhyHop;
 use crate::operations::why::index::build_reverse_index;
 use crate::resolve::{PackageId, ResolutionGraph, ResolutionRoot, ResolvedPackage, RootDependency};
 
@@ -29,6 +31,12 @@ fn graph_fixture() -> ResolutionGraph {
             bundled_dependencies: None,
             has_bin: false,
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         },
     );
 
@@ -43,6 +51,12 @@ fn graph_fixture() -> ResolutionGraph {
             bundled_dependencies: None,
             has_bin: false,
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         },
     );
 
@@ -57,6 +71,12 @@ fn graph_fixture() -> ResolutionGraph {
             bundled_dependencies: None,
             has_bin: false,
             bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
         },
     );
 
@@ -102,3 +122,77 @@ fn truncates_when_depth_reached() {
     assert!(paths[0].truncated);
     assert_eq!(paths[0].hops.len(), 1);
 }
+
+#[test]
+fn shortest_path_prefers_the_nearest_root() {
+    let mut graph = graph_fixture();
+    let target = package_id("target", "1.0.0");
+
+    let index = build_reverse_index(&graph);
+    let path = shortest_path(&target, &index).unwrap();
+    assert_eq!(path.hops.len(), 3);
+    assert!(matches!(&path.hops[2], WhyHop::Root { name, .. } if name == "top"));
+
+    graph.root.dependencies.insert(
+        "target".to_string(),
+        RootDependency {
+            requested: "^1.0.0".to_string(),
+            resolved: target.clone(),
+        },
+    );
+    let index = build_reverse_index(&graph);
+    let path = shortest_path(&target, &index).unwrap();
+    assert!(matches!(&path.hops[..], [WhyHop::Root { name, .. }] if name == "target"));
+}
+
+#[test]
+fn scope_to_importer_keeps_only_the_members_subgraph() {
+    let mut graph = graph_fixture();
+    let other = package_id("other", "1.0.0");
+    graph.packages.insert(
+        other.clone(),
+        ResolvedPackage {
+            id: other.clone(),
+            tarball: String::new(),
+            integrity: None,
+            dependencies: BTreeMap::new(),
+            peer_dependencies: BTreeMap::new(),
+            bundled_dependencies: None,
+            has_bin: false,
+            bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
+        },
+    );
+    graph.root.dependencies.insert(
+        "other".to_string(),
+        RootDependency {
+            requested: "^1.0.0".to_string(),
+            resolved: other.clone(),
+        },
+    );
+
+    let importer = crate::lockfile::LockImporter {
+        dev_dependencies: BTreeMap::from([(
+            "other".to_string(),
+            crate::lockfile::LockRootDependency {
+                requested: "^1.0.0".to_string(),
+                package: None,
+                version: Some("1.0.0".to_string()),
+                optional: false,
+            },
+        )]),
+        ..crate::lockfile::LockImporter::default()
+    };
+    scope_to_importer(&mut graph, &importer);
+
+    assert_eq!(
+        graph.root.dependencies.keys().collect::<Vec<_>>(),
+        vec!["other"]
+    );
+    assert_eq!(graph.packages.keys().collect::<Vec<_>>(), vec![&other]);
+}
@@ -0,0 +1,71 @@
+use snpm_semver::{RangeSet, Version, parse_version};
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Runtime versions a package's `engines` field is checked against.
+/// Engines left `None` are unknown and never reported as incompatible.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EngineTargets {
+    pub node: Option<Version>,
+    pub npm: Option<Version>,
+    pub snpm: Option<Version>,
+}
+
+/// One `engines` entry that excludes the target runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineMismatch {
+    pub engine: String,
+    pub range: String,
+    pub target: Version,
+}
+
+impl fmt::Display for EngineMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} (have {})", self.engine, self.range, self.target)
+    }
+}
+
+impl EngineTargets {
+    pub fn is_empty(&self) -> bool {
+        self.node.is_none() && self.npm.is_none() && self.snpm.is_none()
+    }
+
+    /// Records the running snpm's version, which only the binary knows.
+    pub fn set_snpm_version(&mut self, version: &str) {
+        self.snpm = parse_version(version).ok();
+    }
+
+    fn target(&self, engine: &str) -> Option<&Version> {
+        match engine {
+            "node" => self.node.as_ref(),
+            "npm" => self.npm.as_ref(),
+            "snpm" => self.snpm.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The entries of `engines` whose range excludes the matching target.
+    /// Unknown engines and ranges that fail to parse are ignored, and
+    /// prerelease targets are compared by their release version, as npm
+    /// does for `engine-strict`.
+    pub fn mismatches(&self, engines: &BTreeMap<String, String>) -> Vec<EngineMismatch> {
+        engines
+            .iter()
+            .filter_map(|(engine, range)| {
+                let target = self.target(engine)?;
+                let release = Version::new(target.major, target.minor, target.patch);
+                let parsed = RangeSet::parse(range).ok()?;
+                (!parsed.matches(&release)).then(|| EngineMismatch {
+                    engine: engine.clone(),
+                    range: range.clone(),
+                    target: target.clone(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn supports(&self, engines: &BTreeMap<String, String>) -> bool {
+        self.mismatches(engines).is_empty()
+    }
+}
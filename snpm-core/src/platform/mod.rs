@@ -1,8 +1,12 @@
 mod current;
+mod engines;
 mod matching;
+mod supported;
 
 pub use current::{current_cpu, current_libc, current_os};
+pub use engines::{EngineMismatch, EngineTargets};
 pub use matching::{is_compatible, is_compatible_with_libc, matches_cpu, matches_os};
+pub use supported::SupportedArchitectures;
 
 #[cfg(test)]
 mod tests;
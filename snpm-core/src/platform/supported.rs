@@ -0,0 +1,64 @@
+use super::matching::check_platform_list;
+use super::{current_cpu, current_libc, current_os};
+
+use serde::{Deserialize, Serialize};
+
+/// Token that stands for the host's own value in any of the lists.
+const CURRENT: &str = "current";
+
+/// Platforms to install for (`supportedArchitectures`). An empty list means
+/// the host only; otherwise a package must match one listed value in each
+/// of `os`, `cpu` and `libc`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SupportedArchitectures {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub os: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cpu: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub libc: Vec<String>,
+}
+
+impl SupportedArchitectures {
+    pub fn is_empty(&self) -> bool {
+        self.os.is_empty() && self.cpu.is_empty() && self.libc.is_empty()
+    }
+
+    /// Fills every list left empty here from `fallback`, so a CLI flag
+    /// replaces one dimension of the configured targets and keeps the rest.
+    pub fn or(mut self, fallback: &SupportedArchitectures) -> Self {
+        if self.os.is_empty() {
+            self.os = fallback.os.clone();
+        }
+        if self.cpu.is_empty() {
+            self.cpu = fallback.cpu.clone();
+        }
+        if self.libc.is_empty() {
+            self.libc = fallback.libc.clone();
+        }
+        self
+    }
+
+    /// Whether a package declaring `os`, `cpu` and `libc` runs on at least
+    /// one target in each dimension. A host whose libc is unknown (anything
+    /// but Linux) skips the libc check unless targets are listed explicitly.
+    pub fn supports(&self, os: &[String], cpu: &[String], libc: &[String]) -> bool {
+        targets(&self.os, current_os()).any(|target| check_platform_list(os, target))
+            && targets(&self.cpu, current_cpu()).any(|target| check_platform_list(cpu, target))
+            && targets(&self.libc, current_libc())
+                .any(|target| target == "unknown" || check_platform_list(libc, target))
+    }
+}
+
+fn targets<'a>(list: &'a [String], current: &'static str) -> impl Iterator<Item = &'a str> {
+    let host = list.is_empty().then_some(current);
+    list.iter()
+        .map(move |entry| {
+            if entry == CURRENT {
+                current
+            } else {
+                entry.as_str()
+            }
+        })
+        .chain(host)
+}
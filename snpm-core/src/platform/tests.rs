@@ -135,3 +135,88 @@ fn negation_of_current_os_excludes() {
     let os = vec![format!("!{}", current_os())];
     assert!(!is_compatible(&os, &[]));
 }
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn default_supported_architectures_are_the_host() {
+    let supported = SupportedArchitectures::default();
+    assert!(supported.supports(&strings(&[current_os()]), &strings(&[current_cpu()]), &[]));
+    assert!(!supported.supports(&strings(&["nonexistent-os"]), &[], &[]));
+}
+
+#[test]
+fn supported_architectures_accept_any_listed_target() {
+    let supported = SupportedArchitectures {
+        os: strings(&["current", "nonexistent-os"]),
+        cpu: strings(&["nonexistent-cpu"]),
+        libc: Vec::new(),
+    };
+
+    assert!(supported.supports(&strings(&["nonexistent-os"]), &[], &[]));
+    assert!(supported.supports(&strings(&[current_os()]), &[], &[]));
+    assert!(supported.supports(&[], &strings(&["nonexistent-cpu"]), &[]));
+    assert!(!supported.supports(&[], &strings(&[current_cpu()]), &[]));
+}
+
+#[test]
+fn explicit_libc_targets_apply_on_every_host() {
+    let supported = SupportedArchitectures {
+        libc: strings(&["musl"]),
+        ..SupportedArchitectures::default()
+    };
+
+    assert!(supported.supports(&[], &[], &strings(&["musl"])));
+    assert!(!supported.supports(&[], &[], &strings(&["glibc"])));
+}
+
+#[test]
+fn or_fills_only_empty_lists() {
+    let flags = SupportedArchitectures {
+        cpu: strings(&["arm64"]),
+        ..SupportedArchitectures::default()
+    };
+    let configured = SupportedArchitectures {
+        os: strings(&["darwin"]),
+        cpu: strings(&["x64"]),
+        libc: Vec::new(),
+    };
+
+    assert_eq!(
+        flags.or(&configured),
+        SupportedArchitectures {
+            os: strings(&["darwin"]),
+            cpu: strings(&["arm64"]),
+            libc: Vec::new(),
+        }
+    );
+}
+
+#[test]
+fn engine_targets_report_only_known_incompatible_engines() {
+    let mut targets = EngineTargets {
+        node: Some(snpm_semver::Version::new(18, 19, 0)),
+        ..Default::default()
+    };
+    targets.set_snpm_version("2026.7.3-r.1");
+
+    let engines = |entries: &[(&str, &str)]| -> std::collections::BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(engine, range)| (engine.to_string(), range.to_string()))
+            .collect()
+    };
+
+    assert!(targets.supports(&engines(&[("node", ">=16"), ("snpm", ">=2026")])));
+    assert!(targets.supports(&engines(&[("npm", ">=10"), ("vscode", "^1.80.0")])));
+    assert!(targets.supports(&engines(&[("node", "not a range")])));
+
+    let mismatches = targets.mismatches(&engines(&[("node", ">=20"), ("snpm", "<2026")]));
+    let rendered: Vec<String> = mismatches.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        rendered,
+        ["node >=20 (have 18.19.0)", "snpm <2026 (have 2026.7.3-r.1)"]
+    );
+}
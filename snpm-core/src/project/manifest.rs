@@ -1,3 +1,5 @@
+use crate::platform::SupportedArchitectures;
+use crate::resolve::{PackageExtensions, PeerDependencyRules};
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::BTreeMap;
 
@@ -108,6 +110,12 @@ pub struct ManifestPnpm {
 pub struct ManifestSnpm {
     #[serde(default)]
     pub overrides: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub package_extensions: PackageExtensions,
+    #[serde(default, skip_serializing_if = "PeerDependencyRules::is_empty")]
+    pub peer_dependency_rules: PeerDependencyRules,
+    #[serde(default, skip_serializing_if = "SupportedArchitectures::is_empty")]
+    pub supported_architectures: SupportedArchitectures,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub patched_dependencies: Option<BTreeMap<String, String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1,6 +1,6 @@
 use crate::config::OfflineMode;
 use crate::console;
-use crate::registry::RegistryPackage;
+use crate::registry::{RegistryPackage, RegistryProtocol};
 use crate::{Result, SnpmConfig, SnpmError};
 use reqwest::Client;
 use reqwest::header::{ACCEPT, HeaderValue};
@@ -12,7 +12,7 @@ pub async fn fetch_package(
     client: &Client,
     name: &str,
 ) -> Result<RegistryPackage> {
-    fetch_package_with_offline(config, client, name, OfflineMode::Online).await
+    fetch_package_with_offline(config, client, name, config.offline_mode).await
 }
 
 pub async fn fetch_package_with_offline(
@@ -40,7 +40,7 @@ pub async fn fetch_package_with_offline(
 
     let mut request = client.get(&url);
 
-    let accept = if config.min_package_age_days.is_some() {
+    let accept = if config.needs_publish_times() {
         HeaderValue::from_static("application/json; q=1.0, */*")
     } else {
         HeaderValue::from_static(
@@ -103,6 +103,23 @@ pub async fn fetch_package_with_offline(
     Ok(package)
 }
 
+/// Whether `tarball` was served by the jsr registry.
+pub(crate) fn is_jsr_tarball(tarball: &str) -> bool {
+    tarball
+        .strip_prefix(&jsr_registry_base())
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// The protocol whose registry served `tarball`: jsr for jsr tarballs,
+/// npm for every other registry.
+pub(crate) fn tarball_protocol(tarball: &str) -> RegistryProtocol {
+    if is_jsr_tarball(tarball) {
+        RegistryProtocol::jsr()
+    } else {
+        RegistryProtocol::npm()
+    }
+}
+
 fn jsr_registry_base() -> String {
     if let Ok(value) = env::var("SNPM_REGISTRY_JSR") {
         let trimmed = value.trim();
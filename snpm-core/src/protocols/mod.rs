@@ -19,11 +19,16 @@ pub fn registry_version_from_manifest(manifest: Manifest, dist_url: &str) -> Reg
         dist: RegistryDist {
             tarball: dist_url.to_string(),
             integrity: None,
+            signatures: Vec::new(),
+            attestations: None,
         },
         os: vec![],
         cpu: vec![],
         libc: vec![],
+        engines: BTreeMap::new(),
+        deprecated: None,
         bin: None,
+        has_install_script: false,
     }
 }
 
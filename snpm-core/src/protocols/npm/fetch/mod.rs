@@ -59,3 +59,43 @@ pub(super) async fn fetch_registry_package(
 
     handle_registry_response(config, name, &url, response).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::fetch_registry_package;
+    use crate::SnpmConfig;
+    use crate::config::OfflineMode;
+    use crate::operations::install::test_support::serve_registry;
+
+    use reqwest::Client;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn offline_fetch_serves_cached_metadata_and_names_missing_packages() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            cache_dir: dir.path().join("cache"),
+            data_dir: dir.path().join("data"),
+            default_registry: serve_registry(&[("foo", ""), ("bar", "")]).await,
+            ..SnpmConfig::for_tests()
+        };
+        let client = Client::new();
+
+        fetch_registry_package(&config, &client, "foo", "npm", OfflineMode::Online)
+            .await
+            .unwrap();
+
+        let cached = fetch_registry_package(&config, &client, "foo", "npm", OfflineMode::Offline)
+            .await
+            .unwrap();
+        assert!(cached.versions.contains_key("1.0.0"));
+
+        let error = fetch_registry_package(&config, &client, "bar", "npm", OfflineMode::Offline)
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("package metadata for bar"),
+            "{error}"
+        );
+    }
+}
@@ -18,7 +18,7 @@ pub(super) fn build_request(
     url: &str,
 ) -> reqwest::RequestBuilder {
     let mut request = client.get(url);
-    let accept = if config.min_package_age_days.is_some() {
+    let accept = if config.needs_publish_times() {
         HeaderValue::from_static("application/json; q=1.0, */*")
     } else {
         HeaderValue::from_static(
@@ -50,7 +50,7 @@ pub(super) fn build_request(
 #[cfg(test)]
 mod tests {
     use super::build_request;
-    use crate::config::SnpmConfig;
+    use crate::config::{ResolutionMode, SnpmConfig};
     use reqwest::header::ACCEPT;
 
     fn make_config(min_package_age_days: Option<u32>) -> SnpmConfig {
@@ -80,4 +80,24 @@ mod tests {
             Some("application/json; q=1.0, */*")
         );
     }
+
+    #[test]
+    fn time_based_resolution_requests_full_packument_metadata() {
+        let client = reqwest::Client::new();
+        let config = SnpmConfig {
+            resolution_mode: ResolutionMode::TimeBased,
+            ..SnpmConfig::for_tests()
+        };
+        let request = build_request(&config, &client, "pkg", "https://registry.npmjs.org/pkg")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request
+                .headers()
+                .get(ACCEPT)
+                .and_then(|value| value.to_str().ok()),
+            Some("application/json; q=1.0, */*")
+        );
+    }
 }
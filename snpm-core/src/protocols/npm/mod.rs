@@ -15,7 +15,7 @@ pub async fn fetch_package(
     name: &str,
     protocol_name: &str,
 ) -> Result<RegistryPackage> {
-    fetch_package_with_offline(config, client, name, protocol_name, OfflineMode::Online).await
+    fetch_package_with_offline(config, client, name, protocol_name, config.offline_mode).await
 }
 
 /// Fetch package metadata respecting offline mode.
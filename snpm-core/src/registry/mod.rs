@@ -1,5 +1,9 @@
+mod signatures;
 pub mod types;
 
+#[cfg(test)]
+pub(crate) use signatures::tests::TestSigner;
+pub use signatures::{RegistryKey, SignatureCheck, fetch_registry_keys, verify_signatures};
 pub use types::*;
 
 use crate::config::OfflineMode;
@@ -12,7 +16,7 @@ pub async fn fetch_package(
     name: &str,
     protocol: &RegistryProtocol,
 ) -> Result<RegistryPackage> {
-    fetch_package_with_offline(config, client, name, protocol, OfflineMode::Online).await
+    fetch_package_with_offline(config, client, name, protocol, config.offline_mode).await
 }
 
 pub async fn fetch_package_with_offline(
@@ -0,0 +1,372 @@
+//! Registry signatures.
+//!
+//! npm-compatible registries sign `name@version:integrity` for every
+//! version they serve with an ECDSA P-256 key and put the signature in
+//! `dist.signatures`. The public half of each key is published at
+//! `/-/npm/v1/keys` as base64 DER (SubjectPublicKeyInfo). Key sets are
+//! cached per registry under `registry_keys_dir`.
+
+use super::RegistrySignature;
+use crate::config::OfflineMode;
+use crate::{Result, SnpmConfig, SnpmError};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use reqwest::{Client, StatusCode};
+use ring::signature::{ECDSA_P256_SHA256_ASN1, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+const KEYS_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// DER prefix of a SubjectPublicKeyInfo holding an uncompressed P-256
+/// point; the 65-byte point follows it.
+const P256_SPKI_PREFIX: [u8; 26] = [
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RegistryKey {
+    pub keyid: String,
+    #[serde(default)]
+    pub keytype: String,
+    #[serde(default)]
+    pub scheme: String,
+    /// Base64 DER-encoded SubjectPublicKeyInfo.
+    pub key: String,
+    /// When the key stopped signing new versions, if it has.
+    #[serde(default)]
+    pub expires: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct KeySet {
+    #[serde(default)]
+    keys: Vec<RegistryKey>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignatureCheck {
+    Verified,
+    /// The registry served no signature for this version.
+    Missing,
+    Invalid {
+        reason: String,
+    },
+}
+
+/// Checks `signatures` over `name@version:integrity` against `keys`.
+/// `published` is the version's publish time when the packument carries
+/// one; a signature from a key that expired before then is invalid.
+pub fn verify_signatures(
+    name: &str,
+    version: &str,
+    integrity: &str,
+    signatures: &[RegistrySignature],
+    keys: &[RegistryKey],
+    published: Option<OffsetDateTime>,
+) -> SignatureCheck {
+    if signatures.is_empty() {
+        return SignatureCheck::Missing;
+    }
+
+    let payload = format!("{name}@{version}:{integrity}");
+    let mut reason = None;
+    for signature in signatures {
+        let Some(key) = keys.iter().find(|key| key.keyid == signature.keyid) else {
+            reason.get_or_insert_with(|| format!("signed with unknown key {}", signature.keyid));
+            continue;
+        };
+
+        if let (Some(expires), Some(published)) = (key_expiry(key), published)
+            && expires < published
+        {
+            reason = Some(format!(
+                "signed with key {} which expired before the version was published",
+                key.keyid
+            ));
+            continue;
+        }
+
+        match verify_with_key(key, payload.as_bytes(), &signature.sig) {
+            Ok(()) => return SignatureCheck::Verified,
+            Err(error) => reason = Some(error),
+        }
+    }
+
+    SignatureCheck::Invalid {
+        reason: reason.unwrap_or_else(|| "no usable signature".to_string()),
+    }
+}
+
+fn verify_with_key(
+    key: &RegistryKey,
+    payload: &[u8],
+    signature: &str,
+) -> std::result::Result<(), String> {
+    let der = STANDARD
+        .decode(key.key.trim())
+        .map_err(|_| format!("key {} is not valid base64", key.keyid))?;
+    let point = der
+        .strip_prefix(&P256_SPKI_PREFIX[..])
+        .filter(|point| point.len() == 65)
+        .ok_or_else(|| format!("key {} is not an ECDSA P-256 key", key.keyid))?;
+    let signature = STANDARD
+        .decode(signature.trim())
+        .map_err(|_| format!("signature from key {} is not valid base64", key.keyid))?;
+
+    UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, point)
+        .verify(payload, &signature)
+        .map_err(|_| format!("signature from key {} does not match", key.keyid))
+}
+
+fn key_expiry(key: &RegistryKey) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(key.expires.as_deref()?, &Rfc3339).ok()
+}
+
+/// The signing keys `registry` publishes, from the cache while it is
+/// less than a day old (or at any age offline). An empty list means the
+/// registry does not sign packages.
+pub async fn fetch_registry_keys(
+    config: &SnpmConfig,
+    client: &Client,
+    registry: &str,
+) -> Result<Vec<RegistryKey>> {
+    let registry = registry.trim_end_matches('/');
+    let cache_path = keys_cache_path(config, registry);
+    let cached = read_cached_keys(&cache_path);
+
+    match (cached, config.offline_mode) {
+        (Some((keys, _)), OfflineMode::Offline | OfflineMode::PreferOffline) => return Ok(keys),
+        (Some((keys, fresh)), OfflineMode::Online) if fresh => return Ok(keys),
+        (None, OfflineMode::Offline) => {
+            return Err(SnpmError::OfflineRequired {
+                resource: format!("signing keys for {registry}"),
+            });
+        }
+        _ => {}
+    }
+
+    let url = format!("{registry}/-/npm/v1/keys");
+    let mut request = client.get(&url);
+    if let Some(header) = config.authorization_header_for_url(&url) {
+        request = request.header("Authorization", header);
+    }
+    let response = request.send().await.map_err(|source| SnpmError::Http {
+        url: url.clone(),
+        source,
+    })?;
+
+    let key_set = match response.status() {
+        StatusCode::NOT_FOUND => KeySet::default(),
+        status if status.is_success() => {
+            let body = response.text().await.map_err(|source| SnpmError::Http {
+                url: url.clone(),
+                source,
+            })?;
+            serde_json::from_str(&body).map_err(|error| SnpmError::RegistryKeys {
+                registry: registry.to_string(),
+                reason: format!("invalid key set: {error}"),
+            })?
+        }
+        status => {
+            return Err(SnpmError::RegistryKeys {
+                registry: registry.to_string(),
+                reason: format!("{url} returned {status}"),
+            });
+        }
+    };
+
+    write_cached_keys(&cache_path, &key_set)?;
+    Ok(key_set.keys)
+}
+
+fn keys_cache_path(config: &SnpmConfig, registry: &str) -> PathBuf {
+    let digest = hex::encode(Sha256::digest(registry.as_bytes()));
+    config
+        .registry_keys_dir()
+        .join(format!("{}.json", &digest[..32]))
+}
+
+fn read_cached_keys(path: &Path) -> Option<(Vec<RegistryKey>, bool)> {
+    let data = fs::read(path).ok()?;
+    let key_set: KeySet = serde_json::from_slice(&data).ok()?;
+    let fresh = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age <= KEYS_TTL);
+    Some((key_set.keys, fresh))
+}
+
+fn write_cached_keys(path: &Path, key_set: &KeySet) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|source| SnpmError::WriteFile {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+
+    let data = serde_json::to_vec(key_set).map_err(|error| SnpmError::SerializeJson {
+        path: path.to_path_buf(),
+        reason: error.to_string(),
+    })?;
+    fs::write(path, data).map_err(|source| SnpmError::WriteFile {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{ECDSA_P256_SHA256_ASN1_SIGNING, EcdsaKeyPair, KeyPair};
+    use tempfile::tempdir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// A registry signing key for tests, as the registry would hold it.
+    pub(crate) struct TestSigner {
+        pair: EcdsaKeyPair,
+        pub(crate) keyid: String,
+    }
+
+    impl TestSigner {
+        pub(crate) fn generate(keyid: &str) -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 =
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+            let pair =
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)
+                    .unwrap();
+            TestSigner {
+                pair,
+                keyid: keyid.to_string(),
+            }
+        }
+
+        pub(crate) fn public_key(&self) -> RegistryKey {
+            let mut der = P256_SPKI_PREFIX.to_vec();
+            der.extend_from_slice(self.pair.public_key().as_ref());
+            RegistryKey {
+                keyid: self.keyid.clone(),
+                keytype: "ecdsa-sha2-nistp256".to_string(),
+                scheme: "ecdsa-sha2-nistp256".to_string(),
+                key: STANDARD.encode(der),
+                expires: None,
+            }
+        }
+
+        pub(crate) fn sign(&self, name: &str, version: &str, integrity: &str) -> RegistrySignature {
+            let payload = format!("{name}@{version}:{integrity}");
+            let sig = self
+                .pair
+                .sign(&SystemRandom::new(), payload.as_bytes())
+                .unwrap();
+            RegistrySignature {
+                keyid: self.keyid.clone(),
+                sig: STANDARD.encode(sig.as_ref()),
+            }
+        }
+    }
+
+    #[test]
+    fn verifies_registry_signature() {
+        let signer = TestSigner::generate("SHA256:test");
+        let keys = vec![signer.public_key()];
+        let signatures = vec![signer.sign("left-pad", "1.3.0", "sha512-abc")];
+
+        assert_eq!(
+            verify_signatures("left-pad", "1.3.0", "sha512-abc", &signatures, &keys, None),
+            SignatureCheck::Verified
+        );
+        assert!(matches!(
+            verify_signatures("left-pad", "1.3.0", "sha512-xyz", &signatures, &keys, None),
+            SignatureCheck::Invalid { .. }
+        ));
+        assert_eq!(
+            verify_signatures("left-pad", "1.3.0", "sha512-abc", &[], &keys, None),
+            SignatureCheck::Missing
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_expired_keys() {
+        let signer = TestSigner::generate("SHA256:test");
+        let signatures = vec![signer.sign("pkg", "1.0.0", "sha512-abc")];
+
+        let other = TestSigner::generate("SHA256:other").public_key();
+        match verify_signatures("pkg", "1.0.0", "sha512-abc", &signatures, &[other], None) {
+            SignatureCheck::Invalid { reason } => assert!(reason.contains("unknown key")),
+            other => panic!("expected invalid, got {other:?}"),
+        }
+
+        let mut expired = signer.public_key();
+        expired.expires = Some("2024-01-01T00:00:00Z".to_string());
+        let published = OffsetDateTime::parse("2024-06-01T00:00:00Z", &Rfc3339).ok();
+        let keys = [expired];
+        match verify_signatures("pkg", "1.0.0", "sha512-abc", &signatures, &keys, published) {
+            SignatureCheck::Invalid { reason } => assert!(reason.contains("expired")),
+            other => panic!("expected invalid, got {other:?}"),
+        }
+
+        let published = OffsetDateTime::parse("2023-06-01T00:00:00Z", &Rfc3339).ok();
+        assert_eq!(
+            verify_signatures("pkg", "1.0.0", "sha512-abc", &signatures, &keys, published),
+            SignatureCheck::Verified
+        );
+    }
+
+    #[tokio::test]
+    async fn caches_registry_keys() {
+        let signer = TestSigner::generate("SHA256:test");
+        let body = serde_json::to_string(&KeySet {
+            keys: vec![signer.public_key()],
+        })
+        .unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                return;
+            };
+            let mut request = vec![0; 2048];
+            let read = socket.read(&mut request).await.unwrap();
+            let request = String::from_utf8_lossy(&request[..read]);
+            assert!(request.starts_with("GET /-/npm/v1/keys "));
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().to_path_buf(),
+            ..SnpmConfig::for_tests()
+        };
+        let client = Client::new();
+        let registry = format!("http://{addr}/");
+
+        let keys = fetch_registry_keys(&config, &client, &registry)
+            .await
+            .unwrap();
+        assert_eq!(keys, vec![signer.public_key()]);
+
+        // The stand-in registry only answers once; the second read is
+        // served from the cache.
+        let keys = fetch_registry_keys(&config, &client, &registry)
+            .await
+            .unwrap();
+        assert_eq!(keys, vec![signer.public_key()]);
+    }
+}
@@ -3,5 +3,8 @@ mod models;
 mod protocol;
 
 pub use bundled::BundledDependencies;
-pub use models::{PeerDependencyMeta, RegistryDist, RegistryPackage, RegistryVersion};
+pub use models::{
+    PeerDependencyMeta, RegistryAttestations, RegistryDist, RegistryPackage, RegistryProvenance,
+    RegistrySignature, RegistryVersion,
+};
 pub use protocol::RegistryProtocol;
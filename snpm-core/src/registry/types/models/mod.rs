@@ -2,7 +2,10 @@ mod package;
 mod version;
 
 pub use package::RegistryPackage;
-pub use version::{PeerDependencyMeta, RegistryDist, RegistryVersion};
+pub use version::{
+    PeerDependencyMeta, RegistryAttestations, RegistryDist, RegistryProvenance, RegistrySignature,
+    RegistryVersion,
+};
 
 #[cfg(test)]
 mod tests;
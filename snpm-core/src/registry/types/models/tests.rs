@@ -16,11 +16,16 @@ fn registry_version_has_bin_string() {
         dist: RegistryDist {
             tarball: "url".to_string(),
             integrity: None,
+            signatures: Vec::new(),
+            attestations: None,
         },
         os: vec![],
         cpu: vec![],
         libc: vec![],
+        engines: BTreeMap::new(),
+        deprecated: None,
         bin: Some(serde_json::json!("./cli.js")),
+        has_install_script: false,
     };
     assert!(version.has_bin());
 }
@@ -38,11 +43,16 @@ fn registry_version_has_bin_null() {
         dist: RegistryDist {
             tarball: "url".to_string(),
             integrity: None,
+            signatures: Vec::new(),
+            attestations: None,
         },
         os: vec![],
         cpu: vec![],
         libc: vec![],
+        engines: BTreeMap::new(),
+        deprecated: None,
         bin: Some(serde_json::json!(null)),
+        has_install_script: false,
     };
     assert!(!version.has_bin());
 }
@@ -60,11 +70,16 @@ fn registry_version_has_bin_none() {
         dist: RegistryDist {
             tarball: "url".to_string(),
             integrity: None,
+            signatures: Vec::new(),
+            attestations: None,
         },
         os: vec![],
         cpu: vec![],
         libc: vec![],
+        engines: BTreeMap::new(),
+        deprecated: None,
         bin: None,
+        has_install_script: false,
     };
     assert!(!version.has_bin());
 }
@@ -82,11 +97,16 @@ fn registry_version_get_bundled_prefers_bundled_dependencies() {
         dist: RegistryDist {
             tarball: "url".to_string(),
             integrity: None,
+            signatures: Vec::new(),
+            attestations: None,
         },
         os: vec![],
         cpu: vec![],
         libc: vec![],
+        engines: BTreeMap::new(),
+        deprecated: None,
         bin: None,
+        has_install_script: false,
     };
     let bundled = version.get_bundled_dependencies().unwrap();
     match bundled {
@@ -108,11 +128,16 @@ fn registry_version_get_bundled_falls_back_to_bundle() {
         dist: RegistryDist {
             tarball: "url".to_string(),
             integrity: None,
+            signatures: Vec::new(),
+            attestations: None,
         },
         os: vec![],
         cpu: vec![],
         libc: vec![],
+        engines: BTreeMap::new(),
+        deprecated: None,
         bin: None,
+        has_install_script: false,
     };
     let bundled = version.get_bundled_dependencies().unwrap();
     match bundled {
@@ -139,3 +164,59 @@ fn registry_package_deserializes_from_json() {
         Some("1.0.0")
     );
 }
+
+#[test]
+fn registry_version_keeps_string_engines_and_ignores_other_shapes() {
+    let json = r#"{
+        "versions": {
+            "1.0.0": {
+                "version": "1.0.0",
+                "engines": { "node": ">=18", "vscode": null },
+                "dist": { "tarball": "https://example.com/pkg-1.0.0.tgz" }
+            },
+            "0.1.0": {
+                "version": "0.1.0",
+                "engines": ["node >= 0.4"],
+                "dist": { "tarball": "https://example.com/pkg-0.1.0.tgz" }
+            }
+        }
+    }"#;
+    let package: RegistryPackage = serde_json::from_str(json).unwrap();
+
+    assert_eq!(
+        package.versions["1.0.0"].engines,
+        BTreeMap::from([("node".to_string(), ">=18".to_string())])
+    );
+    assert!(package.versions["0.1.0"].engines.is_empty());
+}
+
+#[test]
+fn registry_version_keeps_deprecation_messages_only() {
+    let json = r#"{
+        "versions": {
+            "2.88.2": {
+                "version": "2.88.2",
+                "deprecated": "request has been deprecated, see https://github.com/request/request/issues/3142",
+                "dist": { "tarball": "https://example.com/request-2.88.2.tgz" }
+            },
+            "2.88.1": {
+                "version": "2.88.1",
+                "deprecated": "",
+                "dist": { "tarball": "https://example.com/request-2.88.1.tgz" }
+            },
+            "2.88.0": {
+                "version": "2.88.0",
+                "deprecated": false,
+                "dist": { "tarball": "https://example.com/request-2.88.0.tgz" }
+            }
+        }
+    }"#;
+    let package: RegistryPackage = serde_json::from_str(json).unwrap();
+
+    assert_eq!(
+        package.versions["2.88.2"].deprecated.as_deref(),
+        Some("request has been deprecated, see https://github.com/request/request/issues/3142")
+    );
+    assert!(package.versions["2.88.1"].deprecated.is_none());
+    assert!(package.versions["2.88.0"].deprecated.is_none());
+}
@@ -1,11 +1,11 @@
 use crate::project::BinField;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use std::collections::BTreeMap;
 
 use super::super::bundled::BundledDependencies;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct PeerDependencyMeta {
     #[serde(default)]
     pub optional: bool,
@@ -33,8 +33,17 @@ pub struct RegistryVersion {
     pub cpu: Vec<String>,
     #[serde(default)]
     pub libc: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_engines")]
+    pub engines: BTreeMap<String, String>,
+    /// The author's deprecation message, when this version is deprecated.
+    #[serde(default, deserialize_with = "deserialize_deprecated")]
+    pub deprecated: Option<String>,
     #[serde(default)]
     pub bin: Option<serde_json::Value>,
+    /// Set by the registry when the version has a `preinstall`, `install`
+    /// or `postinstall` script.
+    #[serde(default, rename = "hasInstallScript")]
+    pub has_install_script: bool,
 }
 
 impl RegistryVersion {
@@ -63,4 +72,85 @@ pub struct RegistryDist {
     pub tarball: String,
     #[serde(default)]
     pub integrity: Option<String>,
+    /// The registry's ECDSA signatures over `name@version:integrity`.
+    #[serde(default)]
+    pub signatures: Vec<RegistrySignature>,
+    /// Present when the version was published with attestations.
+    #[serde(default)]
+    pub attestations: Option<RegistryAttestations>,
+}
+
+impl RegistryDist {
+    /// Whether the version was published with a provenance attestation.
+    pub fn has_provenance(&self) -> bool {
+        self.attestations
+            .as_ref()
+            .is_some_and(|attestations| attestations.provenance.is_some())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RegistrySignature {
+    /// Matches the `keyid` of a key in the registry's `/-/npm/v1/keys`.
+    pub keyid: String,
+    /// Base64 DER-encoded signature.
+    pub sig: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RegistryAttestations {
+    pub url: String,
+    #[serde(default)]
+    pub provenance: Option<RegistryProvenance>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RegistryProvenance {
+    #[serde(rename = "predicateType")]
+    pub predicate_type: String,
+}
+
+/// Old packuments sometimes carry `engines` as an array of strings or with
+/// non-string values; those are dropped rather than failing the whole
+/// packument. The binary metadata cache is not self-describing, so it
+/// decodes the map directly.
+fn deserialize_engines<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if !deserializer.is_human_readable() {
+        return BTreeMap::deserialize(deserializer);
+    }
+
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    let Some(serde_json::Value::Object(entries)) = value else {
+        return Ok(BTreeMap::new());
+    };
+
+    Ok(entries
+        .into_iter()
+        .filter_map(|(engine, range)| match range {
+            serde_json::Value::String(range) => Some((engine, range)),
+            _ => None,
+        })
+        .collect())
+}
+
+/// npm un-deprecates a version by setting `deprecated` to an empty string,
+/// and some old packuments use `false`; both mean "not deprecated".
+fn deserialize_deprecated<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if !deserializer.is_human_readable() {
+        return Option::deserialize(deserializer);
+    }
+
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::String(message)) if !message.trim().is_empty() => Some(message),
+            Some(serde_json::Value::Bool(true)) => Some("deprecated".to_string()),
+            _ => None,
+        },
+    )
 }
@@ -7,9 +7,11 @@ use super::super::types::ResolvedPackage;
 use crate::SnpmConfig;
 use crate::config::OfflineMode;
 use crate::registry::RegistryProtocol;
+use crate::resolve::extensions::PackageExtensions;
 use crate::resolve::types::ResolutionGraph;
 use reqwest::Client;
 use std::collections::BTreeMap;
+use time::OffsetDateTime;
 use tokio::sync::mpsc;
 
 pub(crate) use registry::prefetch_registry_request;
@@ -29,9 +31,16 @@ pub(in crate::resolve) struct ResolverContext<'a> {
     pub(in crate::resolve) min_age_days: Option<u32>,
     pub(in crate::resolve) force: bool,
     pub(in crate::resolve) overrides: Option<&'a BTreeMap<String, String>>,
+    pub(in crate::resolve) package_extensions: Option<&'a PackageExtensions>,
     pub(in crate::resolve) workspace_sources: Option<&'a BTreeMap<String, String>>,
     pub(in crate::resolve) existing_graph: Option<&'a ResolutionGraph>,
+    /// Re-select every edge from the versions already in `existing_graph`
+    /// instead of reusing its edges verbatim (`snpm dedupe`).
+    pub(in crate::resolve) prefer_locked: bool,
     pub(in crate::resolve) offline_mode: OfflineMode,
+    /// Newest publish time a transitive dependency may have under
+    /// `time-based` resolution. Set once the direct dependencies are known.
+    pub(in crate::resolve) time_cutoff: Option<OffsetDateTime>,
     pub(super) state: ResolverState,
     pub(super) prefetch_tx: mpsc::UnboundedSender<ResolvedPackage>,
     pub(super) metadata_prefetch_tx: mpsc::UnboundedSender<RegistryPrefetchRequest>,
@@ -60,12 +69,28 @@ impl<'a> ResolverContext<'a> {
             min_age_days,
             force,
             overrides,
+            package_extensions: None,
             workspace_sources,
             existing_graph,
+            prefer_locked: false,
             offline_mode,
+            time_cutoff: None,
             state,
             prefetch_tx,
             metadata_prefetch_tx,
         }
     }
+
+    /// Serves `package` for `cache_key` without touching the registry.
+    pub(crate) async fn cache_registry_package(
+        &self,
+        cache_key: &str,
+        package: crate::registry::RegistryPackage,
+    ) {
+        self.state
+            .package_cache
+            .write()
+            .await
+            .insert(cache_key.to_string(), std::sync::Arc::new(package));
+    }
 }
@@ -1,6 +1,7 @@
 use super::{RegistryPrefetchRequest, ResolverContext, ResolverState};
 use crate::config::OfflineMode;
 use crate::registry::{RegistryPackage, RegistryProtocol};
+use crate::version::published_before;
 use crate::{Result, SnpmConfig, console};
 
 use reqwest::Client;
@@ -118,6 +119,12 @@ async fn fetch_registry_package_cached(
                     offline_mode,
                 )
                 .await?;
+                let fetched = match config.before {
+                    Some(cutoff) if prefetchable_protocol(protocol) => {
+                        published_before(fetched, cutoff)
+                    }
+                    _ => fetched,
+                };
 
                 let fetched = Arc::new(fetched);
                 state
@@ -159,6 +166,9 @@ async fn cleanup_package_fetch_lock(
     }
 }
 
+/// Protocols served by a registry, whose packuments can be prefetched and
+/// carry the publish times `--before` goes by. Local and git sources
+/// have neither.
 fn prefetchable_protocol(protocol: &RegistryProtocol) -> bool {
     !matches!(protocol.name.as_str(), "file" | "git")
 }
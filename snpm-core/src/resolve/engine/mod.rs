@@ -1,9 +1,9 @@
 mod context;
 
-use super::peers::validate_peers;
+use super::peers::{PeerDependencyRules, validate_peers};
 use super::types::{ResolutionGraph, ResolvedPackage};
-use crate::config::OfflineMode;
-use crate::{Result, SnpmConfig, console};
+use crate::config::{OfflineMode, ResolutionMode};
+use crate::{Result, SnpmConfig, SnpmError, console};
 use futures::future::join3;
 use reqwest::Client;
 use std::collections::{BTreeMap, BTreeSet};
@@ -28,8 +28,11 @@ pub async fn resolve_with_offline<F, Fut>(
     min_age_days: Option<u32>,
     force: bool,
     overrides: Option<&BTreeMap<String, String>>,
+    package_extensions: Option<&super::extensions::PackageExtensions>,
+    peer_rules: Option<&PeerDependencyRules>,
     workspace_sources: Option<&BTreeMap<String, String>>,
     existing_graph: Option<&super::types::ResolutionGraph>,
+    prefer_locked: bool,
     offline_mode: OfflineMode,
     on_package: F,
 ) -> Result<ResolutionGraph>
@@ -42,21 +45,30 @@ where
     let (prefetch_tx, prefetch_rx) = mpsc::unbounded_channel();
     let (metadata_prefetch_tx, metadata_prefetch_rx) = mpsc::unbounded_channel();
 
-    let resolver_context = ResolverContext {
+    let mut resolver_context = ResolverContext {
         config,
         client,
         min_age_days,
         force,
         overrides,
+        package_extensions,
         workspace_sources,
         existing_graph,
+        prefer_locked,
         offline_mode,
+        time_cutoff: None,
         state: state.clone(),
         prefetch_tx: prefetch_tx.clone(),
         metadata_prefetch_tx: metadata_prefetch_tx.clone(),
     };
 
     let resolver_task = async move {
+        if config.resolution_mode == ResolutionMode::TimeBased {
+            resolver_context.time_cutoff = resolver_context
+                .direct_dependency_cutoff(root_deps, root_protocols)
+                .await;
+        }
+
         let result = resolver_context
             .resolve_root_dependencies(root_deps, root_protocols, optional_root_names)
             .await;
@@ -81,13 +93,17 @@ where
         packages: state.take_packages().await,
     };
 
-    if let Err(error) = validate_peers(&graph) {
+    let default_rules = PeerDependencyRules::default();
+    let peer_report = validate_peers(&graph, peer_rules.unwrap_or(&default_rules));
+    if !peer_report.is_empty() {
         if config.strict_peers {
-            return Err(error);
+            return Err(SnpmError::PeerDependencies {
+                report: peer_report,
+            });
         }
 
         console::warn(&format!(
-            "peer dependency issues detected (non‑fatal): {error}"
+            "peer dependency issues detected (non‑fatal):\n{peer_report}"
         ));
     }
 
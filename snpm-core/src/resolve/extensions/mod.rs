@@ -0,0 +1,116 @@
+//! `packageExtensions`: fields merged into registry manifests before their
+//! dependencies are resolved, for packages that forgot to declare a
+//! dependency or peer.
+//!
+//! Keys are selectors: a bare name (`react-redux`) matches every version,
+//! `name@range` (`@scope/pkg@^1`) only the versions in range.
+
+use crate::registry::{PeerDependencyMeta, RegistryVersion};
+use crate::version::parse_range_set;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use snpm_semver::parse_version;
+use std::collections::BTreeMap;
+
+pub type PackageExtensions = BTreeMap<String, PackageExtension>;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageExtension {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub optional_dependencies: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub peer_dependencies: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub peer_dependencies_meta: BTreeMap<String, PeerDependencyMeta>,
+}
+
+/// Merges every extension whose selector matches `name` at
+/// `version_meta.version` into `version_meta`. Entries from an extension
+/// replace what the registry declared for the same dependency.
+pub fn apply_package_extensions(
+    extensions: &PackageExtensions,
+    name: &str,
+    version_meta: &mut RegistryVersion,
+) {
+    for (selector, extension) in extensions {
+        if !selector_matches(selector, name, &version_meta.version) {
+            continue;
+        }
+
+        version_meta
+            .dependencies
+            .extend(extension.dependencies.clone());
+        version_meta
+            .optional_dependencies
+            .extend(extension.optional_dependencies.clone());
+        version_meta
+            .peer_dependencies
+            .extend(extension.peer_dependencies.clone());
+        version_meta
+            .peer_dependencies_meta
+            .extend(extension.peer_dependencies_meta.clone());
+    }
+}
+
+/// Stable digest of the extension set, recorded in the lockfile so an
+/// edited extension forces re-resolution. `None` when there are none.
+pub fn package_extensions_checksum(extensions: &PackageExtensions) -> Option<String> {
+    if extensions.is_empty() {
+        return None;
+    }
+
+    let encoded = serde_json::to_vec(extensions).ok()?;
+    Some(format!("sha256-{:x}", Sha256::digest(encoded)))
+}
+
+/// Rejects selectors without a package name or whose range does not
+/// parse. The error is the reason, for callers to wrap with the file that
+/// declared the extensions.
+pub fn validate_package_extensions(
+    extensions: &PackageExtensions,
+) -> std::result::Result<(), String> {
+    for selector in extensions.keys() {
+        let (name, range) = split_selector(selector);
+        if name.is_empty() {
+            return Err("selector has no package name".to_string());
+        }
+
+        if let Some(range) = range {
+            parse_range_set(name, range).map_err(|error| error.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn selector_matches(selector: &str, name: &str, version: &str) -> bool {
+    let (selector_name, range) = split_selector(selector);
+    if selector_name != name {
+        return false;
+    }
+
+    let Some(range) = range else {
+        return true;
+    };
+
+    match (parse_range_set(name, range), parse_version(version)) {
+        (Ok(range), Ok(version)) => range.matches(&version),
+        _ => false,
+    }
+}
+
+/// `@scope/pkg@^1` → (`@scope/pkg`, `^1`); a leading `@` is the scope,
+/// not a range separator.
+fn split_selector(selector: &str) -> (&str, Option<&str>) {
+    match selector.rfind('@') {
+        Some(index) if index > 0 => (&selector[..index], Some(&selector[index + 1..])),
+        _ => (selector, None),
+    }
+}
+
+#[cfg(test)]
+mod tests;
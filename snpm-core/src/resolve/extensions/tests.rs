@@ -0,0 +1,110 @@
+use super::{
+    PackageExtension, PackageExtensions, apply_package_extensions, package_extensions_checksum,
+    validate_package_extensions,
+};
+use crate::registry::{PeerDependencyMeta, RegistryVersion};
+
+use std::collections::BTreeMap;
+
+fn version(version: &str) -> RegistryVersion {
+    serde_json::from_value(serde_json::json!({
+        "version": version,
+        "dependencies": { "loose-envify": "^1.1.0" },
+        "dist": { "tarball": "https://registry.npmjs.org/pkg.tgz" }
+    }))
+    .unwrap()
+}
+
+fn extensions(entries: &[(&str, PackageExtension)]) -> PackageExtensions {
+    entries
+        .iter()
+        .map(|(selector, extension)| (selector.to_string(), extension.clone()))
+        .collect()
+}
+
+fn adds_dependency(name: &str, range: &str) -> PackageExtension {
+    PackageExtension {
+        dependencies: BTreeMap::from([(name.to_string(), range.to_string())]),
+        ..PackageExtension::default()
+    }
+}
+
+#[test]
+fn applies_extensions_whose_range_matches() {
+    let extensions = extensions(&[
+        ("react-redux@^7", adds_dependency("react-dom", "*")),
+        ("react-redux@^8", adds_dependency("redux", "^4")),
+    ]);
+
+    let mut meta = version("7.2.9");
+    apply_package_extensions(&extensions, "react-redux", &mut meta);
+
+    assert_eq!(meta.dependencies["react-dom"], "*");
+    assert_eq!(meta.dependencies["loose-envify"], "^1.1.0");
+    assert!(!meta.dependencies.contains_key("redux"));
+}
+
+#[test]
+fn bare_and_scoped_selectors_match_by_name() {
+    let peer = PackageExtension {
+        peer_dependencies: BTreeMap::from([("react".to_string(), "*".to_string())]),
+        peer_dependencies_meta: BTreeMap::from([(
+            "react".to_string(),
+            PeerDependencyMeta { optional: true },
+        )]),
+        ..PackageExtension::default()
+    };
+    let extensions = extensions(&[
+        ("@scope/ui@>=2", peer.clone()),
+        ("plain", adds_dependency("tslib", "^2")),
+    ]);
+
+    let mut scoped = version("2.1.0");
+    apply_package_extensions(&extensions, "@scope/ui", &mut scoped);
+    assert_eq!(scoped.peer_dependencies["react"], "*");
+    assert!(scoped.peer_dependencies_meta["react"].optional);
+
+    let mut plain = version("0.0.1");
+    apply_package_extensions(&extensions, "plain", &mut plain);
+    assert_eq!(plain.dependencies["tslib"], "^2");
+
+    let mut other = version("2.1.0");
+    apply_package_extensions(&extensions, "@scope/other", &mut other);
+    assert!(other.peer_dependencies.is_empty());
+}
+
+#[test]
+fn checksum_tracks_extension_contents() {
+    let empty = PackageExtensions::new();
+    assert_eq!(package_extensions_checksum(&empty), None);
+
+    let first = extensions(&[("pkg", adds_dependency("a", "^1"))]);
+    let second = extensions(&[("pkg", adds_dependency("a", "^2"))]);
+
+    let checksum = package_extensions_checksum(&first).unwrap();
+    assert!(checksum.starts_with("sha256-"));
+    assert_eq!(
+        package_extensions_checksum(&first.clone()),
+        Some(checksum.clone())
+    );
+    assert_ne!(package_extensions_checksum(&second), Some(checksum));
+}
+
+#[test]
+fn validation_rejects_bad_selectors() {
+    let valid = extensions(&[
+        ("pkg@^1", PackageExtension::default()),
+        ("@scope/pkg", PackageExtension::default()),
+    ]);
+    assert!(validate_package_extensions(&valid).is_ok());
+
+    let bad_range = extensions(&[("pkg@>=x<", PackageExtension::default())]);
+    let error = validate_package_extensions(&bad_range).unwrap_err();
+    assert!(error.contains("pkg@>=x<"), "{error}");
+
+    let no_name = extensions(&[("", PackageExtension::default())]);
+    assert_eq!(
+        validate_package_extensions(&no_name).unwrap_err(),
+        "selector has no package name"
+    );
+}
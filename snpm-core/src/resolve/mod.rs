@@ -1,18 +1,19 @@
 mod engine;
+pub mod extensions;
 mod package;
 pub mod peers;
 pub mod query;
 mod source;
 pub mod types;
 
-use crate::config::OfflineMode;
 use crate::registry::RegistryProtocol;
 use crate::{Result, SnpmConfig};
 use reqwest::Client;
 use std::collections::{BTreeMap, BTreeSet};
 
 pub use engine::resolve_with_offline;
-pub use peers::validate_peers;
+pub use extensions::{PackageExtension, PackageExtensions};
+pub use peers::{PeerDependencyRules, PeerIssue, PeerIssueKind, PeerReport, validate_peers};
 pub use types::*;
 
 /// Resolve dependencies with default online mode.
@@ -25,6 +26,8 @@ pub async fn resolve<F, Fut>(
     min_age_days: Option<u32>,
     force: bool,
     overrides: Option<&BTreeMap<String, String>>,
+    package_extensions: Option<&PackageExtensions>,
+    peer_rules: Option<&PeerDependencyRules>,
     workspace_sources: Option<&BTreeMap<String, String>>,
     on_package: F,
 ) -> Result<ResolutionGraph>
@@ -41,6 +44,8 @@ where
         min_age_days,
         force,
         overrides,
+        package_extensions,
+        peer_rules,
         workspace_sources,
         on_package,
     )
@@ -57,6 +62,8 @@ pub async fn resolve_with_optional_roots<F, Fut>(
     min_age_days: Option<u32>,
     force: bool,
     overrides: Option<&BTreeMap<String, String>>,
+    package_extensions: Option<&PackageExtensions>,
+    peer_rules: Option<&PeerDependencyRules>,
     workspace_sources: Option<&BTreeMap<String, String>>,
     on_package: F,
 ) -> Result<ResolutionGraph>
@@ -73,9 +80,12 @@ where
         min_age_days,
         force,
         overrides,
+        package_extensions,
+        peer_rules,
         workspace_sources,
         None,
-        OfflineMode::Online,
+        false,
+        config.offline_mode,
         on_package,
     )
     .await
@@ -91,6 +101,8 @@ pub async fn resolve_with_optional_roots_with_seed<F, Fut>(
     min_age_days: Option<u32>,
     force: bool,
     overrides: Option<&BTreeMap<String, String>>,
+    package_extensions: Option<&PackageExtensions>,
+    peer_rules: Option<&PeerDependencyRules>,
     workspace_sources: Option<&BTreeMap<String, String>>,
     existing_graph: Option<&ResolutionGraph>,
     on_package: F,
@@ -108,9 +120,56 @@ where
         min_age_days,
         force,
         overrides,
+        package_extensions,
+        peer_rules,
         workspace_sources,
         existing_graph,
-        OfflineMode::Online,
+        false,
+        config.offline_mode,
+        on_package,
+    )
+    .await
+}
+
+/// Re-resolve against `existing_graph`, picking for every edge the highest
+/// already-locked version that satisfies its range before falling back to
+/// the registry. Unlike the seeded path, locked edges are not reused
+/// verbatim, so duplicates that one version could serve collapse.
+#[allow(clippy::too_many_arguments)]
+pub async fn resolve_deduped<F, Fut>(
+    config: &SnpmConfig,
+    client: &Client,
+    root_deps: &BTreeMap<String, String>,
+    root_protocols: &BTreeMap<String, RegistryProtocol>,
+    optional_root_names: &BTreeSet<String>,
+    min_age_days: Option<u32>,
+    force: bool,
+    overrides: Option<&BTreeMap<String, String>>,
+    package_extensions: Option<&PackageExtensions>,
+    peer_rules: Option<&PeerDependencyRules>,
+    workspace_sources: Option<&BTreeMap<String, String>>,
+    existing_graph: &ResolutionGraph,
+    on_package: F,
+) -> Result<ResolutionGraph>
+where
+    F: FnMut(ResolvedPackage) -> Fut + Send,
+    Fut: std::future::Future<Output = Result<()>> + Send,
+{
+    engine::resolve_with_offline(
+        config,
+        client,
+        root_deps,
+        root_protocols,
+        optional_root_names,
+        min_age_days,
+        force,
+        overrides,
+        package_extensions,
+        peer_rules,
+        workspace_sources,
+        Some(existing_graph),
+        true,
+        config.offline_mode,
         on_package,
     )
     .await
@@ -0,0 +1,47 @@
+use super::super::engine::ResolverContext;
+use super::super::types::PackageId;
+use crate::registry::{RegistryPackage, RegistryVersion};
+use crate::version::select_version_with;
+
+impl<'a> ResolverContext<'a> {
+    /// Under `engine-strict`, swaps a `selected` version whose `engines`
+    /// exclude the target runtime for the best version in `range` that
+    /// fits, keeping `selected` when none does so the install reports it.
+    pub(super) fn engine_compatible_version(
+        &self,
+        name: &str,
+        range: &str,
+        package: &RegistryPackage,
+        selected: RegistryVersion,
+        parent_id: Option<&PackageId>,
+    ) -> RegistryVersion {
+        let targets = &self.config.engine_targets;
+        if !self.config.engine_strict || targets.supports(&selected.engines) {
+            return selected;
+        }
+
+        // Dist-tags pointing at a dropped version fall back to the
+        // range's best compatible match.
+        let compatible = RegistryPackage {
+            versions: package
+                .versions
+                .iter()
+                .filter(|(_, meta)| targets.supports(&meta.engines))
+                .map(|(version, meta)| (version.clone(), meta.clone()))
+                .collect(),
+            time: package.time.clone(),
+            dist_tags: package.dist_tags.clone(),
+        };
+
+        select_version_with(
+            name,
+            range,
+            &compatible,
+            self.min_age_days,
+            self.config.no_downgrade_exclusions(),
+            self.force,
+            self.version_preference(parent_id),
+        )
+        .unwrap_or(selected)
+    }
+}
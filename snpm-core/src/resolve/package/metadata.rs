@@ -1,4 +1,5 @@
 use super::super::types::{PackageId, ResolvedPackage};
+use crate::platform::SupportedArchitectures;
 use crate::registry::RegistryVersion;
 use crate::{Result, SnpmError};
 
@@ -8,19 +9,22 @@ pub(super) fn ensure_platform_compatible(
     name: &str,
     range: &str,
     version_meta: &RegistryVersion,
+    supported: &SupportedArchitectures,
 ) -> Result<()> {
-    if crate::platform::is_compatible_with_libc(
-        &version_meta.os,
-        &version_meta.cpu,
-        &version_meta.libc,
-    ) {
+    if supported.supports(&version_meta.os, &version_meta.cpu, &version_meta.libc) {
         return Ok(());
     }
 
+    let reason = if supported.is_empty() {
+        "package is not compatible with current OS/CPU/libc"
+    } else {
+        "package is not compatible with any supportedArchitectures target"
+    };
+
     Err(SnpmError::ResolutionFailed {
         name: name.to_string(),
         range: range.to_string(),
-        reason: "package is not compatible with current OS/CPU/libc".to_string(),
+        reason: reason.to_string(),
     })
 }
 
@@ -34,6 +38,12 @@ pub(super) fn build_placeholder(id: &PackageId, version_meta: &RegistryVersion)
         bundled_dependencies: version_meta.get_bundled_dependencies().cloned(),
         has_bin: version_meta.has_bin(),
         bin: version_meta.bin_definition(),
+        os: version_meta.os.clone(),
+        cpu: version_meta.cpu.clone(),
+        libc: version_meta.libc.clone(),
+        engines: version_meta.engines.clone(),
+        deprecated: version_meta.deprecated.clone(),
+        has_install_script: version_meta.has_install_script,
     }
 }
 
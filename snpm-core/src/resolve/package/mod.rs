@@ -1,14 +1,17 @@
 mod dependencies;
+mod engines;
 mod metadata;
+mod preference;
 #[cfg(test)]
 mod tests;
 
 use super::engine::ResolverContext;
-use super::query::build_dep_request;
+use super::extensions::apply_package_extensions;
+use super::query::{build_dep_request, override_excludes_version};
 use super::types::{PackageId, ResolutionGraph};
 use crate::Result;
-use crate::registry::RegistryProtocol;
-use crate::version::{parse_range_set, select_version};
+use crate::registry::{RegistryPackage, RegistryProtocol, RegistryVersion};
+use crate::version::{parse_range_set, select_version_with};
 use async_recursion::async_recursion;
 use snpm_semver::parse_version;
 use std::collections::BTreeSet;
@@ -24,14 +27,18 @@ impl<'a> ResolverContext<'a> {
         protocol: &RegistryProtocol,
         parent_id: Option<&PackageId>,
     ) -> Result<PackageId> {
-        if let Some(existing_graph) = self.existing_graph
-            && let Some(seed_id) = self.seeded_dependency_id(name, range, parent_id, existing_graph)
+        let seed_id = self
+            .existing_graph
+            .filter(|_| !self.prefer_locked)
+            .and_then(|graph| self.seeded_dependency_id(name, range, parent_id, graph));
+        if let Some(seed_id) = &seed_id
+            && let Some(existing_graph) = self.existing_graph
             && self.seeded_subgraph_complete(seed_id.clone(), existing_graph)
             && self
                 .import_seed_package_chain(seed_id.clone(), existing_graph)
                 .await?
         {
-            return Ok(seed_id);
+            return Ok(seed_id.clone());
         }
 
         let request = build_dep_request(
@@ -46,15 +53,41 @@ impl<'a> ResolverContext<'a> {
             .fetch_registry_package(&cache_key, &request.source, &request.protocol)
             .await?;
 
-        let version_meta = select_version(
+        // A seed whose subtree can't be reused as a whole (an override now
+        // excludes something in it) still keeps its own version; its
+        // dependencies are resolved one by one against the seed below it.
+        let seeded = seed_id
+            .filter(|id| id.name == request.source)
+            .filter(|id| !override_excludes_version(&id.name, &id.version, self.overrides))
+            .and_then(|id| package.versions.get(&id.version).cloned());
+        let selected = match seeded
+            .or_else(|| self.locked_version(&request.source, &request.range, &package))
+        {
+            Some(version_meta) => version_meta,
+            None => select_version_with(
+                &request.source,
+                &request.range,
+                &package,
+                self.min_age_days,
+                self.config.no_downgrade_exclusions(),
+                self.force,
+                self.version_preference(parent_id),
+            )?,
+        };
+        let mut version_meta = self.engine_compatible_version(
             &request.source,
             &request.range,
             &package,
-            self.min_age_days,
-            self.force,
-        )?;
+            selected,
+            parent_id,
+        );
 
-        ensure_platform_compatible(name, range, &version_meta)?;
+        ensure_platform_compatible(
+            name,
+            range,
+            &version_meta,
+            &self.config.supported_architectures,
+        )?;
 
         // Identity is the *resolved* package, not the edge name: an
         // aliased dep ("foo": "npm:bar@^1") resolves to bar's content
@@ -72,6 +105,10 @@ impl<'a> ResolverContext<'a> {
                 name.to_string()
             };
 
+        if let Some(extensions) = self.package_extensions {
+            apply_package_extensions(extensions, &resolved_name, &mut version_meta);
+        }
+
         let id = PackageId {
             name: resolved_name,
             version: version_meta.version.clone(),
@@ -112,6 +149,35 @@ impl<'a> ResolverContext<'a> {
         Ok(id)
     }
 
+    /// Highest version of `name` already present in the existing graph that
+    /// satisfies `range`, when re-resolving for dedupe. Dist-tags and ranges
+    /// that don't parse fall back to normal selection.
+    pub(super) fn locked_version(
+        &self,
+        name: &str,
+        range: &str,
+        package: &RegistryPackage,
+    ) -> Option<RegistryVersion> {
+        if !self.prefer_locked || package.dist_tags.contains_key(range.trim()) {
+            return None;
+        }
+
+        let graph = self.existing_graph?;
+        let ranges = parse_range_set(name, range).ok()?;
+
+        graph
+            .packages
+            .keys()
+            .filter(|id| id.name == name)
+            .filter_map(|id| {
+                let version = parse_version(&id.version).ok()?;
+                let meta = package.versions.get(&id.version)?;
+                ranges.matches(&version).then_some((version, meta))
+            })
+            .max_by(|(left, _), (right, _)| left.cmp(right))
+            .map(|(_, meta)| meta.clone())
+    }
+
     fn seeded_dependency_id(
         &self,
         name: &str,
@@ -158,6 +224,10 @@ impl<'a> ResolverContext<'a> {
                 return false;
             };
 
+            if override_excludes_version(&current.name, &current.version, self.overrides) {
+                return false;
+            }
+
             for dependency_id in package.dependencies.values() {
                 stack.push(dependency_id.clone());
             }
@@ -0,0 +1,100 @@
+use super::super::engine::ResolverContext;
+use super::super::query::build_dep_request;
+use super::super::types::PackageId;
+use crate::config::ResolutionMode;
+use crate::registry::RegistryProtocol;
+use crate::version::{VersionPreference, published_at, select_version};
+
+use futures::future::join_all;
+use std::collections::BTreeMap;
+use time::OffsetDateTime;
+
+impl<'a> ResolverContext<'a> {
+    /// How to pick among the versions matching a range requested by
+    /// `parent_id` (`None` for a direct dependency) under the configured
+    /// `resolutionMode`.
+    pub(super) fn version_preference(&self, parent_id: Option<&PackageId>) -> VersionPreference {
+        let direct = parent_id.is_none();
+
+        match self.config.resolution_mode {
+            ResolutionMode::Highest => VersionPreference::Highest,
+            ResolutionMode::Lowest => VersionPreference::Lowest,
+            ResolutionMode::LowestDirect if direct => VersionPreference::Lowest,
+            ResolutionMode::LowestDirect => VersionPreference::Highest,
+            ResolutionMode::TimeBased => match self.time_cutoff {
+                Some(cutoff) if !direct => VersionPreference::PublishedBy(cutoff),
+                _ => VersionPreference::Highest,
+            },
+        }
+    }
+
+    /// Publish time of the newest version the direct dependencies resolve
+    /// to, which caps transitive dependencies under `time-based`
+    /// resolution. A direct dependency the lockfile already pins counts at
+    /// its locked version. Ones that aren't registry packages, or whose
+    /// metadata can't be fetched here, are left out; resolving them for
+    /// real reports the error.
+    pub(in crate::resolve) async fn direct_dependency_cutoff(
+        &self,
+        root_deps: &BTreeMap<String, String>,
+        root_protocols: &BTreeMap<String, RegistryProtocol>,
+    ) -> Option<OffsetDateTime> {
+        let default_protocol = RegistryProtocol::npm();
+        let tasks = root_deps.iter().map(|(name, range)| {
+            let protocol = root_protocols.get(name).unwrap_or(&default_protocol);
+            self.direct_dependency_published_at(name, range, protocol)
+        });
+
+        join_all(tasks).await.into_iter().flatten().max()
+    }
+
+    async fn direct_dependency_published_at(
+        &self,
+        name: &str,
+        range: &str,
+        protocol: &RegistryProtocol,
+    ) -> Option<OffsetDateTime> {
+        let request = build_dep_request(
+            name,
+            range,
+            protocol,
+            self.overrides,
+            self.workspace_sources,
+        );
+        if request.protocol.name != "npm" {
+            return None;
+        }
+
+        let cache_key = format!("{}:{}", request.protocol.name, request.source);
+        let package = self
+            .fetch_registry_package(&cache_key, &request.source, &request.protocol)
+            .await
+            .ok()?;
+
+        let seeded = self
+            .existing_graph
+            .filter(|_| !self.prefer_locked)
+            .and_then(|graph| self.seeded_dependency_id(name, range, None, graph))
+            .map(|id| id.version);
+        let version = match seeded {
+            Some(version) => version,
+            None => match self.locked_version(&request.source, &request.range, &package) {
+                Some(meta) => meta.version,
+                None => {
+                    select_version(
+                        &request.source,
+                        &request.range,
+                        &package,
+                        self.min_age_days,
+                        self.config.no_downgrade_exclusions(),
+                        self.force,
+                    )
+                    .ok()?
+                    .version
+                }
+            },
+        };
+
+        published_at(&package, &version)
+    }
+}
@@ -3,7 +3,8 @@ use super::super::resolve_with_optional_roots_with_seed;
 use super::super::types::{
     PackageId, ResolutionGraph, ResolutionRoot, ResolvedPackage, RootDependency,
 };
-use crate::config::{OfflineMode, SnpmConfig};
+use crate::config::{OfflineMode, ResolutionMode, SnpmConfig};
+use crate::registry::{RegistryPackage, RegistryProtocol};
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -44,6 +45,12 @@ fn make_seed_graph_for_chain() -> ResolutionGraph {
                     bundled_dependencies: None,
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             ),
             (
@@ -57,6 +64,12 @@ fn make_seed_graph_for_chain() -> ResolutionGraph {
                     bundled_dependencies: None,
                     has_bin: false,
                     bin: None,
+                    os: Vec::new(),
+                    cpu: Vec::new(),
+                    libc: Vec::new(),
+                    engines: BTreeMap::new(),
+                    deprecated: None,
+                    has_install_script: false,
                 },
             ),
         ]),
@@ -109,6 +122,12 @@ fn make_parent_scoped_graph() -> (ResolutionGraph, PackageId, PackageId, Package
                         bundled_dependencies: None,
                         has_bin: false,
                         bin: None,
+                        os: Vec::new(),
+                        cpu: Vec::new(),
+                        libc: Vec::new(),
+                        engines: BTreeMap::new(),
+                        deprecated: None,
+                        has_install_script: false,
                     },
                 ),
                 (
@@ -122,6 +141,12 @@ fn make_parent_scoped_graph() -> (ResolutionGraph, PackageId, PackageId, Package
                         bundled_dependencies: None,
                         has_bin: false,
                         bin: None,
+                        os: Vec::new(),
+                        cpu: Vec::new(),
+                        libc: Vec::new(),
+                        engines: BTreeMap::new(),
+                        deprecated: None,
+                        has_install_script: false,
                     },
                 ),
                 (
@@ -135,6 +160,12 @@ fn make_parent_scoped_graph() -> (ResolutionGraph, PackageId, PackageId, Package
                         bundled_dependencies: None,
                         has_bin: false,
                         bin: None,
+                        os: Vec::new(),
+                        cpu: Vec::new(),
+                        libc: Vec::new(),
+                        engines: BTreeMap::new(),
+                        deprecated: None,
+                        has_install_script: false,
                     },
                 ),
             ]),
@@ -179,6 +210,8 @@ async fn resolve_with_seeded_graph_reuses_complete_seed_graph() {
         false,
         None,
         None,
+        None,
+        None,
         Some(&seed_graph),
         {
             let call_count = call_count.clone();
@@ -225,6 +258,8 @@ async fn resolve_with_seeded_graph_prefers_parent_scoped_dependency_over_root_ca
         false,
         None,
         None,
+        None,
+        None,
         Some(&seed_graph),
         |_package| async move { Ok::<(), crate::SnpmError>(()) },
     )
@@ -302,3 +337,236 @@ async fn import_seed_package_chain_imports_complete_graph_and_reports_missing()
             .unwrap()
     );
 }
+
+#[test]
+fn seeded_subgraph_is_rejected_when_an_override_excludes_part_of_it() {
+    let config = make_config();
+    let client = reqwest::Client::new();
+    let (seed_graph, foo_id, _, _) = make_parent_scoped_graph();
+    let overrides = BTreeMap::from([("bar@^1.0.0".to_string(), "^1.0.2".to_string())]);
+    let context = ResolverContext::new_for_tests(
+        &config,
+        &client,
+        Some(&seed_graph),
+        None,
+        false,
+        Some(&overrides),
+        None,
+        OfflineMode::Online,
+    );
+
+    assert!(
+        make_context(&config, &client, &seed_graph)
+            .seeded_subgraph_complete(foo_id.clone(), &seed_graph)
+    );
+    assert!(!context.seeded_subgraph_complete(foo_id, &seed_graph));
+}
+
+fn make_registry_package(versions: &[&str]) -> RegistryPackage {
+    let versions = versions
+        .iter()
+        .map(|version| {
+            (
+                version.to_string(),
+                serde_json::json!({
+                    "version": version,
+                    "dist": { "tarball": format!("https://registry.example/bar-{version}.tgz") }
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    serde_json::from_value(serde_json::json!({
+        "versions": versions,
+        "dist-tags": { "latest": "2.9.0" }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn locked_version_picks_highest_locked_match_only_when_deduping() {
+    let config = make_config();
+    let client = reqwest::Client::new();
+    let (seed_graph, ..) = make_parent_scoped_graph();
+    let package = make_registry_package(&["1.0.1", "2.0.0", "2.9.0"]);
+
+    let mut context = make_context(&config, &client, &seed_graph);
+    assert!(context.locked_version("bar", "^1.0.0", &package).is_none());
+
+    context.prefer_locked = true;
+    assert_eq!(
+        context
+            .locked_version("bar", ">=1.0.0", &package)
+            .map(|meta| meta.version),
+        Some("2.0.0".to_string())
+    );
+    assert_eq!(
+        context
+            .locked_version("bar", "^1.0.0", &package)
+            .map(|meta| meta.version),
+        Some("1.0.1".to_string())
+    );
+    assert!(context.locked_version("bar", "^3.0.0", &package).is_none());
+    assert!(context.locked_version("bar", "latest", &package).is_none());
+}
+
+fn make_timed_package(
+    name: &str,
+    versions: &[(&str, &str)],
+    dependencies: &[(&str, &str)],
+) -> RegistryPackage {
+    let dependencies: serde_json::Map<_, _> = dependencies
+        .iter()
+        .map(|(dep, range)| (dep.to_string(), serde_json::json!(range)))
+        .collect();
+    let manifests: serde_json::Map<_, _> = versions
+        .iter()
+        .map(|(version, _)| {
+            (
+                version.to_string(),
+                serde_json::json!({
+                    "version": version,
+                    "dependencies": dependencies,
+                    "dist": { "tarball": format!("https://registry.example/{name}-{version}.tgz") }
+                }),
+            )
+        })
+        .collect();
+    let time: serde_json::Map<_, _> = versions
+        .iter()
+        .map(|(version, published)| (version.to_string(), serde_json::json!(published)))
+        .collect();
+
+    serde_json::from_value(serde_json::json!({
+        "versions": manifests,
+        "time": time,
+        "dist-tags": {}
+    }))
+    .unwrap()
+}
+
+async fn assert_resolves(mode: ResolutionMode, app: &str, dep: &str) {
+    let config = SnpmConfig {
+        resolution_mode: mode,
+        ..make_config()
+    };
+    let client = reqwest::Client::new();
+    let mut context = ResolverContext::new_for_tests(
+        &config,
+        &client,
+        None,
+        None,
+        false,
+        None,
+        None,
+        OfflineMode::Offline,
+    );
+    context
+        .cache_registry_package(
+            "npm:app",
+            make_timed_package(
+                "app",
+                &[
+                    ("1.0.0", "2024-01-10T00:00:00.000Z"),
+                    ("1.1.0", "2024-03-10T00:00:00.000Z"),
+                ],
+                &[("dep", "^1.0.0")],
+            ),
+        )
+        .await;
+    context
+        .cache_registry_package(
+            "npm:dep",
+            make_timed_package(
+                "dep",
+                &[
+                    ("1.0.0", "2024-01-01T00:00:00.000Z"),
+                    ("1.1.0", "2024-02-01T00:00:00.000Z"),
+                    ("1.2.0", "2024-04-01T00:00:00.000Z"),
+                ],
+                &[],
+            ),
+        )
+        .await;
+
+    let root_deps = BTreeMap::from([("app".to_string(), "^1.0.0".to_string())]);
+    if mode == ResolutionMode::TimeBased {
+        context.time_cutoff = context
+            .direct_dependency_cutoff(&root_deps, &BTreeMap::new())
+            .await;
+    }
+
+    context
+        .resolve_package("app", "^1.0.0", &RegistryProtocol::npm(), None)
+        .await
+        .unwrap();
+
+    for (name, version) in [("app", app), ("dep", dep)] {
+        let id = PackageId {
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+        assert!(
+            context.package_already_resolved(&id).await,
+            "{mode:?} should pick {name}@{version}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn resolution_modes_pick_versions_for_direct_and_transitive_dependencies() {
+    assert_resolves(ResolutionMode::Highest, "1.1.0", "1.2.0").await;
+    assert_resolves(ResolutionMode::Lowest, "1.0.0", "1.0.0").await;
+    assert_resolves(ResolutionMode::LowestDirect, "1.0.0", "1.2.0").await;
+    assert_resolves(ResolutionMode::TimeBased, "1.1.0", "1.1.0").await;
+}
+
+#[test]
+fn engine_strict_prefers_a_version_whose_engines_fit() {
+    let package: RegistryPackage = serde_json::from_value(serde_json::json!({
+        "versions": {
+            "1.4.0": { "version": "1.4.0", "dist": { "tarball": "https://registry.example/lib-1.4.0.tgz" } },
+            "1.5.0": {
+                "version": "1.5.0",
+                "engines": { "node": ">=16" },
+                "dist": { "tarball": "https://registry.example/lib-1.5.0.tgz" }
+            },
+            "1.6.0": {
+                "version": "1.6.0",
+                "engines": { "node": ">=20" },
+                "dist": { "tarball": "https://registry.example/lib-1.6.0.tgz" }
+            }
+        },
+        "dist-tags": { "latest": "1.6.0" }
+    }))
+    .unwrap();
+    let newest = package.versions["1.6.0"].clone();
+
+    let client = reqwest::Client::new();
+    let graph = ResolutionGraph {
+        root: ResolutionRoot {
+            dependencies: BTreeMap::new(),
+        },
+        packages: BTreeMap::new(),
+    };
+    let mut config = SnpmConfig {
+        engine_targets: crate::platform::EngineTargets {
+            node: Some(snpm_semver::Version::new(18, 19, 0)),
+            ..Default::default()
+        },
+        ..make_config()
+    };
+
+    let pick = |config: &SnpmConfig, range: &str| {
+        make_context(config, &client, &graph)
+            .engine_compatible_version("lib", range, &package, newest.clone(), None)
+            .version
+    };
+
+    assert_eq!(pick(&config, "^1.0.0"), "1.6.0");
+
+    config.engine_strict = true;
+    assert_eq!(pick(&config, "^1.0.0"), "1.5.0");
+    assert_eq!(pick(&config, "latest"), "1.5.0");
+    assert_eq!(pick(&config, "1.6.0"), "1.6.0");
+}
@@ -1,20 +1,27 @@
 mod index;
+mod report;
+mod rules;
 mod validate;
 
-use crate::Result;
 use crate::resolve::types::ResolutionGraph;
 
 use index::collect_versions_by_name;
+pub use report::{PeerIssue, PeerIssueKind, PeerReport};
+pub use rules::{PeerDependencyRules, validate_peer_dependency_rules};
 use validate::validate_package_peers;
 
-pub fn validate_peers(graph: &ResolutionGraph) -> Result<()> {
+/// Checks every declared peer in `graph` against the versions it contains,
+/// applying `rules`, and reports all the requirements still unmet.
+pub fn validate_peers(graph: &ResolutionGraph, rules: &PeerDependencyRules) -> PeerReport {
     let versions_by_name = collect_versions_by_name(graph);
+    let rules = rules.compile();
+    let mut issues = Vec::new();
 
     for package in graph.packages.values() {
-        validate_package_peers(package, &versions_by_name)?;
+        validate_package_peers(package, &versions_by_name, &rules, &mut issues);
     }
 
-    Ok(())
+    PeerReport { issues }
 }
 
 #[cfg(test)]
@@ -0,0 +1,99 @@
+use crate::resolve::types::PackageId;
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerIssueKind {
+    /// No version of the peer is installed anywhere in the graph.
+    Missing,
+    /// Installed versions exist but none is in range.
+    Unsatisfied { installed: Vec<String> },
+    /// The declared range does not parse.
+    InvalidRange { reason: String },
+}
+
+/// One peer requirement that is still unmet after `peerDependencyRules`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerIssue {
+    /// The package declaring the peer.
+    pub package: PackageId,
+    pub peer: String,
+    pub range: String,
+    pub kind: PeerIssueKind,
+}
+
+impl fmt::Display for PeerIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let package = format!("{}@{}", self.package.name, self.package.version);
+        match &self.kind {
+            PeerIssueKind::Missing => {
+                write!(f, "{package}: missing peer {}@{}", self.peer, self.range)
+            }
+            PeerIssueKind::Unsatisfied { installed } => write!(
+                f,
+                "{package}: peer {}@{} is not satisfied (installed: {})",
+                self.peer,
+                self.range,
+                installed.join(", ")
+            ),
+            PeerIssueKind::InvalidRange { reason } => write!(
+                f,
+                "{package}: invalid peer range {}@{}: {reason}",
+                self.peer, self.range
+            ),
+        }
+    }
+}
+
+/// Every unmet peer in a graph, ordered by declaring package.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerReport {
+    pub issues: Vec<PeerIssue>,
+}
+
+impl PeerReport {
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Peers with no installed version at all.
+    pub fn missing(&self) -> impl Iterator<Item = &PeerIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.kind == PeerIssueKind::Missing)
+    }
+
+    /// Peers installed only at versions out of range, or declared with a
+    /// range that does not parse.
+    pub fn unsatisfied(&self) -> impl Iterator<Item = &PeerIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.kind != PeerIssueKind::Missing)
+    }
+}
+
+impl fmt::Display for PeerReport {
+    /// Missing peers first, then the ones installed at the wrong version,
+    /// each group under its own heading.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups = [
+            ("Missing", self.missing().collect::<Vec<_>>()),
+            ("Not satisfied", self.unsatisfied().collect()),
+        ];
+        let mut first = true;
+        for (heading, issues) in groups {
+            if issues.is_empty() {
+                continue;
+            }
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            write!(f, "  {heading}:")?;
+            for issue in issues {
+                write!(f, "\n    {issue}")?;
+            }
+        }
+        Ok(())
+    }
+}
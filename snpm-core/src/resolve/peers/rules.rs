@@ -0,0 +1,120 @@
+use crate::version::parse_range_set;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// `peerDependencyRules`: exceptions to peer validation for peers known
+/// to work outside their declared range.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerDependencyRules {
+    /// Peer name globs whose absence is not reported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore_missing: Vec<String>,
+    /// Peer name globs satisfied by any installed version.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_any: Vec<String>,
+    /// Extra ranges accepted for a peer, keyed by `peer` (every parent) or
+    /// `parent>peer` (only when `parent` declares it).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub allowed_versions: BTreeMap<String, String>,
+}
+
+impl PeerDependencyRules {
+    pub fn is_empty(&self) -> bool {
+        self.ignore_missing.is_empty()
+            && self.allow_any.is_empty()
+            && self.allowed_versions.is_empty()
+    }
+
+    /// Adds `other`'s rules; its `allowedVersions` win for the same key.
+    pub fn extend(&mut self, other: &PeerDependencyRules) {
+        self.ignore_missing
+            .extend(other.ignore_missing.iter().cloned());
+        self.allow_any.extend(other.allow_any.iter().cloned());
+        self.allowed_versions.extend(
+            other
+                .allowed_versions
+                .iter()
+                .map(|(key, range)| (key.clone(), range.clone())),
+        );
+    }
+
+    /// Compiles the name globs once for a validation pass. Invalid globs
+    /// were rejected when the rules were loaded and are skipped here.
+    pub(super) fn compile(&self) -> CompiledPeerRules<'_> {
+        CompiledPeerRules {
+            rules: self,
+            ignore_missing: compile_globs(&self.ignore_missing),
+            allow_any: compile_globs(&self.allow_any),
+        }
+    }
+
+    /// Ranges accepted for `peer` when `parent` declares it, on top of the
+    /// declared range.
+    pub(super) fn allowed_ranges(&self, parent: &str, peer: &str) -> Vec<&str> {
+        self.allowed_versions
+            .iter()
+            .filter(|(key, _)| match key.split_once('>') {
+                Some((key_parent, key_peer)) => key_parent == parent && key_peer == peer,
+                None => key.as_str() == peer,
+            })
+            .map(|(_, range)| range.as_str())
+            .collect()
+    }
+}
+
+/// [`PeerDependencyRules`] with the name globs compiled.
+pub(super) struct CompiledPeerRules<'a> {
+    rules: &'a PeerDependencyRules,
+    ignore_missing: Vec<glob::Pattern>,
+    allow_any: Vec<glob::Pattern>,
+}
+
+impl CompiledPeerRules<'_> {
+    pub(super) fn ignores_missing(&self, peer: &str) -> bool {
+        self.ignore_missing
+            .iter()
+            .any(|pattern| pattern.matches(peer))
+    }
+
+    pub(super) fn allows_any(&self, peer: &str) -> bool {
+        self.allow_any.iter().any(|pattern| pattern.matches(peer))
+    }
+
+    pub(super) fn allowed_ranges(&self, parent: &str, peer: &str) -> Vec<&str> {
+        self.rules.allowed_ranges(parent, peer)
+    }
+}
+
+/// Rejects globs that do not compile and `allowedVersions` entries with an
+/// empty name or a range that does not parse. The error is the reason,
+/// for callers to wrap with the file that declared the rules.
+pub fn validate_peer_dependency_rules(
+    rules: &PeerDependencyRules,
+) -> std::result::Result<(), String> {
+    for pattern in rules.ignore_missing.iter().chain(&rules.allow_any) {
+        glob::Pattern::new(pattern)
+            .map_err(|error| format!("invalid glob `{pattern}`: {error}"))?;
+    }
+
+    for (key, range) in &rules.allowed_versions {
+        let (parent, peer) = key.split_once('>').unwrap_or(("", key.as_str()));
+        if peer.is_empty() || (key.contains('>') && parent.is_empty()) {
+            return Err(format!(
+                "allowedVersions key `{key}` should be `peer` or `parent>peer`"
+            ));
+        }
+
+        parse_range_set(peer, range).map_err(|error| error.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn compile_globs(patterns: &[String]) -> Vec<glob::Pattern> {
+    patterns
+        .iter()
+        .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+        .collect()
+}
@@ -1,4 +1,4 @@
-use super::validate_peers;
+use super::{PeerDependencyRules, PeerIssueKind, validate_peers};
 use crate::resolve::types::*;
 
 use std::collections::BTreeMap;
@@ -17,6 +17,12 @@ fn make_package(name: &str, version: &str) -> (PackageId, ResolvedPackage) {
         bundled_dependencies: None,
         has_bin: false,
         bin: None,
+        os: Vec::new(),
+        cpu: Vec::new(),
+        libc: Vec::new(),
+        engines: BTreeMap::new(),
+        deprecated: None,
+        has_install_script: false,
     };
     (id, pkg)
 }
@@ -40,7 +46,7 @@ fn validates_satisfied_peer_dependency() {
         packages,
     };
 
-    assert!(validate_peers(&graph).is_ok());
+    assert!(validate_peers(&graph, &PeerDependencyRules::default()).is_empty());
 }
 
 #[test]
@@ -62,7 +68,7 @@ fn rejects_unsatisfied_peer_dependency() {
         packages,
     };
 
-    assert!(validate_peers(&graph).is_err());
+    assert!(!validate_peers(&graph, &PeerDependencyRules::default()).is_empty());
 }
 
 #[test]
@@ -82,7 +88,7 @@ fn rejects_missing_peer_dependency() {
         packages,
     };
 
-    assert!(validate_peers(&graph).is_err());
+    assert!(!validate_peers(&graph, &PeerDependencyRules::default()).is_empty());
 }
 
 #[test]
@@ -99,7 +105,7 @@ fn no_peer_deps_passes() {
         packages,
     };
 
-    assert!(validate_peers(&graph).is_ok());
+    assert!(validate_peers(&graph, &PeerDependencyRules::default()).is_empty());
 }
 
 #[test]
@@ -111,5 +117,113 @@ fn empty_graph_passes() {
         packages: BTreeMap::new(),
     };
 
-    assert!(validate_peers(&graph).is_ok());
+    assert!(validate_peers(&graph, &PeerDependencyRules::default()).is_empty());
+}
+
+fn graph_of(packages: Vec<(PackageId, ResolvedPackage)>) -> ResolutionGraph {
+    ResolutionGraph {
+        root: ResolutionRoot {
+            dependencies: BTreeMap::new(),
+        },
+        packages: packages.into_iter().collect(),
+    }
+}
+
+fn with_peers(name: &str, version: &str, peers: &[(&str, &str)]) -> (PackageId, ResolvedPackage) {
+    let (id, mut package) = make_package(name, version);
+    for (peer, range) in peers {
+        package
+            .peer_dependencies
+            .insert(peer.to_string(), range.to_string());
+    }
+    (id, package)
+}
+
+#[test]
+fn reports_every_issue_instead_of_the_first() {
+    let graph = graph_of(vec![
+        make_package("react", "17.0.2"),
+        with_peers("a-plugin", "1.0.0", &[("react", "^18.0.0"), ("vue", "^3")]),
+        with_peers("b-plugin", "1.0.0", &[("react-dom", "^18.0.0")]),
+    ]);
+
+    let report = validate_peers(&graph, &PeerDependencyRules::default());
+
+    assert_eq!(report.issues.len(), 3);
+    assert_eq!(report.missing().count(), 2);
+    assert_eq!(
+        report.unsatisfied().next().unwrap().kind,
+        PeerIssueKind::Unsatisfied {
+            installed: vec!["17.0.2".to_string()]
+        }
+    );
+    assert_eq!(
+        report.issues[0].to_string(),
+        "a-plugin@1.0.0: peer react@^18.0.0 is not satisfied (installed: 17.0.2)"
+    );
+    assert_eq!(
+        report.to_string(),
+        "  Missing:\n    a-plugin@1.0.0: missing peer vue@^3\n    b-plugin@1.0.0: missing peer react-dom@^18.0.0\n  Not satisfied:\n    a-plugin@1.0.0: peer react@^18.0.0 is not satisfied (installed: 17.0.2)"
+    );
+}
+
+#[test]
+fn rules_silence_known_good_peers() {
+    let graph = graph_of(vec![
+        make_package("react", "17.0.2"),
+        make_package("eslint", "9.1.0"),
+        with_peers(
+            "a-plugin",
+            "1.0.0",
+            &[("react", "^18.0.0"), ("@types/react", "*")],
+        ),
+        with_peers("b-plugin", "1.0.0", &[("react", "^18.0.0")]),
+        with_peers("lint-plugin", "1.0.0", &[("eslint", "^8")]),
+    ]);
+    let rules = PeerDependencyRules {
+        ignore_missing: vec!["@types/*".to_string()],
+        allow_any: vec!["eslint".to_string()],
+        allowed_versions: BTreeMap::from([("a-plugin>react".to_string(), "17".to_string())]),
+    };
+
+    let report = validate_peers(&graph, &rules);
+
+    // Only b-plugin is left: the `a-plugin>react` exception is scoped.
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].package.name, "b-plugin");
+}
+
+#[test]
+fn bare_allowed_versions_key_applies_to_every_parent() {
+    let graph = graph_of(vec![
+        make_package("react", "17.0.2"),
+        with_peers("a-plugin", "1.0.0", &[("react", "^18.0.0")]),
+        with_peers("b-plugin", "1.0.0", &[("react", "^18.0.0")]),
+    ]);
+    let rules = PeerDependencyRules {
+        allowed_versions: BTreeMap::from([("react".to_string(), "^17.0.0".to_string())]),
+        ..PeerDependencyRules::default()
+    };
+
+    assert!(validate_peers(&graph, &rules).is_empty());
+}
+
+#[test]
+fn rule_validation_rejects_bad_keys_and_ranges() {
+    let rules = |key: &str, range: &str| PeerDependencyRules {
+        allowed_versions: BTreeMap::from([(key.to_string(), range.to_string())]),
+        ..PeerDependencyRules::default()
+    };
+
+    assert!(super::validate_peer_dependency_rules(&rules("a>react", "^17")).is_ok());
+    assert!(super::validate_peer_dependency_rules(&rules(">react", "^17")).is_err());
+    assert!(super::validate_peer_dependency_rules(&rules("a>", "^17")).is_err());
+    assert!(super::validate_peer_dependency_rules(&rules("react", "not a range")).is_err());
+    assert!(
+        super::validate_peer_dependency_rules(&PeerDependencyRules {
+            ignore_missing: vec!["[".to_string()],
+            ..PeerDependencyRules::default()
+        })
+        .is_err()
+    );
 }
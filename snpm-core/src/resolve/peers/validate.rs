@@ -1,58 +1,61 @@
+use super::report::{PeerIssue, PeerIssueKind};
+use super::rules::CompiledPeerRules;
 use crate::resolve::types::ResolvedPackage;
-use crate::{Result, SnpmError};
+use crate::version::parse_range_set;
 
 use std::collections::BTreeMap;
 
 pub(super) fn validate_package_peers(
     package: &ResolvedPackage,
     versions_by_name: &BTreeMap<String, Vec<snpm_semver::Version>>,
-) -> Result<()> {
-    if package.peer_dependencies.is_empty() {
-        return Ok(());
-    }
-
+    rules: &CompiledPeerRules<'_>,
+    issues: &mut Vec<PeerIssue>,
+) {
     for (peer_name, peer_range) in &package.peer_dependencies {
-        let range_set = crate::version::parse_range_set(peer_name, peer_range)?;
-
-        let candidates = versions_by_name
-            .get(peer_name)
-            .ok_or_else(|| missing_peer(package, peer_name, peer_range))?;
+        let issue = |kind| PeerIssue {
+            package: package.id.clone(),
+            peer: peer_name.clone(),
+            range: peer_range.clone(),
+            kind,
+        };
+
+        let Some(candidates) = versions_by_name.get(peer_name) else {
+            if !rules.ignores_missing(peer_name) {
+                issues.push(issue(PeerIssueKind::Missing));
+            }
+            continue;
+        };
 
-        if candidates.iter().any(|version| range_set.matches(version)) {
+        if rules.allows_any(peer_name) {
             continue;
         }
 
-        return Err(unsatisfied_peer(package, peer_name, peer_range, candidates));
-    }
-
-    Ok(())
-}
-
-fn missing_peer(package: &ResolvedPackage, peer_name: &str, peer_range: &str) -> SnpmError {
-    SnpmError::ResolutionFailed {
-        name: package.id.name.clone(),
-        range: peer_range.to_string(),
-        reason: format!("missing peer dependency {peer_name}"),
-    }
-}
+        let declared = match parse_range_set(peer_name, peer_range) {
+            Ok(range_set) => range_set,
+            Err(error) => {
+                issues.push(issue(PeerIssueKind::InvalidRange {
+                    reason: error.to_string(),
+                }));
+                continue;
+            }
+        };
+
+        // Rule ranges were validated when the rules were loaded.
+        let allowed: Vec<_> = rules
+            .allowed_ranges(&package.id.name, peer_name)
+            .into_iter()
+            .filter_map(|range| parse_range_set(peer_name, range).ok())
+            .collect();
+
+        let satisfied = candidates.iter().any(|version| {
+            declared.matches(version) || allowed.iter().any(|range| range.matches(version))
+        });
+        if satisfied {
+            continue;
+        }
 
-fn unsatisfied_peer(
-    package: &ResolvedPackage,
-    peer_name: &str,
-    peer_range: &str,
-    candidates: &[snpm_semver::Version],
-) -> SnpmError {
-    let installed = candidates
-        .iter()
-        .map(|version| version.to_string())
-        .collect::<Vec<_>>()
-        .join(", ");
-
-    SnpmError::ResolutionFailed {
-        name: package.id.name.clone(),
-        range: peer_range.to_string(),
-        reason: format!(
-            "peer dependency {peer_name}@{peer_range} is not satisfied; installed versions: {installed}"
-        ),
+        issues.push(issue(PeerIssueKind::Unsatisfied {
+            installed: candidates.iter().map(ToString::to_string).collect(),
+        }));
     }
 }
@@ -1,5 +1,7 @@
 use crate::registry::RegistryProtocol;
+use crate::version::parse_range_set;
 
+use snpm_semver::parse_version;
 use std::collections::BTreeMap;
 
 use super::{DepRequest, split_protocol_spec};
@@ -67,6 +69,46 @@ fn select_override<'a>(
         .map(|value| value.as_str())
 }
 
+/// Whether an override would steer `name` away from `version`: a bare
+/// `name` override, or a `name@<range>` one whose range covers `version`,
+/// whose value `version` does not satisfy. Lockfile packages that fail this
+/// were resolved before the override existed and must not seed a resolve.
+pub fn override_excludes_version(
+    name: &str,
+    version: &str,
+    overrides: Option<&BTreeMap<String, String>>,
+) -> bool {
+    let Some(overrides) = overrides else {
+        return false;
+    };
+    let Ok(parsed) = parse_version(version) else {
+        return false;
+    };
+    let satisfies = |range: &str| {
+        parse_range_set(name, range)
+            .map(|ranges| ranges.matches(&parsed))
+            .ok()
+    };
+
+    overrides.iter().any(|(key, value)| {
+        let applies = if key == name {
+            true
+        } else if let Some(requested) = key
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('@'))
+        {
+            let requested = requested
+                .split_once(':')
+                .map_or(requested, |(_, range)| range);
+            satisfies(requested) == Some(true)
+        } else {
+            false
+        };
+
+        applies && satisfies(value) == Some(false)
+    })
+}
+
 fn split_package_less_registry_spec(spec: &str) -> Option<(RegistryProtocol, &str)> {
     for (prefix, protocol) in [
         ("npm:", RegistryProtocol::npm()),
@@ -2,7 +2,7 @@ mod build;
 mod parse;
 mod request;
 
-pub use build::build_dep_request;
+pub use build::{build_dep_request, override_excludes_version};
 pub use parse::split_protocol_spec;
 pub use request::DepRequest;
 
@@ -167,3 +167,43 @@ fn split_protocol_spec_empty_rest_returns_none() {
 fn split_protocol_spec_no_colon_returns_none() {
     assert!(split_protocol_spec("lodash").is_none());
 }
+
+#[test]
+fn override_excludes_version_checks_bare_and_scoped_overrides() {
+    let overrides = BTreeMap::from([
+        ("lodash".to_string(), "^4.17.21".to_string()),
+        ("minimist@^1.2.0".to_string(), "^1.2.6".to_string()),
+    ]);
+
+    assert!(override_excludes_version(
+        "lodash",
+        "4.17.20",
+        Some(&overrides)
+    ));
+    assert!(!override_excludes_version(
+        "lodash",
+        "4.17.21",
+        Some(&overrides)
+    ));
+    assert!(override_excludes_version(
+        "minimist",
+        "1.2.5",
+        Some(&overrides)
+    ));
+    assert!(!override_excludes_version(
+        "minimist",
+        "1.2.6",
+        Some(&overrides)
+    ));
+    assert!(!override_excludes_version(
+        "minimist",
+        "0.0.8",
+        Some(&overrides)
+    ));
+    assert!(!override_excludes_version(
+        "lodash-es",
+        "4.0.0",
+        Some(&overrides)
+    ));
+    assert!(!override_excludes_version("lodash", "4.17.20", None));
+}
@@ -19,6 +19,12 @@ pub struct ResolvedPackage {
     pub bundled_dependencies: Option<BundledDependencies>,
     pub has_bin: bool,
     pub bin: Option<BinField>,
+    pub os: Vec<String>,
+    pub cpu: Vec<String>,
+    pub libc: Vec<String>,
+    pub engines: BTreeMap<String, String>,
+    pub deprecated: Option<String>,
+    pub has_install_script: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -37,3 +43,12 @@ pub struct ResolutionGraph {
     pub root: ResolutionRoot,
     pub packages: BTreeMap<PackageId, ResolvedPackage>,
 }
+
+impl ResolutionGraph {
+    /// The package a project's `name` dependency is linked to: the root
+    /// entry for `name`, as long as the graph holds that package.
+    pub fn linked_package(&self, name: &str) -> Option<&ResolvedPackage> {
+        let root = self.root.dependencies.get(name)?;
+        self.packages.get(&root.resolved)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{Result, SnpmError};
 
 use flate2::read::GzDecoder;
+use std::collections::BTreeMap;
 use std::fs;
 #[cfg(test)]
 use std::io::Cursor;
@@ -8,7 +9,8 @@ use std::io::{BufReader, ErrorKind, Read};
 use std::path::Path;
 use tar::Archive;
 
-use super::paths::safe_join;
+use super::paths::{normalized_relative, safe_join};
+use crate::store::manifest::{FileDigest, digest_reader};
 
 const READ_BUFFER_BYTES: usize = 64 * 1024;
 
@@ -29,6 +31,47 @@ pub(crate) fn unpack_tarball_file(pkg_dir: &Path, tarball_path: &Path) -> Result
     unpack_tarball_reader(pkg_dir, buffered)
 }
 
+/// SHA-512 of every regular file `unpack_tarball_file` would write, keyed
+/// by its `/`-separated path relative to the extraction root. A later entry
+/// for the same path wins, as it does when unpacking.
+pub(crate) fn tarball_file_digests(tarball_path: &Path) -> Result<BTreeMap<String, FileDigest>> {
+    let file = fs::File::open(tarball_path).map_err(|source| SnpmError::ReadFile {
+        path: tarball_path.to_path_buf(),
+        source,
+    })?;
+    let buffered = BufReader::with_capacity(READ_BUFFER_BYTES, file);
+    let mut archive = Archive::new(GzDecoder::new(buffered));
+    let archive_error = |source| SnpmError::Archive {
+        path: tarball_path.to_path_buf(),
+        source,
+    };
+
+    let mut digests = BTreeMap::new();
+    for entry in archive.entries().map_err(archive_error)? {
+        let entry = entry.map_err(archive_error)?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let rel_path = entry.path().map_err(archive_error)?.into_owned();
+        let Some(relative) = normalized_relative(&rel_path) else {
+            return invalid_entry_error(
+                tarball_path,
+                format!(
+                    "archive entry escapes extraction root: {}",
+                    rel_path.display()
+                ),
+            );
+        };
+
+        let mode = entry.header().mode().map_err(archive_error)? & 0o777;
+        let digest = digest_reader(entry, mode).map_err(archive_error)?;
+        digests.insert(relative, digest);
+    }
+
+    Ok(digests)
+}
+
 fn unpack_tarball_reader<R: Read>(pkg_dir: &Path, reader: R) -> Result<()> {
     let decoder = GzDecoder::new(reader);
     let mut archive = Archive::new(decoder);
@@ -3,7 +3,7 @@ mod paths;
 
 #[cfg(test)]
 pub(super) use extract::unpack_tarball;
-pub(super) use extract::unpack_tarball_file;
+pub(super) use extract::{tarball_file_digests, unpack_tarball_file};
 
 #[cfg(test)]
 mod tests;
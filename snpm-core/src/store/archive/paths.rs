@@ -11,3 +11,17 @@ pub(super) fn safe_join(root: &Path, rel: &Path) -> Option<PathBuf> {
     }
     Some(out)
 }
+
+/// `rel` as a `/`-separated path under the extraction root, or `None` when
+/// `safe_join` would reject it.
+pub(super) fn normalized_relative(rel: &Path) -> Option<String> {
+    let mut segments = Vec::new();
+    for component in rel.components() {
+        match component {
+            Component::Normal(segment) => segments.push(segment.to_string_lossy()),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(segments.join("/"))
+}
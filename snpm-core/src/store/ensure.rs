@@ -8,17 +8,20 @@ use std::path::PathBuf;
 use std::time::Instant;
 
 use super::filesystem::{package_root_dir, sanitize_name};
+use super::layout::ensure_store_layout;
 use super::local::materialize_local_package;
+use super::manifest::{COMPLETE_MARKER, record_file_manifest};
+use super::pool::import_package_files;
 use super::remote::materialize_remote_package;
 use super::{PACKAGE_METADATA_FILE, persist_package_metadata};
 
-/// Ensure a package is in the store (Online mode).
+/// Ensure a package is in the store in the configured offline mode.
 pub async fn ensure_package(
     config: &SnpmConfig,
     package: &ResolvedPackage,
     client: &reqwest::Client,
 ) -> Result<PathBuf> {
-    ensure_package_with_offline(config, package, client, OfflineMode::Online).await
+    ensure_package_with_offline(config, package, client, config.offline_mode).await
 }
 
 /// Ensure a package is in the store, respecting offline mode.
@@ -29,8 +32,9 @@ pub async fn ensure_package_with_offline(
     offline_mode: OfflineMode,
 ) -> Result<PathBuf> {
     let start = Instant::now();
+    ensure_store_layout(config).await?;
     let package_dir = package_dir(config, package);
-    let marker = package_dir.join(".snpm_complete");
+    let marker = package_dir.join(COMPLETE_MARKER);
 
     if marker.is_file() {
         let root = package_root_dir(&package_dir);
@@ -49,9 +53,10 @@ pub async fn ensure_package_with_offline(
         return Ok(root);
     }
 
-    if matches!(offline_mode, OfflineMode::Offline) {
+    // `file:` tarballs are on disk, so they materialize even offline.
+    if matches!(offline_mode, OfflineMode::Offline) && !package.tarball.starts_with("file://") {
         return Err(SnpmError::OfflineRequired {
-            resource: format!("package {}@{}", package.id.name, package.id.version),
+            resource: format!("tarball for {}@{}", package.id.name, package.id.version),
         });
     }
 
@@ -66,6 +71,18 @@ pub async fn ensure_package_with_offline(
         materialize_remote_package(config, package, client, &package_dir).await?;
     }
 
+    let manifest_dir = package_dir.clone();
+    let manifest_package = package.clone();
+    let files_dir = config.files_dir();
+    tokio::task::spawn_blocking(move || {
+        let manifest = record_file_manifest(&manifest_dir, &manifest_package)?;
+        import_package_files(&files_dir, &manifest_dir, &manifest.files)
+    })
+    .await
+    .map_err(|error| SnpmError::StoreTask {
+        reason: error.to_string(),
+    })??;
+
     fs::write(&marker, []).map_err(|source| SnpmError::WriteFile {
         path: marker.clone(),
         source,
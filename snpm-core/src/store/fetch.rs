@@ -312,7 +312,7 @@ async fn download_blob(
     ))
 }
 
-fn cached_blob_paths(
+pub(super) fn cached_blob_paths(
     config: &SnpmConfig,
     url: &str,
     integrity: Option<&IntegritySpec>,
@@ -3,7 +3,7 @@ mod paths;
 
 pub(super) use copy::{atomic_finalize_extracted_dir, copy_dir_all};
 pub use paths::package_root_dir;
-pub(super) use paths::sanitize_name;
+pub(crate) use paths::sanitize_name;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,280 @@
+use crate::{Result, SnpmConfig, SnpmError, console};
+
+use rayon::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use super::filesystem::package_root_dir;
+use super::manifest::{
+    ManifestProvenance, collect_file_digests, new_file_manifest, read_file_manifest,
+    read_manifest_provenance, write_file_manifest,
+};
+use super::pool::import_package_files;
+use super::verify::stored_package_dirs;
+
+/// Version of the on-disk store layout, recorded in
+/// `SnpmConfig::store_version_path`.
+///
+/// 1: one extracted directory per `name/version`.
+/// 2: package files are hard links into the content-addressed pool, and
+///    every entry carries a `.snpm-files.json` index.
+pub const STORE_FORMAT_VERSION: u32 = 2;
+
+/// Per store, whether its layout was checked in this process. The outer
+/// lock is only held to look an entry up; the entry's own lock is held
+/// while that store is checked and migrated.
+static CHECKED_STORES: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<bool>>>>> = OnceLock::new();
+
+/// Brings the store under `config.data_dir` up to `STORE_FORMAT_VERSION`,
+/// once per process. Migrating indexes every existing entry and moves its
+/// files into the pool; entries already indexed by a newer layout are left
+/// as they are.
+///
+/// The check and migration run on the blocking pool, so concurrent callers
+/// wait on a task instead of holding up an async worker.
+pub async fn ensure_store_layout(config: &SnpmConfig) -> Result<()> {
+    let checked = checked_store(config);
+    if checked.try_lock().is_ok_and(|checked| *checked) {
+        return Ok(());
+    }
+
+    let config = config.clone();
+    tokio::task::spawn_blocking(move || ensure_store_layout_blocking(&config, &checked))
+        .await
+        .map_err(|error| SnpmError::StoreTask {
+            reason: error.to_string(),
+        })?
+}
+
+fn checked_store(config: &SnpmConfig) -> Arc<Mutex<bool>> {
+    let stores = CHECKED_STORES.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut stores = stores.lock().unwrap_or_else(|err| err.into_inner());
+    stores.entry(config.data_dir.clone()).or_default().clone()
+}
+
+fn ensure_store_layout_blocking(config: &SnpmConfig, checked: &Mutex<bool>) -> Result<()> {
+    let mut checked = checked.lock().unwrap_or_else(|err| err.into_inner());
+    if *checked {
+        return Ok(());
+    }
+
+    match read_store_version(config) {
+        Some(version) if version > STORE_FORMAT_VERSION => console::verbose(&format!(
+            "store at {} uses layout version {version}, newer than this snpm ({STORE_FORMAT_VERSION})",
+            config.data_dir.display()
+        )),
+        Some(STORE_FORMAT_VERSION) => {}
+        Some(_) => migrate_and_record(config)?,
+        None if has_packages(config) => migrate_and_record(config)?,
+        // A new store starts out in the current layout.
+        None => {
+            if let Err(error) = write_store_version(config) {
+                console::verbose(&format!("failed to record the store layout: {error}"));
+            }
+        }
+    }
+
+    *checked = true;
+    Ok(())
+}
+
+/// Records the new layout only once every entry migrated. Entries that
+/// failed keep working as plain directories and are retried next run.
+fn migrate_and_record(config: &SnpmConfig) -> Result<()> {
+    let failed = migrate_store(config)?;
+    if failed.is_empty() {
+        return write_store_version(config);
+    }
+
+    let entries = failed
+        .iter()
+        .map(|(package_dir, error)| format!("  {}: {error}", package_dir.display()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    console::warn(&format!(
+        "{} store entries could not be moved into the file pool; the migration will be retried on the next install:\n{entries}",
+        failed.len()
+    ));
+    Ok(())
+}
+
+/// The recorded layout version; `None` for a store that predates the
+/// marker or does not exist yet.
+fn read_store_version(config: &SnpmConfig) -> Option<u32> {
+    fs::read_to_string(config.store_version_path())
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn has_packages(config: &SnpmConfig) -> bool {
+    fs::read_dir(config.packages_dir())
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
+}
+
+fn write_store_version(config: &SnpmConfig) -> Result<()> {
+    let path = config.store_version_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|source| SnpmError::WriteFile {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+    fs::write(&path, format!("{STORE_FORMAT_VERSION}\n"))
+        .map_err(|source| SnpmError::WriteFile { path, source })
+}
+
+/// Migrates every stored entry, returning the ones that failed.
+fn migrate_store(config: &SnpmConfig) -> Result<Vec<(PathBuf, SnpmError)>> {
+    let package_dirs = stored_package_dirs(config)?;
+    if package_dirs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    console::info(&format!(
+        "Moving {} stored packages into the content-addressed file pool (one-time migration)...",
+        package_dirs.len()
+    ));
+
+    let files_dir = config.files_dir();
+    let failed = package_dirs
+        .into_par_iter()
+        .filter_map(|package_dir| {
+            migrate_package(&files_dir, &package_dir)
+                .err()
+                .map(|error| (package_dir, error))
+        })
+        .collect();
+
+    Ok(failed)
+}
+
+fn migrate_package(files_dir: &Path, package_dir: &Path) -> Result<()> {
+    let manifest = match read_file_manifest(package_dir) {
+        Some(manifest) => manifest,
+        None => {
+            let provenance = read_manifest_provenance(package_dir)
+                .unwrap_or_else(|| provenance_from_directory(package_dir));
+            let manifest = new_file_manifest(provenance, collect_file_digests(package_dir)?);
+            write_file_manifest(package_dir, &manifest)?;
+            manifest
+        }
+    };
+
+    import_package_files(files_dir, package_dir, &manifest.files)
+}
+
+/// Name and version of an entry extracted before snpm kept an index, from
+/// its `package.json`, falling back to the directory names (scoped names
+/// are stored with `/` as `_`).
+fn provenance_from_directory(package_dir: &Path) -> ManifestProvenance {
+    #[derive(Default, Deserialize)]
+    struct NameAndVersion {
+        name: Option<String>,
+        version: Option<String>,
+    }
+
+    let dir_name = |path: Option<&Path>| {
+        path.and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+    let manifest: NameAndVersion = fs::read(package_root_dir(package_dir).join("package.json"))
+        .ok()
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .unwrap_or_default();
+
+    ManifestProvenance {
+        name: manifest
+            .name
+            .unwrap_or_else(|| dir_name(package_dir.parent())),
+        package_version: manifest
+            .version
+            .unwrap_or_else(|| dir_name(Some(package_dir))),
+        tarball: None,
+        integrity: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{STORE_FORMAT_VERSION, ensure_store_layout};
+    use crate::config::SnpmConfig;
+    use crate::store::manifest::{FILE_MANIFEST_FILE, read_file_manifest};
+    use crate::store::pool::pool_path;
+
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn migrates_extracted_packages_into_the_pool() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+        let package_dir = config.packages_dir().join("@scope_pkg").join("1.0.0");
+        fs::create_dir_all(package_dir.join("package")).unwrap();
+        fs::write(
+            package_dir.join("package/package.json"),
+            r#"{"name":"@scope/pkg","version":"1.0.0"}"#,
+        )
+        .unwrap();
+        fs::write(package_dir.join(".snpm_complete"), "").unwrap();
+
+        ensure_store_layout(&config).await.unwrap();
+
+        assert_eq!(
+            fs::read_to_string(config.store_version_path()).unwrap(),
+            format!("{STORE_FORMAT_VERSION}\n")
+        );
+        let index = read_file_manifest(&package_dir).unwrap();
+        assert_eq!(index.name, "@scope/pkg");
+        assert_eq!(index.tarball, None);
+
+        let file = &index.files["package/package.json"];
+        let blob = pool_path(&config.files_dir(), file);
+        assert_eq!(
+            fs::read(&blob).unwrap(),
+            fs::read(package_dir.join("package/package.json")).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn new_stores_start_in_the_current_layout() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+
+        ensure_store_layout(&config).await.unwrap();
+
+        assert!(config.store_version_path().is_file());
+        assert!(!config.files_dir().exists());
+    }
+
+    #[tokio::test]
+    async fn a_failed_migration_leaves_the_layout_unrecorded() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+        let package_dir = config.packages_dir().join("pkg").join("1.0.0");
+        fs::create_dir_all(package_dir.join("package")).unwrap();
+        fs::write(package_dir.join("package/index.js"), "").unwrap();
+        fs::write(package_dir.join(".snpm_complete"), "").unwrap();
+        // The index can't be written over a directory.
+        fs::create_dir_all(package_dir.join(FILE_MANIFEST_FILE)).unwrap();
+
+        ensure_store_layout(&config).await.unwrap();
+
+        assert!(!config.store_version_path().exists());
+    }
+}
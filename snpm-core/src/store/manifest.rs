@@ -0,0 +1,244 @@
+use crate::resolve::PackageId;
+use crate::resolve::types::ResolvedPackage;
+use crate::{Result, SnpmError};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use super::PACKAGE_METADATA_FILE;
+
+pub(crate) const FILE_MANIFEST_FILE: &str = ".snpm-files.json";
+pub(crate) const COMPLETE_MARKER: &str = ".snpm_complete";
+// v2: files carry their mode; the provenance is optional for entries
+// indexed while migrating an older store.
+const FILE_MANIFEST_VERSION: u32 = 2;
+
+/// The per-package index of a store entry: where it came from, and the
+/// SHA-512 and mode of every file snpm unpacked into it. Paths are relative
+/// to the package directory and `/`-separated. Each file is a hard link to
+/// its blob in the content-addressed pool (`SnpmConfig::files_dir`).
+///
+/// snpm's own bookkeeping files (the completion marker, this index and
+/// `.snpm-package-metadata.json`, which is rewritten on store hits) are not
+/// part of the package contents and are left out.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageFileManifest {
+    pub version: u32,
+    pub name: String,
+    pub package_version: String,
+    /// Unknown for entries indexed while migrating an older store, which
+    /// can be verified but not re-downloaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tarball: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<String>,
+    pub files: BTreeMap<String, FileDigest>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDigest {
+    pub size: u64,
+    pub sha512: String,
+    /// Permission bits, e.g. `0o755`.
+    pub mode: u32,
+}
+
+impl FileDigest {
+    pub(crate) fn is_executable(&self) -> bool {
+        self.mode & 0o111 != 0
+    }
+
+    /// Whether both describe the same contents, whatever their modes.
+    pub(crate) fn same_contents(&self, other: &FileDigest) -> bool {
+        self.size == other.size && self.sha512 == other.sha512
+    }
+}
+
+impl PackageFileManifest {
+    /// The package to re-download this entry from, when its tarball is known.
+    pub(crate) fn to_resolved_package(&self) -> Option<ResolvedPackage> {
+        let tarball = self.tarball.clone()?;
+        Some(ResolvedPackage {
+            id: PackageId {
+                name: self.name.clone(),
+                version: self.package_version.clone(),
+            },
+            tarball,
+            integrity: self.integrity.clone(),
+            dependencies: BTreeMap::new(),
+            peer_dependencies: BTreeMap::new(),
+            bundled_dependencies: None,
+            has_bin: false,
+            bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
+        })
+    }
+}
+
+pub(in crate::store) fn record_file_manifest(
+    package_dir: &Path,
+    package: &ResolvedPackage,
+) -> Result<PackageFileManifest> {
+    let manifest = PackageFileManifest {
+        version: FILE_MANIFEST_VERSION,
+        name: package.id.name.clone(),
+        package_version: package.id.version.clone(),
+        tarball: Some(package.tarball.clone()),
+        integrity: package.integrity.clone(),
+        files: collect_file_digests(package_dir)?,
+    };
+    write_file_manifest(package_dir, &manifest)?;
+    Ok(manifest)
+}
+
+pub(in crate::store) fn write_file_manifest(
+    package_dir: &Path,
+    manifest: &PackageFileManifest,
+) -> Result<()> {
+    let path = package_dir.join(FILE_MANIFEST_FILE);
+    let data = serde_json::to_vec(manifest).map_err(|source| SnpmError::SerializeJson {
+        path: path.clone(),
+        reason: source.to_string(),
+    })?;
+    fs::write(&path, data).map_err(|source| SnpmError::WriteFile { path, source })
+}
+
+pub(crate) fn read_file_manifest(package_dir: &Path) -> Option<PackageFileManifest> {
+    let data = fs::read(package_dir.join(FILE_MANIFEST_FILE)).ok()?;
+    let manifest = serde_json::from_slice::<PackageFileManifest>(&data).ok()?;
+    (manifest.version == FILE_MANIFEST_VERSION).then_some(manifest)
+}
+
+/// Where an entry came from, read from an index of any version.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(in crate::store) struct ManifestProvenance {
+    pub name: String,
+    pub package_version: String,
+    #[serde(default)]
+    pub tarball: Option<String>,
+    #[serde(default)]
+    pub integrity: Option<String>,
+}
+
+pub(in crate::store) fn read_manifest_provenance(package_dir: &Path) -> Option<ManifestProvenance> {
+    let data = fs::read(package_dir.join(FILE_MANIFEST_FILE)).ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+pub(in crate::store) fn new_file_manifest(
+    provenance: ManifestProvenance,
+    files: BTreeMap<String, FileDigest>,
+) -> PackageFileManifest {
+    PackageFileManifest {
+        version: FILE_MANIFEST_VERSION,
+        name: provenance.name,
+        package_version: provenance.package_version,
+        tarball: provenance.tarball,
+        integrity: provenance.integrity,
+        files,
+    }
+}
+
+/// Hashes every package file under `package_dir`.
+pub(crate) fn collect_file_digests(package_dir: &Path) -> Result<BTreeMap<String, FileDigest>> {
+    let mut files = BTreeMap::new();
+    collect_directory_digests(package_dir, "", &mut files)?;
+    Ok(files)
+}
+
+pub(crate) fn digest_file(path: &Path) -> Result<FileDigest> {
+    let read_error = |source| SnpmError::ReadFile {
+        path: path.to_path_buf(),
+        source,
+    };
+    let file = fs::File::open(path).map_err(read_error)?;
+    let mode = file_mode(&file.metadata().map_err(read_error)?);
+    digest_reader(file, mode).map_err(read_error)
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o777
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &fs::Metadata) -> u32 {
+    0o644
+}
+
+pub(crate) fn digest_reader<R: Read>(mut reader: R, mode: u32) -> std::io::Result<FileDigest> {
+    let mut hasher = Sha512::new();
+    let mut buffer = [0_u8; 64 * 1024];
+    let mut size = 0_u64;
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        size += read as u64;
+    }
+
+    Ok(FileDigest {
+        size,
+        sha512: hex::encode(hasher.finalize()),
+        mode,
+    })
+}
+
+pub(crate) fn is_bookkeeping_file(relative: &str) -> bool {
+    matches!(relative, COMPLETE_MARKER | FILE_MANIFEST_FILE)
+        || relative.rsplit('/').next() == Some(PACKAGE_METADATA_FILE)
+}
+
+fn collect_directory_digests(
+    directory: &Path,
+    relative: &str,
+    files: &mut BTreeMap<String, FileDigest>,
+) -> Result<()> {
+    let entries = fs::read_dir(directory).map_err(|source| SnpmError::ReadFile {
+        path: directory.to_path_buf(),
+        source,
+    })?;
+
+    for entry in entries {
+        let entry = entry.map_err(|source| SnpmError::ReadFile {
+            path: directory.to_path_buf(),
+            source,
+        })?;
+        let entry_type = entry.file_type().map_err(|source| SnpmError::ReadFile {
+            path: entry.path(),
+            source,
+        })?;
+
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let entry_relative = if relative.is_empty() {
+            name.into_owned()
+        } else {
+            format!("{relative}/{name}")
+        };
+
+        if entry_type.is_dir() {
+            collect_directory_digests(&entry.path(), &entry_relative, files)?;
+        } else if entry_type.is_file() && !is_bookkeeping_file(&entry_relative) {
+            let digest = digest_file(&entry.path())?;
+            files.insert(entry_relative, digest);
+        }
+    }
+
+    Ok(())
+}
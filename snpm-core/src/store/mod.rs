@@ -4,20 +4,37 @@ mod ensure;
 mod fetch;
 mod filesystem;
 mod integrity;
+mod layout;
 mod limits;
 mod local;
+mod manifest;
 mod metadata;
+mod pool;
+mod projects;
 mod remote;
+mod verify;
 
 pub(crate) use cache_index::{
     StoreResidencyIndexView, load_store_residency_index_lossy, persist_store_residency_index,
 };
 pub use ensure::{ensure_package, ensure_package_with_offline};
 pub use filesystem::package_root_dir;
+pub(crate) use filesystem::sanitize_name;
+pub use layout::{STORE_FORMAT_VERSION, ensure_store_layout};
 pub(crate) use limits::{
     acquire_store_task_permit, registry_task_concurrency, store_task_concurrency,
 };
+pub use manifest::{FileDigest, PackageFileManifest};
 pub(crate) use metadata::{
     PACKAGE_METADATA_FILE, read_package_filesystem_shape_lossy, read_package_metadata_lossy,
 };
 pub(in crate::store) use metadata::{persist_package_metadata, read_store_package_metadata_lossy};
+pub(crate) use pool::pooled_file_sources;
+pub use projects::{
+    ProjectRecord, StoreBaseline, forget_projects, lockfile_hash, record_project,
+    registered_projects, store_baseline,
+};
+pub use verify::{
+    PackageHealth, PackageVerification, VerifyMethod, repair_package, stored_package_dirs,
+    stored_package_label, verify_package,
+};
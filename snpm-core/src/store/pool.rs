@@ -0,0 +1,282 @@
+use crate::console;
+use crate::{Result, SnpmError};
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use super::manifest::{FILE_MANIFEST_FILE, FileDigest, digest_file, read_file_manifest};
+
+/// Where the blob for `file` lives in the pool: `<sha512[..2]>/<sha512[2..]>`,
+/// with an `-exec` suffix for executables so a hard link to the blob always
+/// carries the right mode.
+pub(crate) fn pool_path(files_dir: &Path, file: &FileDigest) -> PathBuf {
+    let (head, tail) = file.sha512.split_at(2.min(file.sha512.len()));
+    let name = if file.is_executable() {
+        format!("{tail}-exec")
+    } else {
+        tail.to_string()
+    };
+    files_dir.join(head).join(name)
+}
+
+/// Moves the files of a freshly indexed store entry into the pool.
+///
+/// A file whose contents are already pooled is replaced by a hard link to
+/// the pooled blob, so identical files across packages and versions share
+/// one copy on disk. Otherwise the file itself becomes the pooled blob. If
+/// the filesystem refuses hard links the entry keeps its own copy.
+pub(in crate::store) fn import_package_files(
+    files_dir: &Path,
+    package_dir: &Path,
+    files: &BTreeMap<String, FileDigest>,
+) -> Result<()> {
+    for (relative, file) in files {
+        let blob = pool_path(files_dir, file);
+        let target = package_dir.join(relative);
+
+        if !blob.is_file() {
+            if let Some(parent) = blob.parent() {
+                fs::create_dir_all(parent).map_err(|source| SnpmError::WriteFile {
+                    path: parent.to_path_buf(),
+                    source,
+                })?;
+            }
+
+            match fs::hard_link(&target, &blob) {
+                Ok(()) => continue,
+                // Another install pooled the same contents first.
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => {}
+                Err(error) => {
+                    console::verbose(&format!(
+                        "keeping {} out of the file pool: {error}",
+                        target.display()
+                    ));
+                    continue;
+                }
+            }
+        }
+
+        if same_file(&blob, &target) {
+            continue;
+        }
+
+        replace_with_link(&blob, &target)?;
+    }
+
+    Ok(())
+}
+
+/// Removes pooled blobs of `files` whose contents no longer match their
+/// digest, so repairing a package does not link the corrupt blob back in.
+pub(in crate::store) fn evict_corrupt_blobs(
+    files_dir: &Path,
+    files: &BTreeMap<String, FileDigest>,
+) -> usize {
+    let mut evicted = 0;
+    for file in files.values() {
+        let blob = pool_path(files_dir, file);
+        let intact = match digest_file(&blob) {
+            Ok(actual) => actual.same_contents(file),
+            Err(_) => continue,
+        };
+
+        if !intact && fs::remove_file(&blob).is_ok() {
+            evicted += 1;
+        }
+    }
+    evicted
+}
+
+/// The pooled blob behind each file of the store package rooted at
+/// `package_root`, keyed by the file's path under that root. `None` when
+/// the root is not an indexed store entry.
+pub(crate) fn pooled_file_sources(package_root: &Path) -> Option<HashMap<PathBuf, PathBuf>> {
+    let package_dir = if package_root.join(FILE_MANIFEST_FILE).is_file() {
+        package_root
+    } else {
+        package_root.parent()?
+    };
+    let manifest = read_file_manifest(package_dir)?;
+    let files_dir = files_dir_for(package_dir)?;
+
+    let sources = manifest
+        .files
+        .iter()
+        .filter_map(|(relative, file)| {
+            let path = package_dir.join(relative);
+            if !path.starts_with(package_root) {
+                return None;
+            }
+            let blob = pool_path(&files_dir, file);
+            blob.is_file().then_some((path, blob))
+        })
+        .collect();
+
+    Some(sources)
+}
+
+/// Store entries live at `<data>/packages/<name>/<version>` and the pool at
+/// `<data>/files` (see `SnpmConfig::packages_dir` and `files_dir`), which
+/// lets readers without a config, such as `copy_dir`, find the pool.
+fn files_dir_for(package_dir: &Path) -> Option<PathBuf> {
+    let packages_dir = package_dir.parent()?.parent()?;
+    if packages_dir.file_name()? != "packages" {
+        return None;
+    }
+    Some(packages_dir.parent()?.join("files"))
+}
+
+fn replace_with_link(blob: &Path, target: &Path) -> Result<()> {
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let staging = target.with_file_name(format!(".{file_name}.snpm-pool"));
+    let _ = fs::remove_file(&staging);
+
+    if let Err(error) = fs::hard_link(blob, &staging) {
+        console::verbose(&format!(
+            "keeping a private copy of {}: {error}",
+            target.display()
+        ));
+        return Ok(());
+    }
+
+    fs::rename(&staging, target).map_err(|source| {
+        let _ = fs::remove_file(&staging);
+        SnpmError::WriteFile {
+            path: target.to_path_buf(),
+            source,
+        }
+    })
+}
+
+#[cfg(unix)]
+fn same_file(left: &Path, right: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (fs::metadata(left), fs::metadata(right)) {
+        (Ok(left), Ok(right)) => left.dev() == right.dev() && left.ino() == right.ino(),
+        _ => false,
+    }
+}
+
+/// Without inode numbers on stable std there is no cheap check, so every
+/// file is relinked to its blob; the result is the same, only slower.
+#[cfg(not(unix))]
+fn same_file(_left: &Path, _right: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pool_path, pooled_file_sources};
+    use crate::config::SnpmConfig;
+    use crate::resolve::{PackageId, ResolvedPackage};
+    use crate::store::ensure_package;
+    use crate::store::manifest::read_file_manifest;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::io::Write;
+    use std::path::Path;
+    use tar::{Builder, Header};
+    use tempfile::tempdir;
+
+    fn write_tarball(path: &Path, version: &str) {
+        let manifest = format!(r#"{{"name":"pkg","version":"{version}"}}"#);
+        let mut builder = Builder::new(Vec::new());
+        for (file, content, mode) in [
+            ("package/package.json", manifest.as_bytes(), 0o644),
+            (
+                "package/lib/shared.js",
+                &b"module.exports = 'shared';\n"[..],
+                0o644,
+            ),
+            ("package/bin/cli.js", &b"#!/usr/bin/env node\n"[..], 0o755),
+        ] {
+            let mut header = Header::new_gnu();
+            header.set_path(file).unwrap();
+            header.set_size(content.len() as u64);
+            header.set_mode(mode);
+            header.set_cksum();
+            builder.append(&header, content).unwrap();
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&builder.into_inner().unwrap()).unwrap();
+        fs::write(path, encoder.finish().unwrap()).unwrap();
+    }
+
+    fn package(tarball: &Path, version: &str) -> ResolvedPackage {
+        ResolvedPackage {
+            id: PackageId {
+                name: "pkg".to_string(),
+                version: version.to_string(),
+            },
+            tarball: format!("file://{}", tarball.display()),
+            integrity: None,
+            dependencies: BTreeMap::new(),
+            peer_dependencies: BTreeMap::new(),
+            bundled_dependencies: None,
+            has_bin: false,
+            bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn identical_files_across_versions_share_one_pooled_blob() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            cache_dir: dir.path().join("cache"),
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+        let client = reqwest::Client::new();
+
+        let mut roots = Vec::new();
+        for version in ["1.0.0", "1.0.1"] {
+            let tarball = dir.path().join(format!("pkg-{version}.tgz"));
+            write_tarball(&tarball, version);
+            roots.push(
+                ensure_package(&config, &package(&tarball, version), &client)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        let inode = |path: &Path| fs::metadata(path).unwrap().ino();
+        assert_eq!(
+            inode(&roots[0].join("lib/shared.js")),
+            inode(&roots[1].join("lib/shared.js"))
+        );
+        assert_ne!(
+            inode(&roots[0].join("package.json")),
+            inode(&roots[1].join("package.json"))
+        );
+
+        let index = read_file_manifest(roots[0].parent().unwrap()).unwrap();
+        let cli = &index.files["package/bin/cli.js"];
+        assert_eq!(cli.mode, 0o755);
+        let blob = pool_path(&config.files_dir(), cli);
+        assert!(blob.to_string_lossy().ends_with("-exec"));
+
+        let sources = pooled_file_sources(&roots[0]).unwrap();
+        assert_eq!(sources[&roots[0].join("bin/cli.js")], blob);
+        assert_eq!(sources.len(), 3);
+        assert_eq!(inode(&roots[0].join("bin/cli.js")), inode(&blob));
+    }
+}
@@ -0,0 +1,363 @@
+use crate::{Result, SnpmConfig, SnpmError};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const PROJECT_REGISTRY_VERSION: u32 = 1;
+
+static NEXT_TMP_WRITE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A project (or workspace root) that installed from the store, as recorded
+/// in `SnpmConfig::project_registry_path`. `store prune` keeps whatever the
+/// lockfiles of the projects still on disk reference.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRecord {
+    pub root: PathBuf,
+    pub lockfile: PathBuf,
+    /// `lockfile_hash` of the lockfile the project's `node_modules` was
+    /// installed from. `None` when there was none to hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lockfile_hash: Option<String>,
+}
+
+/// The stored packages and shared virtual store entries that existed when
+/// the registry was created, in `SnpmConfig::store_baseline_path`. Projects
+/// installed before then were never recorded, so `store prune` cannot tell
+/// whether they still use these and keeps them.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreBaseline {
+    /// Package directories, relative to `SnpmConfig::packages_dir`.
+    pub packages: BTreeSet<PathBuf>,
+    /// Entry names in `SnpmConfig::virtual_store_dir`.
+    pub virtual_store_entries: BTreeSet<String>,
+}
+
+impl ProjectRecord {
+    /// Records `root` as installed from the lockfile at `lockfile` as it
+    /// reads now.
+    pub fn new(root: PathBuf, lockfile: PathBuf) -> Self {
+        let lockfile_hash = lockfile_hash(&lockfile);
+        Self {
+            root,
+            lockfile,
+            lockfile_hash,
+        }
+    }
+
+    /// Whether the lockfile changed since the project last installed, say
+    /// after a checkout without a reinstall. Its `node_modules` may then
+    /// still use packages the lockfile no longer lists.
+    pub fn lockfile_changed(&self) -> bool {
+        self.lockfile_hash
+            .as_ref()
+            .is_some_and(|recorded| lockfile_hash(&self.lockfile).as_ref() != Some(recorded))
+    }
+
+    /// Whether the project directory still exists. Projects deleted since
+    /// their last install no longer keep anything in the store alive.
+    pub fn is_live(&self) -> bool {
+        self.root.is_dir()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProjectRegistry {
+    version: u32,
+    projects: Vec<ProjectRecord>,
+}
+
+/// Every recorded project, whether or not it still exists. `None` when no
+/// install has recorded one yet, e.g. a store last used by an snpm that
+/// did not keep the registry.
+pub fn registered_projects(config: &SnpmConfig) -> Option<Vec<ProjectRecord>> {
+    read_registry(config).map(|registry| registry.projects)
+}
+
+/// The store's baseline, empty when the registry was created on an empty
+/// store.
+pub fn store_baseline(config: &SnpmConfig) -> StoreBaseline {
+    fs::read(config.store_baseline_path())
+        .ok()
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .unwrap_or_default()
+}
+
+/// SHA-256 of the lockfile at `path`, `None` when it cannot be read.
+pub fn lockfile_hash(path: &Path) -> Option<String> {
+    let data = fs::read(path).ok()?;
+    Some(format!("{:x}", Sha256::digest(&data)))
+}
+
+/// Records that `record.root` installed from the store, replacing its
+/// previous record. Leaves the file untouched when nothing changed, so hot
+/// installs stay read-only. Creating the registry first writes the store's
+/// baseline.
+pub fn record_project(config: &SnpmConfig, record: ProjectRecord) -> Result<()> {
+    let _lock = lock_registry(config)?;
+    let mut registry = match read_registry(config) {
+        Some(registry) => registry,
+        None => {
+            write_baseline(config)?;
+            ProjectRegistry::default()
+        }
+    };
+
+    match registry
+        .projects
+        .iter_mut()
+        .find(|existing| existing.root == record.root)
+    {
+        Some(existing) if *existing == record => return Ok(()),
+        Some(existing) => *existing = record,
+        None => registry.projects.push(record),
+    }
+
+    registry
+        .projects
+        .sort_by(|left, right| left.root.cmp(&right.root));
+    write_registry(config, &registry)
+}
+
+/// Drops the records of `roots`, used once `store prune` found them gone.
+pub fn forget_projects(config: &SnpmConfig, roots: &[PathBuf]) -> Result<()> {
+    if roots.is_empty() {
+        return Ok(());
+    }
+
+    let _lock = lock_registry(config)?;
+    let Some(mut registry) = read_registry(config) else {
+        return Ok(());
+    };
+
+    let before = registry.projects.len();
+    registry
+        .projects
+        .retain(|record| !roots.contains(&record.root));
+    if registry.projects.len() == before {
+        return Ok(());
+    }
+
+    write_registry(config, &registry)
+}
+
+/// Locks the registry for a read-modify-write until the returned file is
+/// dropped. The lock is on a file next to the registry, so installs in
+/// other processes (and other threads, which open it separately) wait
+/// instead of overwriting each other's records.
+fn lock_registry(config: &SnpmConfig) -> Result<File> {
+    let path = config.project_registry_path().with_extension("lock");
+    let parent = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(parent).map_err(|source| SnpmError::WriteFile {
+        path: parent.to_path_buf(),
+        source,
+    })?;
+
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .map_err(|source| SnpmError::WriteFile {
+            path: path.clone(),
+            source,
+        })?;
+    file.lock()
+        .map_err(|source| SnpmError::WriteFile { path, source })?;
+    Ok(file)
+}
+
+fn read_registry(config: &SnpmConfig) -> Option<ProjectRegistry> {
+    let data = fs::read(config.project_registry_path()).ok()?;
+    let registry = serde_json::from_slice::<ProjectRegistry>(&data).ok()?;
+    (registry.version == PROJECT_REGISTRY_VERSION).then_some(registry)
+}
+
+/// Adds everything in the store now to the baseline. Merging keeps a
+/// registry that was lost or unreadable from freeing what an earlier
+/// baseline protected.
+fn write_baseline(config: &SnpmConfig) -> Result<()> {
+    let mut baseline = store_baseline(config);
+    let packages_dir = config.packages_dir();
+    baseline
+        .packages
+        .extend(
+            super::stored_package_dirs(config)?
+                .into_iter()
+                .filter_map(|package_dir| {
+                    Some(package_dir.strip_prefix(&packages_dir).ok()?.to_path_buf())
+                }),
+        );
+    if let Ok(entries) = fs::read_dir(config.virtual_store_dir()) {
+        baseline.virtual_store_entries.extend(
+            entries
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| !name.starts_with('.')),
+        );
+    }
+
+    if baseline == StoreBaseline::default() {
+        return Ok(());
+    }
+    let path = config.store_baseline_path();
+    let data = serde_json::to_vec(&baseline).map_err(|source| SnpmError::SerializeJson {
+        path: path.clone(),
+        reason: source.to_string(),
+    })?;
+    write_atomically(path, data)
+}
+
+fn write_registry(config: &SnpmConfig, registry: &ProjectRegistry) -> Result<()> {
+    let path = config.project_registry_path();
+    let registry = ProjectRegistry {
+        version: PROJECT_REGISTRY_VERSION,
+        projects: registry.projects.clone(),
+    };
+    let data = serde_json::to_vec_pretty(&registry).map_err(|source| SnpmError::SerializeJson {
+        path: path.clone(),
+        reason: source.to_string(),
+    })?;
+
+    write_atomically(path, data)
+}
+
+fn write_atomically(path: PathBuf, data: Vec<u8>) -> Result<()> {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(parent).map_err(|source| SnpmError::WriteFile {
+        path: parent.to_path_buf(),
+        source,
+    })?;
+
+    let tmp_path = parent.join(format!(
+        ".projects-v1-{}.{}.tmp",
+        std::process::id(),
+        NEXT_TMP_WRITE_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&tmp_path, data).map_err(|source| SnpmError::WriteFile {
+        path: tmp_path.clone(),
+        source,
+    })?;
+
+    fs::rename(&tmp_path, &path).map_err(|source| {
+        fs::remove_file(&tmp_path).ok();
+        SnpmError::WriteFile { path, source }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ProjectRecord, forget_projects, record_project, registered_projects, store_baseline,
+    };
+    use crate::config::SnpmConfig;
+
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+
+    fn record(root: &str, lockfile: &str) -> ProjectRecord {
+        ProjectRecord {
+            root: PathBuf::from(root),
+            lockfile: PathBuf::from(root).join(lockfile),
+            lockfile_hash: None,
+        }
+    }
+
+    #[test]
+    fn records_replace_and_forget_projects() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+
+        assert_eq!(registered_projects(&config), None);
+
+        record_project(&config, record("/work/b", "snpm-lock.yaml")).unwrap();
+        record_project(&config, record("/work/a", "snpm-lock.yaml")).unwrap();
+        record_project(&config, record("/work/b", "other-lock.yaml")).unwrap();
+        assert_eq!(
+            registered_projects(&config).unwrap(),
+            vec![
+                record("/work/a", "snpm-lock.yaml"),
+                record("/work/b", "other-lock.yaml")
+            ]
+        );
+
+        forget_projects(&config, &[PathBuf::from("/work/a")]).unwrap();
+        assert_eq!(
+            registered_projects(&config).unwrap(),
+            vec![record("/work/b", "other-lock.yaml")]
+        );
+    }
+
+    #[test]
+    fn concurrent_records_all_land() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+
+        std::thread::scope(|scope| {
+            for index in 0..8 {
+                let config = &config;
+                scope.spawn(move || {
+                    record_project(config, record(&format!("/work/{index}"), "snpm-lock.yaml"))
+                        .unwrap();
+                });
+            }
+        });
+
+        assert_eq!(registered_projects(&config).unwrap().len(), 8);
+    }
+
+    #[test]
+    fn notices_a_lockfile_changed_since_the_install() {
+        let dir = tempdir().unwrap();
+        let lockfile = dir.path().join("snpm-lock.yaml");
+        fs::write(&lockfile, "version: 1\n").unwrap();
+
+        let record = ProjectRecord::new(dir.path().to_path_buf(), lockfile.clone());
+        assert!(!record.lockfile_changed());
+
+        fs::write(&lockfile, "version: 1\npackages: {}\n").unwrap();
+        assert!(record.lockfile_changed());
+    }
+
+    #[test]
+    fn creating_the_registry_records_the_store_baseline() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+        let package = config.packages_dir().join("pkg/1.0.0");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join(".snpm_complete"), "").unwrap();
+        fs::create_dir_all(config.virtual_store_dir().join("pkg@1.0.0-aa")).unwrap();
+        fs::create_dir_all(config.virtual_store_dir().join(".staging")).unwrap();
+
+        record_project(&config, record("/work/a", "snpm-lock.yaml")).unwrap();
+        // Later entries belong to recorded projects.
+        fs::create_dir_all(config.virtual_store_dir().join("pkg@2.0.0-bb")).unwrap();
+        record_project(&config, record("/work/b", "snpm-lock.yaml")).unwrap();
+
+        let baseline = store_baseline(&config);
+        assert_eq!(
+            baseline.packages,
+            BTreeSet::from([PathBuf::from("pkg/1.0.0")])
+        );
+        assert_eq!(
+            baseline.virtual_store_entries,
+            BTreeSet::from(["pkg@1.0.0-aa".to_string()])
+        );
+    }
+}
@@ -0,0 +1,441 @@
+use crate::console;
+use crate::{Result, SnpmConfig, SnpmError};
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::archive::tarball_file_digests;
+use super::ensure::ensure_package;
+use super::fetch::cached_blob_paths;
+use super::integrity::{IntegritySpec, verify_integrity_file};
+use super::manifest::{
+    COMPLETE_MARKER, FileDigest, PackageFileManifest, digest_file, is_bookkeeping_file,
+    read_file_manifest,
+};
+use super::pool::evict_corrupt_blobs;
+
+/// What a store entry's files were compared against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyMethod {
+    /// The cached tarball, after re-checking it against its integrity.
+    Tarball,
+    /// The file manifest recorded when the entry was extracted.
+    Manifest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackageHealth {
+    Intact(VerifyMethod),
+    /// Files that are missing or whose contents changed, relative to the
+    /// package directory.
+    Corrupt {
+        method: VerifyMethod,
+        problems: Vec<String>,
+    },
+    /// Extracted before snpm recorded file manifests, with no cached
+    /// tarball to compare against.
+    Unverifiable,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageVerification {
+    pub name: String,
+    pub version: String,
+    pub package_dir: PathBuf,
+    pub health: PackageHealth,
+    manifest: Option<PackageFileManifest>,
+}
+
+impl PackageVerification {
+    pub fn is_corrupt(&self) -> bool {
+        matches!(self.health, PackageHealth::Corrupt { .. })
+    }
+}
+
+/// Every completed package directory under `packages_dir()`.
+pub fn stored_package_dirs(config: &SnpmConfig) -> Result<Vec<PathBuf>> {
+    let packages_dir = config.packages_dir();
+    if !packages_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(&packages_dir).map_err(|source| SnpmError::ReadFile {
+        path: packages_dir.clone(),
+        source,
+    })?;
+
+    let mut dirs = Vec::new();
+    for name_entry in entries.flatten() {
+        let Ok(version_entries) = fs::read_dir(name_entry.path()) else {
+            continue;
+        };
+
+        for version_entry in version_entries.flatten() {
+            let version_path = version_entry.path();
+            let aside = version_entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(".snpm-");
+            if !aside && version_path.join(COMPLETE_MARKER).is_file() {
+                dirs.push(version_path);
+            }
+        }
+    }
+
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// `name@version` of the store entry at `package_dir`, from its index when
+/// it has one.
+pub fn stored_package_label(package_dir: &Path) -> String {
+    let (name, version) = match read_file_manifest(package_dir) {
+        Some(manifest) => (manifest.name, manifest.package_version),
+        None => directory_label(package_dir),
+    };
+    format!("{name}@{version}")
+}
+
+/// Re-hashes the files of the store entry at `package_dir`.
+///
+/// When the entry's tarball is still in the blob cache and matches its
+/// integrity, the files are compared with the tarball's contents. A blob
+/// that no longer matches is removed so a repair downloads it again, and
+/// the entry falls back to its extraction-time file manifest.
+pub fn verify_package(config: &SnpmConfig, package_dir: &Path) -> Result<PackageVerification> {
+    let manifest = read_file_manifest(package_dir);
+    let (name, version) = match &manifest {
+        Some(manifest) => (manifest.name.clone(), manifest.package_version.clone()),
+        None => directory_label(package_dir),
+    };
+
+    let health = match &manifest {
+        Some(manifest) => {
+            let (method, expected) = match verified_tarball_digests(config, manifest)? {
+                Some(expected) => (VerifyMethod::Tarball, expected),
+                None => (VerifyMethod::Manifest, manifest.files.clone()),
+            };
+
+            let problems = compare_files(package_dir, &expected)?;
+            if problems.is_empty() {
+                PackageHealth::Intact(method)
+            } else {
+                PackageHealth::Corrupt { method, problems }
+            }
+        }
+        None => PackageHealth::Unverifiable,
+    };
+
+    Ok(PackageVerification {
+        name,
+        version,
+        package_dir: package_dir.to_path_buf(),
+        health,
+        manifest,
+    })
+}
+
+/// Replaces a corrupt store entry with a fresh download of its tarball and
+/// returns the new package root. The corrupt directory is moved aside
+/// first and restored if the download fails, so a failed repair leaves the
+/// store as it found it.
+pub async fn repair_package(
+    config: &SnpmConfig,
+    verification: &PackageVerification,
+    client: &reqwest::Client,
+) -> Result<PathBuf> {
+    let Some(package) = verification
+        .manifest
+        .as_ref()
+        .and_then(PackageFileManifest::to_resolved_package)
+    else {
+        return Err(SnpmError::Internal {
+            reason: format!(
+                "cannot repair {}@{}: its index does not record where it came from",
+                verification.name, verification.version
+            ),
+        });
+    };
+
+    let package_dir = &verification.package_dir;
+    let aside = aside_path(package_dir);
+    if aside.exists() {
+        fs::remove_dir_all(&aside).map_err(|source| SnpmError::WriteFile {
+            path: aside.clone(),
+            source,
+        })?;
+    }
+    fs::rename(package_dir, &aside).map_err(|source| SnpmError::WriteFile {
+        path: package_dir.clone(),
+        source,
+    })?;
+
+    if let Some(manifest) = &verification.manifest {
+        evict_corrupt_blobs(&config.files_dir(), &manifest.files);
+    }
+
+    match ensure_package(config, &package, client).await {
+        Ok(root) => {
+            if let Err(error) = fs::remove_dir_all(&aside) {
+                console::verbose(&format!(
+                    "failed to remove replaced store entry {}: {error}",
+                    aside.display()
+                ));
+            }
+            Ok(root)
+        }
+        Err(error) => {
+            let _ = fs::remove_dir_all(package_dir);
+            let _ = fs::rename(&aside, package_dir);
+            Err(error)
+        }
+    }
+}
+
+fn verified_tarball_digests(
+    config: &SnpmConfig,
+    manifest: &PackageFileManifest,
+) -> Result<Option<BTreeMap<String, FileDigest>>> {
+    let integrity = manifest.integrity.as_deref();
+    let (Some(tarball), Some(spec)) = (&manifest.tarball, IntegritySpec::parse(integrity)) else {
+        return Ok(None);
+    };
+
+    let Some(blob) = cached_blob_paths(config, tarball, Some(&spec))?
+        .into_iter()
+        .find(|path| path.is_file())
+    else {
+        return Ok(None);
+    };
+
+    if verify_integrity_file(tarball, integrity, &blob).is_err() {
+        console::verbose(&format!(
+            "cached tarball for {}@{} no longer matches its integrity; removing {}",
+            manifest.name,
+            manifest.package_version,
+            blob.display()
+        ));
+        let _ = fs::remove_file(&blob);
+        return Ok(None);
+    }
+
+    let mut digests = tarball_file_digests(&blob)?;
+    digests.retain(|relative, _| !is_bookkeeping_file(relative));
+    Ok(Some(digests))
+}
+
+fn compare_files(
+    package_dir: &Path,
+    expected: &BTreeMap<String, FileDigest>,
+) -> Result<Vec<String>> {
+    let mut problems = Vec::new();
+
+    for (relative, digest) in expected {
+        let path = package_dir.join(relative);
+        if !path.is_file() {
+            problems.push(format!("missing {relative}"));
+            continue;
+        }
+
+        if !digest_file(&path)?.same_contents(digest) {
+            problems.push(format!("modified {relative}"));
+        }
+    }
+
+    Ok(problems)
+}
+
+fn directory_label(package_dir: &Path) -> (String, String) {
+    let file_name = |path: Option<&Path>| {
+        path.and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+
+    (
+        file_name(package_dir.parent()),
+        file_name(Some(package_dir)),
+    )
+}
+
+fn aside_path(package_dir: &Path) -> PathBuf {
+    let file_name = package_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    package_dir.with_file_name(format!(".snpm-repair-{file_name}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PackageHealth, VerifyMethod, repair_package, stored_package_dirs, verify_package};
+    use crate::config::SnpmConfig;
+    use crate::resolve::{PackageId, ResolvedPackage};
+    use crate::store::ensure_package;
+    use crate::store::fetch::cached_blob_paths;
+    use crate::store::integrity::IntegritySpec;
+    use crate::store::manifest::FILE_MANIFEST_FILE;
+
+    use base64::Engine;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use sha2::{Digest, Sha512};
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::io::Write;
+    use std::path::Path;
+    use tar::{Builder, Header};
+    use tempfile::tempdir;
+
+    fn build_tarball() -> Vec<u8> {
+        let mut builder = Builder::new(Vec::new());
+        for (path, content) in [
+            (
+                "package/package.json",
+                &br#"{"name":"pkg","version":"1.0.0"}"#[..],
+            ),
+            ("package/index.js", &b"module.exports = 1;\n"[..]),
+        ] {
+            let mut header = Header::new_gnu();
+            header.set_path(path).unwrap();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, content).unwrap();
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&builder.into_inner().unwrap()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn package(tarball: &Path, integrity: &str) -> ResolvedPackage {
+        ResolvedPackage {
+            id: PackageId {
+                name: "pkg".to_string(),
+                version: "1.0.0".to_string(),
+            },
+            tarball: format!("file://{}", tarball.display()),
+            integrity: Some(integrity.to_string()),
+            dependencies: BTreeMap::new(),
+            peer_dependencies: BTreeMap::new(),
+            bundled_dependencies: None,
+            has_bin: false,
+            bin: None,
+            os: Vec::new(),
+            cpu: Vec::new(),
+            libc: Vec::new(),
+            engines: BTreeMap::new(),
+            deprecated: None,
+            has_install_script: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn detects_and_repairs_modified_files() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            cache_dir: dir.path().join("cache"),
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+        let bytes = build_tarball();
+        let integrity = format!(
+            "sha512-{}",
+            base64::engine::general_purpose::STANDARD.encode(Sha512::digest(&bytes))
+        );
+        let tarball = dir.path().join("pkg.tgz");
+        fs::write(&tarball, &bytes).unwrap();
+
+        let client = reqwest::Client::new();
+        let root = ensure_package(&config, &package(&tarball, &integrity), &client)
+            .await
+            .unwrap();
+        let package_dir = stored_package_dirs(&config).unwrap().remove(0);
+
+        let verification = verify_package(&config, &package_dir).unwrap();
+        assert_eq!(
+            verification.health,
+            PackageHealth::Intact(VerifyMethod::Manifest)
+        );
+
+        fs::write(root.join("index.js"), "module.exports = 2;\n").unwrap();
+        let verification = verify_package(&config, &package_dir).unwrap();
+        assert_eq!(
+            verification.health,
+            PackageHealth::Corrupt {
+                method: VerifyMethod::Manifest,
+                problems: vec!["modified package/index.js".to_string()],
+            }
+        );
+
+        let repaired = repair_package(&config, &verification, &client)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(repaired.join("index.js")).unwrap(),
+            "module.exports = 1;\n"
+        );
+        assert_eq!(
+            verify_package(&config, &package_dir).unwrap().health,
+            PackageHealth::Intact(VerifyMethod::Manifest)
+        );
+        assert_eq!(stored_package_dirs(&config).unwrap(), vec![package_dir]);
+    }
+
+    #[tokio::test]
+    async fn compares_against_the_cached_tarball_when_it_still_verifies() {
+        let dir = tempdir().unwrap();
+        let config = SnpmConfig {
+            cache_dir: dir.path().join("cache"),
+            data_dir: dir.path().join("data"),
+            ..SnpmConfig::for_tests()
+        };
+        let bytes = build_tarball();
+        let integrity = format!(
+            "sha512-{}",
+            base64::engine::general_purpose::STANDARD.encode(Sha512::digest(&bytes))
+        );
+        let tarball = dir.path().join("pkg.tgz");
+        fs::write(&tarball, &bytes).unwrap();
+
+        let client = reqwest::Client::new();
+        let package = package(&tarball, &integrity);
+        let root = ensure_package(&config, &package, &client).await.unwrap();
+        let package_dir = stored_package_dirs(&config).unwrap().remove(0);
+
+        let spec = IntegritySpec::parse(Some(&integrity)).unwrap();
+        let blob = cached_blob_paths(&config, &package.tarball, Some(&spec))
+            .unwrap()
+            .remove(0);
+        fs::create_dir_all(blob.parent().unwrap()).unwrap();
+        fs::write(&blob, &bytes).unwrap();
+
+        fs::remove_file(root.join("index.js")).unwrap();
+        assert_eq!(
+            verify_package(&config, &package_dir).unwrap().health,
+            PackageHealth::Corrupt {
+                method: VerifyMethod::Tarball,
+                problems: vec!["missing package/index.js".to_string()],
+            }
+        );
+
+        fs::write(&blob, b"not the tarball").unwrap();
+        let verification = verify_package(&config, &package_dir).unwrap();
+        assert!(matches!(
+            verification.health,
+            PackageHealth::Corrupt {
+                method: VerifyMethod::Manifest,
+                ..
+            }
+        ));
+        assert!(!blob.exists());
+
+        fs::remove_file(package_dir.join(FILE_MANIFEST_FILE)).unwrap();
+        assert_eq!(
+            verify_package(&config, &package_dir).unwrap().health,
+            PackageHealth::Unverifiable
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::registry::RegistryPackage;
+use snpm_semver::{Version, parse_version};
+use time::OffsetDateTime;
+
+use super::select::published_at;
+
+/// `package` as the registry served it at `cutoff` (`--before`): versions
+/// published later, or with no recorded publish time, are dropped. A
+/// dist-tag that pointed past the cutoff moves to the highest version
+/// left — a stable one when the tag pointed at a stable release — and is
+/// dropped when nothing is left for it.
+pub(crate) fn published_before(
+    mut package: RegistryPackage,
+    cutoff: OffsetDateTime,
+) -> RegistryPackage {
+    let unpublished: Vec<String> = package
+        .versions
+        .keys()
+        .filter(|version| {
+            published_at(&package, version).is_none_or(|published| published > cutoff)
+        })
+        .cloned()
+        .collect();
+    for version in unpublished {
+        package.versions.remove(&version);
+    }
+
+    let remaining: Vec<(Version, String)> = package
+        .versions
+        .keys()
+        .filter_map(|version| Some((parse_version(version).ok()?, version.clone())))
+        .collect();
+
+    package.dist_tags.retain(|_, target| {
+        if package.versions.contains_key(target.as_str()) {
+            return true;
+        }
+
+        let stable_only = parse_version(target).is_ok_and(|version| version.pre.is_empty());
+        let fallback = remaining
+            .iter()
+            .filter(|(version, _)| !stable_only || version.pre.is_empty())
+            .max_by(|left, right| left.0.cmp(&right.0));
+
+        match fallback {
+            Some((_, version)) => {
+                *target = version.clone();
+                true
+            }
+            None => false,
+        }
+    });
+
+    package
+}
@@ -1,9 +1,12 @@
+mod before;
 mod ranges;
 mod select;
+mod trust;
 
+pub(crate) use before::published_before;
 pub use ranges::parse_range_set;
-pub use select::select_version;
-pub(crate) use select::version_age_days;
+pub use select::{VersionPreference, select_version, select_version_with};
+pub(crate) use select::{published_at, version_age_days};
 
 #[cfg(test)]
 mod tests;
@@ -4,16 +4,58 @@ use snpm_semver::{Version, parse_version};
 use time::OffsetDateTime;
 use time::format_description::well_known::Rfc3339;
 
+use std::collections::BTreeSet;
+
 use super::ranges::parse_range_set;
+use super::trust::{Downgrade, TrustCheck};
+
+/// Which of the versions matching a range [`select_version_with`] picks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionPreference {
+    Highest,
+    Lowest,
+    /// The highest version published at or before the cutoff, or the
+    /// lowest matching version when none was. Versions without a publish
+    /// time never count as published before it.
+    PublishedBy(OffsetDateTime),
+}
 
 pub fn select_version(
     name: &str,
     range: &str,
     package: &RegistryPackage,
     min_age_days: Option<u32>,
+    no_downgrade: Option<&BTreeSet<String>>,
+    force: bool,
+) -> Result<RegistryVersion> {
+    select_version_with(
+        name,
+        range,
+        package,
+        min_age_days,
+        no_downgrade,
+        force,
+        VersionPreference::Highest,
+    )
+}
+
+/// `no_downgrade` holds the `trustPolicyExclude` entries when
+/// `trustPolicy: no-downgrade` is on; versions that lost the provenance
+/// attestation an earlier version had are then skipped like versions
+/// younger than `min_age_days`.
+pub fn select_version_with(
+    name: &str,
+    range: &str,
+    package: &RegistryPackage,
+    min_age_days: Option<u32>,
+    no_downgrade: Option<&BTreeSet<String>>,
     force: bool,
+    preference: VersionPreference,
 ) -> Result<RegistryVersion> {
     let trimmed = range.trim();
+    let trust = no_downgrade
+        .filter(|_| !force)
+        .map(|exclusions| TrustCheck::new(name, package, exclusions));
 
     if let Some(tag_version) = package.dist_tags.get(trimmed)
         && let Some(meta) = package.versions.get(tag_version)
@@ -35,13 +77,29 @@ pub fn select_version(
             });
         }
 
+        if let Some(downgrade) = trust
+            .as_ref()
+            .and_then(|trust| trust.downgrade(&meta.version))
+        {
+            return Err(SnpmError::ResolutionFailed {
+                name: name.to_string(),
+                range: range.to_string(),
+                reason: format!(
+                    "dist-tag {} points to a trust downgrade: {}",
+                    range,
+                    downgrade.describe(name)
+                ),
+            });
+        }
+
         return Ok(meta.clone());
     }
 
     let ranges = parse_range_set(name, range)?;
-    let mut selected: Option<(Version, RegistryVersion)> = None;
+    let mut candidates: Vec<(Version, &RegistryVersion)> = Vec::new();
     let now = OffsetDateTime::now_utc();
     let mut latest_rejected: Option<(Version, String, i64)> = None;
+    let mut latest_downgrade: Option<(Version, Downgrade)> = None;
 
     for (version_str, meta) in package.versions.iter() {
         let parsed = parse_version(version_str);
@@ -62,15 +120,23 @@ pub fn select_version(
                 continue;
             }
 
-            match &selected {
-                Some((best, _)) if ver <= *best => {}
-                _ => selected = Some((ver, meta.clone())),
+            if let Some(downgrade) = trust
+                .as_ref()
+                .and_then(|trust| trust.downgrade(version_str))
+            {
+                match &latest_downgrade {
+                    Some((latest, _)) if ver <= *latest => {}
+                    _ => latest_downgrade = Some((ver, downgrade)),
+                }
+                continue;
             }
+
+            candidates.push((ver, meta));
         }
     }
 
-    if let Some((_, meta)) = selected {
-        Ok(meta)
+    if let Some(meta) = pick_candidate(package, candidates, preference) {
+        Ok(meta.clone())
     } else {
         if let Some(min_days) = min_age_days
             && !force
@@ -85,6 +151,17 @@ pub fn select_version(
             });
         }
 
+        if let Some((_, downgrade)) = latest_downgrade {
+            return Err(SnpmError::ResolutionFailed {
+                name: name.to_string(),
+                range: range.to_string(),
+                reason: format!(
+                    "every matching version is a trust downgrade; the latest, {}",
+                    downgrade.describe(name)
+                ),
+            });
+        }
+
         Err(SnpmError::ResolutionFailed {
             name: name.to_string(),
             range: range.to_string(),
@@ -96,6 +173,30 @@ pub fn select_version(
     }
 }
 
+fn pick_candidate<'a>(
+    package: &RegistryPackage,
+    candidates: Vec<(Version, &'a RegistryVersion)>,
+    preference: VersionPreference,
+) -> Option<&'a RegistryVersion> {
+    let picked = match preference {
+        VersionPreference::Highest => candidates.into_iter().max_by(|a, b| a.0.cmp(&b.0)),
+        VersionPreference::Lowest => candidates.into_iter().min_by(|a, b| a.0.cmp(&b.0)),
+        VersionPreference::PublishedBy(cutoff) => {
+            let (published, later): (Vec<_>, Vec<_>) =
+                candidates.into_iter().partition(|(_, meta)| {
+                    published_at(package, &meta.version).is_some_and(|time| time <= cutoff)
+                });
+
+            match published.into_iter().max_by(|a, b| a.0.cmp(&b.0)) {
+                Some(candidate) => Some(candidate),
+                None => later.into_iter().min_by(|a, b| a.0.cmp(&b.0)),
+            }
+        }
+    };
+
+    picked.map(|(_, meta)| meta)
+}
+
 /// A short hint listing the newest available versions, so a failed
 /// resolve tells the user what they *could* pick instead of a bare
 /// "not found".
@@ -136,8 +237,13 @@ pub(crate) fn version_age_days(
     version: &str,
     now: OffsetDateTime,
 ) -> Option<i64> {
-    let time_str = package.time.get(version)?;
-    let published = OffsetDateTime::parse(time_str, &Rfc3339).ok()?;
+    let published = published_at(package, version)?;
     let age = now - published;
     Some(age.whole_days())
 }
+
+/// When `version` was published, from the packument's `time` map.
+pub(crate) fn published_at(package: &RegistryPackage, version: &str) -> Option<OffsetDateTime> {
+    let time_str = package.time.get(version)?;
+    OffsetDateTime::parse(time_str, &Rfc3339).ok()
+}
@@ -1,6 +1,7 @@
 use super::*;
 use crate::registry::{RegistryPackage, RegistryVersion};
 use snpm_semver::Version;
+use std::collections::BTreeSet;
 use time::format_description::well_known::Rfc3339;
 use time::{Duration, OffsetDateTime};
 
@@ -25,11 +26,16 @@ fn selects_custom_dist_tag() {
         dist: crate::registry::RegistryDist {
             tarball: "Url".to_string(),
             integrity: None,
+            signatures: Vec::new(),
+            attestations: None,
         },
         os: vec![],
         cpu: vec![],
         libc: vec![],
+        engines: Default::default(),
+        deprecated: None,
         bin: None,
+        has_install_script: false,
     };
     versions.insert("1.0.0".to_string(), version_meta);
 
@@ -42,7 +48,7 @@ fn selects_custom_dist_tag() {
         dist_tags,
     };
 
-    let result = select_version("pkg", "ts5.9", &package, None, false);
+    let result = select_version("pkg", "ts5.9", &package, None, None, false);
     assert!(result.is_ok());
     assert_eq!(result.unwrap().version, "1.0.0");
 }
@@ -59,11 +65,16 @@ fn make_version(ver: &str) -> RegistryVersion {
         dist: crate::registry::RegistryDist {
             tarball: format!("https://example.com/{}.tgz", ver),
             integrity: None,
+            signatures: Vec::new(),
+            attestations: None,
         },
         os: vec![],
         cpu: vec![],
         libc: vec![],
+        engines: Default::default(),
+        deprecated: None,
         bin: None,
+        has_install_script: false,
     }
 }
 
@@ -109,49 +120,140 @@ fn make_package_with_version_ages(versions: &[(&str, i64)]) -> RegistryPackage {
 #[test]
 fn selects_highest_matching_version() {
     let package = make_package_with_versions(&["1.0.0", "1.1.0", "1.2.0", "2.0.0"]);
-    let result = select_version("pkg", "^1.0.0", &package, None, false).unwrap();
+    let result = select_version("pkg", "^1.0.0", &package, None, None, false).unwrap();
     assert_eq!(result.version, "1.2.0");
 }
 
 #[test]
 fn selects_exact_version() {
     let package = make_package_with_versions(&["1.0.0", "1.1.0", "1.2.0"]);
-    let result = select_version("pkg", "1.1.0", &package, None, false).unwrap();
+    let result = select_version("pkg", "1.1.0", &package, None, None, false).unwrap();
     assert_eq!(result.version, "1.1.0");
 }
 
 #[test]
 fn returns_error_for_no_match() {
     let package = make_package_with_versions(&["1.0.0", "1.1.0"]);
-    let result = select_version("pkg", "^2.0.0", &package, None, false);
+    let result = select_version("pkg", "^2.0.0", &package, None, None, false);
     assert!(result.is_err());
 }
 
 #[test]
 fn selects_latest_dist_tag() {
     let package = make_package_with_versions(&["1.0.0", "2.0.0"]);
-    let result = select_version("pkg", "latest", &package, None, false).unwrap();
+    let result = select_version("pkg", "latest", &package, None, None, false).unwrap();
     assert_eq!(result.version, "2.0.0");
 }
 
+#[test]
+fn lowest_preference_selects_lowest_matching_version() {
+    let package = make_package_with_versions(&["0.9.0", "1.0.0", "1.1.0", "1.2.0"]);
+    let result = select_version_with(
+        "pkg",
+        "^1.0.0",
+        &package,
+        None,
+        None,
+        false,
+        VersionPreference::Lowest,
+    )
+    .unwrap();
+    assert_eq!(result.version, "1.0.0");
+}
+
+#[test]
+fn published_by_preference_caps_at_the_cutoff() {
+    let package = make_package_with_version_ages(&[("1.0.0", 30), ("1.1.0", 20), ("1.2.0", 1)]);
+    let cutoff = OffsetDateTime::now_utc() - Duration::days(10);
+    let result = select_version_with(
+        "pkg",
+        "^1.0.0",
+        &package,
+        None,
+        None,
+        false,
+        VersionPreference::PublishedBy(cutoff),
+    )
+    .unwrap();
+    assert_eq!(result.version, "1.1.0");
+}
+
+#[test]
+fn published_by_preference_falls_back_to_lowest_after_the_cutoff() {
+    let package =
+        make_package_with_version_ages(&[("1.0.0", 30), ("1.1.0", 20), ("2.0.0", 5), ("2.1.0", 1)]);
+    let cutoff = OffsetDateTime::now_utc() - Duration::days(10);
+    let result = select_version_with(
+        "pkg",
+        "^2.0.0",
+        &package,
+        None,
+        None,
+        false,
+        VersionPreference::PublishedBy(cutoff),
+    )
+    .unwrap();
+    assert_eq!(result.version, "2.0.0");
+}
+
+#[test]
+fn published_before_hides_later_versions_and_moves_dist_tags_back() {
+    let mut package = make_package_with_version_ages(&[
+        ("1.0.0", 30),
+        ("1.1.0", 20),
+        ("2.0.0-beta.1", 15),
+        ("2.0.0", 5),
+    ]);
+    package
+        .dist_tags
+        .insert("next".to_string(), "2.0.0-beta.1".to_string());
+    package
+        .dist_tags
+        .insert("canary".to_string(), "2.0.0".to_string());
+    package
+        .versions
+        .insert("0.1.0".to_string(), make_version("0.1.0"));
+
+    let cutoff = OffsetDateTime::now_utc() - Duration::days(10);
+    let package = published_before(package, cutoff);
+
+    let versions: Vec<&str> = package.versions.keys().map(String::as_str).collect();
+    assert_eq!(versions, ["1.0.0", "1.1.0", "2.0.0-beta.1"]);
+    assert_eq!(package.dist_tags["latest"], "1.1.0");
+    assert_eq!(package.dist_tags["next"], "2.0.0-beta.1");
+    assert_eq!(package.dist_tags["canary"], "1.1.0");
+
+    let result = select_version("pkg", "^1.0.0 || ^2.0.0", &package, None, None, false).unwrap();
+    assert_eq!(result.version, "1.1.0");
+}
+
+#[test]
+fn published_before_drops_dist_tags_with_nothing_left() {
+    let package = make_package_with_version_ages(&[("1.0.0", 5)]);
+    let package = published_before(package, OffsetDateTime::now_utc() - Duration::days(10));
+
+    assert!(package.versions.is_empty());
+    assert!(package.dist_tags.is_empty());
+}
+
 #[test]
 fn min_package_age_skips_young_matching_versions() {
     let package = make_package_with_version_ages(&[("1.0.0", 30), ("1.1.0", 1)]);
-    let result = select_version("pkg", "^1.0.0", &package, Some(7), false).unwrap();
+    let result = select_version("pkg", "^1.0.0", &package, Some(7), None, false).unwrap();
     assert_eq!(result.version, "1.0.0");
 }
 
 #[test]
 fn min_package_age_rejects_young_dist_tag() {
     let package = make_package_with_version_ages(&[("1.0.0", 1)]);
-    let result = select_version("pkg", "latest", &package, Some(7), false);
+    let result = select_version("pkg", "latest", &package, Some(7), None, false);
     assert!(result.is_err());
 }
 
 #[test]
 fn min_package_age_reports_latest_young_matching_version() {
     let package = make_package_with_version_ages(&[("1.0.0", 3), ("1.1.0", 1)]);
-    let result = select_version("pkg", "^1.0.0", &package, Some(7), false);
+    let result = select_version("pkg", "^1.0.0", &package, Some(7), None, false);
     match result.unwrap_err() {
         crate::SnpmError::ResolutionFailed { reason, .. } => {
             assert!(reason.contains("1.1.0"), "{reason}");
@@ -163,14 +265,14 @@ fn min_package_age_reports_latest_young_matching_version() {
 #[test]
 fn force_bypasses_min_package_age() {
     let package = make_package_with_version_ages(&[("1.0.0", 1)]);
-    let result = select_version("pkg", "latest", &package, Some(7), true).unwrap();
+    let result = select_version("pkg", "latest", &package, Some(7), None, true).unwrap();
     assert_eq!(result.version, "1.0.0");
 }
 
 #[test]
 fn min_package_age_is_tolerant_of_missing_time_metadata() {
     let package = make_package_with_versions(&["1.0.0"]);
-    let result = select_version("pkg", "latest", &package, Some(7), false).unwrap();
+    let result = select_version("pkg", "latest", &package, Some(7), None, false).unwrap();
     assert_eq!(result.version, "1.0.0");
 }
 
@@ -189,6 +291,79 @@ fn parse_range_set_wildcard() {
 #[test]
 fn selects_tilde_range() {
     let package = make_package_with_versions(&["1.0.0", "1.0.5", "1.1.0", "2.0.0"]);
-    let result = select_version("pkg", "~1.0.0", &package, None, false).unwrap();
+    let result = select_version("pkg", "~1.0.0", &package, None, None, false).unwrap();
     assert_eq!(result.version, "1.0.5");
 }
+
+fn with_provenance(package: &mut RegistryPackage, versions: &[&str]) {
+    for version in versions {
+        package
+            .versions
+            .get_mut(*version)
+            .unwrap()
+            .dist
+            .attestations = Some(crate::registry::RegistryAttestations {
+            url: format!("https://registry.example/-/npm/v1/attestations/pkg@{version}"),
+            provenance: Some(crate::registry::RegistryProvenance {
+                predicate_type: "https://slsa.dev/provenance/v1".to_string(),
+            }),
+        });
+    }
+}
+
+#[test]
+fn no_downgrade_skips_versions_that_lost_provenance() {
+    let mut package = make_package_with_version_ages(&[("1.0.0", 30), ("1.1.0", 20), ("1.2.0", 1)]);
+    with_provenance(&mut package, &["1.1.0"]);
+    let exclusions = BTreeSet::new();
+
+    let result = select_version("pkg", "^1.0.0", &package, None, Some(&exclusions), false).unwrap();
+    assert_eq!(result.version, "1.1.0");
+
+    // Versions from before the package adopted provenance are fine.
+    let result = select_version("pkg", "1.0.0", &package, None, Some(&exclusions), false).unwrap();
+    assert_eq!(result.version, "1.0.0");
+}
+
+#[test]
+fn no_downgrade_reports_the_attested_version_as_evidence() {
+    let mut package = make_package_with_version_ages(&[("1.0.0", 30), ("1.1.0", 1)]);
+    with_provenance(&mut package, &["1.0.0"]);
+    let exclusions = BTreeSet::new();
+
+    for range in ["1.1.0", "latest"] {
+        match select_version("pkg", range, &package, None, Some(&exclusions), false).unwrap_err() {
+            crate::SnpmError::ResolutionFailed { reason, .. } => {
+                assert!(reason.contains("version 1.1.0"), "{reason}");
+                assert!(reason.contains("but 1.0.0 (published "), "{reason}");
+                assert!(reason.contains("\"pkg@1.1.0\""), "{reason}");
+            }
+            error => panic!("expected resolution failure, got {error:?}"),
+        }
+    }
+}
+
+#[test]
+fn no_downgrade_honours_exclusions() {
+    let mut package = make_package_with_version_ages(&[("1.0.0", 30), ("1.1.0", 1)]);
+    with_provenance(&mut package, &["1.0.0"]);
+
+    for entry in ["pkg", "pkg@1.1.0"] {
+        let exclusions = BTreeSet::from([entry.to_string()]);
+        let result =
+            select_version("pkg", "latest", &package, None, Some(&exclusions), false).unwrap();
+        assert_eq!(result.version, "1.1.0");
+    }
+}
+
+#[test]
+fn no_downgrade_falls_back_to_semver_order_without_publish_times() {
+    let mut package = make_package_with_versions(&["1.0.0", "1.1.0", "1.2.0"]);
+    with_provenance(&mut package, &["1.1.0"]);
+    let exclusions = BTreeSet::new();
+
+    let result = select_version("pkg", "^1.0.0", &package, None, Some(&exclusions), false).unwrap();
+    assert_eq!(result.version, "1.1.0");
+    let result = select_version("pkg", "^1.0.0", &package, None, None, false).unwrap();
+    assert_eq!(result.version, "1.2.0");
+}
@@ -0,0 +1,133 @@
+use crate::registry::RegistryPackage;
+use snpm_semver::{Version, parse_version};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// `trustPolicy: no-downgrade` for one package: a version published
+/// without a provenance attestation is rejected once an earlier version
+/// of the package was published with one.
+pub(super) struct TrustCheck<'a> {
+    name: &'a str,
+    package: &'a RegistryPackage,
+    exclusions: &'a BTreeSet<String>,
+    /// Publish times from the packument, parsed once for every candidate.
+    times: BTreeMap<&'a str, OffsetDateTime>,
+    attested: Vec<Published<'a>>,
+}
+
+struct Published<'a> {
+    version: &'a str,
+    parsed: Option<Version>,
+    time: Option<OffsetDateTime>,
+}
+
+/// The evidence for rejecting `version`.
+pub(super) struct Downgrade {
+    pub(super) version: String,
+    pub(super) attested: String,
+    pub(super) attested_at: Option<OffsetDateTime>,
+}
+
+impl<'a> TrustCheck<'a> {
+    pub(super) fn new(
+        name: &'a str,
+        package: &'a RegistryPackage,
+        exclusions: &'a BTreeSet<String>,
+    ) -> Self {
+        let times = package
+            .time
+            .iter()
+            .filter_map(|(version, time)| {
+                let time = OffsetDateTime::parse(time, &Rfc3339).ok()?;
+                Some((version.as_str(), time))
+            })
+            .collect();
+        let mut check = Self {
+            name,
+            package,
+            exclusions,
+            times,
+            attested: Vec::new(),
+        };
+        check.attested = package
+            .versions
+            .iter()
+            .filter(|(_, meta)| meta.dist.has_provenance())
+            .map(|(version, _)| check.published(version))
+            .collect();
+
+        check
+    }
+
+    /// Why `version` is a downgrade, `None` when it is not (or the
+    /// package or version is excluded).
+    pub(super) fn downgrade(&self, version: &str) -> Option<Downgrade> {
+        if self.attested.is_empty()
+            || self
+                .package
+                .versions
+                .get(version)
+                .is_none_or(|meta| meta.dist.has_provenance())
+            || self.exclusions.contains(self.name)
+            || self
+                .exclusions
+                .contains(&format!("{}@{}", self.name, version))
+        {
+            return None;
+        }
+
+        let candidate = self.published(version);
+        let evidence = self
+            .attested
+            .iter()
+            .filter(|attested| attested.precedes(&candidate))
+            .max_by(|left, right| {
+                left.time
+                    .cmp(&right.time)
+                    .then_with(|| left.parsed.cmp(&right.parsed))
+            })?;
+
+        Some(Downgrade {
+            version: version.to_string(),
+            attested: evidence.version.to_string(),
+            attested_at: evidence.time,
+        })
+    }
+
+    fn published<'v>(&self, version: &'v str) -> Published<'v> {
+        Published {
+            version,
+            parsed: parse_version(version).ok(),
+            time: self.times.get(version).copied(),
+        }
+    }
+}
+
+impl Published<'_> {
+    /// Publish order when both times are known, semver order otherwise.
+    fn precedes(&self, other: &Published<'_>) -> bool {
+        match (self.time, other.time) {
+            (Some(time), Some(other_time)) => time < other_time,
+            _ => match (&self.parsed, &other.parsed) {
+                (Some(version), Some(other_version)) => version < other_version,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Downgrade {
+    pub(super) fn describe(&self, name: &str) -> String {
+        let published = self
+            .attested_at
+            .map(|time| format!(" (published {})", time.date()))
+            .unwrap_or_default();
+
+        format!(
+            "version {} was published without a provenance attestation, but {}{} was published with one. If this is expected, add \"{}@{}\" to trustPolicyExclude",
+            self.version, self.attested, published, name, self.version
+        )
+    }
+}
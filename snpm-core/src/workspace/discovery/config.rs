@@ -1,5 +1,9 @@
 use super::super::types::{CatalogConfig, WorkspaceConfig};
+use crate::platform::SupportedArchitectures;
 use crate::project::{CatalogMap, NamedCatalogsMap};
+use crate::resolve::PeerDependencyRules;
+use crate::resolve::extensions::validate_package_extensions;
+use crate::resolve::peers::validate_peer_dependency_rules;
 use crate::{Result, SnpmError};
 use serde::Deserialize;
 use std::collections::BTreeMap;
@@ -15,6 +19,12 @@ pub(super) fn empty_workspace_config() -> WorkspaceConfig {
         ignored_built_dependencies: Vec::new(),
         disable_global_virtual_store_for_packages: None,
         hoisting: None,
+        package_extensions: BTreeMap::new(),
+        peer_dependency_rules: PeerDependencyRules::default(),
+        supported_architectures: SupportedArchitectures::default(),
+        resolution_mode: None,
+        trust_policy: None,
+        trust_policy_exclude: Vec::new(),
     }
 }
 
@@ -24,10 +34,27 @@ pub(super) fn read_config(path: &Path) -> Result<WorkspaceConfig> {
         source,
     })?;
 
-    serde_yaml::from_str(&data).map_err(|error| SnpmError::WorkspaceConfig {
-        path: path.to_path_buf(),
-        reason: error.to_string(),
-    })
+    let config: WorkspaceConfig =
+        serde_yaml::from_str(&data).map_err(|error| SnpmError::WorkspaceConfig {
+            path: path.to_path_buf(),
+            reason: error.to_string(),
+        })?;
+
+    validate_package_extensions(&config.package_extensions).map_err(|error| {
+        SnpmError::WorkspaceConfig {
+            path: path.to_path_buf(),
+            reason: format!("packageExtensions: {error}"),
+        }
+    })?;
+
+    validate_peer_dependency_rules(&config.peer_dependency_rules).map_err(|error| {
+        SnpmError::WorkspaceConfig {
+            path: path.to_path_buf(),
+            reason: format!("peerDependencyRules: {error}"),
+        }
+    })?;
+
+    Ok(config)
 }
 
 pub(super) fn merge_catalog_entries(
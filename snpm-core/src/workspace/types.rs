@@ -1,5 +1,9 @@
+use crate::config::{ResolutionMode, TrustPolicy};
+use crate::platform::SupportedArchitectures;
+use crate::resolve::{PackageExtensions, PeerDependencyRules};
 use crate::{Project, Result, SnpmError};
 use serde::Deserialize;
+use serde_yaml::Mapping;
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -23,6 +27,18 @@ pub struct WorkspaceConfig {
     pub disable_global_virtual_store_for_packages: Option<Vec<String>>,
     #[serde(default)]
     pub hoisting: Option<String>,
+    #[serde(default, rename = "packageExtensions")]
+    pub package_extensions: PackageExtensions,
+    #[serde(default, rename = "peerDependencyRules")]
+    pub peer_dependency_rules: PeerDependencyRules,
+    #[serde(default, rename = "supportedArchitectures")]
+    pub supported_architectures: SupportedArchitectures,
+    #[serde(default, rename = "resolutionMode")]
+    pub resolution_mode: Option<ResolutionMode>,
+    #[serde(default, rename = "trustPolicy")]
+    pub trust_policy: Option<TrustPolicy>,
+    #[serde(default, rename = "trustPolicyExclude")]
+    pub trust_policy_exclude: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -81,6 +97,121 @@ impl OverridesConfig {
 
         Ok(Some(config))
     }
+
+    /// Adds `entries` to `snpm-overrides.yaml` under `root`, creating the
+    /// file if needed and replacing existing overrides with the same key.
+    /// The file is edited line by line, so comments, key order and other
+    /// top-level keys stay as written.
+    pub fn add(root: &Path, entries: &BTreeMap<String, String>) -> Result<PathBuf> {
+        let path = root.join("snpm-overrides.yaml");
+        let data = if path.is_file() {
+            fs::read_to_string(&path).map_err(|source| SnpmError::ReadFile {
+                path: path.clone(),
+                source,
+            })?
+        } else {
+            String::new()
+        };
+
+        let invalid = |reason: String| SnpmError::WorkspaceConfig {
+            path: path.clone(),
+            reason,
+        };
+        let updated = add_override_lines(&data, entries).ok_or_else(|| {
+            invalid("`overrides` must be a block mapping to add entries to it".to_string())
+        })?;
+        let written: Self =
+            serde_yaml::from_str(&updated).map_err(|err| invalid(err.to_string()))?;
+        if let Some((key, _)) = entries
+            .iter()
+            .find(|(key, value)| written.overrides.get(*key) != Some(*value))
+        {
+            return Err(invalid(format!("could not add the override for {key}")));
+        }
+
+        fs::write(&path, updated).map_err(|source| SnpmError::WriteFile {
+            path: path.clone(),
+            source,
+        })?;
+
+        Ok(path)
+    }
+}
+
+/// `data` with `entries` set under the top-level `overrides` block,
+/// appending the block when there is none. `None` when `overrides` is
+/// written in flow style (`overrides: { a: b }`).
+fn add_override_lines(data: &str, entries: &BTreeMap<String, String>) -> Option<String> {
+    let mut lines: Vec<String> = data.lines().map(str::to_string).collect();
+    let header = match lines.iter().position(|line| line.starts_with("overrides:")) {
+        Some(index) => {
+            let value = lines[index]["overrides:".len()..]
+                .split(" #")
+                .next()
+                .unwrap_or_default()
+                .trim();
+            match value {
+                "" => {}
+                "{}" => lines[index] = "overrides:".to_string(),
+                _ => return None,
+            }
+            index
+        }
+        None => {
+            lines.push("overrides:".to_string());
+            lines.len() - 1
+        }
+    };
+
+    // The block runs until the next top-level line; blank lines and
+    // comments inside it don't end it.
+    let mut end = header + 1;
+    let mut indent = None;
+    for (index, line) in lines.iter().enumerate().skip(header + 1) {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed.len() == line.len() {
+            break;
+        }
+        indent.get_or_insert_with(|| line[..line.len() - trimmed.len()].to_string());
+        end = index + 1;
+    }
+    let indent = indent.unwrap_or_else(|| "  ".to_string());
+
+    for (key, value) in entries {
+        let entry = format!("{indent}{}: {}", yaml_scalar(key), yaml_scalar(value));
+        let existing = (header + 1..end).find(|&index| {
+            let line = &lines[index];
+            line.strip_prefix(indent.as_str())
+                .is_some_and(|rest| !rest.starts_with(char::is_whitespace))
+                && entry_key(line).as_deref() == Some(key.as_str())
+        });
+        match existing {
+            Some(index) => lines[index] = entry,
+            None => {
+                lines.insert(end, entry);
+                end += 1;
+            }
+        }
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    Some(updated)
+}
+
+/// The key of a one-line `key: value` mapping entry.
+fn entry_key(line: &str) -> Option<String> {
+    let entry: Mapping = serde_yaml::from_str(line.trim()).ok()?;
+    entry.keys().next()?.as_str().map(str::to_string)
+}
+
+fn yaml_scalar(value: &str) -> String {
+    serde_yaml::to_string(value)
+        .map(|yaml| yaml.trim_end().to_string())
+        .unwrap_or_else(|_| format!("{value:?}"))
 }
 
 #[derive(Debug)]
@@ -101,3 +232,65 @@ impl Workspace {
             .find(|project| project.manifest.name.as_deref() == Some(name))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::OverridesConfig;
+
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    fn entries(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn add_keeps_comments_and_other_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snpm-overrides.yaml");
+        fs::write(
+            &path,
+            "# pinned for the audit\noverrides:\n    # keep until upstream ships a fix\n    deep@~2.1.0: ~2.1.2\n    left-pad: 1.3.0 # exact\n\nnotes: kept\n",
+        )
+        .unwrap();
+
+        OverridesConfig::add(
+            dir.path(),
+            &entries(&[("deep@~2.1.0", "~2.1.4"), ("@scope/pkg@^1.0.0", "^1.2.0")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# pinned for the audit\noverrides:\n    # keep until upstream ships a fix\n    deep@~2.1.0: ~2.1.4\n    left-pad: 1.3.0 # exact\n    '@scope/pkg@^1.0.0': ^1.2.0\n\nnotes: kept\n"
+        );
+        let config = OverridesConfig::load(dir.path()).unwrap().unwrap();
+        assert_eq!(config.overrides["@scope/pkg@^1.0.0"], "^1.2.0");
+        assert_eq!(config.overrides["left-pad"], "1.3.0");
+    }
+
+    #[test]
+    fn add_creates_the_file_or_the_overrides_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snpm-overrides.yaml");
+
+        OverridesConfig::add(dir.path(), &entries(&[("deep", "^2.2.0")])).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "overrides:\n  deep: ^2.2.0\n"
+        );
+
+        fs::write(&path, "# nothing yet\noverrides: {}\n").unwrap();
+        OverridesConfig::add(dir.path(), &entries(&[("deep", "^2.2.0")])).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# nothing yet\noverrides:\n  deep: ^2.2.0\n"
+        );
+
+        fs::write(&path, "overrides: { deep: ^2.0.0 }\n").unwrap();
+        assert!(OverridesConfig::add(dir.path(), &entries(&[("deep", "^2.2.0")])).is_err());
+    }
+}
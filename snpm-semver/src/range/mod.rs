@@ -1,6 +1,6 @@
 mod parse;
 
-use semver::{Version, VersionReq};
+use semver::{Comparator, Op, Version, VersionReq};
 
 use crate::Error;
 
@@ -22,6 +22,44 @@ impl RangeSet {
     pub fn original(&self) -> &str {
         &self.original
     }
+
+    /// The lowest version the range allows, e.g. `18.0.0` for `>=18 <21`
+    /// or `20.0.0` for `20`. `None` when no version satisfies it.
+    pub fn min_version(&self) -> Option<Version> {
+        self.ranges.iter().filter_map(min_matching).min()
+    }
+}
+
+fn min_matching(range: &VersionReq) -> Option<Version> {
+    let candidate = range
+        .comparators
+        .iter()
+        .map(lower_bound)
+        .max()
+        .unwrap_or_else(|| Version::new(0, 0, 0));
+
+    range.matches(&candidate).then_some(candidate)
+}
+
+fn lower_bound(comparator: &Comparator) -> Version {
+    let major = comparator.major;
+    let minor = comparator.minor.unwrap_or(0);
+    let patch = comparator.patch.unwrap_or(0);
+
+    let mut bound = match comparator.op {
+        Op::Less | Op::LessEq => return Version::new(0, 0, 0),
+        Op::Greater => match (comparator.minor, comparator.patch) {
+            (Some(_), Some(patch)) => Version::new(major, minor, patch + 1),
+            (Some(minor), None) => Version::new(major, minor + 1, 0),
+            _ => Version::new(major + 1, 0, 0),
+        },
+        _ => Version::new(major, minor, patch),
+    };
+
+    if !matches!(comparator.op, Op::Greater) {
+        bound.pre = comparator.pre.clone();
+    }
+    bound
 }
 
 #[cfg(test)]
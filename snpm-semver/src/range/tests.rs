@@ -238,3 +238,20 @@ fn is_plain_exact_version_false() {
     assert!(!is_plain_exact_version(""));
     assert!(!is_plain_exact_version("1.2"));
 }
+
+#[test]
+fn min_version_is_the_lowest_allowed_version() {
+    let min = |range: &str| {
+        RangeSet::parse(range)
+            .unwrap()
+            .min_version()
+            .map(|version| version.to_string())
+    };
+
+    assert_eq!(min(">=18 <21").as_deref(), Some("18.0.0"));
+    assert_eq!(min("20").as_deref(), Some("20.0.0"));
+    assert_eq!(min("^16.14.0 || >=18").as_deref(), Some("16.14.0"));
+    assert_eq!(min(">16.2").as_deref(), Some("16.3.0"));
+    assert_eq!(min("<20").as_deref(), Some("0.0.0"));
+    assert_eq!(min(">=20 <18"), None);
+}